@@ -26,8 +26,22 @@ pub enum Error {
     ActionWillResultInInvalidMesh(String),
     #[error("action {0} will produce a non-manifold mesh")]
     ActionWillResultInNonManifoldMesh(String),
+    #[error("the input does not describe a valid mesh: {0}")]
+    InvalidInput(InputReport),
     #[error("the mesh has ended up in an invalid state: {0}")]
-    MeshIsInvalid(String),
+    MeshIsInvalid(ValidationReport),
+    #[error("{0} requires the mesh to be closed")]
+    MeshIsNotClosed(String),
+    #[error("{0} requires the mesh to be a topological disk")]
+    MeshIsNotDisk(String),
+    #[error("{0} requires the mesh to be a topological sphere")]
+    MeshIsNotSphere(String),
+    #[error("failed to triangulate the polygon: {0}")]
+    TriangulationFailed(String),
+    #[error("failed to solve the linear system: {0}")]
+    SolverFailed(String),
+    #[error("failed to split at the intersection: {0}")]
+    SplitFailed(String),
 }
 
 #[cfg(test)]
@@ -4,8 +4,10 @@
 
 use cgmath;
 pub use cgmath::prelude::*;
-pub use cgmath::{Deg, Matrix3, Matrix4, Rad, Vector3, Vector4};
+pub use cgmath::{Deg, Matrix3, Matrix4, Quaternion, Rad, Vector2, Vector3, Vector4};
 
+/// Vector with two elements.
+pub type Vec2 = Vector2<f64>;
 /// Vector with three elements.
 pub type Vec3 = Vector3<f64>;
 /// Vector with four elements.
@@ -16,11 +18,19 @@ pub type Mat3 = Matrix3<f64>;
 /// 4x4 matrix.
 pub type Mat4 = Matrix4<f64>;
 
+/// Quaternion, typically used for representing a rotation.
+pub type Quat = Quaternion<f64>;
+
 /// Degrees
 pub type Degrees = Deg<f64>;
 /// Radians
 pub type Radians = Rad<f64>;
 
+/// Constructs a [Vec2]
+pub const fn vec2(x: f64, y: f64) -> Vec2 {
+    Vector2::new(x, y)
+}
+
 /// Constructs a [Vec3]
 pub const fn vec3(x: f64, y: f64, z: f64) -> Vec3 {
     Vector3::new(x, y, z)
@@ -39,3 +49,7 @@ pub const fn degrees(v: f64) -> Degrees {
 pub const fn radians(v: f64) -> Radians {
     Rad(v)
 }
+
+mod interop;
+#[doc(inline)]
+pub use interop::*;
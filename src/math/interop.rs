@@ -0,0 +1,197 @@
+//!
+//! Conversions between the math types of this crate and the math types of other crates.
+//!
+
+use super::*;
+
+///
+/// Conversion between a math type of this crate, for example [Vec3] or [Mat4], and the equivalent type `T` of another crate.
+///
+/// The conversions to and from [mint](https://crates.io/crates/mint) (requires the `mint` feature), [glam](https://crates.io/crates/glam) (requires the `glam` feature)
+/// and [nalgebra](https://crates.io/crates/nalgebra) (requires the `nalgebra` feature) types are implemented.
+/// For mint, the standard [From] and [Into] conversions are also available.
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::*;
+/// # #[cfg(feature = "glam")]
+/// # {
+/// let v = Vec3::from_interop(glam::DVec3::new(1.0, 2.0, 3.0));
+/// assert_eq!(v, vec3(1.0, 2.0, 3.0));
+/// let w: glam::DVec3 = v.to_interop();
+/// assert_eq!(w, glam::DVec3::new(1.0, 2.0, 3.0));
+/// # }
+/// ```
+///
+pub trait MathInterop<T>: Sized {
+    /// Converts from the type of the other crate.
+    fn from_interop(value: T) -> Self;
+    /// Converts to the type of the other crate.
+    fn to_interop(&self) -> T;
+}
+
+impl MathInterop<Vec3> for Vec3 {
+    fn from_interop(value: Vec3) -> Self {
+        value
+    }
+    fn to_interop(&self) -> Vec3 {
+        *self
+    }
+}
+
+impl MathInterop<Mat4> for Mat4 {
+    fn from_interop(value: Mat4) -> Self {
+        value
+    }
+    fn to_interop(&self) -> Mat4 {
+        *self
+    }
+}
+
+#[cfg(feature = "mint")]
+impl MathInterop<mint::Vector3<f64>> for Vec3 {
+    fn from_interop(value: mint::Vector3<f64>) -> Self {
+        value.into()
+    }
+    fn to_interop(&self) -> mint::Vector3<f64> {
+        (*self).into()
+    }
+}
+
+#[cfg(feature = "mint")]
+impl MathInterop<mint::ColumnMatrix4<f64>> for Mat4 {
+    fn from_interop(value: mint::ColumnMatrix4<f64>) -> Self {
+        value.into()
+    }
+    fn to_interop(&self) -> mint::ColumnMatrix4<f64> {
+        (*self).into()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl MathInterop<glam::DVec3> for Vec3 {
+    fn from_interop(value: glam::DVec3) -> Self {
+        vec3(value.x, value.y, value.z)
+    }
+    fn to_interop(&self) -> glam::DVec3 {
+        glam::DVec3::new(self.x, self.y, self.z)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl MathInterop<glam::Vec3> for Vec3 {
+    fn from_interop(value: glam::Vec3) -> Self {
+        vec3(value.x as f64, value.y as f64, value.z as f64)
+    }
+    fn to_interop(&self) -> glam::Vec3 {
+        glam::Vec3::new(self.x as f32, self.y as f32, self.z as f32)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl MathInterop<glam::DMat4> for Mat4 {
+    fn from_interop(value: glam::DMat4) -> Self {
+        value.to_cols_array_2d().into()
+    }
+    fn to_interop(&self) -> glam::DMat4 {
+        let m: [[f64; 4]; 4] = (*self).into();
+        glam::DMat4::from_cols_array_2d(&m)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl MathInterop<glam::Mat4> for Mat4 {
+    fn from_interop(value: glam::Mat4) -> Self {
+        Mat4::from_interop(value.as_dmat4())
+    }
+    fn to_interop(&self) -> glam::Mat4 {
+        MathInterop::<glam::DMat4>::to_interop(self).as_mat4()
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl MathInterop<nalgebra::Vector3<f64>> for Vec3 {
+    fn from_interop(value: nalgebra::Vector3<f64>) -> Self {
+        vec3(value.x, value.y, value.z)
+    }
+    fn to_interop(&self) -> nalgebra::Vector3<f64> {
+        nalgebra::Vector3::new(self.x, self.y, self.z)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl MathInterop<nalgebra::Point3<f64>> for Vec3 {
+    fn from_interop(value: nalgebra::Point3<f64>) -> Self {
+        vec3(value.x, value.y, value.z)
+    }
+    fn to_interop(&self) -> nalgebra::Point3<f64> {
+        nalgebra::Point3::new(self.x, self.y, self.z)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl MathInterop<nalgebra::Matrix4<f64>> for Mat4 {
+    fn from_interop(value: nalgebra::Matrix4<f64>) -> Self {
+        // Both are stored in column major order
+        let mut m = Mat4::zero();
+        for c in 0..4 {
+            for r in 0..4 {
+                m[c][r] = value[(r, c)];
+            }
+        }
+        m
+    }
+    fn to_interop(&self) -> nalgebra::Matrix4<f64> {
+        nalgebra::Matrix4::from_fn(|r, c| self[c][r])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam_interop() {
+        let m = Mat4::from_translation(vec3(1.0, 2.0, 3.0)) * Mat4::from_angle_x(degrees(30.0));
+        let g: glam::DMat4 = m.to_interop();
+        let p = g.transform_point3(glam::DVec3::new(0.5, -1.0, 2.0));
+        assert_eq!(
+            Vec3::from_interop(p),
+            (m * vec4(0.5, -1.0, 2.0, 1.0)).truncate()
+        );
+        assert_eq!(Mat4::from_interop(g), m);
+
+        let mesh = crate::Mesh::from_positions_and_indices(
+            &[
+                glam::DVec3::new(0.0, 0.0, 0.0),
+                glam::DVec3::new(1.0, 0.0, 0.0),
+                glam::DVec3::new(0.0, 1.0, 0.0),
+            ],
+            &[0, 1, 2],
+        );
+        assert_eq!(mesh.no_faces(), 1);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_nalgebra_interop() {
+        let m = Mat4::from_translation(vec3(1.0, 2.0, 3.0)) * Mat4::from_angle_x(degrees(30.0));
+        let n: nalgebra::Matrix4<f64> = m.to_interop();
+        let p = n.transform_point(&nalgebra::Point3::new(0.5, -1.0, 2.0));
+        assert!(
+            (Vec3::from_interop(p) - (m * vec4(0.5, -1.0, 2.0, 1.0)).truncate()).magnitude()
+                < 0.000001
+        );
+        assert_eq!(Mat4::from_interop(n), m);
+    }
+
+    #[cfg(feature = "mint")]
+    #[test]
+    fn test_mint_interop() {
+        let v: mint::Vector3<f64> = vec3(1.0, 2.0, 3.0).into();
+        assert_eq!(Vec3::from_interop(v), vec3(1.0, 2.0, 3.0));
+    }
+}
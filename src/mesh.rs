@@ -1,12 +1,19 @@
 pub use crate::math::*;
 
 mod io;
+#[doc(inline)]
+pub use io::*;
+
+#[cfg(feature = "bevy")]
+mod bevy;
 
 mod utility;
 
 mod append;
 
 mod cleanup;
+#[doc(inline)]
+pub use cleanup::*;
 
 mod ids;
 #[doc(inline)]
@@ -16,48 +23,117 @@ mod iterators;
 #[doc(inline)]
 pub use iterators::*;
 
+mod property;
+#[doc(inline)]
+pub use property::*;
+
 mod traversal;
 #[doc(inline)]
 pub use traversal::*;
 
 mod edit;
 
+mod journal;
+
+mod observers;
+#[doc(inline)]
+pub use observers::*;
+
+mod crease;
+
+mod normal_cache;
+
+mod attributes;
+
+mod face_groups;
+
+mod precision;
+#[doc(inline)]
+pub use precision::*;
+
+mod shapes;
+
+mod convex_hull;
+
+mod delaunay;
+
 mod orientation;
 
 mod connectivity_info;
 
-use crate::mesh::connectivity_info::ConnectivityInfo;
-use std::collections::HashMap;
+use crate::mesh::attributes::VertexAttributes;
+use crate::mesh::connectivity_info::{ConnectivityInfo, CowVec};
+use std::collections::{HashMap, HashSet};
 
 ///
 /// A representation of a triangle mesh which is efficient for calculating on and making changes to a mesh.
 ///
-/// Use [Mesh::new] to construct a new mesh.
+/// Use [Mesh::new] to construct a new mesh or one of the [shape](#shapes) constructors, for example [Mesh::torus].
 /// Use [Mesh::export] to export the mesh to a format that is efficient for visualization.
 ///
+/// When cloning a mesh, the connectivity, the vertex positions and the [normal cache](#normal-cache) are shared between the clones, such that only the parts that are changed afterwards are copied.
+/// A clone starts with an empty [journal](#journal) and without [observers](#observers), so the recorded changes are not copied.
+/// The [creases](#creases), the [vertex attributes](#vertex-attributes) and the [face groups](#face-groups) are copied when cloning,
+/// so a clone of a mesh without those is cheap and can be used as a snapshot of the mesh, for example before a speculative edit.
+///
 /// ## Basic functionality:
+/// - [Shapes](#shapes)
+/// - [Convex hull](#convex-hull)
+/// - [Delaunay triangulation](#delaunay-triangulation)
 /// - [Iterators](#iterators)
 /// - [Traversal](#traversal)
 /// - [Edit](#edit)
+/// - [Journal](#journal)
+/// - [Observers](#observers)
 /// - [Orientation](#orientation)
+/// - [Creases](#creases)
+/// - [Normal cache](#normal-cache)
+/// - [Vertex attributes](#vertex-attributes)
+/// - [Face groups](#face-groups)
+/// - [Position precision](#position-precision)
 ///
 /// ## Simple operations
 /// - [Connectivity](#connectivity)
 /// - [Vertex measures](#vertex-measures)
 /// - [Edge measures](#edge-measures)
 /// - [Face measures](#face-measures)
+/// - [Mesh measures](#mesh-measures)
 /// - [Transformations](#transformations)
+/// - [Deform](#deform)
+/// - [Lattice deformation](#lattice-deformation)
 /// - [Bounding box](#bounding-box)
+/// - [Statistics](#statistics)
 /// - [Validity](#validity)
 ///
 /// ## Advanced operations
 /// - [Quality](#quality)
+/// - [Quality report](#quality-report)
+/// - [Decimation](#decimation)
+/// - [Curvature](#curvature)
+/// - [Principal axes](#principal-axes)
+/// - [Geodesics](#geodesics)
+/// - [Distance](#distance)
+/// - [Sampling](#sampling)
+/// - [Heat method](#heat-method) (requires the `solver` feature)
+/// - [Laplacian](#laplacian)
+/// - [Parameterization](#parameterization) (requires the `solver` feature)
+/// - [Parallel](#parallel) (requires the `rayon` feature)
 /// - [Connected components](#connected-components)
+/// - [Segmentation](#segmentation)
 /// - [Intersection](#intersection)
+/// - [Spatial queries](#spatial-queries)
 /// - [Merge](#merge)
+/// - [Bevel](#bevel)
 /// - [Split](#split)
+/// - [Cut](#cut)
+/// - [Vertex cache optimization](#vertex-cache-optimization)
 ///
 #[derive(Debug, Clone)]
 pub struct Mesh {
     connectivity_info: ConnectivityInfo,
+    merge_epsilon: f64,
+    creases: HashSet<(VertexID, VertexID)>,
+    vertex_normals: CowVec<Vec3>,
+    vertex_attributes: VertexAttributes,
+    face_groups: FaceProperty<String>,
 }
@@ -5,7 +5,12 @@ impl Mesh {
     /// Appends the `other` mesh to this mesh without creating a connection between them.
     /// Use `merge_with` if merging of overlapping primitives is desired, thereby creating a connection.
     /// All the primitives of the `other` mesh are copied to the current mesh and the `other` mesh is therefore not changed.
-    pub fn append(&mut self, other: &Self) {
+    /// Returns the mapping from the ids of the vertices and faces of the `other` mesh to the ids of the copies in this mesh,
+    /// which can be used to carry attributes and selections across. Vertices which are not part of a face are not copied.
+    pub fn append(
+        &mut self,
+        other: &Self,
+    ) -> (HashMap<VertexID, VertexID>, HashMap<FaceID, FaceID>) {
         let mut mapping: HashMap<VertexID, VertexID> = HashMap::new();
         let mut get_or_create_vertex = |mesh: &mut Mesh, vertex_id| -> VertexID {
             if let Some(vid) = mapping.get(&vertex_id) {
@@ -31,20 +36,22 @@ impl Mesh {
             for halfedge_id in other.face_halfedge_iter(other_face_id) {
                 if let Some(fid) = other.walker_from_halfedge(halfedge_id).as_twin().face_id() {
                     if let Some(self_face_id) = face_mapping.get(&fid) {
-                        for halfedge_id1 in self.face_halfedge_iter(*self_face_id) {
+                        let halfedges1: Vec<_> = self.face_halfedge_iter(*self_face_id).collect();
+                        for halfedge_id1 in halfedges1 {
                             let mut walker1 = self.walker_from_halfedge(halfedge_id1);
                             let source_vertex_id = walker1.vertex_id().unwrap();
                             let sink_vertex_id = walker1.as_next().vertex_id().unwrap();
+                            let halfedge_id1 = walker1.halfedge_id().unwrap();
 
-                            for halfedge_id2 in self.face_halfedge_iter(new_face_id) {
+                            let halfedges2: Vec<_> = self.face_halfedge_iter(new_face_id).collect();
+                            for halfedge_id2 in halfedges2 {
                                 let mut walker2 = self.walker_from_halfedge(halfedge_id2);
                                 if sink_vertex_id == walker2.vertex_id().unwrap()
                                     && source_vertex_id == walker2.as_next().vertex_id().unwrap()
                                 {
-                                    self.connectivity_info.set_halfedge_twin(
-                                        walker1.halfedge_id().unwrap(),
-                                        walker2.halfedge_id().unwrap(),
-                                    );
+                                    let halfedge_id2 = walker2.halfedge_id().unwrap();
+                                    self.connectivity_info
+                                        .set_halfedge_twin(halfedge_id1, halfedge_id2);
                                 }
                             }
                         }
@@ -52,22 +59,33 @@ impl Mesh {
                 }
             }
 
+            if let Some(name) = other.face_group(other_face_id) {
+                self.assign_group(new_face_id, name);
+            }
             face_mapping.insert(other_face_id, new_face_id);
         }
 
+        for (vertex_id0, vertex_id1) in other.creases.iter() {
+            if let (Some(v0), Some(v1)) = (mapping.get(vertex_id0), mapping.get(vertex_id1)) {
+                self.creases
+                    .insert(if v0 < v1 { (*v0, *v1) } else { (*v1, *v0) });
+            }
+        }
+
+        self.vertex_attributes
+            .append(&other.vertex_attributes, &mapping);
+
         self.create_boundary_edges();
+        (mapping, face_mapping)
     }
 
     fn create_boundary_edges(&mut self) {
-        let mut walker = self.walker();
         for halfedge_id in self.halfedge_iter() {
-            walker.as_halfedge_walker(halfedge_id);
+            let mut walker = self.walker_from_halfedge(halfedge_id);
             if walker.twin_id().is_none() {
-                let boundary_halfedge_id = self.connectivity_info.new_halfedge(
-                    walker.as_previous().vertex_id(),
-                    None,
-                    None,
-                );
+                let vertex_id = walker.as_previous().vertex_id();
+                let boundary_halfedge_id =
+                    self.connectivity_info.new_halfedge(vertex_id, None, None);
                 self.connectivity_info
                     .set_halfedge_twin(halfedge_id, boundary_halfedge_id);
             }
@@ -109,4 +127,29 @@ mod tests {
                 .is_some());
         }
     }
+
+    #[test]
+    fn test_append_mapping() {
+        let mut mesh1 = Mesh::icosphere(1);
+        let mut mesh2 = Mesh::icosphere(2);
+        mesh2.translate(vec3(3.0, 0.0, 0.0));
+
+        let (vertices, faces) = mesh1.append(&mesh2);
+
+        assert_eq!(vertices.len(), mesh2.no_vertices());
+        assert_eq!(faces.len(), mesh2.no_faces());
+        for vertex_id in mesh2.vertex_iter() {
+            assert_eq!(
+                mesh1.vertex_position(vertices[&vertex_id]),
+                mesh2.vertex_position(vertex_id)
+            );
+        }
+        for face_id in mesh2.face_iter() {
+            let (v0, v1, v2) = mesh2.face_vertices(face_id);
+            let (w0, w1, w2) = mesh1.face_vertices(faces[&face_id]);
+            for v in [v0, v1, v2] {
+                assert!([w0, w1, w2].contains(&vertices[&v]));
+            }
+        }
+    }
 }
@@ -0,0 +1,498 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+///
+/// The vertex attributes given when constructing the mesh (see [Mesh::new]), which are stored such that they can be exported again without loss.
+///
+#[derive(Debug, Clone, Default)]
+pub(crate) struct VertexAttributes {
+    pub normals: Option<VertexProperty<three_d_asset::Vec3>>,
+    pub tangents: Option<VertexProperty<three_d_asset::Vec4>>,
+    pub uvs: Option<VertexProperty<three_d_asset::Vec2>>,
+    pub colors: Option<VertexProperty<three_d_asset::Srgba>>,
+}
+
+impl VertexAttributes {
+    pub fn new(input: &three_d_asset::TriMesh) -> Self {
+        fn collect<T: Copy>(values: &Option<Vec<T>>) -> Option<VertexProperty<T>> {
+            values.as_ref().map(|values| {
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (unsafe { VertexID::new(i as u32) }, *v))
+                    .collect()
+            })
+        }
+        Self {
+            normals: collect(&input.normals),
+            tangents: collect(&input.tangents),
+            uvs: collect(&input.uvs),
+            colors: collect(&input.colors),
+        }
+    }
+
+    pub fn remap(&mut self, mapping: &HashMap<VertexID, VertexID>) {
+        fn remap<T: Copy>(
+            values: &mut Option<VertexProperty<T>>,
+            mapping: &HashMap<VertexID, VertexID>,
+        ) {
+            if let Some(values) = values {
+                *values = values
+                    .iter()
+                    .filter_map(|(v, value)| Some((*mapping.get(v)?, *value)))
+                    .collect();
+            }
+        }
+        remap(&mut self.normals, mapping);
+        remap(&mut self.tangents, mapping);
+        remap(&mut self.uvs, mapping);
+        remap(&mut self.colors, mapping);
+    }
+
+    /// Transforms the normals by the inverse transpose of the given linear transformation and the tangents by the transformation itself.
+    /// If the transformation mirrors, the handedness of the tangents is flipped as well.
+    pub fn transform(&mut self, linear: &Mat3) {
+        let Some(normal_matrix) = linear.invert().map(|m| m.transpose()) else {
+            self.normals = None;
+            self.tangents = None;
+            return;
+        };
+        if let Some(normals) = self.normals.as_mut() {
+            for normal in normals.values_mut() {
+                let n = normal_matrix * normal.cast::<f64>().unwrap();
+                *normal = n.normalize().cast::<f32>().unwrap();
+            }
+        }
+        let handedness = linear.determinant().signum() as f32;
+        if let Some(tangents) = self.tangents.as_mut() {
+            for tangent in tangents.values_mut() {
+                let t = linear * tangent.truncate().cast::<f64>().unwrap();
+                *tangent = t
+                    .normalize()
+                    .cast::<f32>()
+                    .unwrap()
+                    .extend(handedness * tangent.w);
+            }
+        }
+    }
+
+    /// Removes the normals and tangents of the given vertices, such that the computed normals are exported for those vertices.
+    pub fn remove_normals(&mut self, vertices: &[VertexID]) {
+        if let Some(normals) = self.normals.as_mut() {
+            for vertex_id in vertices {
+                normals.remove(vertex_id);
+            }
+        }
+        if let Some(tangents) = self.tangents.as_mut() {
+            for vertex_id in vertices {
+                tangents.remove(vertex_id);
+            }
+        }
+    }
+
+    /// Flips the normals and the handedness of the tangents, which corresponds to flipping the orientation of the faces.
+    pub fn flip(&mut self) {
+        if let Some(normals) = self.normals.as_mut() {
+            for normal in normals.values_mut() {
+                *normal = -*normal;
+            }
+        }
+        if let Some(tangents) = self.tangents.as_mut() {
+            for tangent in tangents.values_mut() {
+                tangent.w = -tangent.w;
+            }
+        }
+    }
+
+    /// Flips the normals and the handedness of the tangents of the given vertices, see [VertexAttributes::flip].
+    pub fn flip_vertices(&mut self, vertices: &HashSet<VertexID>) {
+        if let Some(normals) = self.normals.as_mut() {
+            for (vertex_id, normal) in normals.iter_mut() {
+                if vertices.contains(vertex_id) {
+                    *normal = -*normal;
+                }
+            }
+        }
+        if let Some(tangents) = self.tangents.as_mut() {
+            for (vertex_id, tangent) in tangents.iter_mut() {
+                if vertices.contains(vertex_id) {
+                    tangent.w = -tangent.w;
+                }
+            }
+        }
+    }
+
+    pub fn append(&mut self, other: &Self, mapping: &HashMap<VertexID, VertexID>) {
+        fn append<T: Copy>(
+            values: &mut Option<VertexProperty<T>>,
+            other: &Option<VertexProperty<T>>,
+            mapping: &HashMap<VertexID, VertexID>,
+        ) {
+            if let Some(other) = other {
+                let values = values.get_or_insert_with(HashMap::new);
+                for (v, value) in other.iter() {
+                    if let Some(v) = mapping.get(v) {
+                        values.entry(*v).or_insert(*value);
+                    }
+                }
+            }
+        }
+        append(&mut self.normals, &other.normals, mapping);
+        append(&mut self.tangents, &other.tangents, mapping);
+        append(&mut self.uvs, &other.uvs, mapping);
+        append(&mut self.colors, &other.colors, mapping);
+    }
+}
+
+/// # Vertex attributes
+///
+/// The normals, tangents, uv coordinates and colors given when constructing the mesh (see [Mesh::new]) are stored per vertex
+/// and reused when exporting the mesh (see [Mesh::export]), such that the mesh can be an editing step in an asset pipeline without losing authored data.
+/// The stored normals are exported instead of the computed normals (see [Mesh::vertex_normal]) as long as they are valid:
+/// The stored normals and tangents are transformed together with the mesh by the [transformations](#transformations), for example [Mesh::rotate] and [Mesh::transform],
+/// and flipped by [Mesh::flip_orientation], but when a vertex is moved in any other way, for example by [Mesh::set_vertex_position] or [Mesh::smooth_vertices],
+/// the stored normals and tangents of the vertex and its neighbours are removed and the computed normals are exported for those vertices.
+/// Use [Mesh::clear_stored_normals] to export the computed normals for all vertices.
+/// Vertices that are added after construction, for example by [Mesh::split_edge], get the attributes of a neighbouring vertex when exported.
+///
+impl Mesh {
+    /// Returns the normal given for the vertex when constructing the mesh, if any, see [Mesh::vertex_normal] for the computed normal.
+    pub fn stored_vertex_normal(&self, vertex_id: VertexID) -> Option<three_d_asset::Vec3> {
+        self.vertex_attributes
+            .normals
+            .as_ref()?
+            .get(&vertex_id)
+            .copied()
+    }
+
+    /// Returns the tangent given for the vertex when constructing the mesh, if any.
+    pub fn vertex_tangent(&self, vertex_id: VertexID) -> Option<three_d_asset::Vec4> {
+        self.vertex_attributes
+            .tangents
+            .as_ref()?
+            .get(&vertex_id)
+            .copied()
+    }
+
+    /// Returns the uv coordinates given for the vertex when constructing the mesh or using [Mesh::set_vertex_uv], if any.
+    pub fn vertex_uv(&self, vertex_id: VertexID) -> Option<three_d_asset::Vec2> {
+        self.vertex_attributes
+            .uvs
+            .as_ref()?
+            .get(&vertex_id)
+            .copied()
+    }
+
+    /// Sets the uv coordinates of the vertex which are exported with the mesh.
+    pub fn set_vertex_uv(&mut self, vertex_id: VertexID, uv: three_d_asset::Vec2) {
+        self.vertex_attributes
+            .uvs
+            .get_or_insert_with(HashMap::new)
+            .insert(vertex_id, uv);
+    }
+
+    /// Returns the color given for the vertex when constructing the mesh, if any.
+    pub fn vertex_color(&self, vertex_id: VertexID) -> Option<three_d_asset::Srgba> {
+        self.vertex_attributes
+            .colors
+            .as_ref()?
+            .get(&vertex_id)
+            .copied()
+    }
+
+    /// Copies the stored uv coordinates and color of the `from` vertex to the `to` vertex, for example when a vertex is split into several vertices.
+    pub(crate) fn copy_vertex_attributes(&mut self, from: VertexID, to: VertexID) {
+        if let Some(uv) = self.vertex_uv(from) {
+            self.set_vertex_uv(to, uv);
+        }
+        if let Some(color) = self.vertex_color(from) {
+            if let Some(colors) = self.vertex_attributes.colors.as_mut() {
+                colors.insert(to, color);
+            }
+        }
+    }
+
+    ///
+    /// Moves each vertex to the position returned by the given function and transforms the stored normals and tangents by the given linear transformation,
+    /// instead of removing them as when moving the vertices using [Mesh::set_vertex_position].
+    /// If the transformation mirrors, the stored normals are flipped as well, since the orientation of the faces is unchanged.
+    ///
+    pub(crate) fn transform_vertices(&mut self, linear: &Mat3, position: impl Fn(Vec3) -> Vec3) {
+        let normals = self.vertex_attributes.normals.take();
+        let tangents = self.vertex_attributes.tangents.take();
+        for vertex_id in self.vertex_iter() {
+            let p = self.vertex_position(vertex_id);
+            self.set_vertex_position(vertex_id, position(p));
+        }
+        self.vertex_attributes.normals = normals;
+        self.vertex_attributes.tangents = tangents;
+        self.vertex_attributes.transform(linear);
+        if linear.determinant() < 0.0 {
+            self.vertex_attributes.flip();
+        }
+    }
+
+    /// Removes the stored normals such that the computed normals (see [Mesh::vertex_normal]) are exported instead.
+    pub fn clear_stored_normals(&mut self) {
+        self.vertex_attributes.normals = None;
+    }
+
+    /// Removes all stored vertex attributes, ie. normals, tangents, uv coordinates and colors.
+    pub fn clear_vertex_attributes(&mut self) {
+        self.vertex_attributes = VertexAttributes::default();
+    }
+
+    ///
+    /// Returns the stored tangent of each vertex in the order of [Mesh::vertex_iter], or `None` if no tangents are stored.
+    /// Vertices without a stored tangent get the tangent of a neighbouring vertex (see [Mesh::vertex_attribute_buffer]) made orthogonal to the given normal of the vertex.
+    ///
+    pub(super) fn tangent_buffer(
+        &self,
+        normals: &[three_d_asset::Vec3],
+    ) -> Option<Vec<three_d_asset::Vec4>> {
+        let tangents = self.vertex_attributes.tangents.as_ref()?;
+        let buffer = self.vertex_attribute_buffer(
+            &self.vertex_attributes.tangents,
+            three_d_asset::vec4(1.0, 0.0, 0.0, 1.0),
+        )?;
+        Some(
+            self.vertex_iter()
+                .zip(buffer)
+                .zip(normals)
+                .map(|((vertex_id, tangent), normal)| {
+                    if tangents.contains_key(&vertex_id) {
+                        tangent
+                    } else {
+                        orthogonal_tangent(tangent, *normal)
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    ///
+    /// Returns the stored value of each vertex in the order of [Mesh::vertex_iter], or `None` if no values are stored.
+    /// Vertices without a stored value get the value of a neighbouring vertex, or the given default value if none of the neighbours have a value.
+    ///
+    pub(super) fn vertex_attribute_buffer<T: Copy>(
+        &self,
+        values: &Option<VertexProperty<T>>,
+        default: T,
+    ) -> Option<Vec<T>> {
+        let values = values.as_ref()?;
+        Some(
+            self.vertex_iter()
+                .map(|vertex_id| {
+                    values.get(&vertex_id).copied().unwrap_or_else(|| {
+                        self.vertex_halfedge_iter(vertex_id)
+                            .find_map(|halfedge_id| {
+                                values.get(&self.walker_from_halfedge(halfedge_id).vertex_id()?)
+                            })
+                            .copied()
+                            .unwrap_or(default)
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+///
+/// Returns the given tangent made orthogonal to the given normal, or an arbitrary tangent orthogonal to the normal if they are parallel.
+/// The handedness (the `w` component) is kept.
+///
+pub(super) fn orthogonal_tangent(
+    tangent: three_d_asset::Vec4,
+    normal: three_d_asset::Vec3,
+) -> three_d_asset::Vec4 {
+    let t = tangent.truncate();
+    let mut t = t - normal * normal.dot(t);
+    if t.magnitude2() < 0.000001 {
+        let axis = if normal.x.abs() < 0.5 {
+            three_d_asset::vec3(1.0, 0.0, 0.0)
+        } else {
+            three_d_asset::vec3(0.0, 1.0, 0.0)
+        };
+        t = normal.cross(axis);
+    }
+    t.normalize().extend(tangent.w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::{Indices, Positions, TriMesh};
+
+    #[test]
+    fn test_lossless_round_trip() {
+        let mut input = TriMesh::square();
+        input.tangents = Some(
+            input
+                .normals
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|n| {
+                    let axis = if n.x.abs() < 0.5 {
+                        three_d_asset::vec3(1.0, 0.0, 0.0)
+                    } else {
+                        three_d_asset::vec3(0.0, 1.0, 0.0)
+                    };
+                    n.cross(axis).normalize().extend(1.0)
+                })
+                .collect(),
+        );
+        input.colors = Some(vec![three_d_asset::Srgba::RED; input.vertex_count()]);
+        // Normals that differ from the computed normals
+        input.normals = Some(vec![
+            three_d_asset::vec3(0.0, 1.0, 0.0);
+            input.vertex_count()
+        ]);
+
+        let mesh: Mesh = (&input).into();
+        let output = mesh.export();
+
+        // The same triangles, but each triangle might start at another corner
+        let triangles = |indices: Vec<u32>| {
+            let mut triangles: Vec<[u32; 3]> = indices
+                .chunks(3)
+                .map(|t| {
+                    let i = (0..3).min_by_key(|i| t[*i]).unwrap();
+                    [t[i], t[(i + 1) % 3], t[(i + 2) % 3]]
+                })
+                .collect();
+            triangles.sort();
+            triangles
+        };
+        assert_eq!(
+            triangles(output.indices.to_u32().unwrap()),
+            triangles(input.indices.to_u32().unwrap())
+        );
+        assert_eq!(output.positions.to_f64(), input.positions.to_f64());
+        assert_eq!(output.normals, input.normals);
+        assert_eq!(output.tangents, input.tangents);
+        assert_eq!(output.uvs, input.uvs);
+        assert_eq!(output.colors, input.colors);
+    }
+
+    #[test]
+    fn test_vertex_attributes_after_edits() {
+        let input = TriMesh {
+            indices: Indices::U8(vec![0, 1, 2, 2, 1, 3]),
+            positions: Positions::F64(vec![
+                vec3(-1.0, -1.0, 0.0),
+                vec3(1.0, -1.0, 0.0),
+                vec3(-1.0, 1.0, 0.0),
+                vec3(1.0, 1.0, 0.0),
+            ]),
+            uvs: Some(vec![
+                three_d_asset::vec2(0.0, 0.0),
+                three_d_asset::vec2(1.0, 0.0),
+                three_d_asset::vec2(0.0, 1.0),
+                three_d_asset::vec2(1.0, 1.0),
+            ]),
+            ..Default::default()
+        };
+        let mut mesh = Mesh::new(&input);
+        let halfedge_id = mesh.halfedge_iter().next().unwrap();
+        let vertex_id = mesh.split_edge(halfedge_id, vec3(0.0, 0.0, 0.0));
+        assert_eq!(mesh.vertex_uv(vertex_id), None);
+        mesh.set_vertex_uv(vertex_id, three_d_asset::vec2(0.5, 0.5));
+
+        let face_id = mesh.face_iter().next().unwrap();
+        mesh.remove_face(face_id);
+        let remap = mesh.compact();
+        let output = mesh.export();
+        let uvs = output.uvs.unwrap();
+        assert_eq!(uvs.len(), mesh.no_vertices());
+        assert_eq!(
+            mesh.vertex_uv(remap.vertices[&vertex_id]),
+            Some(three_d_asset::vec2(0.5, 0.5))
+        );
+        assert!(output.normals.is_some());
+        assert!(output.tangents.is_none());
+
+        mesh.clear_vertex_attributes();
+        assert!(mesh.export().uvs.is_none());
+    }
+
+    #[test]
+    fn test_stored_normals_after_transformations() {
+        let mut input = TriMesh::sphere(8);
+        input.tangents = Some(
+            input
+                .normals
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|n| {
+                    let axis = if n.x.abs() < 0.5 {
+                        three_d_asset::vec3(1.0, 0.0, 0.0)
+                    } else {
+                        three_d_asset::vec3(0.0, 1.0, 0.0)
+                    };
+                    n.cross(axis).normalize().extend(1.0)
+                })
+                .collect(),
+        );
+        let mut mesh: Mesh = (&input).into();
+        assert!(mesh
+            .stored_vertex_normal(mesh.vertex_iter().next().unwrap())
+            .is_some());
+
+        let check = |mesh: &Mesh| {
+            let output = mesh.export();
+            for (i, vertex_id) in mesh.vertex_iter().enumerate() {
+                let normal = output.normals.as_ref().unwrap()[i].cast::<f64>().unwrap();
+                assert!(normal.distance(mesh.vertex_normal(vertex_id)) < 0.1);
+                if let Some(tangents) = output.tangents.as_ref() {
+                    let tangent = tangents[i].truncate().cast::<f64>().unwrap();
+                    assert!(tangent.dot(normal).abs() < 0.1);
+                }
+            }
+        };
+        mesh.rotate(Mat3::from_angle_x(degrees(90.0)));
+        check(&mesh);
+        mesh.transform(&Mat4::from_nonuniform_scale(-1.0, 2.0, 1.0));
+        check(&mesh);
+        mesh.mirror(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 1.0));
+        check(&mesh);
+        mesh.scale(-1.0);
+        check(&mesh);
+        mesh.flip_orientation();
+        check(&mesh);
+        assert!(mesh
+            .stored_vertex_normal(mesh.vertex_iter().next().unwrap())
+            .is_some());
+
+        // Moving a vertex in any other way removes the stored normals and tangents
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        mesh.move_vertex_by(vertex_id, vec3(0.1, 0.0, 0.0));
+        assert!(mesh.stored_vertex_normal(vertex_id).is_none());
+        assert!(mesh.vertex_tangent(vertex_id).is_none());
+        check(&mesh);
+    }
+
+    #[test]
+    fn test_stored_normals_are_kept_away_from_moved_vertex() {
+        let mut mesh: Mesh = TriMesh::sphere(8).into();
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        let one_ring: Vec<VertexID> = std::iter::once(vertex_id)
+            .chain(mesh.vertex_vertex_iter(vertex_id))
+            .collect();
+
+        mesh.move_vertex_by(vertex_id, vec3(0.1, 0.0, 0.0));
+
+        let output = mesh.export();
+        for (i, v) in mesh.vertex_iter().enumerate() {
+            if one_ring.contains(&v) {
+                assert!(mesh.stored_vertex_normal(v).is_none());
+                let normal = output.normals.as_ref().unwrap()[i].cast::<f64>().unwrap();
+                assert!(normal.distance(mesh.vertex_normal(v)) < 0.000001);
+            } else {
+                assert!(mesh.stored_vertex_normal(v).is_some());
+            }
+        }
+    }
+}
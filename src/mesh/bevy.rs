@@ -0,0 +1,72 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use bevy_mesh::{Indices, PrimitiveTopology};
+
+///
+/// Converts the [Mesh] into a [bevy_mesh::Mesh], which is the same type as `bevy_render::mesh::Mesh`, with positions, normals, indices and the stored vertex attributes (see [Vertex attributes](Mesh#vertex-attributes)) (requires the `bevy` feature).
+/// The vertices are in the same order as [Mesh::export].
+///
+impl From<&Mesh> for bevy_mesh::Mesh {
+    fn from(mesh: &Mesh) -> Self {
+        let tri_mesh = mesh.export();
+        let positions: Vec<[f32; 3]> = tri_mesh
+            .positions
+            .to_f32()
+            .into_iter()
+            .map(|p| p.into())
+            .collect();
+        let normals: Vec<[f32; 3]> = tri_mesh
+            .normals
+            .unwrap_or_default()
+            .into_iter()
+            .map(|n| n.into())
+            .collect();
+        let mut bevy_mesh = bevy_mesh::Mesh::new(
+            PrimitiveTopology::TriangleList,
+            bevy_asset::RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(bevy_mesh::Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(bevy_mesh::Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_indices(Indices::U32(tri_mesh.indices.to_u32().unwrap()));
+        if let Some(uvs) = tri_mesh.uvs {
+            let uvs: Vec<[f32; 2]> = uvs.into_iter().map(|uv| uv.into()).collect();
+            bevy_mesh.insert_attribute(bevy_mesh::Mesh::ATTRIBUTE_UV_0, uvs);
+        }
+        if let Some(tangents) = tri_mesh.tangents {
+            let tangents: Vec<[f32; 4]> = tangents.into_iter().map(|t| t.into()).collect();
+            bevy_mesh.insert_attribute(bevy_mesh::Mesh::ATTRIBUTE_TANGENT, tangents);
+        }
+        if let Some(colors) = tri_mesh.colors {
+            let colors: Vec<[f32; 4]> = colors
+                .into_iter()
+                .map(|c| c.to_linear_srgb().into())
+                .collect();
+            bevy_mesh.insert_attribute(bevy_mesh::Mesh::ATTRIBUTE_COLOR, colors);
+        }
+        bevy_mesh
+    }
+}
+
+///
+/// Converts the [Mesh] into a [bevy_mesh::Mesh], see the implementation for `&Mesh`.
+///
+impl From<Mesh> for bevy_mesh::Mesh {
+    fn from(mesh: Mesh) -> Self {
+        (&mesh).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_bevy_conversion() {
+        let mesh = crate::test_utility::cube();
+        let bevy_mesh: bevy_mesh::Mesh = (&mesh).into();
+        assert_eq!(bevy_mesh.count_vertices(), mesh.no_vertices());
+        assert_eq!(bevy_mesh.indices().unwrap().len(), 3 * mesh.no_faces());
+        assert!(bevy_mesh
+            .attribute(bevy_mesh::Mesh::ATTRIBUTE_NORMAL)
+            .is_some());
+    }
+}
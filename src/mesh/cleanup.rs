@@ -1,22 +1,169 @@
 //! See [Mesh](crate::mesh::Mesh).
 
 use crate::mesh::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+/// The default maximum distance between two vertices for them to be considered overlapping, see [Mesh::set_merge_epsilon].
+pub const DEFAULT_MERGE_EPSILON: f64 = 0.00001;
+
+///
+/// The mapping from old to new ids returned by [Mesh::compact].
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdRemap {
+    /// The new id of each vertex given by the old id.
+    pub vertices: HashMap<VertexID, VertexID>,
+    /// The new id of each half-edge given by the old id.
+    pub halfedges: HashMap<HalfEdgeID, HalfEdgeID>,
+    /// The new id of each face given by the old id.
+    pub faces: HashMap<FaceID, FaceID>,
+}
+
+///
+/// The mapping from old to new indices returned by [Mesh::remove_lonely_primitives_and_compact],
+/// where the indices are the indices of the vertices and faces in the buffers of the exported mesh (see [Mesh::export]).
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndexRemap {
+    /// The new index of each vertex given by the old index or `None` if the vertex is removed.
+    pub vertices: Vec<Option<usize>>,
+    /// The new index of each face given by the old index or `None` if the face is removed.
+    pub faces: Vec<Option<usize>>,
+}
 
 impl Mesh {
+    ///
+    /// Reassigns the ids of all vertices, half-edges and faces such that they are numbered from zero without gaps.
+    /// After removing many primitives, for example using [Mesh::collapse_small_faces] or [Mesh::remove_face], the ids are sparse
+    /// which wastes memory and makes iteration slower. The relative order of the primitives is kept, so for example the order of the vertices in the exported mesh is unchanged.
+    /// Returns the mapping from the old to the new ids. All ids not found in the mapping are invalid after this call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let face_id = mesh.face_iter().next().unwrap();
+    /// mesh.remove_face(face_id);
+    /// let vertex_id = mesh.vertex_iter().last().unwrap();
+    /// let position = mesh.vertex_position(vertex_id);
+    ///
+    /// let remap = mesh.compact();
+    ///
+    /// assert!(!remap.faces.contains_key(&face_id));
+    /// assert_eq!(mesh.vertex_position(remap.vertices[&vertex_id]), position);
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn compact(&mut self) -> IdRemap {
+        let (vertices, halfedges, faces) = self.connectivity_info.compact();
+        self.creases = self
+            .creases
+            .iter()
+            .filter_map(|(v0, v1)| Some((*vertices.get(v0)?, *vertices.get(v1)?)))
+            .collect();
+        self.vertex_attributes.remap(&vertices);
+        self.face_groups = self
+            .face_groups
+            .drain()
+            .filter_map(|(face_id, name)| Some((*faces.get(&face_id)?, name)))
+            .collect();
+        let remap = IdRemap {
+            vertices,
+            halfedges,
+            faces,
+        };
+        self.notify(|o| o.on_compacted(&remap));
+        remap
+    }
+
+    ///
+    /// Removes the edges and vertices that are not connected to any face (see [Mesh::remove_lonely_primitives]) and then reassigns the ids (see [Mesh::compact]).
+    /// Returns the mapping from the old to the new index of each vertex and face in the buffers of the exported mesh (see [Mesh::export]),
+    /// so that data stored outside the mesh in arrays following the exported buffers can be remapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(1);
+    /// let face_id = mesh.face_iter().nth(10).unwrap();
+    /// mesh.remove_face(face_id);
+    /// // Data stored for each face in the order of the exported buffers
+    /// let face_data: Vec<usize> = (0..mesh.no_faces()).collect();
+    ///
+    /// let remap = mesh.remove_lonely_primitives_and_compact();
+    ///
+    /// let mut new_face_data = vec![0; mesh.no_faces()];
+    /// for (old_index, new_index) in remap.faces.iter().enumerate() {
+    ///     if let Some(new_index) = new_index {
+    ///         new_face_data[*new_index] = face_data[old_index];
+    ///     }
+    /// }
+    /// assert_eq!(new_face_data, face_data);
+    /// ```
+    ///
+    pub fn remove_lonely_primitives_and_compact(&mut self) -> IndexRemap {
+        let vertices: Vec<VertexID> = self.vertex_iter().collect();
+        let faces: Vec<FaceID> = self.face_iter().collect();
+        self.remove_lonely_primitives();
+        let remap = self.compact();
+        // The compacted ids are numbered from zero in the order of the iterators, ie. they are equal to the indices
+        IndexRemap {
+            vertices: vertices
+                .iter()
+                .map(|vertex_id| remap.vertices.get(vertex_id).map(|v| **v as usize))
+                .collect(),
+            faces: faces
+                .iter()
+                .map(|face_id| remap.faces.get(face_id).map(|f| **f as usize))
+                .collect(),
+        }
+    }
+
     ///
     /// Merges overlapping faces, edges and vertices if it is possible without creating a non-manifold mesh.
+    /// Two vertices are considered overlapping if the distance between them is less than the [merge epsilon](Mesh::merge_epsilon) of this mesh.
     ///
     pub fn merge_overlapping_primitives(&mut self) {
-        let set_of_vertices_to_merge = self.find_overlapping_vertices();
+        self.merge_overlapping_primitives_with_epsilon(self.merge_epsilon);
+    }
+
+    ///
+    /// Merges overlapping faces, edges and vertices if it is possible without creating a non-manifold mesh.
+    /// Two vertices are considered overlapping if the distance between them is less than the given epsilon.
+    ///
+    pub fn merge_overlapping_primitives_with_epsilon(&mut self, epsilon: f64) {
+        self.merge_overlapping_primitives_internal(epsilon);
+    }
+
+    /// Merges overlapping primitives and returns the surviving vertex for each vertex merged into another vertex
+    /// and the surviving face for each face removed because it overlaps another face.
+    pub(crate) fn merge_overlapping_primitives_internal(
+        &mut self,
+        epsilon: f64,
+    ) -> (HashMap<VertexID, VertexID>, HashMap<FaceID, FaceID>) {
+        let set_of_vertices_to_merge = self.find_overlapping_vertices(epsilon);
+        self.merge_vertex_groups(set_of_vertices_to_merge)
+    }
+
+    /// Merges each of the given groups of vertices into one vertex together with the edges and faces that then overlap,
+    /// like [Mesh::merge_overlapping_primitives].
+    fn merge_vertex_groups(
+        &mut self,
+        set_of_vertices_to_merge: Vec<Vec<VertexID>>,
+    ) -> (HashMap<VertexID, VertexID>, HashMap<FaceID, FaceID>) {
+        let mut merged_vertices = HashMap::new();
+        let mut merged_faces = HashMap::new();
         let set_of_edges_to_merge = self.find_overlapping_edges(&set_of_vertices_to_merge);
         let set_of_faces_to_merge = self.find_overlapping_faces(&set_of_vertices_to_merge);
 
         for faces_to_merge in set_of_faces_to_merge {
             let mut iter = faces_to_merge.iter();
-            iter.next();
+            let face_id1 = *iter.next().unwrap();
             for face_id2 in iter {
                 self.remove_face_unsafe(*face_id2);
+                merged_faces.insert(*face_id2, face_id1);
             }
         }
 
@@ -25,6 +172,7 @@ impl Mesh {
             let mut vertex_id1 = *iter.next().unwrap();
             for vertex_id2 in iter {
                 vertex_id1 = self.merge_vertices(vertex_id1, *vertex_id2);
+                merged_vertices.insert(*vertex_id2, vertex_id1);
             }
         }
 
@@ -39,6 +187,279 @@ impl Mesh {
         }
 
         self.fix_orientation();
+        (merged_vertices, merged_faces)
+    }
+
+    ///
+    /// Returns the maximum distance between two vertices for them to be considered overlapping
+    /// when merging overlapping primitives, for example in [Mesh::merge_overlapping_primitives] and [Mesh::merge_with].
+    /// The default is [DEFAULT_MERGE_EPSILON].
+    ///
+    pub fn merge_epsilon(&self) -> f64 {
+        self.merge_epsilon
+    }
+
+    ///
+    /// Sets the maximum distance between two vertices for them to be considered overlapping
+    /// when merging overlapping primitives, for example in [Mesh::merge_overlapping_primitives] and [Mesh::merge_with].
+    /// This should be adjusted to the scale of the mesh, for example if the mesh is given in millimeters instead of meters.
+    ///
+    pub fn set_merge_epsilon(&mut self, epsilon: f64) {
+        self.merge_epsilon = epsilon;
+    }
+
+    ///
+    /// Removes faces that span the same three vertices as another face, regardless of the orientation of the faces.
+    /// Only one of the faces spanning the same three vertices is kept. Returns the number of removed faces.
+    ///
+    pub fn remove_duplicate_faces(&mut self) -> usize {
+        let mut faces = HashSet::new();
+        let mut duplicates = Vec::new();
+        for face_id in self.face_iter() {
+            if !faces.insert(self.ordered_face_vertices(face_id)) {
+                duplicates.push(face_id);
+            }
+        }
+        for face_id in duplicates.iter() {
+            self.remove_face(*face_id);
+        }
+        duplicates.len()
+    }
+
+    ///
+    /// Rounds each vertex position to the nearest point on a regular grid with the given cell size and then merges the vertices which end up at the same grid point,
+    /// which makes nearly coincident geometry exactly coincident, for example to stabilize the geometry before [splitting at the intersection](Mesh::split_at_intersection) with another mesh.
+    /// An edge whose end points end up at the same grid point is collapsed if that does not create a non-manifold mesh, otherwise the edge is kept with zero length.
+    /// Other vertices at the same grid point are merged together with the overlapping edges and faces like in [Mesh::merge_overlapping_primitives].
+    /// Returns the number of removed vertices. Nothing is changed if the cell size is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// // The cube consists of 12 unconnected triangles
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::cube().into();
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// mesh.move_vertex_by(vertex_id, vec3(0.001, -0.002, 0.0));
+    ///
+    /// assert_eq!(mesh.snap_to_grid(0.1), 28);
+    /// assert!(mesh.is_closed());
+    /// assert_eq!(mesh.no_vertices(), 8);
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn snap_to_grid(&mut self, cell_size: f64) -> usize {
+        if cell_size <= 0.0 {
+            return 0;
+        }
+        let no_vertices = self.no_vertices();
+        let cell = |p: Vec3| {
+            (
+                (p.x / cell_size).round() as i64,
+                (p.y / cell_size).round() as i64,
+                (p.z / cell_size).round() as i64,
+            )
+        };
+        for vertex_id in self.vertex_iter() {
+            let (x, y, z) = cell(self.vertex_position(vertex_id));
+            self.set_vertex_position(vertex_id, vec3(x as f64, y as f64, z as f64) * cell_size);
+        }
+
+        // Collapse the edges with zero length, where a collapse might allow another collapse which was not possible before
+        loop {
+            let zero_length_edges: Vec<HalfEdgeID> = self
+                .edge_iter()
+                .map(HalfEdgeID::from)
+                .filter(|halfedge_id| self.edge_sqr_length(*halfedge_id) == 0.0)
+                .collect();
+            let mut collapsed = false;
+            for halfedge_id in zero_length_edges {
+                if self.connectivity_info.contains_halfedge(halfedge_id)
+                    && self.collapse_will_keep_manifold(halfedge_id)
+                {
+                    self.collapse_edge(halfedge_id);
+                    collapsed = true;
+                }
+            }
+            if !collapsed {
+                break;
+            }
+        }
+
+        // Merge the remaining vertices at the same grid point, unless two of them are connected by a zero length edge
+        let mut cells: HashMap<_, Vec<VertexID>> = HashMap::new();
+        for vertex_id in self.vertex_iter() {
+            cells
+                .entry(cell(self.vertex_position(vertex_id)))
+                .or_default()
+                .push(vertex_id);
+        }
+        let set_of_vertices_to_merge = cells
+            .into_values()
+            .filter(|vertex_ids| {
+                vertex_ids.len() > 1
+                    && vertex_ids.iter().all(|vertex_id| {
+                        self.vertex_vertex_iter(*vertex_id)
+                            .all(|neighbour_id| !vertex_ids.contains(&neighbour_id))
+                    })
+            })
+            .collect();
+        self.merge_vertex_groups(set_of_vertices_to_merge);
+        no_vertices - self.no_vertices()
+    }
+
+    // An edge can be collapsed if the only vertices connected to both end points are the vertices opposite the edge in the adjacent faces
+    // and if the edge is not an interior edge connecting two boundary vertices
+    pub(crate) fn collapse_will_keep_manifold(&self, halfedge_id: HalfEdgeID) -> bool {
+        let (v0, v1) = self.edge_vertices(halfedge_id);
+        let mut opposite = HashSet::new();
+        let mut walker = self.walker_from_halfedge(halfedge_id);
+        if walker.face_id().is_some() {
+            opposite.insert(walker.clone().as_next().vertex_id().unwrap());
+        }
+        walker.as_twin();
+        if walker.face_id().is_some() {
+            opposite.insert(walker.as_next().vertex_id().unwrap());
+        }
+        if opposite.len() == 2 && self.is_vertex_on_boundary(v0) && self.is_vertex_on_boundary(v1) {
+            return false;
+        }
+        let neighbours0: HashSet<VertexID> = self.vertex_vertex_iter(v0).collect();
+        self.vertex_vertex_iter(v1)
+            .filter(|vertex_id| neighbours0.contains(vertex_id))
+            .collect::<HashSet<_>>()
+            == opposite
+    }
+
+    ///
+    /// Stitches boundary edges whose end points are within the given tolerance of the end points of another boundary edge,
+    /// ie. the two boundary edges are zipped together into one interior edge and the end points are merged.
+    /// Only boundary edges with compatible orientation, ie. which would not cause an inconsistent orientation, are stitched.
+    /// Returns the number of stitched edges.
+    ///
+    /// This is a faster and more targeted alternative to [Mesh::merge_overlapping_primitives] since only the boundary is considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// // The cube consists of 12 unconnected triangles
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::cube().into();
+    /// assert_eq!(mesh.stitch_boundaries(0.0001), 18);
+    /// assert!(mesh.is_closed());
+    /// assert_eq!(mesh.no_vertices(), 8);
+    /// ```
+    ///
+    pub fn stitch_boundaries(&mut self, tolerance: f64) -> usize {
+        let cell_size = if tolerance > 0.0 { tolerance } else { 1.0 };
+        let cell = |p: Vec3| {
+            (
+                (p.x / cell_size).floor() as i64,
+                (p.y / cell_size).floor() as i64,
+                (p.z / cell_size).floor() as i64,
+            )
+        };
+        let endpoints = |halfedge_id: HalfEdgeID| {
+            let mut walker = self.walker_from_halfedge(halfedge_id);
+            let target = self.vertex_position(walker.vertex_id().unwrap());
+            let source = self.vertex_position(walker.as_twin().vertex_id().unwrap());
+            (source, target)
+        };
+
+        let boundary: Vec<HalfEdgeID> = self
+            .halfedge_iter()
+            .filter(|halfedge_id| self.walker_from_halfedge(*halfedge_id).face_id().is_none())
+            .collect();
+        let mut grid: HashMap<(i64, i64, i64), Vec<HalfEdgeID>> = HashMap::new();
+        for halfedge_id in boundary.iter() {
+            grid.entry(cell(endpoints(*halfedge_id).0))
+                .or_default()
+                .push(*halfedge_id);
+        }
+
+        // Find pairs of boundary edges going in opposite directions
+        let mut is_paired = HashSet::new();
+        let mut pairs = Vec::new();
+        for halfedge_id1 in boundary.iter() {
+            if is_paired.contains(halfedge_id1) {
+                continue;
+            }
+            let (p0, p1) = endpoints(*halfedge_id1);
+            let (i, j, k) = cell(p1);
+            let mut best: Option<(HalfEdgeID, f64)> = None;
+            for key in (i - 1..=i + 1).flat_map(|i| {
+                (j - 1..=j + 1).flat_map(move |j| (k - 1..=k + 1).map(move |k| (i, j, k)))
+            }) {
+                for halfedge_id2 in grid.get(&key).into_iter().flatten() {
+                    if halfedge_id2 == halfedge_id1 || is_paired.contains(halfedge_id2) {
+                        continue;
+                    }
+                    let (q0, q1) = endpoints(*halfedge_id2);
+                    let distance = (q0 - p1).magnitude().max((q1 - p0).magnitude());
+                    if distance <= tolerance && best.map(|(_, d)| distance < d).unwrap_or(true) {
+                        best = Some((*halfedge_id2, distance));
+                    }
+                }
+            }
+            if let Some((halfedge_id2, _)) = best {
+                is_paired.insert(*halfedge_id1);
+                is_paired.insert(halfedge_id2);
+                pairs.push((*halfedge_id1, halfedge_id2));
+            }
+        }
+
+        // Zip the pairs together
+        let mut incoming: HashMap<VertexID, Vec<HalfEdgeID>> = HashMap::new();
+        for halfedge_id in self.halfedge_iter() {
+            incoming
+                .entry(self.walker_from_halfedge(halfedge_id).vertex_id().unwrap())
+                .or_default()
+                .push(halfedge_id);
+        }
+        let mut removed = HashSet::new();
+        fn merge(
+            mesh: &mut Mesh,
+            incoming: &mut HashMap<VertexID, Vec<HalfEdgeID>>,
+            removed: &HashSet<HalfEdgeID>,
+            vertex_id1: VertexID,
+            vertex_id2: VertexID,
+        ) {
+            if vertex_id1 != vertex_id2 {
+                let halfedges = incoming.remove(&vertex_id2).unwrap_or_default();
+                for halfedge_id in halfedges.iter() {
+                    if !removed.contains(halfedge_id) {
+                        mesh.connectivity_info
+                            .set_halfedge_vertex(*halfedge_id, vertex_id1);
+                    }
+                }
+                incoming.entry(vertex_id1).or_default().extend(halfedges);
+                mesh.connectivity_info.remove_vertex(vertex_id2);
+            }
+        }
+        let mut count = 0;
+        for (halfedge_id1, halfedge_id2) in pairs {
+            let mut walker1 = self.walker_from_halfedge(halfedge_id1);
+            let mut walker2 = self.walker_from_halfedge(halfedge_id2);
+            let (b, a) = (
+                walker1.vertex_id().unwrap(),
+                walker1.as_twin().vertex_id().unwrap(),
+            );
+            let (d, c) = (
+                walker2.vertex_id().unwrap(),
+                walker2.as_twin().vertex_id().unwrap(),
+            );
+            if a == c || b == d {
+                continue;
+            }
+            merge(self, &mut incoming, &removed, a, d);
+            merge(self, &mut incoming, &removed, b, c);
+            if self.merge_halfedges(halfedge_id1, halfedge_id2).is_some() {
+                removed.insert(halfedge_id1);
+                removed.insert(halfedge_id2);
+                count += 1;
+            }
+        }
+        count
     }
 
     fn merge_halfedges(
@@ -150,7 +571,7 @@ impl Mesh {
         vertex_id1
     }
 
-    fn find_overlapping_vertices(&self) -> Vec<Vec<VertexID>> {
+    fn find_overlapping_vertices(&self, epsilon: f64) -> Vec<Vec<VertexID>> {
         let mut to_check = HashSet::new();
         self.vertex_iter().for_each(|v| {
             to_check.insert(v);
@@ -163,7 +584,7 @@ impl Mesh {
 
             let mut to_merge = Vec::new();
             for id2 in to_check.iter() {
-                if (self.vertex_position(id1) - self.vertex_position(*id2)).magnitude() < 0.00001 {
+                if (self.vertex_position(id1) - self.vertex_position(*id2)).magnitude() < epsilon {
                     to_merge.push(*id2);
                 }
             }
@@ -242,7 +663,7 @@ impl Mesh {
         };
         let mut to_check = HashSet::new();
         self.edge_iter().for_each(|e| {
-            to_check.insert(e);
+            to_check.insert(HalfEdgeID::from(e));
         });
 
         let mut set_to_merge = Vec::new();
@@ -281,6 +702,41 @@ mod tests {
     use super::*;
     use three_d_asset::{Indices, Positions, TriMesh};
 
+    #[test]
+    fn test_compact() {
+        let mut mesh: Mesh = TriMesh::sphere(8).into();
+        for face_id in mesh.face_iter().collect::<Vec<_>>() {
+            if mesh.face_center(face_id).y > 0.5 {
+                mesh.remove_face(face_id);
+            }
+        }
+        let halfedge_id = mesh
+            .halfedge_iter()
+            .find(|h| !mesh.is_edge_on_boundary(*h))
+            .unwrap();
+        mesh.set_crease(halfedge_id, true);
+        let exported = mesh.export();
+        let (positions, indices) = (exported.positions.to_f64(), exported.indices.to_u32());
+
+        let remap = mesh.compact();
+
+        mesh.is_valid().unwrap();
+        assert_eq!(remap.vertices.len(), mesh.no_vertices());
+        assert_eq!(remap.halfedges.len(), mesh.no_halfedges());
+        assert_eq!(remap.faces.len(), mesh.no_faces());
+        assert!(mesh
+            .vertex_iter()
+            .all(|v| (*v as usize) < mesh.no_vertices()));
+        assert!(mesh
+            .halfedge_iter()
+            .all(|h| (*h as usize) < mesh.no_halfedges()));
+        assert!(mesh.face_iter().all(|f| (*f as usize) < mesh.no_faces()));
+        assert!(mesh.is_crease(remap.halfedges[&halfedge_id]));
+        let exported = mesh.export();
+        assert_eq!(exported.positions.to_f64(), positions);
+        assert_eq!(exported.indices.to_u32(), indices);
+    }
+
     #[test]
     fn test_remove_lonely_vertices() {
         let mut mesh = crate::test_utility::subdivided_triangle();
@@ -298,6 +754,28 @@ mod tests {
         mesh.is_valid().unwrap();
     }
 
+    #[test]
+    fn test_remove_duplicate_faces() {
+        let mut mesh: Mesh = TriMesh {
+            indices: Indices::U8(vec![0, 1, 2, 0, 2, 1]),
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+            ]),
+            ..Default::default()
+        }
+        .into();
+
+        assert_eq!(1, mesh.remove_duplicate_faces());
+
+        mesh.is_valid().unwrap();
+        assert_eq!(1, mesh.no_faces());
+        assert_eq!(3, mesh.no_vertices());
+        assert_eq!(6, mesh.no_halfedges());
+        assert_eq!(0, mesh.remove_duplicate_faces());
+    }
+
     #[test]
     fn test_merge_overlapping_primitives() {
         let positions = vec![
@@ -336,6 +814,48 @@ mod tests {
         mesh.is_valid().unwrap();
     }
 
+    #[test]
+    fn test_merge_overlapping_primitives_with_epsilon() {
+        let mut mesh: Mesh = TriMesh {
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(1.0, 1.0, 0.0),
+                vec3(1.001, 1.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(0.0, 0.0, 0.001),
+            ]),
+            ..Default::default()
+        }
+        .into();
+
+        mesh.merge_overlapping_primitives();
+        assert_eq!(mesh.no_vertices(), 6);
+
+        mesh.merge_overlapping_primitives_with_epsilon(0.01);
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_vertices(), 4);
+        assert_eq!(mesh.no_faces(), 2);
+    }
+
+    #[test]
+    fn test_merge_with_mesh_epsilon() {
+        let mut mesh: Mesh = TriMesh::cube().into();
+        mesh.scale(1000.0);
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            mesh.move_vertex_to(
+                vertex_id,
+                p + vec3(0.001, 0.0, 0.0) * (*vertex_id % 2) as f64,
+            );
+        }
+        mesh.set_merge_epsilon(0.01);
+        assert_eq!(mesh.merge_epsilon(), 0.01);
+        mesh.merge_overlapping_primitives();
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_vertices(), 8);
+    }
+
     #[test]
     fn test_merge_overlapping_individual_faces() {
         let mut mesh: Mesh = TriMesh {
@@ -462,8 +982,8 @@ mod tests {
         .into();
 
         let mut heid1 = None;
-        for halfedge_id in mesh.edge_iter() {
-            let (v0, v1) = mesh.edge_vertices(halfedge_id);
+        for edge_id in mesh.edge_iter() {
+            let (v0, v1) = mesh.edge_vertices(edge_id.into());
             if mesh.vertex_position(v0)[2] == 0.0 && mesh.vertex_position(v1)[2] == 0.0 {
                 let halfedge_id = mesh.connecting_edge(v0, v1).unwrap();
                 if heid1.is_none() {
@@ -483,6 +1003,41 @@ mod tests {
         assert_eq!(2, mesh.no_faces());
     }
 
+    #[test]
+    fn test_stitch_boundaries() {
+        let mut mesh: Mesh = TriMesh {
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(1.0, 1.0, 0.0),
+                vec3(1.00001, 0.99999, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(0.0, 0.0, 0.00001),
+            ]),
+            indices: Indices::U8(vec![0, 1, 2, 3, 4, 5]),
+            ..Default::default()
+        }
+        .into();
+
+        assert_eq!(mesh.stitch_boundaries(0.000001), 0);
+        assert_eq!(mesh.stitch_boundaries(0.001), 1);
+
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_vertices(), 4);
+        assert_eq!(mesh.no_edges(), 5);
+        assert_eq!(mesh.no_faces(), 2);
+    }
+
+    #[test]
+    fn test_stitch_boundaries_of_cube() {
+        let mut mesh: Mesh = TriMesh::cube().into();
+        assert_eq!(mesh.stitch_boundaries(0.0001), 18);
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.no_vertices(), 8);
+        assert_eq!(mesh.no_faces(), 12);
+    }
+
     #[test]
     fn test_merge_overlapping_primitives_with_cube() {
         let mut mesh: Mesh = TriMesh::cube().into();
@@ -491,4 +1046,80 @@ mod tests {
         assert_eq!(mesh.no_vertices(), 8);
         mesh.is_valid().unwrap();
     }
+
+    #[test]
+    fn test_snap_to_grid() {
+        let mut mesh = Mesh::icosphere(1);
+        assert_eq!(mesh.snap_to_grid(0.001), 0);
+        assert_eq!(mesh.snap_to_grid(0.0), 0);
+
+        // A short edge is collapsed
+        let halfedge_id = mesh.halfedge_iter().next().unwrap();
+        let (vertex_id, _) = mesh.edge_vertices(halfedge_id);
+        let position = mesh.vertex_position(vertex_id);
+        mesh.split_edge(halfedge_id, position + vec3(0.0001, 0.0, 0.0));
+        assert_eq!(mesh.snap_to_grid(0.001), 1);
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_vertices(), 42);
+        assert_eq!(mesh.no_faces(), 80);
+
+        // A short interior edge between two boundary vertices is not collapsed
+        let mut mesh: Mesh = TriMesh {
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(0.0001, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(0.0, -1.0, 0.0),
+            ]),
+            indices: Indices::U8(vec![0, 1, 2, 1, 0, 3]),
+            ..Default::default()
+        }
+        .into();
+        assert_eq!(mesh.snap_to_grid(0.001), 0);
+        assert_eq!(mesh.no_vertices(), 4);
+        assert_eq!(mesh.no_faces(), 2);
+    }
+
+    #[test]
+    fn test_remove_lonely_primitives_and_compact() {
+        let mut mesh = crate::test_utility::subdivided_triangle();
+        let face_id = mesh.face_iter().next().unwrap();
+        mesh.remove_face(face_id);
+        let mut iter = mesh.face_iter();
+        let face_id1 = iter.next().unwrap();
+        let face_id2 = iter.next().unwrap();
+        mesh.remove_face_unsafe(face_id1);
+        mesh.remove_face_unsafe(face_id2);
+        let exported = mesh.export();
+        let (positions, indices) = (
+            exported.positions.to_f64(),
+            exported.indices.to_u32().unwrap(),
+        );
+
+        let remap = mesh.remove_lonely_primitives_and_compact();
+
+        mesh.is_valid().unwrap();
+        assert_eq!(remap.vertices.len(), positions.len());
+        assert_eq!(remap.faces.len(), indices.len() / 3);
+        assert_eq!(remap.vertices.iter().flatten().count(), mesh.no_vertices());
+        assert_eq!(remap.faces.iter().flatten().count(), mesh.no_faces());
+        let exported = mesh.export();
+        let (new_positions, new_indices) = (
+            exported.positions.to_f64(),
+            exported.indices.to_u32().unwrap(),
+        );
+        for (old_index, new_index) in remap.vertices.iter().enumerate() {
+            if let Some(new_index) = new_index {
+                assert_eq!(new_positions[*new_index], positions[old_index]);
+            }
+        }
+        for (old_index, new_index) in remap.faces.iter().enumerate() {
+            let new_index = new_index.unwrap();
+            for i in 0..3 {
+                let old_vertex = indices[3 * old_index + i] as usize;
+                let new_vertex = new_indices[3 * new_index + i] as usize;
+                assert_eq!(remap.vertices[old_vertex], Some(new_vertex));
+            }
+        }
+    }
 }
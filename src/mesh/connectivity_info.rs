@@ -1,38 +1,335 @@
 use crate::math::*;
 use crate::mesh::ids::*;
-use std::cell::RefCell;
+use crate::mesh::observers::Observers;
+use crate::mesh::PositionPrecision;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
+///
+/// The connectivity is stored as a structure of arrays, ie. each attribute of the vertices, half-edges and faces
+/// are stored in a separate dense array indexed by the id. The ids of removed primitives are kept in free lists and reused.
+/// The arrays are split into shared chunks which are copied on write, so a clone is cheap and only the chunks changed afterwards are copied.
+///
 #[derive(Clone)]
 pub(super) struct ConnectivityInfo {
-    vertices: RefCell<IDMap<VertexID, Vertex>>,
-    halfedges: RefCell<IDMap<HalfEdgeID, HalfEdge>>,
-    faces: RefCell<IDMap<FaceID, Face>>,
+    vertices: Slots<VertexID>,
+    vertex_positions: VertexPositions,
+    vertex_halfedges: CowVec<Option<HalfEdgeID>>,
+
+    halfedges: Slots<HalfEdgeID>,
+    halfedge_vertices: CowVec<Option<VertexID>>,
+    halfedge_twins: CowVec<Option<HalfEdgeID>>,
+    halfedge_nexts: CowVec<Option<HalfEdgeID>>,
+    halfedge_faces: CowVec<Option<FaceID>>,
+
+    faces: Slots<FaceID>,
+    face_halfedges: CowVec<Option<HalfEdgeID>>,
+
+    /// The vertices whose position or adjacent faces have changed since the changes were last taken, if the changes are tracked
+    changed_vertices: Option<HashSet<VertexID>>,
+
+    /// The changes made to the connectivity and positions, if the changes are recorded
+    journal: Option<Journal>,
+
+    /// The observers which are notified when a vertex or face is added or removed or a vertex is moved
+    observers: Observers,
+}
+
+/// A single change of the connectivity or positions, which can be reverted and applied again.
+/// The changes of the values store the old and the new value.
+#[derive(Clone, Debug)]
+enum Change {
+    InsertVertex(VertexID, bool),
+    RemoveVertex(VertexID),
+    InsertHalfEdge(HalfEdgeID, bool),
+    RemoveHalfEdge(HalfEdgeID),
+    InsertFace(FaceID, bool),
+    RemoveFace(FaceID),
+    VertexPosition(VertexID, Vec3, Vec3),
+    VertexHalfEdge(VertexID, Option<HalfEdgeID>, Option<HalfEdgeID>),
+    HalfEdgeVertex(HalfEdgeID, Option<VertexID>, Option<VertexID>),
+    HalfEdgeTwin(HalfEdgeID, Option<HalfEdgeID>, Option<HalfEdgeID>),
+    HalfEdgeNext(HalfEdgeID, Option<HalfEdgeID>, Option<HalfEdgeID>),
+    HalfEdgeFace(HalfEdgeID, Option<FaceID>, Option<FaceID>),
+    FaceHalfEdge(FaceID, Option<HalfEdgeID>, Option<HalfEdgeID>),
+}
+
+/// The recorded changes, where the changes since the last finished step are kept separately until the step is finished.
+#[derive(Debug, Default)]
+struct Journal {
+    changes: Vec<Change>,
+    undo_steps: Vec<Vec<Change>>,
+    redo_steps: Vec<Vec<Change>>,
+}
+
+/// A clone starts with an empty journal, so that cloning a mesh does not copy the recorded changes.
+impl Clone for Journal {
+    fn clone(&self) -> Self {
+        Journal::default()
+    }
+}
+
+impl Journal {
+    fn finish_step(&mut self) {
+        if !self.changes.is_empty() {
+            self.undo_steps.push(std::mem::take(&mut self.changes));
+        }
+    }
 }
 
 impl ConnectivityInfo {
     pub fn new(no_vertices: usize, no_faces: usize) -> ConnectivityInfo {
+        let no_halfedges = 4 * no_faces;
         ConnectivityInfo {
-            vertices: RefCell::new(IDMap::with_capacity(no_vertices)),
-            halfedges: RefCell::new(IDMap::with_capacity(4 * no_faces)),
-            faces: RefCell::new(IDMap::with_capacity(no_faces)),
+            vertices: Slots::with_capacity(no_vertices),
+            vertex_positions: VertexPositions::F64(CowVec::with_capacity(no_vertices)),
+            vertex_halfedges: CowVec::with_capacity(no_vertices),
+            halfedges: Slots::with_capacity(no_halfedges),
+            halfedge_vertices: CowVec::with_capacity(no_halfedges),
+            halfedge_twins: CowVec::with_capacity(no_halfedges),
+            halfedge_nexts: CowVec::with_capacity(no_halfedges),
+            halfedge_faces: CowVec::with_capacity(no_halfedges),
+            faces: Slots::with_capacity(no_faces),
+            face_halfedges: CowVec::with_capacity(no_faces),
+            changed_vertices: None,
+            journal: None,
+            observers: Observers::default(),
+        }
+    }
+
+    /// Starts tracking the changed vertices if not already tracking and returns the vertices changed since the last call
+    /// or `None` if the changes were not tracked before this call.
+    pub fn take_changed_vertices(&mut self) -> Option<HashSet<VertexID>> {
+        self.changed_vertices.replace(HashSet::new())
+    }
+
+    /// Stops tracking the changed vertices.
+    pub fn stop_tracking_changes(&mut self) {
+        self.changed_vertices = None;
+    }
+
+    /// Starts recording the changes if not already recording.
+    pub fn start_journal(&mut self) {
+        if self.journal.is_none() {
+            self.journal = Some(Journal::default());
+        }
+    }
+
+    /// Stops recording the changes and discards the recorded changes.
+    pub fn stop_journal(&mut self) {
+        self.journal = None;
+    }
+
+    pub fn is_journal_started(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    /// Collects the changes since the last finished step into a step which can be undone.
+    pub fn finish_step(&mut self) {
+        if let Some(journal) = &mut self.journal {
+            journal.finish_step();
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.journal
+            .as_ref()
+            .map(|journal| !journal.changes.is_empty() || !journal.undo_steps.is_empty())
+            .unwrap_or(false)
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.journal
+            .as_ref()
+            .map(|journal| !journal.redo_steps.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Reverts the last step, including the changes which are not yet collected into a step. Returns whether a step was reverted.
+    pub fn undo(&mut self) -> bool {
+        let Some(mut journal) = self.journal.take() else {
+            return false;
+        };
+        journal.finish_step();
+        let step = journal.undo_steps.pop();
+        if let Some(changes) = &step {
+            for change in changes.iter().rev() {
+                self.apply(change, false);
+            }
+        }
+        let undone = step.is_some();
+        journal.redo_steps.extend(step);
+        self.journal = Some(journal);
+        undone
+    }
+
+    /// Applies the last reverted step again. Returns whether a step was applied.
+    pub fn redo(&mut self) -> bool {
+        let Some(mut journal) = self.journal.take() else {
+            return false;
+        };
+        let step = journal.redo_steps.pop();
+        if let Some(changes) = &step {
+            for change in changes.iter() {
+                self.apply(change, true);
+            }
+        }
+        let redone = step.is_some();
+        journal.undo_steps.extend(step);
+        self.journal = Some(journal);
+        redone
+    }
+
+    pub fn observers(&self) -> &Observers {
+        &self.observers
+    }
+
+    pub fn observers_mut(&mut self) -> &mut Observers {
+        &mut self.observers
+    }
+
+    fn record(&mut self, change: Change) {
+        if let Some(journal) = &mut self.journal {
+            journal.changes.push(change);
+            journal.redo_steps.clear();
+        }
+    }
+
+    /// Applies the change if `forward` is true and otherwise reverts it.
+    fn apply(&mut self, change: &Change, forward: bool) {
+        fn pick<T>(forward: bool, old: T, new: T) -> T {
+            if forward {
+                new
+            } else {
+                old
+            }
+        }
+        match *change {
+            Change::InsertVertex(id, reused) => {
+                self.mark_changed(Some(id));
+                if forward {
+                    self.vertices.insert_new();
+                    self.observers.notify(|o| o.on_vertex_added(id));
+                } else {
+                    self.vertices.revert_insert(id, reused);
+                    self.observers.notify(|o| o.on_vertex_removed(id));
+                }
+            }
+            Change::RemoveVertex(id) => {
+                self.mark_changed(Some(id));
+                if forward {
+                    self.vertices.remove(id);
+                    self.observers.notify(|o| o.on_vertex_removed(id));
+                } else {
+                    self.vertices.revert_remove(id);
+                    self.observers.notify(|o| o.on_vertex_added(id));
+                }
+            }
+            Change::InsertHalfEdge(id, reused) => {
+                if forward {
+                    self.halfedges.insert_new();
+                } else {
+                    self.halfedges.revert_insert(id, reused);
+                }
+            }
+            Change::RemoveHalfEdge(id) => {
+                if forward {
+                    self.halfedges.remove(id);
+                } else {
+                    self.halfedges.revert_remove(id);
+                }
+            }
+            Change::InsertFace(id, reused) => {
+                if forward {
+                    self.faces.insert_new();
+                    self.observers.notify(|o| o.on_face_added(id));
+                } else {
+                    self.faces.revert_insert(id, reused);
+                    self.observers.notify(|o| o.on_face_removed(id));
+                }
+            }
+            Change::RemoveFace(id) => {
+                if forward {
+                    self.faces.remove(id);
+                    self.observers.notify(|o| o.on_face_removed(id));
+                } else {
+                    self.faces.revert_remove(id);
+                    self.observers.notify(|o| o.on_face_added(id));
+                }
+            }
+            Change::VertexPosition(id, old, new) => {
+                self.mark_changed(Some(id));
+                let position = pick(forward, old, new);
+                self.vertex_positions.replace(*id as usize, position);
+                if old != new {
+                    self.observers.notify(|o| o.on_vertex_moved(id, position));
+                }
+            }
+            Change::VertexHalfEdge(id, old, new) => {
+                self.mark_changed(Some(id));
+                self.vertex_halfedges[*id as usize] = pick(forward, old, new);
+            }
+            Change::HalfEdgeVertex(id, old, new) => {
+                self.mark_changed(old);
+                self.mark_changed(new);
+                self.halfedge_vertices[*id as usize] = pick(forward, old, new);
+            }
+            Change::HalfEdgeTwin(id, old, new) => {
+                self.halfedge_twins[*id as usize] = pick(forward, old, new);
+            }
+            Change::HalfEdgeNext(id, old, new) => {
+                self.halfedge_nexts[*id as usize] = pick(forward, old, new);
+            }
+            Change::HalfEdgeFace(id, old, new) => {
+                self.mark_changed(self.halfedge_vertices[*id as usize]);
+                self.halfedge_faces[*id as usize] = pick(forward, old, new);
+            }
+            Change::FaceHalfEdge(id, old, new) => {
+                self.face_halfedges[*id as usize] = pick(forward, old, new);
+            }
+        }
+    }
+
+    fn mark_changed(&mut self, vertex_id: Option<VertexID>) {
+        if let (Some(changed_vertices), Some(vertex_id)) = (&mut self.changed_vertices, vertex_id) {
+            changed_vertices.insert(vertex_id);
         }
     }
 
+    pub fn reserve_vertices(&mut self, additional: usize) {
+        self.vertices.reserve(additional);
+        self.vertex_positions.reserve(additional);
+        self.vertex_halfedges.reserve(additional);
+    }
+
+    pub fn reserve_halfedges(&mut self, additional: usize) {
+        self.halfedges.reserve(additional);
+        self.halfedge_vertices.reserve(additional);
+        self.halfedge_twins.reserve(additional);
+        self.halfedge_nexts.reserve(additional);
+        self.halfedge_faces.reserve(additional);
+    }
+
+    pub fn reserve_faces(&mut self, additional: usize) {
+        self.faces.reserve(additional);
+        self.face_halfedges.reserve(additional);
+    }
+
     pub fn no_vertices(&self) -> usize {
-        RefCell::borrow(&self.vertices).len()
+        self.vertices.len()
     }
 
     pub fn no_halfedges(&self) -> usize {
-        RefCell::borrow(&self.halfedges).len()
+        self.halfedges.len()
     }
 
     pub fn no_faces(&self) -> usize {
-        RefCell::borrow(&self.faces).len()
+        self.faces.len()
     }
 
     // Creates a face and the three internal half-edges and connects them to eachother and to the three given vertices
     pub fn create_face(
-        &self,
+        &mut self,
         vertex_id1: VertexID,
         vertex_id2: VertexID,
         vertex_id3: VertexID,
@@ -56,7 +353,7 @@ impl ConnectivityInfo {
     }
 
     pub fn create_face_with_existing_halfedge(
-        &self,
+        &mut self,
         vertex_id1: VertexID,
         vertex_id2: VertexID,
         vertex_id3: VertexID,
@@ -80,177 +377,329 @@ impl ConnectivityInfo {
         id
     }
 
-    pub fn new_vertex(&self, position: Vec3) -> VertexID {
-        let vertices = &mut *RefCell::borrow_mut(&self.vertices);
-        vertices
-            .insert_new(Vertex {
-                halfedge: None,
-                position,
-            })
-            .unwrap()
+    /// Creates a vertex for each of the positions, which are stored in the precision of the given positions.
+    /// Must be called before any other vertices are created.
+    pub fn new_vertices(&mut self, positions: VertexPositions) {
+        debug_assert!(self.vertex_positions.is_empty());
+        self.vertices.reserve(positions.len());
+        for _ in 0..positions.len() {
+            self.vertices.insert_new();
+        }
+        self.vertex_halfedges = std::iter::repeat_n(None, positions.len()).collect();
+        self.vertex_positions = positions;
+    }
+
+    pub fn new_vertex(&mut self, position: Vec3) -> VertexID {
+        let reused = self.vertices.has_free();
+        let id = self.vertices.insert_new();
+        self.record(Change::InsertVertex(id, reused));
+        let (old, position) = self.vertex_positions.set_or_push(*id, position);
+        self.record(Change::VertexPosition(id, old, position));
+        let old = set_or_push(&mut self.vertex_halfedges, *id, None);
+        self.record(Change::VertexHalfEdge(id, old, None));
+        self.mark_changed(Some(id));
+        self.observers.notify(|o| o.on_vertex_added(id));
+        id
+    }
+
+    pub fn position_precision(&self) -> PositionPrecision {
+        self.vertex_positions.precision()
+    }
+
+    /// Converts the stored positions to the given precision. The recorded changes are kept, since they refer to the same ids.
+    pub fn set_position_precision(&mut self, precision: PositionPrecision) {
+        if self.vertex_positions.precision() != precision {
+            self.vertex_positions = self.vertex_positions.to_precision(precision);
+        }
     }
 
     pub fn new_halfedge(
-        &self,
+        &mut self,
         vertex: Option<VertexID>,
         next: Option<HalfEdgeID>,
         face: Option<FaceID>,
     ) -> HalfEdgeID {
-        let halfedges = &mut *RefCell::borrow_mut(&self.halfedges);
-        halfedges
-            .insert_new(HalfEdge {
-                vertex,
-                twin: None,
-                next,
-                face,
-            })
-            .unwrap()
+        let reused = self.halfedges.has_free();
+        let id = self.halfedges.insert_new();
+        self.record(Change::InsertHalfEdge(id, reused));
+        let old = set_or_push(&mut self.halfedge_vertices, *id, vertex);
+        self.record(Change::HalfEdgeVertex(id, old, vertex));
+        let old = set_or_push(&mut self.halfedge_twins, *id, None);
+        self.record(Change::HalfEdgeTwin(id, old, None));
+        let old = set_or_push(&mut self.halfedge_nexts, *id, next);
+        self.record(Change::HalfEdgeNext(id, old, next));
+        let old = set_or_push(&mut self.halfedge_faces, *id, face);
+        self.record(Change::HalfEdgeFace(id, old, face));
+        self.mark_changed(vertex);
+        id
     }
 
-    fn new_face(&self) -> FaceID {
-        let faces = &mut *RefCell::borrow_mut(&self.faces);
-        faces.insert_new(Face { halfedge: None }).unwrap()
+    fn new_face(&mut self) -> FaceID {
+        let reused = self.faces.has_free();
+        let id = self.faces.insert_new();
+        self.record(Change::InsertFace(id, reused));
+        let old = set_or_push(&mut self.face_halfedges, *id, None);
+        self.record(Change::FaceHalfEdge(id, old, None));
+        self.observers.notify(|o| o.on_face_added(id));
+        id
     }
 
-    pub fn remove_vertex(&self, vertex_id: VertexID) {
-        let vertices = &mut *RefCell::borrow_mut(&self.vertices);
-        vertices.remove(vertex_id);
+    pub fn remove_vertex(&mut self, vertex_id: VertexID) {
+        self.mark_changed(Some(vertex_id));
+        if self.vertices.contains(vertex_id) {
+            self.record(Change::RemoveVertex(vertex_id));
+            self.vertices.remove(vertex_id);
+            self.observers.notify(|o| o.on_vertex_removed(vertex_id));
+        }
+    }
+
+    pub fn remove_halfedge(&mut self, halfedge_id: HalfEdgeID) {
+        if let Some(twin_id) = self.halfedge_twins[*halfedge_id as usize] {
+            let old = self.halfedge_twins[*twin_id as usize].take();
+            self.record(Change::HalfEdgeTwin(twin_id, old, None));
+        }
+        if self.halfedges.contains(halfedge_id) {
+            self.record(Change::RemoveHalfEdge(halfedge_id));
+            self.halfedges.remove(halfedge_id);
+        }
     }
 
-    pub fn remove_halfedge(&self, halfedge_id: HalfEdgeID) {
-        let halfedges = &mut *RefCell::borrow_mut(&self.halfedges);
-        let halfedge = halfedges.get(halfedge_id).unwrap();
-        if let Some(twin_id) = halfedge.twin {
-            halfedges.get_mut(twin_id).unwrap().twin = None;
+    pub fn remove_face(&mut self, face_id: FaceID) {
+        if self.faces.contains(face_id) {
+            self.record(Change::RemoveFace(face_id));
+            self.faces.remove(face_id);
+            self.observers.notify(|o| o.on_face_removed(face_id));
         }
-        halfedges.remove(halfedge_id);
     }
 
-    pub fn remove_face(&self, face_id: FaceID) {
-        let faces = &mut *RefCell::borrow_mut(&self.faces);
-        faces.remove(face_id);
+    pub fn set_vertex_halfedge(&mut self, id: VertexID, val: Option<HalfEdgeID>) {
+        self.check_vertex(id);
+        self.mark_changed(Some(id));
+        let old = std::mem::replace(&mut self.vertex_halfedges[*id as usize], val);
+        self.record(Change::VertexHalfEdge(id, old, val));
+    }
+
+    pub fn set_halfedge_next(&mut self, id: HalfEdgeID, val: Option<HalfEdgeID>) {
+        self.check_halfedge(id);
+        let old = std::mem::replace(&mut self.halfedge_nexts[*id as usize], val);
+        self.record(Change::HalfEdgeNext(id, old, val));
+    }
+
+    pub fn set_halfedge_twin(&mut self, id1: HalfEdgeID, id2: HalfEdgeID) {
+        self.check_halfedge(id1);
+        self.check_halfedge(id2);
+        let old = self.halfedge_twins[*id1 as usize].replace(id2);
+        self.record(Change::HalfEdgeTwin(id1, old, Some(id2)));
+        let old = self.halfedge_twins[*id2 as usize].replace(id1);
+        self.record(Change::HalfEdgeTwin(id2, old, Some(id1)));
+    }
+
+    pub fn set_halfedge_vertex(&mut self, id: HalfEdgeID, val: VertexID) {
+        self.check_halfedge(id);
+        self.mark_changed(self.halfedge_vertices[*id as usize]);
+        self.mark_changed(Some(val));
+        let old = self.halfedge_vertices[*id as usize].replace(val);
+        self.record(Change::HalfEdgeVertex(id, old, Some(val)));
+    }
+
+    pub fn set_halfedge_face(&mut self, id: HalfEdgeID, val: Option<FaceID>) {
+        self.check_halfedge(id);
+        self.mark_changed(self.halfedge_vertices[*id as usize]);
+        let old = std::mem::replace(&mut self.halfedge_faces[*id as usize], val);
+        self.record(Change::HalfEdgeFace(id, old, val));
     }
 
-    pub fn set_vertex_halfedge(&self, id: VertexID, val: Option<HalfEdgeID>) {
-        RefCell::borrow_mut(&self.vertices)
-            .get_mut(id)
-            .unwrap()
-            .halfedge = val;
+    pub fn set_face_halfedge(&mut self, id: FaceID, val: HalfEdgeID) {
+        self.check_face(id);
+        let old = self.face_halfedges[*id as usize].replace(val);
+        self.record(Change::FaceHalfEdge(id, old, Some(val)));
     }
 
-    pub fn set_halfedge_next(&self, id: HalfEdgeID, val: Option<HalfEdgeID>) {
-        RefCell::borrow_mut(&self.halfedges)
-            .get_mut(id)
-            .unwrap()
-            .next = val;
+    pub fn contains_vertex(&self, vertex_id: VertexID) -> bool {
+        self.vertices.contains(vertex_id)
     }
 
-    pub fn set_halfedge_twin(&self, id1: HalfEdgeID, id2: HalfEdgeID) {
-        let halfedges = &mut *RefCell::borrow_mut(&self.halfedges);
-        halfedges.get_mut(id1).unwrap().twin = Some(id2);
-        halfedges.get_mut(id2).unwrap().twin = Some(id1);
+    pub fn contains_halfedge(&self, halfedge_id: HalfEdgeID) -> bool {
+        self.halfedges.contains(halfedge_id)
     }
 
-    pub fn set_halfedge_vertex(&self, id: HalfEdgeID, val: VertexID) {
-        RefCell::borrow_mut(&self.halfedges)
-            .get_mut(id)
-            .unwrap()
-            .vertex = Some(val);
+    pub fn contains_face(&self, face_id: FaceID) -> bool {
+        self.faces.contains(face_id)
     }
 
-    pub fn set_halfedge_face(&self, id: HalfEdgeID, val: Option<FaceID>) {
-        RefCell::borrow_mut(&self.halfedges)
-            .get_mut(id)
-            .unwrap()
-            .face = val;
+    pub fn face_from_index(&self, index: u32) -> Option<FaceID> {
+        self.faces.get(index)
     }
 
-    pub fn set_face_halfedge(&self, id: FaceID, val: HalfEdgeID) {
-        RefCell::borrow_mut(&self.faces)
-            .get_mut(id)
-            .unwrap()
-            .halfedge = Some(val);
+    // Reports the use of a stale or invalid id, also in release builds, since it would otherwise silently give the data of another primitive
+    fn check_vertex(&self, vertex_id: VertexID) {
+        assert!(
+            self.contains_vertex(vertex_id),
+            "The vertex id {:?} is stale or invalid",
+            vertex_id
+        );
+    }
+
+    pub fn check_halfedge(&self, halfedge_id: HalfEdgeID) {
+        assert!(
+            self.contains_halfedge(halfedge_id),
+            "The half-edge id {:?} is stale or invalid",
+            halfedge_id
+        );
+    }
+
+    fn check_face(&self, face_id: FaceID) {
+        assert!(
+            self.contains_face(face_id),
+            "The face id {:?} is stale or invalid",
+            face_id
+        );
     }
 
     pub fn vertex_iterator(&self) -> Box<dyn Iterator<Item = VertexID>> {
-        RefCell::borrow(&self.vertices).iter()
+        self.vertices.iter()
     }
 
     pub fn halfedge_iterator(&self) -> Box<dyn Iterator<Item = HalfEdgeID>> {
-        RefCell::borrow(&self.halfedges).iter()
+        self.halfedges.iter()
     }
 
     pub fn face_iterator(&self) -> Box<dyn Iterator<Item = FaceID>> {
-        RefCell::borrow(&self.faces).iter()
+        self.faces.iter()
     }
 
     pub fn vertex_halfedge(&self, vertex_id: VertexID) -> Option<HalfEdgeID> {
-        RefCell::borrow(&self.vertices)
-            .get(vertex_id)
-            .unwrap()
-            .halfedge
-            .clone()
+        self.check_vertex(vertex_id);
+        self.vertex_halfedges[*vertex_id as usize]
     }
 
+    // Stale half-edge ids are not reported here, since removed half-edges are visited while the connectivity is updated,
+    // instead they are reported when a walker is created from a half-edge id given by the user, see Mesh::walker_from_halfedge
     pub fn halfedge(&self, halfedge_id: HalfEdgeID) -> Option<HalfEdge> {
-        RefCell::borrow(&self.halfedges)
-            .get(halfedge_id)
-            .and_then(|halfedge| Some(halfedge.clone()))
+        let i = *halfedge_id as usize;
+        if i < self.halfedge_vertices.len() {
+            Some(HalfEdge {
+                vertex: self.halfedge_vertices[i],
+                twin: self.halfedge_twins[i],
+                next: self.halfedge_nexts[i],
+                face: self.halfedge_faces[i],
+            })
+        } else {
+            None
+        }
     }
 
     pub fn face_halfedge(&self, face_id: FaceID) -> Option<HalfEdgeID> {
-        RefCell::borrow(&self.faces)
-            .get(face_id)
-            .unwrap()
-            .halfedge
-            .clone()
+        self.check_face(face_id);
+        self.face_halfedges[*face_id as usize]
     }
 
     pub fn position(&self, vertex_id: VertexID) -> Vec3 {
-        RefCell::borrow(&self.vertices)
-            .get(vertex_id)
-            .unwrap()
-            .position
+        self.check_vertex(vertex_id);
+        self.vertex_positions.get(*vertex_id as usize)
     }
 
-    pub fn set_position(&self, vertex_id: VertexID, position: Vec3) {
-        RefCell::borrow_mut(&self.vertices)
-            .get_mut(vertex_id)
-            .unwrap()
-            .position = position;
+    pub fn set_position(&mut self, vertex_id: VertexID, position: Vec3) {
+        self.check_vertex(vertex_id);
+        self.mark_changed(Some(vertex_id));
+        let old = self.vertex_positions.replace(*vertex_id as usize, position);
+        let position = self.vertex_positions.get(*vertex_id as usize);
+        self.record(Change::VertexPosition(vertex_id, old, position));
+        self.observers
+            .notify(|o| o.on_vertex_moved(vertex_id, position));
+    }
+
+    /// Reassigns the ids of the vertices, half-edges and faces such that they are dense, ie. numbered from zero without gaps,
+    /// while keeping their relative order. Returns the mapping from the old to the new ids.
+    /// The recorded changes are discarded, since they refer to the old ids.
+    pub fn compact(
+        &mut self,
+    ) -> (
+        HashMap<VertexID, VertexID>,
+        HashMap<HalfEdgeID, HalfEdgeID>,
+        HashMap<FaceID, FaceID>,
+    ) {
+        let vertex_map = self.vertices.dense_map();
+        let halfedge_map = self.halfedges.dense_map();
+        let face_map = self.faces.dense_map();
+        let map_vertex = |id: Option<VertexID>| id.and_then(|id| vertex_map.get(&id).cloned());
+        let map_halfedge =
+            |id: Option<HalfEdgeID>| id.and_then(|id| halfedge_map.get(&id).cloned());
+        let map_face = |id: Option<FaceID>| id.and_then(|id| face_map.get(&id).cloned());
+
+        let vertices: Vec<VertexID> = self.vertices.iter().collect();
+        self.vertex_positions = self
+            .vertex_positions
+            .select(vertices.iter().map(|id| **id as usize));
+        self.vertex_halfedges = vertices
+            .iter()
+            .map(|id| map_halfedge(self.vertex_halfedges[**id as usize]))
+            .collect();
+
+        let halfedges: Vec<HalfEdgeID> = self.halfedges.iter().collect();
+        self.halfedge_vertices = halfedges
+            .iter()
+            .map(|id| map_vertex(self.halfedge_vertices[**id as usize]))
+            .collect();
+        self.halfedge_twins = halfedges
+            .iter()
+            .map(|id| map_halfedge(self.halfedge_twins[**id as usize]))
+            .collect();
+        self.halfedge_nexts = halfedges
+            .iter()
+            .map(|id| map_halfedge(self.halfedge_nexts[**id as usize]))
+            .collect();
+        self.halfedge_faces = halfedges
+            .iter()
+            .map(|id| map_face(self.halfedge_faces[**id as usize]))
+            .collect();
+
+        let faces: Vec<FaceID> = self.faces.iter().collect();
+        self.face_halfedges = faces
+            .iter()
+            .map(|id| map_halfedge(self.face_halfedges[**id as usize]))
+            .collect();
+
+        if let Some(changed_vertices) = &mut self.changed_vertices {
+            *changed_vertices = vertex_map.values().copied().collect();
+        }
+        if let Some(journal) = &mut self.journal {
+            *journal = Journal::default();
+        }
+        self.vertices = Slots::with_len(vertices.len());
+        self.halfedges = Slots::with_len(halfedges.len());
+        self.faces = Slots::with_len(faces.len());
+        (vertex_map, halfedge_map, face_map)
     }
 }
 
 impl std::fmt::Debug for ConnectivityInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         writeln!(f, "**** VERTICES: ****")?;
-        let vertices = RefCell::borrow(&self.vertices);
-        writeln!(f, "Count: {}", vertices.len())?;
-        for id in vertices.iter() {
-            writeln!(f, "{}: {:?}", id, vertices.get(id))?;
+        writeln!(f, "Count: {}", self.vertices.len())?;
+        for id in self.vertices.iter() {
+            writeln!(
+                f,
+                "{}: position: {:?}, halfedge: {:?}",
+                id,
+                self.position(id),
+                self.vertex_halfedge(id)
+            )?;
         }
         writeln!(f, "**** Halfedges: ****")?;
-        let halfedges = RefCell::borrow(&self.halfedges);
-        writeln!(f, "Count: {}", halfedges.len())?;
-        for id in halfedges.iter() {
-            writeln!(f, "{}: {:?}", id, halfedges.get(id))?;
+        writeln!(f, "Count: {}", self.halfedges.len())?;
+        for id in self.halfedges.iter() {
+            writeln!(f, "{}: {:?}", id, self.halfedge(id))?;
         }
         writeln!(f, "**** Faces: ****")?;
-        let faces = RefCell::borrow(&self.faces);
-        writeln!(f, "Count: {}", faces.len())?;
-        for id in faces.iter() {
-            writeln!(f, "{}: {:?}", id, faces.get(id))?;
+        writeln!(f, "Count: {}", self.faces.len())?;
+        for id in self.faces.iter() {
+            writeln!(f, "{}: halfedge: {:?}", id, self.face_halfedge(id))?;
         }
         Ok(())
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-pub struct Vertex {
-    pub halfedge: Option<HalfEdgeID>,
-    pub position: Vec3,
-}
-
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct HalfEdge {
     pub vertex: Option<VertexID>,
@@ -259,60 +708,408 @@ pub struct HalfEdge {
     pub face: Option<FaceID>,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-pub struct Face {
-    pub halfedge: Option<HalfEdgeID>,
+/// The vertex positions, stored in either double or single precision, see [PositionPrecision].
+/// The positions are always given and returned in double precision, so positions stored in single precision are rounded when they are set.
+#[derive(Debug, Clone)]
+pub(super) enum VertexPositions {
+    F64(CowVec<Vec3>),
+    F32(CowVec<Vector3<f32>>),
 }
 
-#[derive(Debug, Clone)]
-struct IDMap<K, V> {
-    values: Vec<V>,
-    free: Vec<K>,
+impl VertexPositions {
+    pub fn precision(&self) -> PositionPrecision {
+        match self {
+            VertexPositions::F64(_) => PositionPrecision::F64,
+            VertexPositions::F32(_) => PositionPrecision::F32,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            VertexPositions::F64(values) => values.len(),
+            VertexPositions::F32(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            VertexPositions::F64(values) => values.is_empty(),
+            VertexPositions::F32(values) => values.is_empty(),
+        }
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        match self {
+            VertexPositions::F64(values) => values.reserve(additional),
+            VertexPositions::F32(values) => values.reserve(additional),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Vec3 {
+        match self {
+            VertexPositions::F64(values) => values[index],
+            VertexPositions::F32(values) => values[index].cast::<f64>().unwrap(),
+        }
+    }
+
+    /// Sets the position and returns the old position.
+    pub fn replace(&mut self, index: usize, position: Vec3) -> Vec3 {
+        match self {
+            VertexPositions::F64(values) => std::mem::replace(&mut values[index], position),
+            VertexPositions::F32(values) => std::mem::replace(&mut values[index], to_f32(position))
+                .cast::<f64>()
+                .unwrap(),
+        }
+    }
+
+    /// Sets or pushes the position and returns the old and the stored position, where the old position is the stored position if it is pushed.
+    pub fn set_or_push(&mut self, index: u32, position: Vec3) -> (Vec3, Vec3) {
+        match self {
+            VertexPositions::F64(values) => (set_or_push(values, index, position), position),
+            VertexPositions::F32(values) => {
+                let position = to_f32(position);
+                (
+                    set_or_push(values, index, position).cast::<f64>().unwrap(),
+                    position.cast::<f64>().unwrap(),
+                )
+            }
+        }
+    }
+
+    /// Returns the positions at the given indices in the same precision.
+    pub fn select(&self, indices: impl Iterator<Item = usize>) -> Self {
+        match self {
+            VertexPositions::F64(values) => {
+                VertexPositions::F64(indices.map(|i| values[i]).collect())
+            }
+            VertexPositions::F32(values) => {
+                VertexPositions::F32(indices.map(|i| values[i]).collect())
+            }
+        }
+    }
+
+    /// Returns the positions converted to the given precision.
+    pub fn to_precision(&self, precision: PositionPrecision) -> Self {
+        match (self, precision) {
+            (VertexPositions::F64(values), PositionPrecision::F32) => {
+                VertexPositions::F32(values.iter().map(|p| to_f32(*p)).collect())
+            }
+            (VertexPositions::F32(values), PositionPrecision::F64) => {
+                VertexPositions::F64(values.iter().map(|p| p.cast::<f64>().unwrap()).collect())
+            }
+            _ => self.clone(),
+        }
+    }
 }
 
-use std::collections::HashSet;
+impl FromIterator<Vec3> for VertexPositions {
+    fn from_iter<I: IntoIterator<Item = Vec3>>(iter: I) -> Self {
+        VertexPositions::F64(iter.into_iter().collect())
+    }
+}
+
+fn to_f32(position: Vec3) -> Vector3<f32> {
+    Vector3::new(position.x as f32, position.y as f32, position.z as f32)
+}
+
+/// Sets or pushes the value and returns the old value, or the given value if it is pushed.
+fn set_or_push<T: Copy>(values: &mut CowVec<T>, index: u32, value: T) -> T {
+    let index = index as usize;
+    if index < values.len() {
+        std::mem::replace(&mut values[index], value)
+    } else {
+        values.push(value);
+        value
+    }
+}
 
-impl<K: ID + 'static, V> IDMap<K, V> {
+/// Keeps track of which ids are in use. Removed ids are kept in a free list and reused before new ids are created.
+/// Each index has a generation which is incremented when the id is removed, so that a reused index results in a new id.
+#[derive(Debug, Clone)]
+struct Slots<K> {
+    alive: CowVec<bool>,
+    generations: CowVec<u32>,
+    free: CowVec<u32>,
+    phantom: std::marker::PhantomData<K>,
+}
+
+impl<K: ID + 'static> Slots<K> {
     pub fn with_capacity(capacity: usize) -> Self {
-        IDMap {
-            values: Vec::with_capacity(capacity),
-            free: Vec::new(),
+        Slots {
+            alive: CowVec::with_capacity(capacity),
+            generations: CowVec::with_capacity(capacity),
+            free: CowVec::with_capacity(0),
+            phantom: std::marker::PhantomData,
         }
     }
 
-    pub fn insert_new(&mut self, value: V) -> Option<K> {
-        let id = if let Some(i) = self.free.pop() {
-            self.values[*i as usize] = value;
-            i
+    /// Returns slots where the given number of ids are in use and there are no free ids.
+    pub fn with_len(len: usize) -> Self {
+        Slots {
+            alive: std::iter::repeat_n(true, len).collect(),
+            generations: std::iter::repeat_n(0, len).collect(),
+            free: CowVec::with_capacity(0),
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns a mapping from the ids in use to dense ids numbered from zero in the same order.
+    pub fn dense_map(&self) -> HashMap<K, K> {
+        self.iter()
+            .enumerate()
+            .map(|(i, id)| (id, unsafe { K::new(i as u32) }))
+            .collect()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        let additional = additional.saturating_sub(self.free.len());
+        self.alive.reserve(additional);
+        self.generations.reserve(additional);
+    }
+
+    pub fn insert_new(&mut self) -> K {
+        if let Some(i) = self.free.pop() {
+            self.alive[i as usize] = true;
+            unsafe { K::new_with_generation(i, self.generations[i as usize]) }
         } else {
-            self.values.push(value);
-            unsafe { K::new(self.values.len() as u32 - 1) }
-        };
-        Some(id)
+            self.alive.push(true);
+            self.generations.push(0);
+            unsafe { K::new(self.alive.len() as u32 - 1) }
+        }
     }
 
     pub fn remove(&mut self, id: K) {
-        self.free.push(id);
+        if self.contains(id) {
+            let i = *id as usize;
+            self.alive[i] = false;
+            self.generations[i] = self.generations[i].wrapping_add(1);
+            self.free.push(*id);
+        }
     }
 
-    pub fn len(&self) -> usize {
-        self.values.len() - self.free.len()
+    /// Returns whether an id from the free list is reused by the next call to [Slots::insert_new].
+    pub fn has_free(&self) -> bool {
+        !self.free.is_empty()
+    }
+
+    /// Reverts the call to [Slots::insert_new] which returned the given id, where `reused` is whether the id was taken from the free list.
+    pub fn revert_insert(&mut self, id: K, reused: bool) {
+        if reused {
+            self.alive[*id as usize] = false;
+            self.free.push(*id);
+        } else {
+            debug_assert_eq!(*id as usize + 1, self.alive.len());
+            self.alive.pop();
+            self.generations.pop();
+        }
+    }
+
+    /// Reverts the call to [Slots::remove] with the given id.
+    pub fn revert_remove(&mut self, id: K) {
+        let i = *id as usize;
+        debug_assert_eq!(self.free.last(), Some(&*id));
+        self.free.pop();
+        self.alive[i] = true;
+        self.generations[i] = self.generations[i].wrapping_sub(1);
     }
 
-    pub fn get(&self, id: K) -> Option<&V> {
-        self.values.get(*id as usize)
+    /// Returns whether the id is in use, ie. that it is not removed and not a stale id of a removed primitive.
+    pub fn contains(&self, id: K) -> bool {
+        let i = *id as usize;
+        i < self.alive.len() && self.alive[i] && self.generations[i] == id.generation()
     }
 
-    pub fn get_mut(&mut self, id: K) -> Option<&mut V> {
-        self.values.get_mut(*id as usize)
+    /// Returns the id in use with the given index, if any.
+    pub fn get(&self, index: u32) -> Option<K> {
+        let i = index as usize;
+        (i < self.alive.len() && self.alive[i])
+            .then(|| unsafe { K::new_with_generation(index, self.generations[i]) })
+    }
+
+    pub fn len(&self) -> usize {
+        self.alive.len() - self.free.len()
     }
 
     pub fn iter(&self) -> Box<dyn Iterator<Item = K>> {
-        let free: HashSet<_> = self.free.iter().cloned().collect();
-        Box::new(
-            (0..self.values.len() as u32)
-                .map(|i| unsafe { K::new(i) })
-                .filter(move |i| !free.contains(&i)),
-        )
+        let ids: Vec<K> = self
+            .alive
+            .iter()
+            .zip(self.generations.iter())
+            .enumerate()
+            .filter(|(_, (alive, _))| **alive)
+            .map(|(i, (_, generation))| unsafe { K::new_with_generation(i as u32, *generation) })
+            .collect();
+        Box::new(ids.into_iter())
+    }
+}
+
+/// The number of elements in each chunk of a [CowVec].
+const CHUNK_SIZE: usize = 1024;
+
+/// A vector which is split into chunks that are shared between clones and copied when they are changed.
+/// A whole buffer can also be moved into the vector without copying, see [CowVec::from_vec],
+/// in which case it is shared as a whole until the vector is changed the first time and then split into chunks.
+#[derive(Debug, Clone)]
+pub(super) struct CowVec<T> {
+    buffer: Arc<Vec<T>>,
+    chunks: Vec<Arc<Vec<T>>>,
+    len: usize,
+}
+
+impl<T: Clone> CowVec<T> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        CowVec {
+            buffer: Arc::new(Vec::new()),
+            chunks: Vec::with_capacity(capacity.div_ceil(CHUNK_SIZE)),
+            len: 0,
+        }
+    }
+
+    /// Moves the given values into a new vector without copying them.
+    pub fn from_vec(values: Vec<T>) -> Self {
+        CowVec {
+            len: values.len(),
+            buffer: Arc::new(values),
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Splits the buffer moved into the vector by [CowVec::from_vec], if any, into chunks before the vector is changed.
+    fn split_buffer(&mut self) {
+        if !self.buffer.is_empty() {
+            let buffer = std::mem::take(&mut self.buffer);
+            let buffer = Arc::try_unwrap(buffer).unwrap_or_else(|buffer| (*buffer).clone());
+            self.chunks = buffer
+                .chunks(CHUNK_SIZE)
+                .map(|chunk| {
+                    let mut values = Vec::with_capacity(CHUNK_SIZE);
+                    values.extend_from_slice(chunk);
+                    Arc::new(values)
+                })
+                .collect();
+        }
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.chunks.reserve(
+            (self.len + additional)
+                .div_ceil(CHUNK_SIZE)
+                .saturating_sub(self.chunks.len()),
+        );
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.buffer
+            .last()
+            .or_else(|| self.chunks.last().and_then(|chunk| chunk.last()))
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.split_buffer();
+        if self.len.is_multiple_of(CHUNK_SIZE) {
+            self.chunks.push(Arc::new(Vec::with_capacity(CHUNK_SIZE)));
+        }
+        Arc::make_mut(self.chunks.last_mut().unwrap()).push(value);
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.split_buffer();
+        let chunk = Arc::make_mut(self.chunks.last_mut()?);
+        let value = chunk.pop();
+        if chunk.is_empty() {
+            self.chunks.pop();
+        }
+        self.len -= 1;
+        value
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.buffer
+            .iter()
+            .chain(self.chunks.iter().flat_map(|chunk| chunk.iter()))
+    }
+}
+
+impl<T: Clone> FromIterator<T> for CowVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut values = CowVec::with_capacity(0);
+        for value in iter {
+            values.push(value);
+        }
+        values
+    }
+}
+
+impl<T> std::ops::Index<usize> for CowVec<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        if self.buffer.is_empty() {
+            &self.chunks[index / CHUNK_SIZE][index % CHUNK_SIZE]
+        } else {
+            &self.buffer[index]
+        }
+    }
+}
+
+impl<T: Clone> std::ops::IndexMut<usize> for CowVec<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.split_buffer();
+        &mut Arc::make_mut(&mut self.chunks[index / CHUNK_SIZE])[index % CHUNK_SIZE]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cow_vec() {
+        let mut values: CowVec<usize> = (0..3 * CHUNK_SIZE).collect();
+        let clone = values.clone();
+        values[CHUNK_SIZE + 1] = 0;
+        values.push(7);
+        assert!(Arc::ptr_eq(&values.chunks[0], &clone.chunks[0]));
+        assert!(!Arc::ptr_eq(&values.chunks[1], &clone.chunks[1]));
+        assert_eq!(clone[CHUNK_SIZE + 1], CHUNK_SIZE + 1);
+        assert_eq!(clone.len(), 3 * CHUNK_SIZE);
+        assert_eq!(values.len(), 3 * CHUNK_SIZE + 1);
+        assert_eq!(values.pop(), Some(7));
+        assert_eq!(values.last(), Some(&(3 * CHUNK_SIZE - 1)));
+        assert_eq!(values.iter().filter(|value| **value == 0).count(), 2);
+    }
+
+    #[test]
+    fn test_cow_vec_from_vec() {
+        let buffer: Vec<usize> = (0..2 * CHUNK_SIZE + 1).collect();
+        let pointer = buffer.as_ptr();
+        let mut values = CowVec::from_vec(buffer);
+        assert_eq!(values.buffer.as_ptr(), pointer);
+        assert_eq!(values.len(), 2 * CHUNK_SIZE + 1);
+        assert_eq!(values[CHUNK_SIZE + 1], CHUNK_SIZE + 1);
+        assert_eq!(values.last(), Some(&(2 * CHUNK_SIZE)));
+
+        let clone = values.clone();
+        values[CHUNK_SIZE + 1] = 0;
+        assert!(values.buffer.is_empty());
+        assert_eq!(values.chunks.len(), 3);
+        assert_eq!(values[CHUNK_SIZE + 1], 0);
+        assert_eq!(clone[CHUNK_SIZE + 1], CHUNK_SIZE + 1);
+        values.push(7);
+        assert_eq!(values.len(), 2 * CHUNK_SIZE + 2);
+        assert_eq!(values.pop(), Some(7));
+        assert!(values.iter().eq(clone
+            .iter()
+            .take(CHUNK_SIZE + 1)
+            .chain([0].iter())
+            .chain(clone.iter().skip(CHUNK_SIZE + 2))));
     }
 }
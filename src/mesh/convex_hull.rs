@@ -0,0 +1,356 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+/// # Convex hull
+impl Mesh {
+    ///
+    /// Constructs the convex hull of the given points, ie. the smallest convex mesh that contains all of the points, for example to use as a collision hull or a bounding proxy.
+    /// The hull is closed and consistently oriented with the normals pointing outwards, and its vertices are the points that are corners of the hull.
+    /// Points that are inside the hull or on a face of the hull are not part of the mesh.
+    ///
+    /// The hull is computed using the quickhull algorithm.
+    /// Points which are not finite, ie. with a NaN or infinite coordinate, are ignored.
+    /// If all of the points are on a plane, and therefore do not span a volume, the returned mesh is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// // The corners of a cube and a point in the center
+    /// let mut points = vec![vec3(0.0, 0.0, 0.0)];
+    /// for i in 0..8 {
+    ///     points.push(vec3((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64) * 2.0 - vec3(1.0, 1.0, 1.0));
+    /// }
+    /// let mesh = Mesh::convex_hull(&points);
+    /// assert_eq!(mesh.no_vertices(), 8);
+    /// assert_eq!(mesh.no_faces(), 12);
+    /// assert!((mesh.volume().unwrap() - 8.0).abs() < 0.000001);
+    /// ```
+    ///
+    pub fn convex_hull(points: &[Vec3]) -> Self {
+        Self::from_used_positions(points, &quickhull(points))
+    }
+}
+
+struct HullFace {
+    vertices: [usize; 3],
+    normal: Vec3,
+    /// The faces on the other side of the edges from `vertices[i]` to `vertices[(i + 1) % 3]`.
+    neighbours: [usize; 3],
+    /// The points that are on the outside of the face and not yet assigned to another face.
+    outside: Vec<usize>,
+    is_removed: bool,
+}
+
+impl HullFace {
+    fn new(points: &[Vec3], vertices: [usize; 3]) -> Self {
+        let [a, b, c] = vertices.map(|v| points[v]);
+        Self {
+            vertices,
+            normal: (b - a).cross(c - a).normalize(),
+            neighbours: [usize::MAX; 3],
+            outside: Vec::new(),
+            is_removed: false,
+        }
+    }
+
+    /// The signed distance from the plane of the face to the point, which is positive if the point is on the outside.
+    fn distance(&self, points: &[Vec3], point: Vec3) -> f64 {
+        self.normal.dot(point - points[self.vertices[0]])
+    }
+
+    /// The index of the edge from `a` to `b`.
+    fn edge(&self, a: usize, b: usize) -> usize {
+        (0..3)
+            .find(|i| self.vertices[*i] == a && self.vertices[(i + 1) % 3] == b)
+            .unwrap()
+    }
+}
+
+///
+/// Returns the counterclockwise triangles of the convex hull of the points as indices into the points, or no triangles if the points do not span a volume.
+/// Points which are not finite are ignored.
+///
+fn quickhull(points: &[Vec3]) -> Vec<[usize; 3]> {
+    let candidates: Vec<usize> = (0..points.len())
+        .filter(|i| {
+            points[*i].x.is_finite() && points[*i].y.is_finite() && points[*i].z.is_finite()
+        })
+        .collect();
+    let scale = candidates
+        .iter()
+        .map(|i| {
+            points[*i]
+                .x
+                .abs()
+                .max(points[*i].y.abs())
+                .max(points[*i].z.abs())
+        })
+        .fold(0.0, f64::max);
+    let epsilon = 1e-10 * scale.max(1.0);
+    let Some(mut faces) = initial_simplex(points, &candidates, epsilon) else {
+        return Vec::new();
+    };
+
+    // Assign each point to the first of the given faces which it is on the outside of
+    let assign = |faces: &mut [HullFace], new_faces: &[usize], candidates: &[usize]| {
+        for i in candidates.iter().copied() {
+            if let Some(f) = new_faces
+                .iter()
+                .find(|f| faces[**f].distance(points, points[i]) > epsilon)
+            {
+                faces[*f].outside.push(i);
+            }
+        }
+    };
+    assign(&mut faces, &[0, 1, 2, 3], &candidates);
+
+    // The faces which might have points on the outside and the removed faces which can be reused
+    let mut worklist: Vec<usize> = (0..4).collect();
+    let mut free = Vec::new();
+    while let Some(f) = worklist.pop() {
+        if faces[f].is_removed || faces[f].outside.is_empty() {
+            continue;
+        }
+
+        // The point furthest away from the face is a corner of the hull
+        let eye = faces[f]
+            .outside
+            .iter()
+            .copied()
+            .max_by(|a, b| {
+                let face = &faces[f];
+                face.distance(points, points[*a])
+                    .total_cmp(&face.distance(points, points[*b]))
+            })
+            .unwrap();
+
+        // Remove the faces that can be seen from the point, which are connected, and find the horizon, ie. the boundary of the removed faces
+        let mut candidates = Vec::new();
+        let mut horizon = Vec::new();
+        let mut stack = vec![f];
+        faces[f].is_removed = true;
+        while let Some(f) = stack.pop() {
+            candidates.append(&mut faces[f].outside);
+            free.push(f);
+            for i in 0..3 {
+                let neighbour = faces[f].neighbours[i];
+                if faces[neighbour].is_removed {
+                    continue;
+                }
+                if faces[neighbour].distance(points, points[eye]) > epsilon {
+                    faces[neighbour].is_removed = true;
+                    stack.push(neighbour);
+                } else {
+                    let a = faces[f].vertices[i];
+                    let b = faces[f].vertices[(i + 1) % 3];
+                    horizon.push((a, b, neighbour));
+                }
+            }
+        }
+
+        // Connect the horizon to the point with new faces, which reuse the removed faces
+        let mut new_faces = Vec::with_capacity(horizon.len());
+        let mut starting_at = HashMap::with_capacity(horizon.len());
+        for (a, b, neighbour) in horizon.iter().copied() {
+            let face = HullFace::new(points, [a, b, eye]);
+            let new_face = if let Some(new_face) = free.pop() {
+                faces[new_face] = face;
+                new_face
+            } else {
+                faces.push(face);
+                faces.len() - 1
+            };
+            faces[new_face].neighbours[0] = neighbour;
+            let edge = faces[neighbour].edge(b, a);
+            faces[neighbour].neighbours[edge] = new_face;
+            starting_at.insert(a, new_face);
+            new_faces.push(new_face);
+        }
+        for new_face in new_faces.iter().copied() {
+            let b = faces[new_face].vertices[1];
+            let next = starting_at[&b];
+            faces[new_face].neighbours[1] = next;
+            faces[next].neighbours[2] = new_face;
+        }
+
+        candidates.retain(|i| *i != eye);
+        assign(&mut faces, &new_faces, &candidates);
+        worklist.extend(new_faces);
+    }
+
+    faces
+        .into_iter()
+        .filter(|face| !face.is_removed)
+        .map(|face| face.vertices)
+        .collect()
+}
+
+///
+/// Returns the faces of a tetrahedron spanned by four of the candidate points with the normals pointing outwards, or `None` if the points do not span a volume.
+///
+fn initial_simplex(points: &[Vec3], candidates: &[usize], epsilon: f64) -> Option<Vec<HullFace>> {
+    // Find the two points furthest apart among the extreme points along each axis
+    let mut extremes = Vec::new();
+    for axis in [0, 1, 2] {
+        let compare = |a: &&usize, b: &&usize| points[**a][axis].total_cmp(&points[**b][axis]);
+        extremes.push(*candidates.iter().min_by(compare)?);
+        extremes.push(*candidates.iter().max_by(compare)?);
+    }
+    let mut v0 = extremes[0];
+    let mut v1 = extremes[1];
+    for a in extremes.iter() {
+        for b in extremes.iter() {
+            if points[*a].distance2(points[*b]) > points[v0].distance2(points[v1]) {
+                v0 = *a;
+                v1 = *b;
+            }
+        }
+    }
+
+    // Then the point furthest from the line through the two points and the point furthest from the plane through the three points
+    let direction = (points[v1] - points[v0]).normalize();
+    let line_distance = |i: usize| {
+        let d = points[i] - points[v0];
+        (d - direction * d.dot(direction)).magnitude()
+    };
+    let v2 = *candidates
+        .iter()
+        .max_by(|a, b| line_distance(**a).total_cmp(&line_distance(**b)))?;
+    if points[v0].distance(points[v1]) <= epsilon || line_distance(v2) <= epsilon {
+        return None;
+    }
+    let normal = (points[v1] - points[v0])
+        .cross(points[v2] - points[v0])
+        .normalize();
+    let plane_distance = |i: usize| normal.dot(points[i] - points[v0]);
+    let v3 = *candidates.iter().max_by(|a, b| {
+        plane_distance(**a)
+            .abs()
+            .total_cmp(&plane_distance(**b).abs())
+    })?;
+    if plane_distance(v3).abs() <= epsilon {
+        return None;
+    }
+
+    let (v1, v2) = if plane_distance(v3) > 0.0 {
+        (v2, v1)
+    } else {
+        (v1, v2)
+    };
+    let mut faces: Vec<HullFace> = [[v0, v1, v2], [v0, v3, v1], [v1, v3, v2], [v2, v3, v0]]
+        .into_iter()
+        .map(|vertices| HullFace::new(points, vertices))
+        .collect();
+    for f in 0..4 {
+        for i in 0..3 {
+            let a = faces[f].vertices[i];
+            let b = faces[f].vertices[(i + 1) % 3];
+            faces[f].neighbours[i] = (0..4)
+                .find(|g| {
+                    *g != f && faces[*g].vertices.contains(&a) && faces[*g].vertices.contains(&b)
+                })
+                .unwrap();
+        }
+    }
+    Some(faces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convex_hull_of_sphere_points() {
+        let sphere = Mesh::icosphere(1);
+        let mut points: Vec<Vec3> = sphere
+            .vertex_iter()
+            .map(|v| sphere.vertex_position(v))
+            .collect();
+        // Points inside the sphere
+        for i in 0..100 {
+            let i = i as f64;
+            points.push(0.5 * vec3((1.3 * i).sin(), (2.9 * i).cos(), (0.7 * i).sin()));
+        }
+
+        let mesh = Mesh::convex_hull(&points);
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.no_vertices(), sphere.no_vertices());
+        assert_eq!(mesh.no_faces(), sphere.no_faces());
+        assert!((mesh.volume().unwrap() - sphere.volume().unwrap()).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_convex_hull_of_random_points() {
+        let points: Vec<Vec3> = (0..500)
+            .map(|i| {
+                let i = i as f64;
+                vec3((12.9898 * i).sin(), (78.233 * i).sin(), (37.719 * i).sin())
+            })
+            .collect();
+        let mesh = Mesh::convex_hull(&points);
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.euler_characteristic(), 2);
+        for face_id in mesh.face_iter() {
+            let normal = mesh.face_normal(face_id);
+            let position = mesh.face_center(face_id);
+            for point in points.iter() {
+                assert!(normal.dot(point - position) < 0.000001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_convex_hull_of_many_points_on_a_sphere() {
+        // All of the points are corners of the hull, which is the worst case for the number of faces removed and added
+        let n = 5000;
+        let points: Vec<Vec3> = (0..n)
+            .map(|i| {
+                let z = 1.0 - 2.0 * (i as f64 + 0.5) / n as f64;
+                let r = (1.0 - z * z).sqrt();
+                let phi = 2.399963229728653 * i as f64;
+                vec3(r * phi.cos(), r * phi.sin(), z)
+            })
+            .collect();
+        let mesh = Mesh::convex_hull(&points);
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_vertices(), n);
+        assert_eq!(mesh.no_faces(), 2 * n - 4);
+    }
+
+    #[test]
+    fn test_convex_hull_ignores_points_which_are_not_finite() {
+        let points = [
+            vec3(f64::NAN, 0.0, 0.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(0.0, f64::INFINITY, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            vec3(0.0, 0.0, 1.0),
+        ];
+        let mesh = Mesh::convex_hull(&points);
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_vertices(), 4);
+        assert_eq!(mesh.no_faces(), 4);
+        assert!((mesh.volume().unwrap() - 1.0 / 6.0).abs() < 0.000001);
+
+        assert_eq!(
+            Mesh::convex_hull(&[vec3(f64::NAN, 0.0, 0.0); 4]).no_faces(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_convex_hull_of_planar_points() {
+        let points = [
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(0.0, 0.0, 1.0),
+            vec3(1.0, 0.0, 1.0),
+        ];
+        assert_eq!(Mesh::convex_hull(&points).no_faces(), 0);
+        assert_eq!(Mesh::convex_hull(&[]).no_faces(), 0);
+    }
+}
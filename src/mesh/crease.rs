@@ -0,0 +1,116 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+/// # Creases
+///
+/// An edge can be marked as a crease, ie. a sharp edge, which is then respected by the operations changing the mesh:
+/// [Mesh::smooth_vertices] only moves vertices on a crease along the crease and does not move vertices where three or more creases meet,
+/// [Mesh::flip_edges] does not flip creases and [Mesh::collapse_small_faces] does not collapse edges if it moves a vertex away from a crease.
+/// Splitting a crease with [Mesh::split_edge] results in two creases.
+///
+impl Mesh {
+    /// Marks the given edge as a crease or removes the mark.
+    pub fn set_crease(&mut self, halfedge_id: HalfEdgeID, is_crease: bool) {
+        let key = self.ordered_edge_vertices(halfedge_id);
+        if is_crease {
+            self.creases.insert(key);
+        } else {
+            self.creases.remove(&key);
+        }
+    }
+
+    /// Returns whether or not the given edge is marked as a crease.
+    pub fn is_crease(&self, halfedge_id: HalfEdgeID) -> bool {
+        !self.creases.is_empty()
+            && self
+                .creases
+                .contains(&self.ordered_edge_vertices(halfedge_id))
+    }
+
+    /// Returns the number of creases connected to the given vertex.
+    pub fn vertex_crease_count(&self, vertex_id: VertexID) -> usize {
+        if self.creases.is_empty() {
+            return 0;
+        }
+        self.vertex_halfedge_iter(vertex_id)
+            .filter(|halfedge_id| self.is_crease(*halfedge_id))
+            .count()
+    }
+
+    /// If the edge between the `from` vertices is a crease, the crease mark is moved to the edges between each of the `to` pairs of vertices.
+    pub(crate) fn replace_crease(
+        &mut self,
+        from: (VertexID, VertexID),
+        to: impl IntoIterator<Item = (VertexID, VertexID)>,
+    ) {
+        let sort = |(a, b): (VertexID, VertexID)| if a < b { (a, b) } else { (b, a) };
+        if self.creases.remove(&sort(from)) {
+            for (a, b) in to {
+                if a != b {
+                    self.creases.insert(sort((a, b)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    #[test]
+    fn test_crease_is_split() {
+        let mut mesh = crate::test_utility::square();
+        let halfedge_id = mesh.halfedge_iter().next().unwrap();
+        let (v0, v1) = mesh.edge_vertices(halfedge_id);
+        mesh.set_crease(halfedge_id, true);
+        assert!(mesh.is_crease(halfedge_id));
+
+        let vertex_id = mesh.split_edge(halfedge_id, vec3(0.5, 0.5, 0.5));
+
+        assert!(mesh.is_crease(mesh.connecting_edge(v0, vertex_id).unwrap()));
+        assert!(mesh.is_crease(mesh.connecting_edge(vertex_id, v1).unwrap()));
+        assert_eq!(mesh.vertex_crease_count(vertex_id), 2);
+        mesh.set_crease(mesh.connecting_edge(v0, vertex_id).unwrap(), false);
+        assert_eq!(mesh.vertex_crease_count(vertex_id), 1);
+    }
+
+    #[test]
+    fn test_smoothing_respects_creases() {
+        let mut mesh = crate::test_utility::cube();
+        for edge_id in mesh.edge_iter().collect::<Vec<_>>() {
+            if mesh.edge_sqr_length(edge_id.into()) < 4.1 {
+                mesh.set_crease(edge_id.into(), true);
+            }
+        }
+        let positions: Vec<Vec3> = mesh
+            .vertex_iter()
+            .map(|vertex_id| mesh.vertex_position(vertex_id))
+            .collect();
+
+        mesh.smooth_vertices(0.5);
+
+        for (vertex_id, p) in mesh.vertex_iter().zip(positions) {
+            assert_eq!(mesh.vertex_position(vertex_id), p);
+        }
+    }
+
+    #[test]
+    fn test_flipping_respects_creases() {
+        let mut mesh: Mesh = TriMesh::sphere(8).into();
+        let mut creases = Vec::new();
+        for edge_id in mesh.edge_iter().collect::<Vec<_>>() {
+            let (v0, v1) = mesh.edge_vertices(edge_id.into());
+            creases.push((v0, v1));
+            mesh.set_crease(edge_id.into(), true);
+        }
+
+        mesh.flip_edges(0.5);
+
+        for (v0, v1) in creases {
+            assert!(mesh.connecting_edge(v0, v1).is_some());
+        }
+    }
+}
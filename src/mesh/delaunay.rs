@@ -0,0 +1,98 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::operations::triangulate_points;
+
+/// # Delaunay triangulation
+impl Mesh {
+    ///
+    /// Constructs a mesh from a point cloud by projecting the points onto the plane with the given normal
+    /// and computing the Delaunay triangulation of the projected points (see [triangulate_points]).
+    /// The vertices of the mesh keep the original positions of the points, so for example a terrain can be constructed from scattered height samples by using the up direction as the normal.
+    /// The faces are oriented such that the face normals point in the same direction as the given normal.
+    ///
+    /// Points that project onto the same position as another point are not part of the mesh, and if all of the points project onto a line, the returned mesh is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// // A terrain from height samples on a grid
+    /// let mut points = Vec::new();
+    /// for i in 0..10 {
+    ///     for j in 0..10 {
+    ///         points.push(vec3(i as f64, (0.3 * i as f64).sin() * (0.5 * j as f64).cos(), j as f64));
+    ///     }
+    /// }
+    /// let mesh = Mesh::delaunay(&points, vec3(0.0, 1.0, 0.0));
+    /// assert_eq!(mesh.no_vertices(), 100);
+    /// assert_eq!(mesh.no_faces(), 2 * 9 * 9);
+    /// ```
+    ///
+    pub fn delaunay(points: &[Vec3], normal: Vec3) -> Self {
+        let normal = normal.normalize();
+        let u = if normal.x.abs() < 0.9 {
+            vec3(1.0, 0.0, 0.0)
+        } else {
+            vec3(0.0, 1.0, 0.0)
+        };
+        let u = (u - normal * normal.dot(u)).normalize();
+        let v = normal.cross(u);
+        let projected: Vec<(f64, f64)> = points.iter().map(|p| (p.dot(u), p.dot(v))).collect();
+        Self::from_used_positions(points, &triangulate_points(&projected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delaunay() {
+        // Scattered points in a tilted plane and a duplicate point
+        let normal = vec3(1.0, 2.0, 3.0).normalize();
+        let u = normal.cross(vec3(0.0, 0.0, 1.0)).normalize();
+        let v = normal.cross(u);
+        let mut points: Vec<Vec3> = (0..200)
+            .map(|i| {
+                let i = i as f64;
+                (12.9898 * i).sin() * u + (78.233 * i).sin() * v
+            })
+            .collect();
+        points.push(points[10]);
+
+        let mesh = Mesh::delaunay(&points, normal);
+        assert_eq!(mesh.no_vertices(), 200);
+        assert_eq!(mesh.euler_characteristic(), 1);
+        for face_id in mesh.face_iter() {
+            assert!(mesh.face_normal(face_id).dot(normal) > 0.999999);
+        }
+
+        // No point is strictly inside the circumcircle of any triangle
+        for face_id in mesh.face_iter() {
+            let (p0, p1, p2) = mesh.face_positions(face_id);
+            let center = p0 + circumcenter_offset(p1 - p0, p2 - p0);
+            let radius = center.distance(p0);
+            for p in points.iter() {
+                assert!(p.distance(center) > radius - 0.000001);
+            }
+        }
+    }
+
+    fn circumcenter_offset(a: Vec3, b: Vec3) -> Vec3 {
+        let n = a.cross(b);
+        (a.magnitude2() * b.cross(n) + b.magnitude2() * n.cross(a)) / (2.0 * n.magnitude2())
+    }
+
+    #[test]
+    fn test_delaunay_of_collinear_points() {
+        let points = [
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(2.0, 0.0, 0.0),
+            vec3(3.0, 5.0, 0.0),
+        ];
+        assert_eq!(Mesh::delaunay(&points, vec3(0.0, 1.0, 0.0)).no_faces(), 0);
+        assert_eq!(Mesh::delaunay(&points, vec3(0.0, 0.0, 1.0)).no_faces(), 2);
+    }
+}
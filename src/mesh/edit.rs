@@ -6,7 +6,15 @@ use crate::Error;
 /// # Edit
 impl Mesh {
     /// Moves the vertex to the specified position.
+    /// The stored normals and tangents (see [Vertex attributes](#vertex-attributes)) of the vertex and its neighbours are removed,
+    /// since moving the vertex changes the shape of the faces around it, whereas the stored normals and tangents of the other vertices are kept.
     pub fn set_vertex_position(&mut self, vertex_id: VertexID, value: Vec3) {
+        if self.vertex_attributes.normals.is_some() || self.vertex_attributes.tangents.is_some() {
+            let one_ring: Vec<VertexID> = std::iter::once(vertex_id)
+                .chain(self.vertex_vertex_iter(vertex_id))
+                .collect();
+            self.vertex_attributes.remove_normals(&one_ring);
+        }
         self.connectivity_info.set_position(vertex_id, value);
     }
 
@@ -87,12 +95,16 @@ impl Mesh {
         self.connectivity_info
             .set_halfedge_face(twin_next_id, Some(face_id));
 
+        self.replace_crease((v0, v1), []);
+        self.notify(|o| o.on_edge_flipped(halfedge_id));
+        self.debug_validate_around(&[v2, v3]);
         Ok(())
     }
 
     /// Split the given edge into two.
     /// Returns the id of the new vertex positioned at the given position.
     pub fn split_edge(&mut self, halfedge_id: HalfEdgeID, position: Vec3) -> VertexID {
+        let (v0, v1) = self.edge_vertices(halfedge_id);
         let mut walker = self.walker_from_halfedge(halfedge_id);
         if walker.face_id().is_none() {
             walker.as_twin();
@@ -119,9 +131,44 @@ impl Mesh {
                 .set_halfedge_vertex(twin_halfedge_id, new_vertex_id);
         };
 
+        self.replace_crease((v0, v1), [(v0, new_vertex_id), (new_vertex_id, v1)]);
+        self.notify(|o| o.on_edge_split(v0, v1, new_vertex_id));
+        self.debug_validate_around(&[new_vertex_id]);
         new_vertex_id
     }
 
+    /// Split the given edge into two at the given parameter along the half-edge,
+    /// ie. the new vertex is positioned at the start of the half-edge when `t = 0` and at the end of the half-edge when `t = 1`.
+    /// Returns the id of the new vertex.
+    pub fn split_edge_at(&mut self, halfedge_id: HalfEdgeID, t: f64) -> VertexID {
+        let (p0, p1) = self.halfedge_positions(halfedge_id);
+        self.split_edge(halfedge_id, p0 + t * (p1 - p0))
+    }
+
+    /// Split the given edge uniformly into `n` edges.
+    /// Returns the ids of the `n - 1` new vertices ordered from the start to the end of the given half-edge.
+    pub fn split_edge_n(&mut self, halfedge_id: HalfEdgeID, n: usize) -> Vec<VertexID> {
+        let mut walker = self.walker_from_halfedge(halfedge_id);
+        let end = walker.vertex_id().unwrap();
+        let mut start = walker.as_twin().vertex_id().unwrap();
+        let (p0, p1) = self.halfedge_positions(halfedge_id);
+        let mut vertices = Vec::new();
+        for i in 1..n {
+            let halfedge_id = self.connecting_edge(start, end).unwrap();
+            start = self.split_edge(halfedge_id, p0 + (i as f64 / n as f64) * (p1 - p0));
+            vertices.push(start);
+        }
+        vertices
+    }
+
+    /// Returns the positions of the start and end vertex of the given half-edge.
+    fn halfedge_positions(&self, halfedge_id: HalfEdgeID) -> (Vec3, Vec3) {
+        let mut walker = self.walker_from_halfedge(halfedge_id);
+        let p1 = self.vertex_position(walker.vertex_id().unwrap());
+        let p0 = self.vertex_position(walker.as_twin().vertex_id().unwrap());
+        (p0, p1)
+    }
+
     /// Split the given face into three new faces.
     /// Returns the id of the new vertex positioned at the given position.
     pub fn split_face(&mut self, face_id: FaceID, position: Vec3) -> VertexID {
@@ -151,6 +198,8 @@ impl Mesh {
             new_vertex_id,
             halfedge_id3,
         );
+        self.copy_group(face_id, face_id1);
+        self.copy_group(face_id, face_id2);
 
         let new_halfedge_id2 = self.connectivity_info.new_halfedge(
             Some(vertex_id3),
@@ -169,7 +218,7 @@ impl Mesh {
 
         // Update twin information
         let mut new_halfedge_id = unsafe { HalfEdgeID::new(0) };
-        for halfedge_id in self.face_halfedge_iter(face_id1) {
+        for halfedge_id in self.face_halfedge_iter(face_id1).collect::<Vec<_>>() {
             let vid = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
             if vid == vertex_id1 {
                 self.connectivity_info
@@ -178,7 +227,7 @@ impl Mesh {
                 new_halfedge_id = halfedge_id;
             }
         }
-        for halfedge_id in self.face_halfedge_iter(face_id2) {
+        for halfedge_id in self.face_halfedge_iter(face_id2).collect::<Vec<_>>() {
             let vid = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
             if vid == vertex_id2 {
                 self.connectivity_info
@@ -188,6 +237,8 @@ impl Mesh {
                     .set_halfedge_twin(new_halfedge_id2, halfedge_id);
             }
         }
+        self.notify(|o| o.on_face_split(face_id, new_vertex_id));
+        self.debug_validate_around(&[new_vertex_id]);
         new_vertex_id
     }
 
@@ -213,6 +264,7 @@ impl Mesh {
             new_vertex_id,
             halfedge_to_reuse,
         );
+        self.copy_group(old_face_id, new_face_id);
 
         // Update old face
         let new_halfedge_id = self.connectivity_info.new_halfedge(
@@ -228,7 +280,7 @@ impl Mesh {
             .set_face_halfedge(old_face_id, halfedge_id);
 
         // Update twin information
-        for halfedge_id in self.face_halfedge_iter(new_face_id) {
+        for halfedge_id in self.face_halfedge_iter(new_face_id).collect::<Vec<_>>() {
             let vid = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
             if vid == vertex_id1 {
                 self.connectivity_info
@@ -249,40 +301,55 @@ impl Mesh {
     /// **Note:** This might make some faces degenerate or produce edges and vertices that are not connected.
     ///
     pub fn collapse_edge(&mut self, halfedge_id: HalfEdgeID) -> VertexID {
-        let mut walker = self.walker_from_halfedge(halfedge_id);
-        let surviving_vertex_id = walker.vertex_id().unwrap();
-        walker.as_twin();
-        let dying_vertex_id = walker.vertex_id().unwrap();
+        let (surviving_vertex_id, dying_vertex_id) = self.edge_vertices(halfedge_id);
+
+        // Move creases from the dying vertex to the surviving vertex
+        if !self.creases.is_empty() {
+            for halfedge_id in self
+                .vertex_halfedge_iter(dying_vertex_id)
+                .collect::<Vec<_>>()
+            {
+                let vertex_id = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                self.replace_crease(
+                    (dying_vertex_id, vertex_id),
+                    [(surviving_vertex_id, vertex_id)],
+                );
+            }
+        }
+
+        let twin_id = self.walker_from_halfedge(halfedge_id).twin_id().unwrap();
         let new_position = 0.5
             * (self.vertex_position(surviving_vertex_id) + self.vertex_position(dying_vertex_id));
 
         // Update halfedges pointing to dying vertex
-        for halfedge_id in self.vertex_halfedge_iter(dying_vertex_id) {
-            self.connectivity_info.set_halfedge_vertex(
-                self.walker_from_halfedge(halfedge_id).twin_id().unwrap(),
-                surviving_vertex_id,
-            );
+        for halfedge_id in self
+            .vertex_halfedge_iter(dying_vertex_id)
+            .collect::<Vec<_>>()
+        {
+            let twin_id = self.walker_from_halfedge(halfedge_id).twin_id().unwrap();
+            self.connectivity_info
+                .set_halfedge_vertex(twin_id, surviving_vertex_id);
         }
 
         // Remove first face + halfedges
-        let mut he_id1 = walker.halfedge_id();
+        let mut he_id1 = Some(twin_id);
+        let walker = self.walker_from_halfedge(twin_id);
         if walker.face_id().is_some() {
-            walker.as_previous();
+            let vertex_halfedge_id = walker.into_previous().twin_id();
             self.connectivity_info
-                .set_vertex_halfedge(surviving_vertex_id, walker.twin_id());
-            walker.as_next();
+                .set_vertex_halfedge(surviving_vertex_id, vertex_halfedge_id);
         } else {
-            self.connectivity_info.remove_halfedge(he_id1.unwrap());
+            self.connectivity_info.remove_halfedge(twin_id);
             he_id1 = None;
         }
 
         // Remove second face + halfedges
-        walker.as_twin();
-        let he_id2 = walker.halfedge_id().unwrap();
+        let he_id2 = halfedge_id;
+        let walker = self.walker_from_halfedge(he_id2);
         if walker.face_id().is_some() {
-            walker.as_previous();
+            let vertex_halfedge_id = walker.into_previous().twin_id();
             self.connectivity_info
-                .set_vertex_halfedge(surviving_vertex_id, walker.twin_id());
+                .set_vertex_halfedge(surviving_vertex_id, vertex_halfedge_id);
             self.remove_one_face(he_id2);
         } else {
             self.connectivity_info.remove_halfedge(he_id2);
@@ -296,6 +363,7 @@ impl Mesh {
         self.connectivity_info.remove_vertex(dying_vertex_id);
 
         self.move_vertex_to(surviving_vertex_id, new_position);
+        self.notify(|o| o.on_edge_collapsed(surviving_vertex_id, dying_vertex_id));
         surviving_vertex_id
     }
 
@@ -312,14 +380,29 @@ impl Mesh {
         let twin_id2 = walker.twin_id().unwrap();
 
         self.connectivity_info.remove_face(face_id);
+        self.face_groups.remove(&face_id);
         self.connectivity_info.remove_halfedge(halfedge_id);
         self.connectivity_info.remove_halfedge(halfedge_id1);
         self.connectivity_info.remove_halfedge(halfedge_id2);
         self.connectivity_info.set_halfedge_twin(twin_id1, twin_id2);
         self.connectivity_info
             .set_vertex_halfedge(vertex_id, Some(twin_id1));
+    }
 
-        walker.as_twin();
+    ///
+    /// Reserves room for at least `additional` more vertices, so that adding them using [Mesh::add_vertex] does not reallocate the underlying storage.
+    ///
+    pub fn reserve_vertices(&mut self, additional: usize) {
+        self.connectivity_info.reserve_vertices(additional);
+    }
+
+    ///
+    /// Reserves room for at least `additional` more faces, so that adding them using [Mesh::add_face] or [Mesh::add_faces]
+    /// does not reallocate the underlying storage. This also reserves room for the half-edges of the faces.
+    ///
+    pub fn reserve_faces(&mut self, additional: usize) {
+        self.connectivity_info.reserve_faces(additional);
+        self.connectivity_info.reserve_halfedges(4 * additional);
     }
 
     ///
@@ -360,7 +443,7 @@ impl Mesh {
             .connectivity_info
             .create_face(vertex_id1, vertex_id2, vertex_id3);
 
-        for halfedge in self.face_halfedge_iter(face_id) {
+        for halfedge in self.face_halfedge_iter(face_id).collect::<Vec<_>>() {
             let target_vertex = self.walker_from_halfedge(halfedge).vertex_id().unwrap();
             let i = if target_vertex == vertex_id2 {
                 0
@@ -387,6 +470,108 @@ impl Mesh {
         Ok(face_id)
     }
 
+    ///
+    /// Adds the given faces to the mesh, where each face is given by three vertices which can be created using the [Mesh::add_vertex] method.
+    /// This is equivalent to calling [Mesh::add_face] for each face, except that the twin half-edges are connected in a single pass at the end,
+    /// which makes it considerably faster when adding many faces. Returns the ids of the new faces in the same order as the given faces.
+    ///
+    /// # Error
+    ///
+    /// Returns an error and leaves the mesh unchanged if one of the faces refers to the same vertex more than once
+    /// or if the action results in a non-manifold mesh,
+    /// ie. if two faces, among the given faces and the faces already in the mesh, have the same edge in the same direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::new(&three_d_asset::TriMesh::default());
+    /// let v0 = mesh.add_vertex(vec3(0.0, 0.0, 0.0));
+    /// let v1 = mesh.add_vertex(vec3(1.0, 0.0, 0.0));
+    /// let v2 = mesh.add_vertex(vec3(1.0, 1.0, 0.0));
+    /// let v3 = mesh.add_vertex(vec3(0.0, 1.0, 0.0));
+    /// let faces = mesh.add_faces(&[(v0, v1, v2), (v0, v2, v3)]).unwrap();
+    /// assert_eq!(faces.len(), 2);
+    /// assert_eq!(mesh.no_edges(), 5);
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn add_faces(
+        &mut self,
+        faces: &[(VertexID, VertexID, VertexID)],
+    ) -> Result<Vec<FaceID>, Error> {
+        let non_manifold = || Error::ActionWillResultInNonManifoldMesh("add_faces".to_string());
+        if faces
+            .iter()
+            .any(|(v0, v1, v2)| v0 == v1 || v1 == v2 || v2 == v0)
+        {
+            return Err(Error::ActionWillResultInInvalidMesh(
+                "Trying to add a face which refers to the same vertex more than once".to_string(),
+            ));
+        }
+
+        // Find the existing half-edges and check that no directed edge is used twice
+        let mut existing = HashMap::new();
+        let mut directed_edges = HashSet::with_capacity(3 * faces.len());
+        for (v0, v1, v2) in faces.iter() {
+            for (a, b) in [(*v0, *v1), (*v1, *v2), (*v2, *v0)] {
+                if !directed_edges.insert((a, b)) {
+                    return Err(non_manifold());
+                }
+                if let Some(halfedge_id) = self.connecting_edge(a, b) {
+                    if self.walker_from_halfedge(halfedge_id).face_id().is_some() {
+                        return Err(non_manifold());
+                    }
+                    existing.insert((a, b), halfedge_id);
+                }
+            }
+        }
+
+        let mut face_ids = Vec::with_capacity(faces.len());
+        let mut new_halfedges = HashMap::with_capacity(3 * faces.len());
+        for (v0, v1, v2) in faces.iter() {
+            let face_id = self.connectivity_info.create_face(*v0, *v1, *v2);
+            for halfedge_id in self.face_halfedge_iter(face_id).collect::<Vec<_>>() {
+                let mut walker = self.walker_from_halfedge(halfedge_id);
+                let sink = walker.vertex_id().unwrap();
+                let source = walker.as_previous().vertex_id().unwrap();
+                new_halfedges.insert((source, sink), halfedge_id);
+            }
+            face_ids.push(face_id);
+        }
+
+        // Connect the twins
+        for (&(source, sink), &halfedge_id) in new_halfedges.iter() {
+            let twin_id = if let Some(old_halfedge_id) = existing.get(&(source, sink)) {
+                let twin_id = self
+                    .walker_from_halfedge(*old_halfedge_id)
+                    .twin_id()
+                    .unwrap();
+                self.connectivity_info.remove_halfedge(*old_halfedge_id);
+                twin_id
+            } else if let Some(twin_id) = new_halfedges.get(&(sink, source)) {
+                if halfedge_id > *twin_id {
+                    continue;
+                }
+                *twin_id
+            } else {
+                self.connectivity_info
+                    .new_halfedge(Some(source), None, None)
+            };
+            self.connectivity_info
+                .set_halfedge_twin(twin_id, halfedge_id);
+        }
+        self.debug_validate_around(
+            &faces
+                .iter()
+                .flat_map(|(v0, v1, v2)| [*v0, *v1, *v2])
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>(),
+        );
+        Ok(face_ids)
+    }
+
     ///
     /// Removes the given face and also the adjacent edges and vertices if they are not connected to any other face.
     ///
@@ -415,11 +600,67 @@ impl Mesh {
         self.connectivity_info.set_halfedge_next(he_id3, None);
 
         self.connectivity_info.remove_face(face_id);
+        self.face_groups.remove(&face_id);
     }
 
-    /// Removes edges and vertices that are not connected to any face.
+    ///
+    /// Removes the given faces together with the edges and vertices which are no longer connected to any face.
+    /// Unlike calling [Mesh::remove_face] for each face, the time it takes is linear in the size of the mesh.
+    ///
+    pub(crate) fn remove_faces(&mut self, face_ids: &HashSet<FaceID>) {
+        let halfedges: Vec<HalfEdgeID> = face_ids
+            .iter()
+            .flat_map(|face_id| self.face_halfedge_iter(*face_id))
+            .collect();
+        for face_id in face_ids {
+            self.remove_face_unsafe(*face_id);
+        }
+
+        let mut vertices = HashSet::new();
+        for halfedge_id in halfedges {
+            if !self.connectivity_info.contains_halfedge(halfedge_id) {
+                continue;
+            }
+            let mut walker = self.walker_from_halfedge(halfedge_id);
+            let vertex_id1 = walker.vertex_id().unwrap();
+            let face_id1 = walker.face_id();
+            walker.as_twin();
+            let twin_id = walker.halfedge_id().unwrap();
+            let vertex_id2 = walker.vertex_id().unwrap();
+            if face_id1.is_none() && walker.face_id().is_none() {
+                self.connectivity_info.remove_halfedge(halfedge_id);
+                self.connectivity_info.remove_halfedge(twin_id);
+                vertices.insert(vertex_id1);
+                vertices.insert(vertex_id2);
+            }
+        }
+
+        // Find a new half-edge for the vertices which pointed to a removed half-edge or remove them if there is none
+        vertices.retain(|vertex_id| {
+            self.connectivity_info
+                .vertex_halfedge(*vertex_id)
+                .map(|halfedge_id| !self.connectivity_info.contains_halfedge(halfedge_id))
+                .unwrap_or(true)
+        });
+        if vertices.is_empty() {
+            return;
+        }
+        for halfedge_id in self.halfedge_iter() {
+            let walker = self.walker_from_halfedge(halfedge_id);
+            let vertex_id = walker.vertex_id().unwrap();
+            if vertices.remove(&vertex_id) {
+                self.connectivity_info
+                    .set_vertex_halfedge(vertex_id, walker.twin_id());
+            }
+        }
+        for vertex_id in vertices {
+            self.connectivity_info.remove_vertex(vertex_id);
+        }
+    }
+
+    /// Removes edges and vertices that are not connected to any face, see also [Mesh::remove_lonely_primitives_and_compact].
     pub fn remove_lonely_primitives(&mut self) {
-        let edges: Vec<HalfEdgeID> = self.edge_iter().collect();
+        let edges: Vec<HalfEdgeID> = self.edge_iter().map(HalfEdgeID::from).collect();
         for halfedge_id in edges {
             self.remove_edge_if_lonely(halfedge_id);
         }
@@ -579,6 +820,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_split_edge_at() {
+        let mut mesh = crate::test_utility::square();
+        let halfedge_id = mesh.halfedge_iter().next().unwrap();
+        let mut walker = mesh.walker_from_halfedge(halfedge_id);
+        let p1 = mesh.vertex_position(walker.vertex_id().unwrap());
+        let p0 = mesh.vertex_position(walker.as_twin().vertex_id().unwrap());
+
+        let vertex_id = mesh.split_edge_at(halfedge_id, 0.25);
+
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.vertex_position(vertex_id), p0 + 0.25 * (p1 - p0));
+    }
+
+    #[test]
+    fn test_split_edge_n() {
+        let mut mesh = crate::test_utility::square();
+        let halfedge_id = mesh.halfedge_iter().next().unwrap();
+        let mut walker = mesh.walker_from_halfedge(halfedge_id);
+        let end = walker.vertex_id().unwrap();
+        let start = walker.as_twin().vertex_id().unwrap();
+        let no_vertices = mesh.no_vertices();
+
+        let vertices = mesh.split_edge_n(halfedge_id, 4);
+
+        mesh.is_valid().unwrap();
+        assert_eq!(vertices.len(), 3);
+        assert_eq!(mesh.no_vertices(), no_vertices + 3);
+        let path: Vec<VertexID> = [start].into_iter().chain(vertices).chain([end]).collect();
+        let p0 = mesh.vertex_position(start);
+        let p1 = mesh.vertex_position(end);
+        for (i, window) in path.windows(2).enumerate() {
+            assert!(mesh.connecting_edge(window[0], window[1]).is_some());
+            assert!(
+                (mesh.vertex_position(window[1]) - (p0 + ((i + 1) as f64 / 4.0) * (p1 - p0)))
+                    .magnitude()
+                    < 0.000001
+            );
+        }
+    }
+
     #[test]
     fn test_split_edge() {
         let mut mesh = crate::test_utility::square();
@@ -830,4 +1112,35 @@ mod tests {
         assert_eq!(mesh.no_faces(), 9);
         mesh.is_valid().unwrap()
     }
+
+    #[test]
+    fn test_add_faces() {
+        let mut mesh = crate::test_utility::triangle();
+        let mut vertex_ids: Vec<VertexID> = mesh.vertex_iter().collect();
+        for i in 0..3 {
+            vertex_ids.push(mesh.add_vertex(vec3(i as f64, 3.0, 0.5)));
+        }
+        let (v0, v1, v2) = mesh.face_vertices(mesh.face_iter().next().unwrap());
+        let (v3, v4, v5) = (vertex_ids[3], vertex_ids[4], vertex_ids[5]);
+
+        // Adding a face with the same edge orientation as an existing face fails and leaves the mesh unchanged
+        assert!(mesh.add_faces(&[(v0, v1, v3)]).is_err());
+        // Adding two faces with the same edge orientation fails
+        assert!(mesh.add_faces(&[(v3, v4, v1), (v1, v3, v5)]).is_err());
+        // Adding a face which refers to the same vertex more than once fails
+        assert!(mesh.add_faces(&[(v1, v0, v3), (v3, v3, v4)]).is_err());
+        assert_eq!(mesh.no_faces(), 1);
+        assert_eq!(mesh.no_halfedges(), 6);
+
+        let face_ids = mesh
+            .add_faces(&[(v1, v0, v3), (v2, v1, v4), (v1, v3, v4), (v3, v0, v5)])
+            .unwrap();
+
+        assert_eq!(face_ids.len(), 4);
+        assert_eq!(mesh.no_vertices(), 6);
+        assert_eq!(mesh.no_faces(), 5);
+        assert_eq!(mesh.no_edges(), 10);
+        assert_eq!(mesh.no_halfedges(), 20);
+        mesh.is_valid().unwrap();
+    }
 }
@@ -0,0 +1,285 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// # Face groups
+///
+/// Each face can be assigned to a named group, for example to specify which material to use for the face.
+/// The faces created when splitting a face (see [Mesh::split_face] and [Mesh::split_edge]) are in the same group as the original face,
+/// while faces created in other ways are not in any group.
+/// Use [Mesh::export_groups] or [Mesh::export_obj] to export the mesh with a separate set of buffers for each group.
+///
+impl Mesh {
+    /// Assigns the given face to the group with the given name, which removes it from any other group.
+    pub fn assign_group(&mut self, face_id: FaceID, name: impl Into<String>) {
+        self.face_groups.insert(face_id, name.into());
+    }
+
+    /// Removes the given face from the group it is assigned to, if any.
+    pub fn remove_from_group(&mut self, face_id: FaceID) {
+        self.face_groups.remove(&face_id);
+    }
+
+    /// Returns the name of the group that the given face is assigned to, if any.
+    pub fn face_group(&self, face_id: FaceID) -> Option<&str> {
+        self.face_groups.get(&face_id).map(|name| name.as_str())
+    }
+
+    /// Returns the names of all groups that at least one face is assigned to, in alphabetical order.
+    pub fn group_names(&self) -> Vec<String> {
+        self.face_groups
+            .values()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    ///
+    /// Exports the mesh like [Mesh::export], but split into one [three_d_asset::TriMesh] for each group, which for example can be rendered with a different material.
+    /// Each exported mesh only contains the vertices used by the faces in the group.
+    /// The faces which are not assigned to a group are exported first with the name `None`, followed by the groups in the order of [Mesh::group_names].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::cylinder(1.0, 2.0, 16, true);
+    /// for face_id in mesh.face_iter().collect::<Vec<_>>() {
+    ///     if mesh.face_normal(face_id).y.abs() > 0.99 {
+    ///         mesh.assign_group(face_id, "caps");
+    ///     } else {
+    ///         mesh.assign_group(face_id, "side");
+    ///     }
+    /// }
+    /// let groups = mesh.export_groups();
+    /// assert_eq!(groups.len(), 2);
+    /// assert_eq!(groups[0].0.as_deref(), Some("caps"));
+    /// assert_eq!(groups[0].1.triangle_count(), 2 * 16);
+    /// ```
+    ///
+    pub fn export_groups(&self) -> Vec<(Option<String>, three_d_asset::TriMesh)> {
+        use three_d_asset::{Indices, Positions, TriMesh};
+        let tri_mesh = self.export();
+        let indices = tri_mesh.indices.to_u32().unwrap();
+        let positions = tri_mesh.positions.to_f64();
+        self.grouped_faces()
+            .into_iter()
+            .map(|(name, faces)| {
+                let mut mapping = HashMap::new();
+                let mut vertices = Vec::new();
+                let group_indices = faces
+                    .iter()
+                    .flat_map(|f| &indices[3 * f..3 * f + 3])
+                    .map(|i| {
+                        *mapping.entry(*i).or_insert_with(|| {
+                            vertices.push(*i as usize);
+                            vertices.len() as u32 - 1
+                        })
+                    })
+                    .collect();
+                fn subset<T: Copy>(values: &Option<Vec<T>>, vertices: &[usize]) -> Option<Vec<T>> {
+                    values
+                        .as_ref()
+                        .map(|values| vertices.iter().map(|v| values[*v]).collect())
+                }
+                let group = TriMesh {
+                    indices: Indices::U32(group_indices),
+                    positions: Positions::F64(vertices.iter().map(|v| positions[*v]).collect()),
+                    normals: subset(&tri_mesh.normals, &vertices),
+                    tangents: subset(&tri_mesh.tangents, &vertices),
+                    uvs: subset(&tri_mesh.uvs, &vertices),
+                    colors: subset(&tri_mesh.colors, &vertices),
+                };
+                (name, group)
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns the mesh in the Wavefront OBJ format, including the normals and the uv coordinates if any are stored (see [Vertex attributes](#vertex-attributes)).
+    /// The faces of each group are written after a `g` and a `usemtl` statement with the name of the group, so the groups are loaded as separate meshes with a material each.
+    /// The faces which are not assigned to a group are written first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::cone(1.0, 2.0, 8);
+    /// let face_id = mesh.face_iter().next().unwrap();
+    /// mesh.assign_group(face_id, "tip");
+    /// let obj = mesh.export_obj();
+    /// assert!(obj.contains("g tip\nusemtl tip\n"));
+    /// ```
+    ///
+    pub fn export_obj(&self) -> String {
+        let tri_mesh = self.export();
+        let indices = tri_mesh.indices.to_u32().unwrap();
+        let mut obj = String::new();
+        for p in tri_mesh.positions.to_f64() {
+            writeln!(obj, "v {} {} {}", p.x, p.y, p.z).unwrap();
+        }
+        if let Some(uvs) = &tri_mesh.uvs {
+            for uv in uvs {
+                writeln!(obj, "vt {} {}", uv.x, uv.y).unwrap();
+            }
+        }
+        if let Some(normals) = &tri_mesh.normals {
+            for n in normals {
+                writeln!(obj, "vn {} {} {}", n.x, n.y, n.z).unwrap();
+            }
+        }
+        for (name, faces) in self.grouped_faces() {
+            if let Some(name) = name {
+                writeln!(obj, "g {}\nusemtl {}", name, name).unwrap();
+            }
+            for f in faces {
+                obj.push('f');
+                for i in &indices[3 * f..3 * f + 3] {
+                    let i = i + 1;
+                    match (tri_mesh.uvs.is_some(), tri_mesh.normals.is_some()) {
+                        (true, true) => write!(obj, " {}/{}/{}", i, i, i),
+                        (true, false) => write!(obj, " {}/{}", i, i),
+                        (false, true) => write!(obj, " {}//{}", i, i),
+                        (false, false) => write!(obj, " {}", i),
+                    }
+                    .unwrap();
+                }
+                obj.push('\n');
+            }
+        }
+        obj
+    }
+
+    ///
+    /// Returns the indices of the faces, in the order of [Mesh::face_iter], which are not assigned to a group followed by the faces of each group in the order of [Mesh::group_names].
+    /// Groups without faces are left out.
+    ///
+    fn grouped_faces(&self) -> Vec<(Option<String>, Vec<usize>)> {
+        let names = self.group_names();
+        let group_indices: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i + 1))
+            .collect();
+        let mut faces = vec![Vec::new(); names.len() + 1];
+        for (i, face_id) in self.face_iter().enumerate() {
+            let group = match self.face_group(face_id) {
+                Some(name) => group_indices[name],
+                None => 0,
+            };
+            faces[group].push(i);
+        }
+        let mut groups: Vec<(Option<String>, Vec<usize>)> = std::iter::once(None)
+            .chain(names.iter().cloned().map(Some))
+            .zip(faces)
+            .collect();
+        groups.retain(|(_, faces)| !faces.is_empty());
+        groups
+    }
+
+    /// Assigns the `to` face to the same group as the `from` face.
+    pub(super) fn copy_group(&mut self, from: FaceID, to: FaceID) {
+        match self.face_groups.get(&from).cloned() {
+            Some(name) => self.face_groups.insert(to, name),
+            None => self.face_groups.remove(&to),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_face_groups_after_edits() {
+        let mut mesh = crate::test_utility::square();
+        let face_ids: Vec<FaceID> = mesh.face_iter().collect();
+        mesh.assign_group(face_ids[0], "a");
+        mesh.assign_group(face_ids[1], "b");
+        assert_eq!(mesh.group_names(), vec!["a".to_string(), "b".to_string()]);
+
+        mesh.split_face(face_ids[0], mesh.face_center(face_ids[0]));
+        let halfedge_id = mesh.face_halfedge_iter(face_ids[1]).next().unwrap();
+        mesh.split_edge(halfedge_id, vec3(0.0, 0.0, 0.0));
+        let count = |mesh: &Mesh, name: &str| {
+            mesh.face_iter()
+                .filter(|face_id| mesh.face_group(*face_id) == Some(name))
+                .count()
+        };
+        assert_eq!(count(&mesh, "a") + count(&mesh, "b"), mesh.no_faces());
+        assert_eq!(count(&mesh, "a"), 3);
+
+        let face_id = mesh
+            .face_iter()
+            .find(|f| mesh.face_group(*f) == Some("b"))
+            .unwrap();
+        mesh.remove_face(face_id);
+        let face_id = mesh
+            .face_iter()
+            .find(|f| *f != face_ids[0] && mesh.face_group(*f) == Some("a"))
+            .unwrap();
+        mesh.remove_from_group(face_id);
+        let remap = mesh.compact();
+        assert_eq!(count(&mesh, "a"), 2);
+        assert_eq!(mesh.face_group(remap.faces[&face_ids[0]]), Some("a"));
+
+        let mut other = Mesh::with_capacity(0, 0);
+        other.append(&mesh);
+        assert_eq!(count(&other, "a"), 2);
+        assert_eq!(count(&other, "b"), count(&mesh, "b"));
+    }
+
+    #[test]
+    fn test_export_groups() {
+        let mut mesh = Mesh::icosphere(1);
+        for face_id in mesh.face_iter().collect::<Vec<_>>() {
+            if mesh.face_center(face_id).y > 0.0 {
+                mesh.assign_group(face_id, "top");
+            }
+        }
+        let no_top_faces = mesh
+            .face_iter()
+            .filter(|f| mesh.face_center(*f).y > 0.0)
+            .count();
+        let groups = mesh.export_groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, None);
+        assert_eq!(groups[1].0.as_deref(), Some("top"));
+        assert_eq!(groups[1].1.triangle_count(), no_top_faces);
+        assert_eq!(
+            groups[0].1.triangle_count() + groups[1].1.triangle_count(),
+            mesh.no_faces()
+        );
+        for (_, group) in groups.iter() {
+            group.validate().unwrap();
+            assert!(group.vertex_count() < mesh.no_vertices());
+            for p in group.positions.to_f64() {
+                assert!((p.magnitude() - 1.0).abs() < 0.000001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_export_obj() {
+        let mut mesh = crate::test_utility::square();
+        let face_id = mesh.face_iter().next().unwrap();
+        mesh.assign_group(face_id, "first");
+
+        let mut raw_assets = three_d_asset::io::RawAssets::new();
+        raw_assets.insert("square.obj", mesh.export_obj().into_bytes());
+        let model: three_d_asset::Model = raw_assets.deserialize("square.obj").unwrap();
+        let triangle_count: usize = model
+            .geometries
+            .iter()
+            .map(|primitive| match &primitive.geometry {
+                three_d_asset::Geometry::Triangles(tri_mesh) => tri_mesh.triangle_count(),
+                _ => 0,
+            })
+            .sum();
+        assert_eq!(triangle_count, 2);
+        assert_eq!(model.geometries.len(), 2);
+    }
+}
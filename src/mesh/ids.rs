@@ -1,25 +1,48 @@
-//! Defines unique id's for a vertex, half-edge and face.
+//! Defines unique id's for a vertex, half-edge, edge and face.
 
 use std::fmt;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::ops::Deref;
 
+///
 /// A unique ID.
+/// The ID consists of an index, which is returned when dereferencing the ID, and a generation.
+/// When a primitive is removed, its index might be reused for a new primitive, but with a new generation,
+/// so an ID of a removed primitive, ie. a stale ID, is never equal to the ID of a new primitive.
+///
 pub trait ID: Clone + Eq + Copy + Ord + Hash + Debug + Deref<Target = u32> {
-    /// Constructs a new ID from an integer. Should only be used for testing or similar.
+    /// Constructs a new ID from an integer with generation zero. Should only be used for testing or similar.
     unsafe fn new(val: u32) -> Self;
+
+    /// Constructs a new ID from an integer and a generation. Should only be used for testing or similar.
+    ///
+    /// # Safety
+    ///
+    /// The constructed ID is only valid if a primitive with the given index and generation exists in the mesh it is used with.
+    unsafe fn new_with_generation(val: u32, generation: u32) -> Self;
+
+    /// Returns the generation of the ID.
+    fn generation(&self) -> u32;
 }
 
 ///
 /// A unique ID for a vertex.
 ///
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-pub struct VertexID(u32);
+pub struct VertexID(u32, u32);
 
 impl ID for VertexID {
     unsafe fn new(val: u32) -> VertexID {
-        VertexID(val)
+        VertexID(val, 0)
+    }
+
+    unsafe fn new_with_generation(val: u32, generation: u32) -> VertexID {
+        VertexID(val, generation)
+    }
+
+    fn generation(&self) -> u32 {
+        self.1
     }
 }
 
@@ -40,11 +63,19 @@ impl fmt::Display for VertexID {
 /// A unique ID for a halfedge.
 ///
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-pub struct HalfEdgeID(u32);
+pub struct HalfEdgeID(u32, u32);
 
 impl ID for HalfEdgeID {
     unsafe fn new(val: u32) -> HalfEdgeID {
-        HalfEdgeID(val)
+        HalfEdgeID(val, 0)
+    }
+
+    unsafe fn new_with_generation(val: u32, generation: u32) -> HalfEdgeID {
+        HalfEdgeID(val, generation)
+    }
+
+    fn generation(&self) -> u32 {
+        self.1
     }
 }
 
@@ -61,15 +92,63 @@ impl fmt::Display for HalfEdgeID {
     }
 }
 
+///
+/// A unique ID for an edge, ie. a pair of twin half-edges, which is given by the smallest of the two half-edge ids.
+/// Use [Mesh::edge_id](crate::Mesh::edge_id) to get the edge of a half-edge and [HalfEdgeID::from] to get one of the half-edges of an edge.
+///
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct EdgeID(u32, u32);
+
+impl ID for EdgeID {
+    unsafe fn new(val: u32) -> EdgeID {
+        EdgeID(val, 0)
+    }
+
+    unsafe fn new_with_generation(val: u32, generation: u32) -> EdgeID {
+        EdgeID(val, generation)
+    }
+
+    fn generation(&self) -> u32 {
+        self.1
+    }
+}
+
+impl Deref for EdgeID {
+    type Target = u32;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for EdgeID {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<EdgeID> for HalfEdgeID {
+    fn from(edge_id: EdgeID) -> Self {
+        HalfEdgeID(edge_id.0, edge_id.1)
+    }
+}
+
 ///
 /// A unique ID for a face.
 ///
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-pub struct FaceID(u32);
+pub struct FaceID(u32, u32);
 
 impl ID for FaceID {
     unsafe fn new(val: u32) -> FaceID {
-        FaceID(val)
+        FaceID(val, 0)
+    }
+
+    unsafe fn new_with_generation(val: u32, generation: u32) -> FaceID {
+        FaceID(val, generation)
+    }
+
+    fn generation(&self) -> u32 {
+        self.1
     }
 }
 
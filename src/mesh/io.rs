@@ -1,6 +1,141 @@
 //! See [Mesh](crate::mesh::Mesh).
 
+use crate::mesh::connectivity_info::{CowVec, VertexPositions};
 use crate::mesh::*;
+use crate::Error;
+use std::fmt;
+
+///
+/// Describes the changes made to the input by [Mesh::new_tolerant] to be able to construct a manifold [Mesh].
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NonManifoldReport {
+    /// The indices of the input triangles that were skipped because they refer to a vertex which does not exist.
+    pub out_of_range_faces: Vec<usize>,
+    /// The indices of the input triangles that were skipped because they refer to the same vertex more than once.
+    pub degenerate_faces: Vec<usize>,
+    /// The edges, given as the two input vertices, that were shared by more than two faces or by two faces using the edge in the same direction.
+    /// The surplus faces are disconnected from the edge by duplicating the vertices of the edge.
+    pub non_manifold_edges: Vec<(VertexID, VertexID)>,
+    /// The duplicated vertices given as the original vertex and the duplicate.
+    pub duplicated_vertices: Vec<(VertexID, VertexID)>,
+    /// The indices of the input triangles whose orientation was flipped because they used an edge in the same direction as an earlier triangle.
+    /// If flipping the triangle does not resolve the conflict, the triangle is instead disconnected from the edge, which is then reported in `non_manifold_edges`.
+    pub flipped_faces: Vec<usize>,
+    /// The input vertices that were removed because they are not used by any face, for example because all the triangles using them were degenerate.
+    pub unused_vertices: Vec<VertexID>,
+}
+
+impl NonManifoldReport {
+    /// Returns whether the input could be used without any changes.
+    pub fn is_empty(&self) -> bool {
+        self.out_of_range_faces.is_empty()
+            && self.degenerate_faces.is_empty()
+            && self.non_manifold_edges.is_empty()
+            && self.duplicated_vertices.is_empty()
+            && self.flipped_faces.is_empty()
+            && self.unused_vertices.is_empty()
+    }
+}
+
+///
+/// A problem with the input given to [Mesh::try_new] which prevents constructing a valid [Mesh].
+/// Vertices are given as their index into the positions and faces as the index of the triangle in the indices.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputIssue {
+    /// The number of indices or the number of position coordinates is not a multiple of three.
+    InvalidBufferLength {
+        /// The number of indices.
+        indices: usize,
+        /// The number of position coordinates.
+        positions: usize,
+    },
+    /// The face refers to a vertex which does not exist.
+    IndexOutOfBounds {
+        /// The index of the face.
+        face: usize,
+        /// The index of the vertex which does not exist.
+        index: u32,
+    },
+    /// The face refers to the same vertex more than once.
+    DegenerateFace(usize),
+    /// The second face has the same vertices as the first face.
+    DuplicateFace(usize, usize),
+    /// The edge between the two vertices is shared by more than two faces.
+    NonManifoldEdge(u32, u32),
+    /// The two faces sharing the edge between the two vertices traverse the edge in the same direction, ie. have opposite orientations.
+    InconsistentOrientation(u32, u32),
+    /// The vertex connects two or more sets of faces which are not otherwise connected.
+    NonManifoldVertex(u32),
+    /// The vertex is not used by any face.
+    IsolatedVertex(u32),
+}
+
+impl fmt::Display for InputIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidBufferLength { indices, positions } => write!(
+                f,
+                "The number of indices ({}) or position coordinates ({}) is not a multiple of three",
+                indices, positions
+            ),
+            Self::IndexOutOfBounds { face, index } => {
+                write!(f, "Face {} refers to vertex {} which does not exist", face, index)
+            }
+            Self::DegenerateFace(face) => {
+                write!(f, "Face {} refers to the same vertex more than once", face)
+            }
+            Self::DuplicateFace(face0, face1) => {
+                write!(f, "Face {} has the same vertices as face {}", face1, face0)
+            }
+            Self::NonManifoldEdge(v0, v1) => write!(
+                f,
+                "The edge between vertex {} and vertex {} is shared by more than two faces",
+                v0, v1
+            ),
+            Self::InconsistentOrientation(v0, v1) => write!(
+                f,
+                "The faces sharing the edge between vertex {} and vertex {} have opposite orientations",
+                v0, v1
+            ),
+            Self::NonManifoldVertex(v) => write!(
+                f,
+                "Vertex {} connects sets of faces which are not otherwise connected",
+                v
+            ),
+            Self::IsolatedVertex(v) => write!(f, "Vertex {} is not used by any face", v),
+        }
+    }
+}
+
+///
+/// The problems found in the input given to [Mesh::try_new].
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputReport {
+    /// The problems found.
+    pub issues: Vec<InputIssue>,
+}
+
+impl InputReport {
+    /// Returns whether no problems were found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for InputReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
 
 impl Mesh {
     ///
@@ -31,21 +166,105 @@ impl Mesh {
     /// ```
     ///
     pub fn new(input: &three_d_asset::TriMesh) -> Self {
-        let no_vertices = input.vertex_count();
-        let no_faces = input.triangle_count();
         let indices = input
             .indices
             .to_u32()
-            .unwrap_or((0..no_faces as u32 * 3).collect::<Vec<_>>());
-        let positions = input.positions.to_f64();
-        let mesh = Mesh {
-            connectivity_info: ConnectivityInfo::new(no_vertices, no_faces),
+            .unwrap_or((0..input.triangle_count() as u32 * 3).collect::<Vec<_>>());
+        Self::new_from_buffers(
+            &indices,
+            input.positions.to_f64().into_iter().collect(),
+            VertexAttributes::new(input),
+        )
+    }
+
+    ///
+    /// Constructs a new [Mesh] from a [three_d_asset::TriMesh] like [Mesh::new], but takes ownership of the input so that its buffers are not copied.
+    /// Positions given in double precision ([three_d_asset::Positions::F64]) are moved into the mesh as they are
+    /// and indices given as [three_d_asset::Indices::U32] are used directly to build the connectivity.
+    /// Positions and indices of any other type are converted first, exactly as in [Mesh::new].
+    ///
+    /// **Note:** The moved positions are shared as one buffer until they are changed the first time,
+    /// at which point they are split into the chunks used to share the positions between clones of the mesh.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::from_tri_mesh(three_d_asset::TriMesh::sphere(4));
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn from_tri_mesh(input: three_d_asset::TriMesh) -> Self {
+        use three_d_asset::{Indices, Positions};
+        let vertex_attributes = VertexAttributes::new(&input);
+        let no_faces = input.triangle_count();
+        let positions = match input.positions {
+            Positions::F64(positions) => VertexPositions::F64(CowVec::from_vec(positions)),
+            positions => positions.to_f64().into_iter().collect(),
         };
+        match input.indices {
+            Indices::U32(indices) => Self::new_from_buffers(&indices, positions, vertex_attributes),
+            indices => Self::new_from_buffers(
+                &indices
+                    .to_u32()
+                    .unwrap_or((0..no_faces as u32 * 3).collect::<Vec<_>>()),
+                positions,
+                vertex_attributes,
+            ),
+        }
+    }
 
-        // Create vertices
-        for i in 0..no_vertices {
-            mesh.connectivity_info.new_vertex(positions[i]);
+    ///
+    /// Constructs a new [Mesh] from triangle indices and positions given as three coordinates for each vertex, like [Mesh::from_positions_and_indices],
+    /// but checks the input first, so that invalid input is reported instead of causing a panic or an invalid mesh.
+    /// Use [Mesh::new_tolerant] to construct a mesh from input which does not describe a manifold surface.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [Error::InvalidInput] error with the [InputReport] listing the problems if an index is out of bounds,
+    /// a face is degenerate or duplicated, an edge or vertex is non-manifold, the faces are not consistently oriented or a vertex is not used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+    /// let mesh = Mesh::try_new(&[0, 1, 2, 2, 1, 3], &positions).unwrap();
+    /// assert_eq!(mesh.no_faces(), 2);
+    ///
+    /// let error = Mesh::try_new(&[0, 1, 2, 2, 1, 4], &positions).unwrap_err();
+    /// assert!(matches!(
+    ///     error,
+    ///     Error::InvalidInput(report) if report.issues[0] == InputIssue::IndexOutOfBounds { face: 1, index: 4 }
+    /// ));
+    /// ```
+    ///
+    pub fn try_new(indices: &[u32], positions: &[f64]) -> Result<Self, Error> {
+        let report = validate_input(indices, positions);
+        if !report.is_valid() {
+            return Err(Error::InvalidInput(report));
         }
+        let positions = positions
+            .chunks(3)
+            .map(|p| vec3(p[0], p[1], p[2]))
+            .collect();
+        Ok(Self::new_from_buffers(
+            indices,
+            positions,
+            VertexAttributes::default(),
+        ))
+    }
+
+    pub(super) fn new_from_buffers(
+        indices: &[u32],
+        positions: VertexPositions,
+        vertex_attributes: VertexAttributes,
+    ) -> Self {
+        let no_faces = indices.len() / 3;
+        let mut mesh = Mesh::with_capacity(0, no_faces);
+
+        // Create vertices
+        mesh.connectivity_info.new_vertices(positions);
 
         let mut twins = HashMap::<(VertexID, VertexID), HalfEdgeID>::new();
         fn sort(a: VertexID, b: VertexID) -> (VertexID, VertexID) {
@@ -69,16 +288,17 @@ impl Mesh {
             );
 
             // mark twin halfedges
-            let mut walker = mesh.walker_from_face(face);
+            let mut halfedge_id = mesh.walker_from_face(face).halfedge_id().unwrap();
             for _ in 0..3 {
+                let mut walker = mesh.walker_from_halfedge(halfedge_id);
                 let vertex_id = walker.vertex_id().unwrap();
                 walker.as_next();
                 let key = sort(vertex_id, walker.vertex_id().unwrap());
+                halfedge_id = walker.halfedge_id().unwrap();
                 if let Some(twin) = twins.get(&key) {
-                    mesh.connectivity_info
-                        .set_halfedge_twin(walker.halfedge_id().unwrap(), *twin);
+                    mesh.connectivity_info.set_halfedge_twin(halfedge_id, *twin);
                 } else {
-                    twins.insert(key, walker.halfedge_id().unwrap());
+                    twins.insert(key, halfedge_id);
                 }
             }
         }
@@ -95,52 +315,662 @@ impl Mesh {
                     .as_previous()
                     .vertex_id()
                     .unwrap();
-                mesh.connectivity_info.set_halfedge_twin(
-                    halfedge,
-                    mesh.connectivity_info
-                        .new_halfedge(Some(vertex), None, None),
-                );
+                let twin = mesh
+                    .connectivity_info
+                    .new_halfedge(Some(vertex), None, None);
+                mesh.connectivity_info.set_halfedge_twin(halfedge, twin);
             }
         }
+        mesh.vertex_attributes = vertex_attributes;
 
         mesh
     }
 
+    ///
+    /// Constructs a new [Mesh] from the given positions and triangle indices like [Mesh::new].
+    /// The positions can be given as any type which can be converted into a [Vec3] using [MathInterop],
+    /// for example `glam::DVec3` (requires the `glam` feature) or `nalgebra::Point3<f64>` (requires the `nalgebra` feature).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::from_positions_and_indices(
+    ///     &[vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0)],
+    ///     &[0, 1, 2],
+    /// );
+    /// assert_eq!(mesh.no_faces(), 1);
+    /// ```
+    ///
+    pub fn from_positions_and_indices<T: Copy>(positions: &[T], indices: &[u32]) -> Self
+    where
+        Vec3: MathInterop<T>,
+    {
+        Self::new(&three_d_asset::TriMesh {
+            indices: three_d_asset::Indices::U32(indices.to_vec()),
+            positions: three_d_asset::Positions::F64(
+                positions.iter().map(|p| Vec3::from_interop(*p)).collect(),
+            ),
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Constructs a new [Mesh] from triangles given as indices into the points, where only the points used by a triangle become vertices of the mesh.
+    ///
+    pub(crate) fn from_used_positions(points: &[Vec3], triangles: &[[usize; 3]]) -> Self {
+        let mut indices = Vec::with_capacity(3 * triangles.len());
+        let mut positions = Vec::new();
+        let mut mapping = HashMap::new();
+        for i in triangles.iter().flatten() {
+            indices.push(*mapping.entry(*i).or_insert_with(|| {
+                positions.push(points[*i]);
+                positions.len() as u32 - 1
+            }));
+        }
+        Self::from_positions_and_indices(&positions, &indices)
+    }
+
+    ///
+    /// Constructs a new empty [Mesh] with room for the given number of vertices and faces,
+    /// so that adding them using [Mesh::add_vertex], [Mesh::add_face] or [Mesh::add_faces] does not reallocate the underlying storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::with_capacity(3, 1);
+    /// let v0 = mesh.add_vertex(vec3(0.0, 0.0, 0.0));
+    /// let v1 = mesh.add_vertex(vec3(1.0, 0.0, 0.0));
+    /// let v2 = mesh.add_vertex(vec3(0.0, 1.0, 0.0));
+    /// mesh.add_face(v0, v1, v2).unwrap();
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn with_capacity(no_vertices: usize, no_faces: usize) -> Self {
+        Mesh {
+            connectivity_info: ConnectivityInfo::new(no_vertices, no_faces),
+            merge_epsilon: DEFAULT_MERGE_EPSILON,
+            creases: HashSet::new(),
+            vertex_normals: CowVec::with_capacity(0),
+            vertex_attributes: VertexAttributes::default(),
+            face_groups: FaceProperty::new(),
+        }
+    }
+
+    ///
+    /// Constructs a new [Mesh] from a [three_d_asset::TriMesh] like [Mesh::new], but also accepts input that does not describe a manifold surface, for example a triangle soup.
+    /// Triangles that refer to a vertex which does not exist or to the same vertex more than once are skipped, faces that use an edge in the same direction as another face are flipped,
+    /// faces that are connected to an edge already shared by two other faces are disconnected from that edge
+    /// and vertices that connect otherwise unconnected sets of faces are duplicated. Vertices that are not used by any face are removed.
+    /// The returned [NonManifoldReport] describes what was changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// // Three triangles sharing the same edge
+    /// let (mesh, report) = Mesh::new_tolerant(&three_d_asset::TriMesh {
+    ///     indices: three_d_asset::Indices::U8(vec![0, 1, 2, 1, 0, 3, 0, 1, 4]),
+    ///     positions: three_d_asset::Positions::F64(vec![
+    ///         vec3(0.0, 0.0, 0.0),
+    ///         vec3(1.0, 0.0, 0.0),
+    ///         vec3(0.0, 1.0, 0.0),
+    ///         vec3(0.0, -1.0, 0.0),
+    ///         vec3(0.0, 0.0, 1.0),
+    ///     ]),
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(report.non_manifold_edges.len(), 1);
+    /// mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn new_tolerant(input: &three_d_asset::TriMesh) -> (Self, NonManifoldReport) {
+        use three_d_asset::{Indices, Positions, TriMesh};
+        let no_faces = input.triangle_count();
+        let indices = input
+            .indices
+            .to_u32()
+            .unwrap_or((0..no_faces as u32 * 3).collect::<Vec<_>>());
+        let mut positions = input.positions.to_f64();
+        let mut report = NonManifoldReport::default();
+
+        fn duplicate_vertex(
+            positions: &mut Vec<Vec3>,
+            report: &mut NonManifoldReport,
+            index: u32,
+        ) -> u32 {
+            positions.push(positions[index as usize]);
+            let new_index = positions.len() as u32 - 1;
+            report
+                .duplicated_vertices
+                .push(unsafe { (VertexID::new(index), VertexID::new(new_index)) });
+            new_index
+        }
+        fn sort(a: u32, b: u32) -> (u32, u32) {
+            if a < b {
+                (a, b)
+            } else {
+                (b, a)
+            }
+        }
+
+        // Skip faces referring to vertices which do not exist and degenerate faces
+        let no_vertices = positions.len();
+        let mut faces = Vec::with_capacity(no_faces);
+        let mut face_indices = Vec::with_capacity(no_faces);
+        for (i, face) in indices.chunks_exact(3).enumerate() {
+            if face.iter().any(|index| *index as usize >= no_vertices) {
+                report.out_of_range_faces.push(i);
+            } else if face[0] == face[1] || face[1] == face[2] || face[2] == face[0] {
+                report.degenerate_faces.push(i);
+            } else {
+                faces.push([face[0], face[1], face[2]]);
+                face_indices.push(i);
+            }
+        }
+
+        // Flip faces which use an edge in the same direction as an earlier face and disconnect faces from edges
+        // which are already shared by two faces or which are still used in the same direction
+        let mut edge_count = HashMap::<(u32, u32), usize>::new();
+        let mut directed_edges = HashSet::<(u32, u32)>::new();
+        for (face, input_index) in faces.iter_mut().zip(face_indices) {
+            let is_used_in_same_direction =
+                |face: &[u32; 3], i: usize| directed_edges.contains(&(face[i], face[(i + 1) % 3]));
+            if (0..3).any(|i| is_used_in_same_direction(face, i)) {
+                let flipped = [face[0], face[2], face[1]];
+                if (0..3).all(|i| {
+                    !is_used_in_same_direction(&flipped, i)
+                        && edge_count
+                            .get(&sort(flipped[i], flipped[(i + 1) % 3]))
+                            .copied()
+                            .unwrap_or(0)
+                            < 2
+                }) {
+                    *face = flipped;
+                    report.flipped_faces.push(input_index);
+                }
+            }
+
+            let mut should_duplicate = [false; 3];
+            for i in 0..3 {
+                let key = sort(face[i], face[(i + 1) % 3]);
+                if edge_count.get(&key).copied().unwrap_or(0) >= 2
+                    || is_used_in_same_direction(face, i)
+                {
+                    let edge = unsafe { (VertexID::new(key.0), VertexID::new(key.1)) };
+                    if !report.non_manifold_edges.contains(&edge) {
+                        report.non_manifold_edges.push(edge);
+                    }
+                    should_duplicate[i] = true;
+                    should_duplicate[(i + 1) % 3] = true;
+                }
+            }
+            for i in 0..3 {
+                if should_duplicate[i] {
+                    face[i] = duplicate_vertex(&mut positions, &mut report, face[i]);
+                }
+            }
+            for i in 0..3 {
+                *edge_count
+                    .entry(sort(face[i], face[(i + 1) % 3]))
+                    .or_insert(0) += 1;
+                directed_edges.insert((face[i], face[(i + 1) % 3]));
+            }
+        }
+
+        // Duplicate vertices which connect more than one fan of faces
+        let mut vertex_faces = HashMap::<u32, Vec<usize>>::new();
+        for (i, face) in faces.iter().enumerate() {
+            for vertex in face {
+                vertex_faces.entry(*vertex).or_default().push(i);
+            }
+        }
+        let mut vertices: Vec<u32> = vertex_faces.keys().copied().collect();
+        vertices.sort();
+        for vertex in vertices {
+            let mut to_visit = vertex_faces.remove(&vertex).unwrap();
+            let mut is_first_fan = true;
+            while let Some(start) = to_visit.pop() {
+                let mut fan = vec![start];
+                let mut i = 0;
+                while i < fan.len() {
+                    let neighbours = faces[fan[i]];
+                    to_visit.retain(|f| {
+                        let is_neighbour = faces[*f]
+                            .iter()
+                            .any(|v| *v != vertex && neighbours.contains(v));
+                        if is_neighbour {
+                            fan.push(*f);
+                        }
+                        !is_neighbour
+                    });
+                    i += 1;
+                }
+                if !is_first_fan {
+                    let new_vertex = duplicate_vertex(&mut positions, &mut report, vertex);
+                    for f in fan {
+                        faces[f]
+                            .iter_mut()
+                            .filter(|v| **v == vertex)
+                            .for_each(|v| *v = new_vertex);
+                    }
+                }
+                is_first_fan = false;
+            }
+        }
+
+        let mut is_used = vec![false; input.vertex_count()];
+        for vertex in faces.iter().flatten() {
+            if let Some(is_used) = is_used.get_mut(*vertex as usize) {
+                *is_used = true;
+            }
+        }
+        report.unused_vertices = (0..is_used.len() as u32)
+            .filter(|i| !is_used[*i as usize])
+            .map(|i| unsafe { VertexID::new(i) })
+            .collect();
+
+        // Copy the vertex attributes to the duplicated vertices
+        let mut origin: Vec<usize> = (0..input.vertex_count()).collect();
+        for (original, duplicate) in report.duplicated_vertices.iter() {
+            debug_assert_eq!(**duplicate as usize, origin.len());
+            origin.push(origin[**original as usize]);
+        }
+        fn duplicate<T: Copy>(values: &Option<Vec<T>>, origin: &[usize]) -> Option<Vec<T>> {
+            values
+                .as_ref()
+                .map(|values| origin.iter().map(|i| values[*i]).collect())
+        }
+
+        let mut mesh = Self::new(&TriMesh {
+            indices: Indices::U32(faces.into_iter().flatten().collect()),
+            positions: Positions::F64(positions),
+            normals: duplicate(&input.normals, &origin),
+            tangents: duplicate(&input.tangents, &origin),
+            uvs: duplicate(&input.uvs, &origin),
+            colors: duplicate(&input.colors, &origin),
+        });
+        if !report.unused_vertices.is_empty() {
+            mesh.remove_lonely_primitives();
+        }
+        (mesh, report)
+    }
+
+    ///
+    /// Returns the index of each vertex in the order the vertices are visited by [Mesh::vertex_iter],
+    /// ie. the index of the vertex in the buffers of the exported mesh (see [Mesh::export]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let vertex_indices = mesh.vertex_index_map();
+    /// let positions = mesh.export().positions.to_f64();
+    /// for vertex_id in mesh.vertex_iter() {
+    ///     assert_eq!(positions[vertex_indices[&vertex_id]], mesh.vertex_position(vertex_id));
+    /// }
+    /// ```
+    ///
+    pub fn vertex_index_map(&self) -> VertexProperty<usize> {
+        self.vertex_iter()
+            .enumerate()
+            .map(|(i, vertex_id)| (vertex_id, i))
+            .collect()
+    }
+
+    ///
+    /// Returns the positions of the corners of each face in the order the faces are visited by [Mesh::face_iter], ie. three positions for each face and no shared vertices,
+    /// which is for example needed when rendering with flat shading or with data given per face, see [Mesh::face_id_buffer].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::icosphere(1);
+    /// let positions = mesh.non_indexed_positions_buffer();
+    /// assert_eq!(positions.len(), 3 * mesh.no_faces());
+    /// ```
+    ///
+    pub fn non_indexed_positions_buffer(&self) -> Vec<Vec3> {
+        let mut positions = Vec::with_capacity(3 * self.no_faces());
+        for face_id in self.face_iter() {
+            for halfedge_id in self.face_halfedge_iter(face_id) {
+                let vertex_id = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                positions.push(self.vertex_position(vertex_id));
+            }
+        }
+        positions
+    }
+
+    ///
+    /// Returns the index of the face id (see [ID]) for each face corner, aligned with [Mesh::non_indexed_positions_buffer],
+    /// for example to render each face with a unique color and map the color of a picked pixel back to the face using [Mesh::face_id_from_index].
+    /// The index of a face is unchanged by edits to other parts of the mesh, but might be reused by a new face when the face is removed, and it is changed by [Mesh::compact].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::icosphere(1);
+    /// let face_ids = mesh.face_id_buffer();
+    /// assert_eq!(face_ids.len(), mesh.non_indexed_positions_buffer().len());
+    ///
+    /// // The value read from the picked pixel
+    /// let picked = face_ids[30];
+    /// let face_id = mesh.face_id_from_index(picked).unwrap();
+    /// assert_eq!(face_id, mesh.face_iter().nth(10).unwrap());
+    /// ```
+    ///
+    pub fn face_id_buffer(&self) -> Vec<u32> {
+        self.face_iter().flat_map(|face_id| [*face_id; 3]).collect()
+    }
+
+    ///
+    /// Returns the id of the face with the given index (see [ID]) or `None` if no face in the mesh has that index, see [Mesh::face_id_buffer].
+    ///
+    pub fn face_id_from_index(&self, index: u32) -> Option<FaceID> {
+        self.connectivity_info.face_from_index(index)
+    }
+
+    ///
+    /// Returns the indices of the two end points of each edge, where each edge is only included once even though it consists of two half-edges.
+    /// The indices refer to the vertices in the same order as [Mesh::export] (see also [Mesh::vertex_index_map]), so they can for example be used to render the mesh as a wireframe using lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::icosphere(1);
+    /// let edge_indices = mesh.edge_indices_buffer();
+    /// assert_eq!(edge_indices.len(), 2 * mesh.no_edges());
+    /// ```
+    ///
+    pub fn edge_indices_buffer(&self) -> Vec<u32> {
+        self.edge_indices(self.edge_iter().map(HalfEdgeID::from))
+    }
+
+    ///
+    /// Returns the indices of the two end points of each edge on the boundary, like [Mesh::edge_indices_buffer] but only for the edges on the boundary,
+    /// for example to highlight the holes in the mesh.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::cylinder(16).into();
+    /// let edge_indices = mesh.boundary_edge_indices_buffer();
+    /// // The two ends of the cylinder are open
+    /// assert_eq!(edge_indices.len(), 2 * 2 * 16);
+    /// ```
+    ///
+    pub fn boundary_edge_indices_buffer(&self) -> Vec<u32> {
+        self.edge_indices(
+            self.edge_iter()
+                .map(HalfEdgeID::from)
+                .filter(|halfedge_id| self.is_edge_on_boundary(*halfedge_id)),
+        )
+    }
+
+    fn edge_indices(&self, halfedge_ids: impl Iterator<Item = HalfEdgeID>) -> Vec<u32> {
+        let vertex_indices = self.vertex_index_map();
+        halfedge_ids
+            .flat_map(|halfedge_id| {
+                let (vertex_id0, vertex_id1) = self.edge_vertices(halfedge_id);
+                [
+                    vertex_indices[&vertex_id0] as u32,
+                    vertex_indices[&vertex_id1] as u32,
+                ]
+            })
+            .collect()
+    }
+
     ///
     /// Exports the [Mesh] into a [three_d_asset::TriMesh] that contain the raw buffer data.
     /// The [three_d_asset::TriMesh] can then for example be visualized or saved to disk (using the [three_d_asset::io] module).
+    /// The normals, tangents, uv coordinates and colors given when constructing the mesh are exported as well, see [Vertex attributes](#vertex-attributes).
     ///
     pub fn export(&self) -> three_d_asset::TriMesh {
-        use three_d_asset::{Indices, Positions, TriMesh};
-        let vertices: Vec<VertexID> = self.vertex_iter().collect();
+        self.export_with_positions(three_d_asset::Positions::F64(
+            self.vertex_iter()
+                .map(|vertex_id| self.vertex_position(vertex_id))
+                .collect(),
+        ))
+    }
+
+    /// Exports the [Mesh] like [Mesh::export], but with the given positions which must be in the same order as [Mesh::vertex_iter].
+    fn export_with_positions(&self, positions: three_d_asset::Positions) -> three_d_asset::TriMesh {
+        use three_d_asset::{Indices, TriMesh};
+        let vertex_indices = self.vertex_index_map();
         let mut indices = Vec::with_capacity(self.no_faces() * 3);
         for face_id in self.face_iter() {
             for halfedge_id in self.face_halfedge_iter(face_id) {
                 let vertex_id = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
-                let index = vertices.iter().position(|v| v == &vertex_id).unwrap();
-                indices.push(index as u32);
+                indices.push(vertex_indices[&vertex_id] as u32);
             }
         }
+        let normals = self
+            .vertex_iter()
+            .map(|vertex_id| {
+                self.stored_vertex_normal(vertex_id)
+                    .unwrap_or_else(|| self.vertex_normal(vertex_id).cast::<f32>().unwrap())
+            })
+            .collect::<Vec<_>>();
+        let tangents = self.tangent_buffer(&normals);
         TriMesh {
             indices: Indices::U32(indices),
-            positions: Positions::F64(
-                self.vertex_iter()
-                    .map(|vertex_id| self.vertex_position(vertex_id))
-                    .collect::<Vec<_>>(),
+            positions,
+            normals: Some(normals),
+            tangents,
+            uvs: self.vertex_attribute_buffer(
+                &self.vertex_attributes.uvs,
+                three_d_asset::vec2(0.0, 0.0),
             ),
-            normals: Some(
-                self.vertex_iter()
-                    .map(|vertex_id| self.vertex_normal(vertex_id).cast::<f32>().unwrap())
-                    .collect::<Vec<_>>(),
+            colors: self.vertex_attribute_buffer(
+                &self.vertex_attributes.colors,
+                three_d_asset::Srgba::default(),
             ),
-            ..Default::default()
+        }
+    }
+
+    ///
+    /// Exports the [Mesh] into a [three_d_asset::TriMesh] like [Mesh::export], but with the positions in single precision ([three_d_asset::Positions::F32]),
+    /// which halves the memory used by the positions and avoids a conversion when for example uploading the positions to the GPU.
+    /// The single precision positions are computed directly from the mesh, so the double precision positions are never allocated.
+    /// Use [Mesh::set_position_precision] to also store the positions of the mesh in single precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let tri_mesh = mesh.export_with_f32_positions();
+    /// assert!(matches!(tri_mesh.positions, three_d_asset::Positions::F32(_)));
+    /// ```
+    ///
+    pub fn export_with_f32_positions(&self) -> three_d_asset::TriMesh {
+        self.export_with_positions(three_d_asset::Positions::F32(
+            self.vertex_iter()
+                .map(|vertex_id| self.vertex_position(vertex_id).cast::<f32>().unwrap())
+                .collect(),
+        ))
+    }
+
+    ///
+    /// Exports the [Mesh] into a [three_d_asset::TriMesh] like [Mesh::export], but the indices are stored as [three_d_asset::Indices::U16] if the number of vertices allows it
+    /// and as [three_d_asset::Indices::U32] otherwise, which halves the memory used by the indices of most meshes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// let tri_mesh = mesh.export_with_smallest_indices();
+    /// assert!(matches!(tri_mesh.indices, three_d_asset::Indices::U16(_)));
+    /// assert_eq!(tri_mesh.indices.to_u32(), mesh.export().indices.to_u32());
+    /// ```
+    ///
+    pub fn export_with_smallest_indices(&self) -> three_d_asset::TriMesh {
+        use three_d_asset::Indices;
+        let mut mesh = self.export();
+        if self.no_vertices() <= u16::MAX as usize + 1 {
+            if let Indices::U32(indices) = &mesh.indices {
+                mesh.indices = Indices::U16(indices.iter().map(|i| *i as u16).collect());
+            }
+        }
+        mesh
+    }
+
+    ///
+    /// Exports the [Mesh] into a [three_d_asset::TriMesh] like [Mesh::export], but with split normals at the sharp edges such that hard edges are shaded correctly.
+    /// An edge is sharp if its dihedral angle (see [Mesh::edge_dihedral_angle]) is larger than the given crease angle or if it is marked as a crease (see [Mesh::set_crease]).
+    ///
+    /// The faces around each vertex are divided into groups separated by the sharp edges and each group gets its own copy of the vertex,
+    /// with the normal given as the average of the normals of the faces in the group.
+    /// Vertices where all faces are in one group are exported once with the same normal as [Mesh::export],
+    /// ie. the stored normal if any (see [Vertex attributes](#vertex-attributes)) or otherwise the computed normal (see [Mesh::vertex_normal]).
+    /// The tangents, uv coordinates and colors are exported like in [Mesh::export] and copied to each copy of a vertex,
+    /// where the tangents are made orthogonal to the normal of the copy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::cube().into();
+    /// mesh.merge_overlapping_primitives();
+    /// let tri_mesh = mesh.export_with_split_normals(degrees(30.0));
+    /// // Each corner of the cube is split into three vertices, one for each side
+    /// assert_eq!(tri_mesh.vertex_count(), 24);
+    /// ```
+    ///
+    pub fn export_with_split_normals(
+        &self,
+        crease_angle: impl Into<Radians>,
+    ) -> three_d_asset::TriMesh {
+        use std::collections::HashMap;
+        use three_d_asset::{Indices, Positions, TriMesh};
+        let crease_angle = crease_angle.into();
+        let is_sharp = |halfedge_id| {
+            self.is_crease(halfedge_id)
+                || match self.edge_dihedral_angle(halfedge_id) {
+                    Some(angle) => angle > crease_angle,
+                    None => true,
+                }
+        };
+
+        let vertex_normals = self
+            .vertex_iter()
+            .map(|vertex_id| {
+                self.stored_vertex_normal(vertex_id)
+                    .unwrap_or_else(|| self.vertex_normal(vertex_id).cast::<f32>().unwrap())
+            })
+            .collect::<Vec<_>>();
+        let vertex_tangents = self.tangent_buffer(&vertex_normals);
+        let vertex_uvs = self
+            .vertex_attribute_buffer(&self.vertex_attributes.uvs, three_d_asset::vec2(0.0, 0.0));
+        let vertex_colors = self.vertex_attribute_buffer(
+            &self.vertex_attributes.colors,
+            three_d_asset::Srgba::default(),
+        );
+
+        let mut positions = Vec::with_capacity(self.no_vertices());
+        let mut normals = Vec::with_capacity(self.no_vertices());
+        let mut tangents = vertex_tangents
+            .as_ref()
+            .map(|_| Vec::with_capacity(self.no_vertices()));
+        let mut uvs = vertex_uvs
+            .as_ref()
+            .map(|_| Vec::with_capacity(self.no_vertices()));
+        let mut colors = vertex_colors
+            .as_ref()
+            .map(|_| Vec::with_capacity(self.no_vertices()));
+        let mut corner_indices = HashMap::with_capacity(3 * self.no_faces());
+        for (i, vertex_id) in self.vertex_iter().enumerate() {
+            // Assign each face around the vertex to a group of faces connected by edges that are not sharp
+            let mut groups: Vec<(FaceID, usize)> = Vec::new();
+            for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
+                if let Some(face_id) = self.walker_from_halfedge(halfedge_id).face_id() {
+                    groups.push((face_id, groups.len()));
+                }
+            }
+            for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
+                let mut walker = self.walker_from_halfedge(halfedge_id);
+                if let (Some(face_id0), Some(face_id1)) =
+                    (walker.face_id(), walker.as_twin().face_id())
+                {
+                    if !is_sharp(halfedge_id) {
+                        let group0 = groups.iter().find(|(f, _)| *f == face_id0).unwrap().1;
+                        let group1 = groups.iter().find(|(f, _)| *f == face_id1).unwrap().1;
+                        for (_, group) in groups.iter_mut() {
+                            if *group == group1 {
+                                *group = group0;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let is_split = groups.iter().any(|(_, g)| *g != groups[0].1);
+            let mut group_indices = HashMap::new();
+            for (face_id, group) in groups.iter() {
+                let index = *group_indices.entry(*group).or_insert_with(|| {
+                    let normal = if is_split {
+                        groups
+                            .iter()
+                            .filter(|(_, g)| g == group)
+                            .map(|(f, _)| self.face_normal(*f))
+                            .sum::<Vec3>()
+                            .normalize()
+                            .cast::<f32>()
+                            .unwrap()
+                    } else {
+                        vertex_normals[i]
+                    };
+                    positions.push(self.vertex_position(vertex_id));
+                    normals.push(normal);
+                    if let (Some(tangents), Some(vertex_tangents)) =
+                        (&mut tangents, &vertex_tangents)
+                    {
+                        tangents.push(if is_split {
+                            super::attributes::orthogonal_tangent(vertex_tangents[i], normal)
+                        } else {
+                            vertex_tangents[i]
+                        });
+                    }
+                    if let (Some(uvs), Some(vertex_uvs)) = (&mut uvs, &vertex_uvs) {
+                        uvs.push(vertex_uvs[i]);
+                    }
+                    if let (Some(colors), Some(vertex_colors)) = (&mut colors, &vertex_colors) {
+                        colors.push(vertex_colors[i]);
+                    }
+                    positions.len() as u32 - 1
+                });
+                corner_indices.insert((vertex_id, *face_id), index);
+            }
+        }
+
+        let mut indices = Vec::with_capacity(self.no_faces() * 3);
+        for face_id in self.face_iter() {
+            for halfedge_id in self.face_halfedge_iter(face_id) {
+                let vertex_id = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                indices.push(corner_indices[&(vertex_id, face_id)]);
+            }
+        }
+        TriMesh {
+            indices: Indices::U32(indices),
+            positions: Positions::F64(positions),
+            normals: Some(normals),
+            tangents,
+            uvs,
+            colors,
         }
     }
 }
 
 impl From<three_d_asset::TriMesh> for Mesh {
     fn from(mesh: three_d_asset::TriMesh) -> Self {
-        Self::new(&mesh)
+        Self::from_tri_mesh(mesh)
     }
 }
 
@@ -161,10 +991,159 @@ impl From<&Mesh> for three_d_asset::TriMesh {
         mesh.export()
     }
 }
+/// Returns the problems which prevent constructing a valid mesh from the given indices and positions.
+fn validate_input(indices: &[u32], positions: &[f64]) -> InputReport {
+    let mut report = InputReport::default();
+    if !indices.len().is_multiple_of(3) || !positions.len().is_multiple_of(3) {
+        report.issues.push(InputIssue::InvalidBufferLength {
+            indices: indices.len(),
+            positions: positions.len(),
+        });
+        return report;
+    }
+    let no_vertices = positions.len() / 3;
+    fn sort(a: u32, b: u32) -> (u32, u32) {
+        if a < b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    let mut faces = HashMap::<[u32; 3], usize>::new();
+    let mut edges = HashMap::<(u32, u32), Vec<(u32, u32)>>::new();
+    let mut vertex_faces = vec![Vec::new(); no_vertices];
+    for (i, face) in indices.chunks(3).enumerate() {
+        let out_of_bounds: Vec<u32> = face
+            .iter()
+            .copied()
+            .filter(|index| *index as usize >= no_vertices)
+            .collect();
+        if !out_of_bounds.is_empty() {
+            for index in out_of_bounds {
+                report
+                    .issues
+                    .push(InputIssue::IndexOutOfBounds { face: i, index });
+            }
+            continue;
+        }
+        if face[0] == face[1] || face[1] == face[2] || face[2] == face[0] {
+            report.issues.push(InputIssue::DegenerateFace(i));
+            continue;
+        }
+        let mut key = [face[0], face[1], face[2]];
+        key.sort();
+        if let Some(first) = faces.get(&key) {
+            report.issues.push(InputIssue::DuplicateFace(*first, i));
+            continue;
+        }
+        faces.insert(key, i);
+        for j in 0..3 {
+            let (a, b) = (face[j], face[(j + 1) % 3]);
+            edges.entry(sort(a, b)).or_default().push((a, b));
+            vertex_faces[a as usize].push([face[0], face[1], face[2]]);
+        }
+    }
+
+    let mut edge_keys: Vec<_> = edges.keys().copied().collect();
+    edge_keys.sort();
+    for key in edge_keys {
+        let directions = &edges[&key];
+        if directions.len() > 2 {
+            report
+                .issues
+                .push(InputIssue::NonManifoldEdge(key.0, key.1));
+        } else if directions.len() == 2 && directions[0] == directions[1] {
+            report
+                .issues
+                .push(InputIssue::InconsistentOrientation(key.0, key.1));
+        }
+    }
+
+    for (vertex, faces) in vertex_faces.into_iter().enumerate() {
+        let vertex = vertex as u32;
+        if faces.is_empty() {
+            report.issues.push(InputIssue::IsolatedVertex(vertex));
+            continue;
+        }
+        // Visit the faces around the vertex which are connected by an edge to the vertex
+        let mut visited = vec![false; faces.len()];
+        let mut stack = vec![0];
+        visited[0] = true;
+        while let Some(i) = stack.pop() {
+            for (j, face) in faces.iter().enumerate() {
+                if !visited[j] && faces[i].iter().any(|v| *v != vertex && face.contains(v)) {
+                    visited[j] = true;
+                    stack.push(j);
+                }
+            }
+        }
+        if visited.contains(&false) {
+            report.issues.push(InputIssue::NonManifoldVertex(vertex));
+        }
+    }
+    report
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use three_d_asset::{Positions, TriMesh};
+    use std::collections::HashSet;
+    use three_d_asset::{Indices, Positions, TriMesh};
+
+    #[test]
+    fn test_try_new() {
+        let mesh = Mesh::icosphere(2).export();
+        let indices = mesh.indices.to_u32().unwrap();
+        let positions: Vec<f64> = mesh
+            .positions
+            .to_f64()
+            .into_iter()
+            .flat_map(|p| [p.x, p.y, p.z])
+            .collect();
+        let result = Mesh::try_new(&indices, &positions).unwrap();
+        result.is_valid().unwrap();
+        assert_eq!(result.no_faces(), mesh.triangle_count());
+
+        let issues = |indices: &[u32], positions: &[f64]| match Mesh::try_new(indices, positions) {
+            Err(Error::InvalidInput(report)) => report.issues,
+            _ => panic!("the input should be invalid"),
+        };
+        let positions = [
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0,
+            -1.0,
+        ];
+        assert_eq!(
+            issues(&[0, 1], &positions),
+            vec![InputIssue::InvalidBufferLength {
+                indices: 2,
+                positions: 18
+            }]
+        );
+        assert_eq!(
+            issues(&[0, 1, 2, 2, 1, 3, 0, 0, 4], &positions),
+            vec![
+                InputIssue::DegenerateFace(2),
+                InputIssue::IsolatedVertex(4),
+                InputIssue::IsolatedVertex(5)
+            ]
+        );
+        assert_eq!(
+            issues(&[0, 1, 2, 2, 1, 3, 1, 2, 0, 1, 0, 4, 0, 1, 5], &positions),
+            vec![
+                InputIssue::DuplicateFace(0, 2),
+                InputIssue::NonManifoldEdge(0, 1)
+            ]
+        );
+        assert_eq!(
+            issues(&[0, 1, 2, 1, 2, 3, 1, 0, 4], &positions[..15]),
+            vec![InputIssue::InconsistentOrientation(1, 2)]
+        );
+        assert_eq!(
+            issues(&[0, 1, 2, 0, 3, 4], &positions[..15]),
+            vec![InputIssue::NonManifoldVertex(0)]
+        );
+    }
 
     #[test]
     fn test_from_obj() {
@@ -202,6 +1181,25 @@ mod tests {
         mesh.is_valid().unwrap();
     }
 
+    #[test]
+    fn test_from_tri_mesh() {
+        let mut inputs = vec![TriMesh::sphere(4), TriMesh::cylinder(8), TriMesh::square()];
+        inputs[0].indices = Indices::U32(inputs[0].indices.to_u32().unwrap());
+        inputs[1].positions = Positions::F32(inputs[1].positions.to_f32());
+        for input in inputs {
+            let mesh = Mesh::new(&input);
+            let moved = Mesh::from_tri_mesh(input);
+            moved.is_valid().unwrap();
+            assert_eq!(moved.no_vertices(), mesh.no_vertices());
+            assert_eq!(moved.no_faces(), mesh.no_faces());
+            let m0 = mesh.export();
+            let m1 = moved.export();
+            assert_eq!(m0.indices.to_u32(), m1.indices.to_u32());
+            assert_eq!(m0.positions.to_f64(), m1.positions.to_f64());
+            assert_eq!(m0.uvs, m1.uvs);
+        }
+    }
+
     #[test]
     fn test_indexed_export() {
         let mesh: Mesh = TriMesh::cylinder(16).into();
@@ -226,6 +1224,298 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_export_with_f32_positions() {
+        let mesh: Mesh = TriMesh::sphere(4).into();
+        let m = mesh.export_with_f32_positions();
+        m.validate().unwrap();
+        assert_eq!(m.indices.to_u32(), mesh.export().indices.to_u32());
+        for (p, q) in m
+            .positions
+            .to_f64()
+            .iter()
+            .zip(mesh.export().positions.to_f64())
+        {
+            assert!(p.distance(q) < 0.000001);
+        }
+    }
+
+    #[test]
+    fn test_export_with_smallest_indices() {
+        let mesh = crate::test_utility::cube();
+        let m = mesh.export_with_smallest_indices();
+        m.validate().unwrap();
+        assert!(matches!(m.indices, Indices::U16(_)));
+        assert_eq!(m.indices.to_u32(), mesh.export().indices.to_u32());
+    }
+
+    #[test]
+    fn test_export_with_split_normals() {
+        let mesh = crate::test_utility::cube();
+        let m = mesh.export_with_split_normals(degrees(30.0));
+        m.validate().unwrap();
+        assert_eq!(m.triangle_count(), mesh.no_faces());
+        assert_eq!(m.vertex_count(), 24);
+        let positions = m.positions.to_f64();
+        let normals = m.normals.as_ref().unwrap();
+        m.for_each_triangle(|i0, i1, i2| {
+            let normal = (positions[i1] - positions[i0])
+                .cross(positions[i2] - positions[i0])
+                .normalize()
+                .cast::<f32>()
+                .unwrap();
+            for i in [i0, i1, i2] {
+                assert!(normals[i].distance(normal) < 0.001);
+            }
+        });
+
+        // No edges are sharper than 100 degrees
+        let m = mesh.export_with_split_normals(degrees(100.0));
+        assert_eq!(m.vertex_count(), 8);
+        assert_eq!(m.normals, mesh.export().normals);
+    }
+
+    #[test]
+    fn test_export_with_split_normals_keeps_vertex_attributes() {
+        let mut input = TriMesh::sphere(4);
+        input.compute_normals();
+        input.uvs = Some(
+            input
+                .positions
+                .to_f32()
+                .iter()
+                .map(|p| three_d_asset::vec2(p.x, p.y))
+                .collect(),
+        );
+        input.compute_tangents();
+        input.colors = Some(vec![three_d_asset::Srgba::RED; input.vertex_count()]);
+        let mesh: Mesh = (&input).into();
+        // No edges are sharper than 180 degrees
+        let m = mesh.export_with_split_normals(degrees(180.0));
+        let export = mesh.export();
+        assert_eq!(m.normals, export.normals);
+        assert_eq!(m.tangents, export.tangents);
+        assert_eq!(m.uvs, export.uvs);
+        assert_eq!(m.colors, export.colors);
+
+        let mut mesh = crate::test_utility::cube();
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            mesh.set_vertex_uv(vertex_id, three_d_asset::vec2(p.x as f32, p.y as f32));
+        }
+        let m = mesh.export_with_split_normals(degrees(30.0));
+        m.validate().unwrap();
+        assert_eq!(m.vertex_count(), 24);
+        let positions = m.positions.to_f64();
+        for (position, uv) in positions.iter().zip(m.uvs.as_ref().unwrap()) {
+            assert_eq!(
+                *uv,
+                three_d_asset::vec2(position.x as f32, position.y as f32)
+            );
+        }
+    }
+
+    #[test]
+    fn test_edge_indices_buffer() {
+        let mut mesh = Mesh::icosphere(1);
+        let face_id = mesh.face_iter().next().unwrap();
+        mesh.remove_face(face_id);
+        let positions = mesh.export().positions.to_f64();
+
+        let edge_indices = mesh.edge_indices_buffer();
+        assert_eq!(edge_indices.len(), 2 * mesh.no_edges());
+        let mut edges = HashSet::new();
+        for edge in edge_indices.chunks(2) {
+            let (i0, i1) = (edge[0] as usize, edge[1] as usize);
+            assert!(edges.insert((i0.min(i1), i0.max(i1))));
+            assert!(mesh.edge_iter().any(|e| {
+                let (v0, v1) = mesh.edge_vertices(e.into());
+                let (p0, p1) = (mesh.vertex_position(v0), mesh.vertex_position(v1));
+                (p0, p1) == (positions[i0], positions[i1])
+                    || (p0, p1) == (positions[i1], positions[i0])
+            }));
+        }
+
+        let boundary_edge_indices = mesh.boundary_edge_indices_buffer();
+        assert_eq!(boundary_edge_indices.len(), 2 * 3);
+        assert!(boundary_edge_indices
+            .chunks(2)
+            .all(|edge| edges
+                .contains(&(edge[0].min(edge[1]) as usize, edge[0].max(edge[1]) as usize))));
+    }
+
+    #[test]
+    fn test_face_id_buffer() {
+        let mut mesh = Mesh::icosphere(1);
+        let face_id = mesh.face_iter().nth(5).unwrap();
+        mesh.remove_face(face_id);
+
+        let positions = mesh.non_indexed_positions_buffer();
+        let face_ids = mesh.face_id_buffer();
+        assert_eq!(positions.len(), 3 * mesh.no_faces());
+        assert_eq!(face_ids.len(), positions.len());
+        for (i, index) in face_ids.iter().enumerate() {
+            let face_id = mesh.face_id_from_index(*index).unwrap();
+            let (p0, p1, p2) = mesh.face_positions(face_id);
+            assert!([p0, p1, p2].contains(&positions[i]));
+        }
+        assert_eq!(mesh.face_id_from_index(*face_id), None);
+        assert_eq!(mesh.face_id_from_index(100), None);
+
+        // A new face reusing the index has a new id
+        let new_vertex_id = mesh.split_face(mesh.face_iter().next().unwrap(), vec3(0.0, 0.0, 0.0));
+        let new_face_id = mesh.face_id_from_index(*face_id).unwrap();
+        assert_ne!(new_face_id, face_id);
+        let (v0, v1, v2) = mesh.face_vertices(new_face_id);
+        assert!([v0, v1, v2].contains(&new_vertex_id));
+    }
+
+    #[test]
+    fn test_new_tolerant_with_non_manifold_edge() {
+        let input = TriMesh {
+            indices: Indices::U8(vec![0, 1, 2, 1, 0, 3, 0, 1, 4]),
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(0.0, -1.0, 0.0),
+                vec3(0.0, 0.0, 1.0),
+            ]),
+            ..Default::default()
+        };
+        let (mesh, report) = Mesh::new_tolerant(&input);
+
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_faces(), 3);
+        assert_eq!(mesh.no_vertices(), 7);
+        assert_eq!(
+            report.non_manifold_edges,
+            vec![unsafe { (VertexID::new(0), VertexID::new(1)) }]
+        );
+        assert_eq!(report.duplicated_vertices.len(), 2);
+        assert!(report.degenerate_faces.is_empty());
+    }
+
+    #[test]
+    fn test_new_tolerant_with_non_manifold_vertex() {
+        let input = TriMesh {
+            indices: Indices::U8(vec![0, 1, 2, 0, 3, 4, 0, 0, 1]),
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(-1.0, 0.0, 0.0),
+                vec3(0.0, -1.0, 0.0),
+            ]),
+            ..Default::default()
+        };
+        let (mesh, report) = Mesh::new_tolerant(&input);
+
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_faces(), 2);
+        assert_eq!(mesh.no_vertices(), 6);
+        assert_eq!(report.degenerate_faces, vec![2]);
+        assert!(report.non_manifold_edges.is_empty());
+        assert_eq!(report.duplicated_vertices.len(), 1);
+    }
+
+    #[test]
+    fn test_new_tolerant_with_inconsistent_orientation() {
+        let input = TriMesh {
+            indices: Indices::U8(vec![0, 1, 2, 0, 1, 3]),
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(0.0, -1.0, 0.0),
+            ]),
+            ..Default::default()
+        };
+        let (mesh, report) = Mesh::new_tolerant(&input);
+
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_faces(), 2);
+        assert_eq!(mesh.no_vertices(), 4);
+        assert_eq!(report.flipped_faces, vec![1]);
+        assert!(report.non_manifold_edges.is_empty());
+        assert!(report.duplicated_vertices.is_empty());
+
+        // The last face cannot be flipped without using another edge in the same direction
+        let input = TriMesh {
+            indices: Indices::U8(vec![0, 1, 2, 1, 0, 3, 0, 4, 5, 0, 3, 4]),
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(0.0, -1.0, 0.0),
+                vec3(-1.0, 0.0, 0.0),
+                vec3(-1.0, 1.0, 0.0),
+            ]),
+            ..Default::default()
+        };
+        let (mesh, report) = Mesh::new_tolerant(&input);
+
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_faces(), 4);
+        assert!(report.flipped_faces.is_empty());
+        assert_eq!(
+            report.non_manifold_edges,
+            vec![unsafe { (VertexID::new(0), VertexID::new(3)) }]
+        );
+    }
+
+    #[test]
+    fn test_new_tolerant_with_unused_vertices() {
+        let input = TriMesh {
+            indices: Indices::U8(vec![0, 1, 2, 3, 3, 1]),
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(0.0, -1.0, 0.0),
+                vec3(0.0, 0.0, 1.0),
+            ]),
+            ..Default::default()
+        };
+        let (mesh, report) = Mesh::new_tolerant(&input);
+
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_vertices(), 3);
+        assert_eq!(report.degenerate_faces, vec![1]);
+        assert_eq!(report.unused_vertices, unsafe {
+            vec![VertexID::new(3), VertexID::new(4)]
+        });
+    }
+
+    #[test]
+    fn test_new_tolerant_with_out_of_range_indices() {
+        let input = TriMesh {
+            indices: Indices::U8(vec![0, 1, 2, 1, 0, 3, 2, 1, 7]),
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(0.0, -1.0, 0.0),
+            ]),
+            ..Default::default()
+        };
+        let (mesh, report) = Mesh::new_tolerant(&input);
+
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_vertices(), 4);
+        assert_eq!(mesh.no_faces(), 2);
+        assert_eq!(report.out_of_range_faces, vec![2]);
+        assert!(report.degenerate_faces.is_empty());
+        assert!(!report.is_empty());
+    }
+
+    #[test]
+    fn test_new_tolerant_with_manifold_input() {
+        let (mesh, report) = Mesh::new_tolerant(&TriMesh::sphere(4));
+        mesh.is_valid().unwrap();
+        assert!(report.is_empty());
+    }
+
     #[test]
     fn test_new_from_positions() {
         let mesh: Mesh = TriMesh {
@@ -1,5 +1,6 @@
 use crate::mesh::connectivity_info::ConnectivityInfo;
 use crate::mesh::*;
+use std::iter::FusedIterator;
 
 /// An iterator over the vertices created by [Mesh::vertex_iter].
 pub type VertexIter = Box<dyn Iterator<Item = VertexID>>;
@@ -11,6 +12,7 @@ pub type HalfEdgeIter = Box<dyn Iterator<Item = HalfEdgeID>>;
 pub type FaceIter = Box<dyn Iterator<Item = FaceID>>;
 
 /// An iterator over the half-edges starting in a given vertex created by [Mesh::vertex_halfedge_iter].
+#[derive(Clone)]
 pub struct VertexHalfedgeIter<'a> {
     walker: Walker<'a>,
     start: Option<HalfEdgeID>,
@@ -59,7 +61,65 @@ impl<'a> Iterator for VertexHalfedgeIter<'a> {
     }
 }
 
+impl<'a> FusedIterator for VertexHalfedgeIter<'a> {}
+
+/// An iterator over the vertices adjacent to a given vertex in counter-clockwise order created by [Mesh::vertex_vertex_iter].
+#[derive(Clone)]
+pub struct VertexVertexIter<'a> {
+    walker: Walker<'a>,
+    start: Option<HalfEdgeID>,
+    is_done: bool,
+}
+
+impl<'a> VertexVertexIter<'a> {
+    fn new(vertex_id: VertexID, connectivity_info: &'a ConnectivityInfo) -> VertexVertexIter<'a> {
+        let mut walker = Walker::new(connectivity_info).into_vertex_halfedge_walker(vertex_id);
+        let mut start = walker.halfedge_id();
+        if let Some(first) = start {
+            // Rotate clockwise until a boundary is reached, if any, such that the iteration starts right after the boundary
+            loop {
+                walker.as_twin();
+                if walker.face_id().is_none() {
+                    walker.as_twin();
+                    break;
+                }
+                walker.as_next();
+                if walker.halfedge_id() == Some(first) {
+                    break;
+                }
+            }
+            start = walker.halfedge_id();
+        }
+        VertexVertexIter {
+            walker,
+            start,
+            is_done: start.is_none(),
+        }
+    }
+}
+
+impl<'a> Iterator for VertexVertexIter<'a> {
+    type Item = VertexID;
+
+    fn next(&mut self) -> Option<VertexID> {
+        if self.is_done {
+            return None;
+        }
+        let curr = self.walker.vertex_id().unwrap();
+        if self.walker.face_id().is_some() {
+            self.walker.as_previous().as_twin();
+            self.is_done = self.walker.halfedge_id() == self.start;
+        } else {
+            self.is_done = true;
+        }
+        Some(curr)
+    }
+}
+
+impl<'a> FusedIterator for VertexVertexIter<'a> {}
+
 /// An iterator over the three half-edges in a face created by [Mesh::face_halfedge_iter].
+#[derive(Clone)]
 pub struct FaceHalfedgeIter<'a> {
     walker: Walker<'a>,
     count: usize,
@@ -85,8 +145,103 @@ impl<'a> Iterator for FaceHalfedgeIter<'a> {
         self.count += 1;
         Some(self.walker.halfedge_id().unwrap())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (3 - self.count, Some(3 - self.count))
+    }
 }
 
+impl<'a> ExactSizeIterator for FaceHalfedgeIter<'a> {}
+
+impl<'a> FusedIterator for FaceHalfedgeIter<'a> {}
+
+/// An iterator over the up to three faces sharing an edge with a given face created by [Mesh::face_neighbours].
+#[derive(Clone)]
+pub struct FaceNeighbourIter<'a> {
+    walker: Walker<'a>,
+    count: usize,
+}
+
+impl<'a> FaceNeighbourIter<'a> {
+    fn new(face_id: FaceID, connectivity_info: &'a ConnectivityInfo) -> FaceNeighbourIter<'a> {
+        FaceNeighbourIter {
+            walker: Walker::new(connectivity_info).into_face_halfedge_walker(face_id),
+            count: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for FaceNeighbourIter<'a> {
+    type Item = FaceID;
+
+    fn next(&mut self) -> Option<FaceID> {
+        while self.count < 3 {
+            self.walker.as_next();
+            self.count += 1;
+            // Edges on the boundary have no face on the other side
+            let face_id = self.walker.as_twin().face_id();
+            self.walker.as_twin();
+            if face_id.is_some() {
+                return face_id;
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(3 - self.count))
+    }
+}
+
+impl<'a> FusedIterator for FaceNeighbourIter<'a> {}
+
+/// An iterator over the half-edges along a boundary loop created by [Mesh::boundary_loop_iter].
+#[derive(Clone)]
+pub struct BoundaryLoopIter<'a> {
+    walker: Walker<'a>,
+    start: Option<HalfEdgeID>,
+    is_done: bool,
+}
+
+impl<'a> BoundaryLoopIter<'a> {
+    fn new(
+        halfedge_id: HalfEdgeID,
+        connectivity_info: &'a ConnectivityInfo,
+    ) -> BoundaryLoopIter<'a> {
+        let mut walker = Walker::new(connectivity_info).into_halfedge_walker(halfedge_id);
+        if walker.face_id().is_some() {
+            walker.as_twin();
+        }
+        let start = if walker.face_id().is_none() {
+            walker.halfedge_id()
+        } else {
+            None
+        };
+        BoundaryLoopIter {
+            walker,
+            start,
+            is_done: start.is_none(),
+        }
+    }
+}
+
+impl<'a> Iterator for BoundaryLoopIter<'a> {
+    type Item = HalfEdgeID;
+
+    fn next(&mut self) -> Option<HalfEdgeID> {
+        if self.is_done {
+            return None;
+        }
+        let curr = self.walker.halfedge_id().unwrap();
+        self.walker.as_next_on_boundary();
+        self.is_done =
+            self.walker.halfedge_id().is_none() || self.walker.halfedge_id() == self.start;
+        Some(curr)
+    }
+}
+
+impl<'a> FusedIterator for BoundaryLoopIter<'a> {}
+
 /// An iterator over the edges created by [Mesh::edge_iter].
 pub struct EdgeIter<'a> {
     walker: Walker<'a>,
@@ -103,14 +258,14 @@ impl<'a> EdgeIter<'a> {
 }
 
 impl<'a> Iterator for EdgeIter<'a> {
-    type Item = HalfEdgeID;
+    type Item = EdgeID;
 
-    fn next(&mut self) -> Option<HalfEdgeID> {
+    fn next(&mut self) -> Option<EdgeID> {
         if let Some(next_id) = self.iter.next() {
             if self.walker.as_halfedge_walker(next_id).twin_id().unwrap() < next_id {
                 self.next()
             } else {
-                Some(next_id)
+                Some(unsafe { EdgeID::new_with_generation(*next_id, next_id.generation()) })
             }
         } else {
             None
@@ -162,7 +317,8 @@ impl Mesh {
     }
 
     ///
-    /// Iterator over the edges given as a half-edge id.
+    /// Iterator over the edge ids.
+    /// Use [HalfEdgeID::from] to get one of the half-edges of the edge.
     ///
     /// **Note:** Each edge is visited once. If you want to visit both half-edges of an edge, then use `halfedge_iter` instead.
     ///
@@ -173,8 +329,8 @@ impl Mesh {
     /// # let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
     /// let mut edge_length_average = 0.0;
     /// let mut i = 0;
-    /// for halfedge_id in mesh.edge_iter() {
-    ///     edge_length_average += mesh.edge_length(halfedge_id);
+    /// for edge_id in mesh.edge_iter() {
+    ///     edge_length_average += mesh.edge_length(edge_id.into());
     ///     i += 1;
     /// }
     /// edge_length_average /= i as f64;
@@ -229,6 +385,50 @@ impl Mesh {
         VertexHalfedgeIter::new(vertex_id, &self.connectivity_info)
     }
 
+    ///
+    /// Iterator over the vertices which are connected to the given vertex by an edge, ie. the one-ring vertices,
+    /// in counter-clockwise order when seen from the side the faces are facing.
+    /// If the vertex is on the boundary, the iteration starts and ends at the two neighbouring vertices on the boundary.
+    ///
+    /// **Note:** If the given vertex is the only connection between two or more separate sets of faces,
+    /// then this iterator will only iterate the vertices in one of the sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// # let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// # let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// let one_ring: Vec<VertexID> = mesh.vertex_vertex_iter(vertex_id).collect();
+    /// assert_eq!(one_ring.len(), mesh.vertex_halfedge_iter(vertex_id).count());
+    /// ```
+    ///
+    pub fn vertex_vertex_iter(&self, vertex_id: VertexID) -> VertexVertexIter<'_> {
+        VertexVertexIter::new(vertex_id, &self.connectivity_info)
+    }
+
+    ///
+    /// Iterator over the half-edges without an adjacent face along the boundary loop, ie. the rim of a hole, containing the given half-edge.
+    /// If the given half-edge has an adjacent face, the iteration starts at its twin instead
+    /// and if none of them are on the boundary, the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// # let mesh: Mesh = three_d_asset::TriMesh::cylinder(16).into();
+    /// # let halfedge_id = mesh.halfedge_iter().find(|h| mesh.walker_from_halfedge(*h).face_id().is_none()).unwrap();
+    /// let mut hole_circumference = 0.0;
+    /// for halfedge_id in mesh.boundary_loop_iter(halfedge_id) {
+    ///     hole_circumference += mesh.edge_length(halfedge_id);
+    /// }
+    /// ```
+    ///
+    pub fn boundary_loop_iter(&self, halfedge_id: HalfEdgeID) -> BoundaryLoopIter<'_> {
+        self.connectivity_info.check_halfedge(halfedge_id);
+        BoundaryLoopIter::new(halfedge_id, &self.connectivity_info)
+    }
+
     ///
     /// Iterator over the three half-edges connected to the given face.
     ///
@@ -247,6 +447,28 @@ impl Mesh {
     pub fn face_halfedge_iter(&self, face_id: FaceID) -> FaceHalfedgeIter {
         FaceHalfedgeIter::new(face_id, &self.connectivity_info)
     }
+
+    ///
+    /// Iterator over the faces which share an edge with the given face, ie. the up to three faces on the other side of the edges of the face,
+    /// in the same order as the edges are visited by [Mesh::face_halfedge_iter]. Edges on the boundary are skipped.
+    /// See [Mesh::face_k_ring] for the faces within a given number of edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(1);
+    /// let face_id = mesh.face_iter().next().unwrap();
+    /// assert_eq!(mesh.face_neighbours(face_id).count(), 3);
+    ///
+    /// let neighbour_id = mesh.face_neighbours(face_id).next().unwrap();
+    /// mesh.remove_face(neighbour_id);
+    /// assert_eq!(mesh.face_neighbours(face_id).count(), 2);
+    /// ```
+    ///
+    pub fn face_neighbours(&self, face_id: FaceID) -> FaceNeighbourIter<'_> {
+        FaceNeighbourIter::new(face_id, &self.connectivity_info)
+    }
 }
 
 #[cfg(test)]
@@ -303,21 +525,43 @@ mod tests {
         assert_eq!(6, i);
 
         // Test that two iterations return the same result
-        let vec: Vec<HalfEdgeID> = mesh.edge_iter().collect();
+        let vec: Vec<HalfEdgeID> = mesh.edge_iter().map(HalfEdgeID::from).collect();
         i = 0;
-        for halfedge_id in mesh.edge_iter() {
-            assert_eq!(halfedge_id, vec[i]);
+        for edge_id in mesh.edge_iter() {
+            assert_eq!(HalfEdgeID::from(edge_id), vec[i]);
             i = i + 1;
         }
 
         // Test that the twin is not returned
-        for halfedge_id in mesh.edge_iter() {
+        for edge_id in mesh.edge_iter() {
+            let halfedge_id = HalfEdgeID::from(edge_id);
             let twin_id = mesh.walker_from_halfedge(halfedge_id).twin_id().unwrap();
             assert!(halfedge_id < twin_id);
             assert!(vec.iter().find(|edge_id| *edge_id == &twin_id).is_none());
         }
     }
 
+    #[test]
+    fn test_edge_id() {
+        let mesh = crate::test_utility::subdivided_triangle();
+        for edge_id in mesh.edge_iter() {
+            let (halfedge_id, twin_id) = mesh.edge_halfedges(edge_id);
+            assert_eq!(mesh.edge_id(halfedge_id), edge_id);
+            assert_eq!(mesh.edge_id(twin_id), edge_id);
+            assert_eq!(
+                mesh.walker_from_halfedge(halfedge_id).twin_id(),
+                Some(twin_id)
+            );
+        }
+        assert_eq!(
+            mesh.halfedge_iter()
+                .map(|h| mesh.edge_id(h))
+                .collect::<std::collections::HashSet<_>>()
+                .len(),
+            mesh.no_edges()
+        );
+    }
+
     #[test]
     fn test_face_iterator() {
         let mesh = crate::test_utility::subdivided_triangle();
@@ -367,6 +611,86 @@ mod tests {
         assert_eq!(i, 4, "All edges of a one-ring are not visited");
     }
 
+    #[test]
+    fn test_vertex_vertex_iterator() {
+        let mesh: Mesh = TriMesh::sphere(4).into();
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            let one_ring: Vec<VertexID> = mesh.vertex_vertex_iter(vertex_id).collect();
+            assert_eq!(one_ring.len(), mesh.vertex_halfedge_iter(vertex_id).count());
+            for i in 0..one_ring.len() {
+                let p0 = mesh.vertex_position(one_ring[i]);
+                let p1 = mesh.vertex_position(one_ring[(i + 1) % one_ring.len()]);
+                assert!((p0 - p).cross(p1 - p).dot(p) > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vertex_vertex_iterator_on_boundary() {
+        let mesh = crate::test_utility::subdivided_triangle();
+        let one_ring: Vec<VertexID> = mesh
+            .vertex_vertex_iter(unsafe { VertexID::new(1) })
+            .collect();
+        assert_eq!(
+            one_ring,
+            vec![
+                unsafe { VertexID::new(2) },
+                unsafe { VertexID::new(0) },
+                unsafe { VertexID::new(3) }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_boundary_loop_iterator() {
+        let mesh: Mesh = TriMesh::cylinder(16).into();
+        let start = mesh
+            .halfedge_iter()
+            .find(|h| mesh.walker_from_halfedge(*h).face_id().is_none())
+            .unwrap();
+        let boundary_loop: Vec<HalfEdgeID> = mesh.boundary_loop_iter(start).collect();
+
+        assert_eq!(boundary_loop.len(), 16);
+        assert_eq!(boundary_loop[0], start);
+        for i in 0..boundary_loop.len() {
+            let walker = mesh.walker_from_halfedge(boundary_loop[i]);
+            assert!(walker.face_id().is_none());
+            let next = mesh
+                .walker_from_halfedge(boundary_loop[(i + 1) % boundary_loop.len()])
+                .into_twin()
+                .vertex_id();
+            assert_eq!(walker.vertex_id(), next);
+        }
+
+        let twin_id = mesh.walker_from_halfedge(start).twin_id().unwrap();
+        assert_eq!(mesh.boundary_loop_iter(twin_id).count(), 16);
+        let sphere: Mesh = TriMesh::sphere(4).into();
+        assert_eq!(
+            sphere
+                .boundary_loop_iter(sphere.halfedge_iter().next().unwrap())
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_orbit_iterator_combinators() {
+        let mesh: Mesh = TriMesh::sphere(4).into();
+        let face_id = mesh.face_iter().next().unwrap();
+        let mut iter = mesh.face_halfedge_iter(face_id);
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        assert_eq!(iter.len(), 2);
+
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        let one_ring = mesh.vertex_halfedge_iter(vertex_id);
+        for (h0, h1) in one_ring.clone().zip(one_ring.cycle().skip(1)) {
+            let mut walker = mesh.walker_from_halfedge(h0);
+            assert_eq!(walker.as_previous().twin_id(), Some(h1));
+        }
+    }
+
     #[test]
     fn test_face_halfedge_iterator() {
         let mesh = crate::test_utility::triangle();
@@ -379,4 +703,20 @@ mod tests {
         }
         assert_eq!(i, 3, "All edges of a face are not visited");
     }
+
+    #[test]
+    fn test_face_neighbour_iterator() {
+        let mesh = crate::test_utility::triangle();
+        assert_eq!(mesh.face_neighbours(unsafe { FaceID::new(0) }).count(), 0);
+
+        let mesh = crate::test_utility::subdivided_triangle();
+        for face_id in mesh.face_iter() {
+            let neighbours: Vec<FaceID> = mesh.face_neighbours(face_id).collect();
+            assert_eq!(neighbours.len(), 2);
+            for neighbour_id in neighbours {
+                assert_ne!(neighbour_id, face_id);
+                assert!(mesh.face_neighbours(neighbour_id).any(|f| f == face_id));
+            }
+        }
+    }
 }
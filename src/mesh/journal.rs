@@ -0,0 +1,194 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+/// # Journal
+///
+/// When the journal is started with [Mesh::start_journal], every change of the connectivity and the vertex positions is recorded together with its inverse,
+/// such that the changes can be undone with [Mesh::undo] and redone with [Mesh::redo] without cloning the mesh.
+/// The changes are grouped into steps, for example one step for each stroke in an interactive editor, by calling [Mesh::finish_step] after each step.
+///
+/// Only the connectivity and the positions are recorded, ie. the [creases](#creases), [vertex attributes](#vertex-attributes) and [face groups](#face-groups) are not changed by undo and redo.
+/// Compacting the ids (see [Mesh::compact]) discards the recorded changes, since they refer to the old ids.
+/// A clone of a mesh starts with an empty journal, if the journal is started, so the changes made before cloning can only be undone on the original mesh.
+///
+impl Mesh {
+    ///
+    /// Starts recording the changes of the connectivity and the vertex positions, if not already started.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(1);
+    /// mesh.start_journal();
+    ///
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// let position = mesh.vertex_position(vertex_id);
+    /// mesh.move_vertex_to(vertex_id, vec3(0.0, 0.0, 0.0));
+    /// mesh.finish_step();
+    ///
+    /// let halfedge_id = mesh.halfedge_iter().next().unwrap();
+    /// mesh.split_edge(halfedge_id, vec3(0.0, 2.0, 0.0));
+    /// mesh.finish_step();
+    ///
+    /// mesh.undo();
+    /// assert_eq!(mesh.no_vertices(), 42);
+    /// mesh.undo();
+    /// assert_eq!(mesh.vertex_position(vertex_id), position);
+    ///
+    /// mesh.redo();
+    /// assert_eq!(mesh.vertex_position(vertex_id), vec3(0.0, 0.0, 0.0));
+    /// ```
+    ///
+    pub fn start_journal(&mut self) {
+        self.connectivity_info.start_journal();
+    }
+
+    /// Stops recording the changes and discards the recorded changes, so they can no longer be undone or redone.
+    pub fn stop_journal(&mut self) {
+        self.connectivity_info.stop_journal();
+    }
+
+    /// Returns whether the changes are recorded, see [Mesh::start_journal].
+    pub fn is_journal_started(&self) -> bool {
+        self.connectivity_info.is_journal_started()
+    }
+
+    /// Collects the changes since the last call into one step, which is undone by a single call to [Mesh::undo].
+    pub fn finish_step(&mut self) {
+        self.connectivity_info.finish_step();
+    }
+
+    /// Returns whether there are recorded changes to undo.
+    pub fn can_undo(&self) -> bool {
+        self.connectivity_info.can_undo()
+    }
+
+    /// Returns whether there are undone changes to redo.
+    pub fn can_redo(&self) -> bool {
+        self.connectivity_info.can_redo()
+    }
+
+    ///
+    /// Undoes the last step (see [Mesh::finish_step]), where changes made after the last finished step are undone as one step.
+    /// Returns whether anything was undone.
+    ///
+    pub fn undo(&mut self) -> bool {
+        self.connectivity_info.undo()
+    }
+
+    ///
+    /// Redoes the last undone step. Any change made after the undo discards the steps that can be redone.
+    /// Returns whether anything was redone.
+    ///
+    pub fn redo(&mut self) -> bool {
+        self.connectivity_info.redo()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(mesh: &Mesh) -> Vec<(VertexID, Vec3)> {
+        mesh.vertex_iter()
+            .map(|vertex_id| (vertex_id, mesh.vertex_position(vertex_id)))
+            .collect()
+    }
+
+    fn faces(mesh: &Mesh) -> Vec<(FaceID, (VertexID, VertexID, VertexID))> {
+        mesh.face_iter()
+            .map(|face_id| (face_id, mesh.face_vertices(face_id)))
+            .collect()
+    }
+
+    #[test]
+    fn test_undo_redo_edits() {
+        let mut mesh = Mesh::icosphere(2);
+        mesh.start_journal();
+        let before = (positions(&mesh), faces(&mesh));
+
+        let halfedge_id = mesh.halfedge_iter().nth(10).unwrap();
+        mesh.flip_edge(halfedge_id).unwrap();
+        let face_id = mesh.face_iter().nth(5).unwrap();
+        let vertex_id = mesh.split_face(face_id, vec3(0.0, 0.0, 0.0));
+        let halfedge_id = mesh.halfedge_iter().nth(40).unwrap();
+        mesh.split_edge(halfedge_id, vec3(0.0, 2.0, 0.0));
+        let halfedge_id = mesh.walker_from_vertex(vertex_id).halfedge_id().unwrap();
+        mesh.collapse_edge(halfedge_id);
+        let face_id = mesh.face_iter().nth(15).unwrap();
+        mesh.remove_face(face_id);
+        // Reuses the ids of the removed primitives
+        let face_id = mesh.face_iter().nth(20).unwrap();
+        mesh.split_face(face_id, vec3(0.0, 0.0, 0.0));
+        mesh.finish_step();
+        mesh.is_valid().unwrap();
+        let after = (positions(&mesh), faces(&mesh));
+
+        assert!(mesh.can_undo());
+        assert!(mesh.undo());
+        mesh.is_valid().unwrap();
+        assert_eq!((positions(&mesh), faces(&mesh)), before);
+        assert!(!mesh.undo());
+
+        assert!(mesh.redo());
+        mesh.is_valid().unwrap();
+        assert_eq!((positions(&mesh), faces(&mesh)), after);
+        assert!(!mesh.can_redo());
+
+        // New edits discard the steps that can be redone
+        mesh.undo();
+        mesh.move_vertex_by(mesh.vertex_iter().next().unwrap(), vec3(1.0, 0.0, 0.0));
+        assert!(!mesh.redo());
+        assert!(mesh.undo());
+        assert_eq!((positions(&mesh), faces(&mesh)), before);
+    }
+
+    #[test]
+    fn test_undo_updates_normal_cache() {
+        let mut mesh = Mesh::icosphere(1);
+        mesh.vertex_normal_buffer();
+        mesh.start_journal();
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        mesh.move_vertex_by(vertex_id, vec3(0.5, 0.5, 0.0));
+        mesh.vertex_normal_buffer();
+        mesh.undo();
+        let normals = mesh.vertex_normal_buffer();
+        for (i, vertex_id) in mesh.vertex_iter().enumerate() {
+            assert_eq!(normals[i], mesh.vertex_normal(vertex_id));
+        }
+    }
+
+    #[test]
+    fn test_stop_journal() {
+        let mut mesh = Mesh::icosphere(1);
+        assert!(!mesh.undo());
+        mesh.start_journal();
+        mesh.split_face(mesh.face_iter().next().unwrap(), vec3(0.0, 0.0, 0.0));
+        mesh.stop_journal();
+        assert!(!mesh.is_journal_started());
+        assert!(!mesh.undo());
+        assert_eq!(mesh.no_faces(), 82);
+    }
+
+    #[test]
+    fn test_clone_starts_with_empty_journal() {
+        let mut mesh = Mesh::icosphere(1);
+        mesh.start_journal();
+        mesh.split_face(mesh.face_iter().next().unwrap(), vec3(0.0, 0.0, 0.0));
+        mesh.finish_step();
+
+        let mut clone = mesh.clone();
+        assert!(clone.is_journal_started());
+        assert!(!clone.can_undo());
+        clone.split_face(clone.face_iter().next().unwrap(), vec3(0.0, 0.0, 0.0));
+        clone.finish_step();
+        assert!(clone.undo());
+        assert!(!clone.undo());
+        assert_eq!(clone.no_faces(), 82);
+
+        assert!(mesh.undo());
+        assert_eq!(mesh.no_faces(), 80);
+    }
+}
@@ -0,0 +1,121 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+/// # Normal cache
+///
+/// The vertex normals can be cached such that only the normals of the vertices close to a change are recomputed, for example when rendering a mesh where a few vertices are moved each frame.
+/// The cache is enabled by the first call to [Mesh::vertex_normal_buffer] and from then on the mesh keeps track of the vertices that are moved (see [Mesh::set_vertex_position])
+/// or whose adjacent faces are changed by an edit, until the cache is disabled again with [Mesh::clear_normal_cache].
+///
+impl Mesh {
+    ///
+    /// Returns the normal (see [Mesh::vertex_normal]) of all vertices in the same order as the vertices are visited by [Mesh::vertex_iter], ie. the same order as the positions of the exported mesh.
+    ///
+    /// The normals are cached, so the first call computes all the normals and subsequent calls only recompute the normals of the vertices
+    /// that have been changed since the last call and their neighbours, since the normal of a vertex depends on the positions of its neighbours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// mesh.vertex_normal_buffer();
+    ///
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// mesh.move_vertex_by(vertex_id, vec3(0.1, 0.0, 0.0));
+    /// // Only the normals around the moved vertex are recomputed
+    /// let new_normals = mesh.vertex_normal_buffer();
+    ///
+    /// for (i, vertex_id) in mesh.vertex_iter().enumerate() {
+    ///     assert_eq!(new_normals[i], mesh.vertex_normal(vertex_id));
+    /// }
+    /// ```
+    ///
+    pub fn vertex_normal_buffer(&mut self) -> Vec<Vec3> {
+        let changed_vertices = self.connectivity_info.take_changed_vertices();
+        let vertices_to_update: Vec<VertexID> = match changed_vertices {
+            Some(changed_vertices) => {
+                let mut vertices = changed_vertices.clone();
+                for vertex_id in changed_vertices {
+                    if self.contains_vertex(vertex_id) {
+                        for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
+                            vertices.insert(
+                                self.walker_from_halfedge(halfedge_id).vertex_id().unwrap(),
+                            );
+                        }
+                    }
+                }
+                vertices
+                    .into_iter()
+                    .filter(|vertex_id| self.contains_vertex(*vertex_id))
+                    .collect()
+            }
+            None => self.vertex_iter().collect(),
+        };
+        for vertex_id in vertices_to_update {
+            let index = *vertex_id as usize;
+            while self.vertex_normals.len() <= index {
+                self.vertex_normals.push(Vec3::zero());
+            }
+            self.vertex_normals[index] = self.vertex_normal(vertex_id);
+        }
+        self.vertex_iter()
+            .map(|vertex_id| self.vertex_normals[*vertex_id as usize])
+            .collect()
+    }
+
+    ///
+    /// Clears the cached normals and stops keeping track of the changed vertices, see [Mesh::vertex_normal_buffer].
+    ///
+    pub fn clear_normal_cache(&mut self) {
+        self.connectivity_info.stop_tracking_changes();
+        self.vertex_normals = CowVec::with_capacity(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    fn assert_normals_are_up_to_date(mesh: &mut Mesh) {
+        let normals = mesh.vertex_normal_buffer();
+        assert_eq!(normals.len(), mesh.no_vertices());
+        for (i, vertex_id) in mesh.vertex_iter().enumerate() {
+            assert_eq!(normals[i], mesh.vertex_normal(vertex_id));
+        }
+    }
+
+    #[test]
+    fn test_normal_cache_is_updated_after_edits() {
+        let mut mesh: Mesh = TriMesh::sphere(8).into();
+        assert_normals_are_up_to_date(&mut mesh);
+
+        let vertex_id = mesh.vertex_iter().nth(10).unwrap();
+        mesh.move_vertex_to(vertex_id, vec3(0.0, 0.0, 0.0));
+        assert_normals_are_up_to_date(&mut mesh);
+
+        let halfedge_id = mesh.halfedge_iter().nth(20).unwrap();
+        mesh.flip_edge(halfedge_id).unwrap();
+        assert_normals_are_up_to_date(&mut mesh);
+
+        let halfedge_id = mesh.halfedge_iter().nth(30).unwrap();
+        let vertex_id = mesh.split_edge(halfedge_id, vec3(2.0, 0.0, 0.0));
+        assert_normals_are_up_to_date(&mut mesh);
+
+        mesh.collapse_edge(mesh.walker_from_vertex(vertex_id).halfedge_id().unwrap());
+        assert_normals_are_up_to_date(&mut mesh);
+
+        let face_id = mesh.face_iter().nth(5).unwrap();
+        mesh.remove_face(face_id);
+        assert_normals_are_up_to_date(&mut mesh);
+
+        mesh.compact();
+        assert_normals_are_up_to_date(&mut mesh);
+
+        mesh.clear_normal_cache();
+        mesh.move_vertex_by(mesh.vertex_iter().next().unwrap(), vec3(0.5, 0.0, 0.0));
+        assert_normals_are_up_to_date(&mut mesh);
+    }
+}
@@ -0,0 +1,246 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use std::sync::Arc;
+
+///
+/// Receives a notification each time the mesh it is added to (see [Mesh::add_observer]) changes, for example to keep user data stored outside the mesh in sync with the edits.
+/// All methods have an empty default implementation, so only the relevant notifications need to be implemented.
+/// The methods take `&self`, so use interior mutability, for example a [std::sync::Mutex], to update the user data.
+///
+/// The notifications are sent while the mesh is changed, ie. the mesh might be in an intermediate state, so the observer should only record the given ids and not access the mesh.
+///
+pub trait MeshObserver: Send + Sync {
+    /// Called when a vertex is added to the mesh.
+    fn on_vertex_added(&self, _vertex_id: VertexID) {}
+
+    /// Called when a vertex is removed from the mesh.
+    fn on_vertex_removed(&self, _vertex_id: VertexID) {}
+
+    /// Called when a vertex is moved to the given position.
+    fn on_vertex_moved(&self, _vertex_id: VertexID, _position: Vec3) {}
+
+    /// Called when a face is added to the mesh.
+    fn on_face_added(&self, _face_id: FaceID) {}
+
+    /// Called when a face is removed from the mesh.
+    fn on_face_removed(&self, _face_id: FaceID) {}
+
+    /// Called after the edge between the two given vertices is split by inserting the new vertex, see [Mesh::split_edge].
+    fn on_edge_split(
+        &self,
+        _vertex_id1: VertexID,
+        _vertex_id2: VertexID,
+        _new_vertex_id: VertexID,
+    ) {
+    }
+
+    /// Called after the given face is split into three faces by inserting the new vertex, see [Mesh::split_face].
+    fn on_face_split(&self, _face_id: FaceID, _new_vertex_id: VertexID) {}
+
+    /// Called after the given edge is flipped, see [Mesh::flip_edge].
+    fn on_edge_flipped(&self, _halfedge_id: HalfEdgeID) {}
+
+    /// Called after an edge is collapsed such that the removed vertex is merged into the surviving vertex, see [Mesh::collapse_edge].
+    fn on_edge_collapsed(&self, _surviving_vertex_id: VertexID, _removed_vertex_id: VertexID) {}
+
+    /// Called after the ids of the mesh are reassigned, see [Mesh::compact].
+    fn on_compacted(&self, _remap: &IdRemap) {}
+}
+
+/// The observers of a mesh. A clone of a mesh starts without observers, since the observers keep track of the original mesh.
+#[derive(Default)]
+pub(super) struct Observers(Vec<Arc<dyn MeshObserver>>);
+
+impl Observers {
+    /// Calls the given function with each of the observers.
+    pub fn notify(&self, f: impl Fn(&dyn MeshObserver)) {
+        for observer in self.0.iter() {
+            f(observer.as_ref());
+        }
+    }
+}
+
+impl Clone for Observers {
+    fn clone(&self) -> Self {
+        Observers::default()
+    }
+}
+
+/// # Observers
+///
+/// Observers (see [MeshObserver]) are notified about the changes of the mesh, so that user data stored outside the mesh can be kept in sync with the edits.
+/// A clone of a mesh starts without observers.
+///
+impl Mesh {
+    ///
+    /// Adds an observer which is notified about the changes of this mesh from now on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// # use std::sync::{Arc, Mutex};
+    /// #[derive(Default)]
+    /// struct RemovedFaces(Mutex<Vec<FaceID>>);
+    ///
+    /// impl MeshObserver for RemovedFaces {
+    ///     fn on_face_removed(&self, face_id: FaceID) {
+    ///         self.0.lock().unwrap().push(face_id);
+    ///     }
+    /// }
+    ///
+    /// let mut mesh = Mesh::icosphere(1);
+    /// let observer = Arc::new(RemovedFaces::default());
+    /// mesh.add_observer(observer.clone());
+    ///
+    /// let face_id = mesh.face_iter().next().unwrap();
+    /// mesh.remove_face(face_id);
+    /// assert_eq!(*observer.0.lock().unwrap(), vec![face_id]);
+    /// ```
+    ///
+    pub fn add_observer(&mut self, observer: Arc<dyn MeshObserver>) {
+        self.connectivity_info.observers_mut().0.push(observer);
+    }
+
+    /// Removes the given observer, such that it is no longer notified about the changes of this mesh.
+    pub fn remove_observer(&mut self, observer: &Arc<dyn MeshObserver>) {
+        self.connectivity_info
+            .observers_mut()
+            .0
+            .retain(|o| !Arc::ptr_eq(o, observer));
+    }
+
+    /// Calls the given function with each of the observers of this mesh.
+    pub(super) fn notify(&self, f: impl Fn(&dyn MeshObserver)) {
+        self.connectivity_info.observers().notify(f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        VertexAdded(VertexID),
+        VertexRemoved(VertexID),
+        VertexMoved(VertexID),
+        FaceAdded(FaceID),
+        FaceRemoved(FaceID),
+        EdgeSplit(VertexID),
+        FaceSplit(FaceID, VertexID),
+        EdgeFlipped(HalfEdgeID),
+        EdgeCollapsed(VertexID, VertexID),
+        Compacted,
+    }
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<Event>>);
+
+    impl Recorder {
+        fn take(&self) -> Vec<Event> {
+            std::mem::take(&mut self.0.lock().unwrap())
+        }
+
+        fn push(&self, event: Event) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    impl MeshObserver for Recorder {
+        fn on_vertex_added(&self, vertex_id: VertexID) {
+            self.push(Event::VertexAdded(vertex_id));
+        }
+        fn on_vertex_removed(&self, vertex_id: VertexID) {
+            self.push(Event::VertexRemoved(vertex_id));
+        }
+        fn on_vertex_moved(&self, vertex_id: VertexID, _position: Vec3) {
+            self.push(Event::VertexMoved(vertex_id));
+        }
+        fn on_face_added(&self, face_id: FaceID) {
+            self.push(Event::FaceAdded(face_id));
+        }
+        fn on_face_removed(&self, face_id: FaceID) {
+            self.push(Event::FaceRemoved(face_id));
+        }
+        fn on_edge_split(&self, _vertex_id1: VertexID, _vertex_id2: VertexID, new: VertexID) {
+            self.push(Event::EdgeSplit(new));
+        }
+        fn on_face_split(&self, face_id: FaceID, new_vertex_id: VertexID) {
+            self.push(Event::FaceSplit(face_id, new_vertex_id));
+        }
+        fn on_edge_flipped(&self, halfedge_id: HalfEdgeID) {
+            self.push(Event::EdgeFlipped(halfedge_id));
+        }
+        fn on_edge_collapsed(&self, surviving: VertexID, removed: VertexID) {
+            self.push(Event::EdgeCollapsed(surviving, removed));
+        }
+        fn on_compacted(&self, _remap: &IdRemap) {
+            self.push(Event::Compacted);
+        }
+    }
+
+    #[test]
+    fn test_observer_events() {
+        let mut mesh = Mesh::icosphere(1);
+        let recorder = Arc::new(Recorder::default());
+        mesh.add_observer(recorder.clone());
+
+        let face_id = mesh.face_iter().next().unwrap();
+        let vertex_id = mesh.split_face(face_id, vec3(0.0, 0.0, 0.0));
+        let events = recorder.take();
+        assert_eq!(events[0], Event::VertexAdded(vertex_id));
+        assert_eq!(
+            events
+                .iter()
+                .filter(|e| matches!(e, Event::FaceAdded(_)))
+                .count(),
+            2
+        );
+        assert_eq!(events.last(), Some(&Event::FaceSplit(face_id, vertex_id)));
+
+        let halfedge_id = mesh.walker_from_vertex(vertex_id).halfedge_id().unwrap();
+        let (surviving, removed) = mesh.edge_vertices(halfedge_id);
+        mesh.collapse_edge(halfedge_id);
+        let events = recorder.take();
+        assert!(events.contains(&Event::VertexRemoved(removed)));
+        assert_eq!(
+            events
+                .iter()
+                .filter(|e| matches!(e, Event::FaceRemoved(_)))
+                .count(),
+            2
+        );
+        assert_eq!(
+            events.last(),
+            Some(&Event::EdgeCollapsed(surviving, removed))
+        );
+
+        let halfedge_id = mesh.halfedge_iter().next().unwrap();
+        let vertex_id = mesh.split_edge(halfedge_id, vec3(0.0, 2.0, 0.0));
+        assert_eq!(recorder.take().last(), Some(&Event::EdgeSplit(vertex_id)));
+
+        let halfedge_id = mesh.walker_from_vertex(vertex_id).halfedge_id().unwrap();
+        let halfedge_id = mesh.walker_from_halfedge(halfedge_id).next_id().unwrap();
+        mesh.flip_edge(halfedge_id).unwrap();
+        assert_eq!(recorder.take(), vec![Event::EdgeFlipped(halfedge_id)]);
+
+        mesh.move_vertex_by(vertex_id, vec3(0.0, 1.0, 0.0));
+        assert_eq!(recorder.take(), vec![Event::VertexMoved(vertex_id)]);
+
+        mesh.compact();
+        assert_eq!(recorder.take(), vec![Event::Compacted]);
+
+        // Clones do not notify the observers of the original mesh
+        let mut clone = mesh.clone();
+        clone.move_vertex_by(clone.vertex_iter().next().unwrap(), vec3(0.0, 1.0, 0.0));
+        assert!(recorder.take().is_empty());
+
+        let observer: Arc<dyn MeshObserver> = recorder.clone();
+        mesh.remove_observer(&observer);
+        mesh.move_vertex_by(mesh.vertex_iter().next().unwrap(), vec3(0.0, 1.0, 0.0));
+        assert!(recorder.take().is_empty());
+    }
+}
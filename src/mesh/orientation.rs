@@ -5,10 +5,12 @@ use crate::mesh::*;
 /// # Orientation
 impl Mesh {
     /// Flip the orientation of all faces in the mesh, ie. such that the normal points in the opposite direction.
+    /// The stored normals (see [Vertex attributes](#vertex-attributes)) are flipped as well.
     pub fn flip_orientation(&mut self) {
         for face_id in self.face_iter() {
             self.flip_orientation_of_face(face_id);
         }
+        self.vertex_attributes.flip();
     }
 
     /// Fix the orientation of all faces in the mesh such that the orientation of each pair of neighbouring faces is aligned.
@@ -24,26 +26,75 @@ impl Mesh {
         }
     }
 
+    ///
+    /// Fix the orientation of the faces such that the orientation is consistent within each connected component (see [Mesh::fix_orientation]).
+    /// If `outward` is true, all faces of a connected component are furthermore flipped if needed, such that the face normals point outwards,
+    /// ie. such that the signed volume enclosed by the component is positive. This is only meaningful for closed components.
+    /// The stored normals (see [Vertex attributes](#vertex-attributes)) of a component flipped this way are flipped as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// mesh.flip_orientation();
+    /// mesh.orient_consistently(true);
+    /// let face_id = mesh.face_iter().next().unwrap();
+    /// assert!(mesh.face_normal(face_id).dot(mesh.face_center(face_id)) > 0.0);
+    /// ```
+    ///
+    pub fn orient_consistently(&mut self, outward: bool) {
+        self.fix_orientation();
+        if outward {
+            for component in self.connected_components() {
+                if self.signed_volume_of_faces(component.iter().cloned()) < 0.0 {
+                    let mut vertices = std::collections::HashSet::new();
+                    for face_id in component {
+                        let (v0, v1, v2) = self.face_vertices(face_id);
+                        vertices.extend([v0, v1, v2]);
+                        self.flip_orientation_of_face(face_id);
+                    }
+                    self.vertex_attributes.flip_vertices(&vertices);
+                }
+            }
+        }
+    }
+
+    /// Returns the sum of the signed volumes of the tetrahedra spanned by origo and each of the given faces.
+    pub(crate) fn signed_volume_of_faces(&self, faces: impl Iterator<Item = FaceID>) -> f64 {
+        faces
+            .map(|face_id| {
+                let mut walker = self.walker_from_face(face_id);
+                let p0 = self.vertex_position(walker.vertex_id().unwrap());
+                let p1 = self.vertex_position(walker.as_next().vertex_id().unwrap());
+                let p2 = self.vertex_position(walker.as_next().vertex_id().unwrap());
+                p0.dot(p1.cross(p2)) / 6.0
+            })
+            .sum()
+    }
+
     fn flip_orientation_of_face(&mut self, face_id: FaceID) {
         let mut update_list = [(None, None, None); 3];
 
-        let mut i = 0;
-        for halfedge_id in self.face_halfedge_iter(face_id) {
+        let halfedges: Vec<_> = self.face_halfedge_iter(face_id).collect();
+        for (i, halfedge_id) in halfedges.into_iter().enumerate() {
             let mut walker = self.walker_from_halfedge(halfedge_id);
             let vertex_id = walker.vertex_id();
             walker.as_previous();
-            update_list[i] = (Some(halfedge_id), walker.vertex_id(), walker.halfedge_id());
-            i += 1;
+            let (previous_vertex_id, previous_id) = (walker.vertex_id(), walker.halfedge_id());
+            walker.as_next().as_twin();
+            let (twin_vertex_id, twin_id) = (walker.vertex_id(), walker.halfedge_id());
+            let is_boundary = walker.face_id().is_none();
+            update_list[i] = (Some(halfedge_id), previous_vertex_id, previous_id);
 
             self.connectivity_info
-                .set_vertex_halfedge(walker.vertex_id().unwrap(), walker.halfedge_id());
+                .set_vertex_halfedge(previous_vertex_id.unwrap(), previous_id);
 
-            walker.as_next().as_twin();
-            if walker.face_id().is_none() {
+            if is_boundary {
                 self.connectivity_info
-                    .set_vertex_halfedge(walker.vertex_id().unwrap(), walker.halfedge_id());
+                    .set_vertex_halfedge(twin_vertex_id.unwrap(), twin_id);
                 self.connectivity_info
-                    .set_halfedge_vertex(walker.halfedge_id().unwrap(), vertex_id.unwrap());
+                    .set_halfedge_vertex(twin_id.unwrap(), vertex_id.unwrap());
             }
         }
 
@@ -94,6 +145,40 @@ mod tests {
         mesh.is_valid().unwrap();
     }
 
+    #[test]
+    fn test_orient_consistently() {
+        let mut mesh = crate::test_utility::cube();
+        let mut other = crate::test_utility::cube();
+        other.translate(vec3(4.0, 0.0, 0.0));
+        other.flip_orientation();
+        mesh.append(&other);
+        let face_id = mesh.face_iter().next().unwrap();
+        mesh.flip_orientation_of_face(face_id);
+
+        mesh.orient_consistently(true);
+
+        mesh.is_valid().unwrap();
+        for component in mesh.connected_components() {
+            assert!(mesh.signed_volume_of_faces(component.into_iter()) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_orient_consistently_flips_stored_normals() {
+        let mut input = TriMesh::sphere(4);
+        input.compute_normals();
+        let mut mesh: Mesh = input.into();
+        mesh.flip_orientation();
+
+        mesh.orient_consistently(true);
+
+        for vertex_id in mesh.vertex_iter() {
+            let normal = mesh.stored_vertex_normal(vertex_id).unwrap();
+            let normal = vec3(normal.x as f64, normal.y as f64, normal.z as f64);
+            assert!(normal.dot(mesh.vertex_normal(vertex_id)) > 0.99);
+        }
+    }
+
     #[test]
     fn test_flip_orientation() {
         let mut mesh: Mesh = TriMesh::sphere(4).into();
@@ -0,0 +1,159 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::connectivity_info::VertexPositions;
+use crate::mesh::*;
+
+///
+/// The precision in which the vertex positions of a [Mesh] are stored, see [Mesh::set_position_precision].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PositionPrecision {
+    /// The positions are stored in double precision (`f64`), which is the default.
+    #[default]
+    F64,
+    /// The positions are stored in single precision (`f32`), which halves the memory used by the positions.
+    F32,
+}
+
+/// # Position precision
+///
+/// The vertex positions are stored in double precision by default, but can instead be stored in single precision,
+/// which halves the memory used by the positions, for example when the mesh is only used for rendering or in a memory constrained environment.
+/// The positions are always given and returned in double precision ([Vec3]), so all functionality works the same for both precisions,
+/// except that a position stored in single precision is rounded to single precision when it is set.
+/// The precision is kept when the mesh is cloned or changed, while the meshes created from scratch, for example by [Mesh::new], use double precision.
+///
+impl Mesh {
+    ///
+    /// Constructs a new [Mesh] from a [three_d_asset::TriMesh] like [Mesh::new], but stores the positions in the given precision.
+    /// The positions are converted directly to the given precision, so the positions are never stored in double precision if single precision is requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::new_with_position_precision(&three_d_asset::TriMesh::sphere(4), PositionPrecision::F32);
+    /// assert_eq!(mesh.position_precision(), PositionPrecision::F32);
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn new_with_position_precision(
+        input: &three_d_asset::TriMesh,
+        precision: PositionPrecision,
+    ) -> Self {
+        use three_d_asset::Positions;
+        let indices = input
+            .indices
+            .to_u32()
+            .unwrap_or((0..input.triangle_count() as u32 * 3).collect::<Vec<_>>());
+        let positions = match (precision, &input.positions) {
+            (PositionPrecision::F64, positions) => positions.to_f64().into_iter().collect(),
+            (PositionPrecision::F32, Positions::F32(positions)) => {
+                VertexPositions::F32(positions.iter().copied().collect())
+            }
+            (PositionPrecision::F32, Positions::F64(positions)) => VertexPositions::F32(
+                positions
+                    .iter()
+                    .map(|p| Vector3::new(p.x as f32, p.y as f32, p.z as f32))
+                    .collect(),
+            ),
+        };
+        Self::new_from_buffers(
+            &indices,
+            positions,
+            crate::mesh::attributes::VertexAttributes::new(input),
+        )
+    }
+
+    /// Returns the precision in which the vertex positions are stored.
+    pub fn position_precision(&self) -> PositionPrecision {
+        self.connectivity_info.position_precision()
+    }
+
+    ///
+    /// Converts the stored vertex positions to the given precision.
+    /// Converting to single precision rounds the positions, which is not recorded in the [journal](#journal),
+    /// so undoing a change afterwards sets the position from before the change rounded to single precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(2);
+    /// mesh.set_position_precision(PositionPrecision::F32);
+    ///
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// mesh.set_vertex_position(vertex_id, vec3(0.1, 0.2, 0.3));
+    /// assert_eq!(mesh.vertex_position(vertex_id), vec3(0.1f32 as f64, 0.2f32 as f64, 0.3f32 as f64));
+    /// ```
+    ///
+    pub fn set_position_precision(&mut self, precision: PositionPrecision) {
+        self.connectivity_info.set_position_precision(precision);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::{Positions, TriMesh};
+
+    #[test]
+    fn test_single_precision_positions() {
+        let mut input = TriMesh::sphere(4);
+        input.positions = Positions::F32(input.positions.to_f32());
+        let mut mesh = Mesh::new_with_position_precision(&input, PositionPrecision::F32);
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.position_precision(), PositionPrecision::F32);
+        let Positions::F32(expected) = &input.positions else {
+            unreachable!()
+        };
+        for (vertex_id, position) in mesh.vertex_iter().zip(expected.iter()) {
+            assert_eq!(
+                mesh.vertex_position(vertex_id),
+                position.cast::<f64>().unwrap()
+            );
+        }
+
+        mesh.start_journal();
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        let old = mesh.vertex_position(vertex_id);
+        mesh.move_vertex_by(vertex_id, vec3(0.1, 0.1, 0.1));
+        let moved = mesh.vertex_position(vertex_id);
+        assert_eq!(moved, moved.cast::<f32>().unwrap().cast::<f64>().unwrap());
+        mesh.undo();
+        assert_eq!(mesh.vertex_position(vertex_id), old);
+        mesh.redo();
+        assert_eq!(mesh.vertex_position(vertex_id), moved);
+
+        let clone = mesh.clone();
+        assert_eq!(clone.position_precision(), PositionPrecision::F32);
+
+        mesh.split_edge(mesh.halfedge_iter().next().unwrap(), vec3(0.3, 0.0, 0.0));
+        mesh.remove_face(mesh.face_iter().next().unwrap());
+        mesh.remove_lonely_primitives();
+        mesh.compact();
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.position_precision(), PositionPrecision::F32);
+    }
+
+    #[test]
+    fn test_convert_position_precision() {
+        let mut mesh = Mesh::icosphere(2);
+        let positions: Vec<Vec3> = mesh
+            .vertex_iter()
+            .map(|v| mesh.vertex_position(v))
+            .collect();
+        assert_eq!(mesh.position_precision(), PositionPrecision::F64);
+
+        mesh.set_position_precision(PositionPrecision::F32);
+        assert_eq!(mesh.position_precision(), PositionPrecision::F32);
+        for (vertex_id, position) in mesh.vertex_iter().zip(positions.iter()) {
+            assert!((mesh.vertex_position(vertex_id) - position).magnitude() < 1e-6);
+        }
+
+        mesh.set_position_precision(PositionPrecision::F64);
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        mesh.set_vertex_position(vertex_id, vec3(0.1, 0.2, 0.3));
+        assert_eq!(mesh.vertex_position(vertex_id), vec3(0.1, 0.2, 0.3));
+    }
+}
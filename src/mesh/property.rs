@@ -0,0 +1,9 @@
+//! Defines containers for storing a value per primitive.
+
+use crate::mesh::*;
+
+/// Stores a value of type `T` for each vertex, for example the result of an operation computing a value per vertex.
+pub type VertexProperty<T> = HashMap<VertexID, T>;
+
+/// Stores a value of type `T` for each face, for example the result of an operation computing a value per face.
+pub type FaceProperty<T> = HashMap<FaceID, T>;
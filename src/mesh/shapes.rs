@@ -0,0 +1,390 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+/// # Shapes
+///
+/// Constructors for simple parametric shapes. Except for the [grid](Mesh::grid) and the [cylinder](Mesh::cylinder) without caps, all shapes are closed, consistently oriented with the normals pointing outwards, and have the y-axis as their main axis.
+/// The number of segments around the y-axis is at least 3.
+///
+impl Mesh {
+    ///
+    /// Constructs a torus lying in the xz-plane with center at the origin.
+    /// The `major_radius` is the distance from the origin to the center of the tube and the `minor_radius` is the radius of the tube.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::torus(1.0, 0.25, 32, 16);
+    /// assert!(mesh.is_closed());
+    /// assert_eq!(mesh.genus(), 1);
+    /// ```
+    ///
+    pub fn torus(
+        major_radius: f64,
+        minor_radius: f64,
+        major_segments: usize,
+        minor_segments: usize,
+    ) -> Self {
+        let minor_segments = minor_segments.max(3);
+        let profile: Vec<(f64, f64)> = (0..minor_segments)
+            .map(|i| {
+                let angle = 2.0 * std::f64::consts::PI * i as f64 / minor_segments as f64;
+                (
+                    major_radius + minor_radius * angle.cos(),
+                    minor_radius * angle.sin(),
+                )
+            })
+            .collect();
+        Self::revolve(&profile, true, major_segments)
+    }
+
+    ///
+    /// Constructs a cylinder with the bottom in the xz-plane with center at the origin and the top at `y = height`.
+    /// If `caps` is true, the cylinder is closed by a disc at each end, otherwise the ends are open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::cylinder(1.0, 2.0, 16, true);
+    /// assert!(mesh.is_closed());
+    ///
+    /// let mesh = Mesh::cylinder(1.0, 2.0, 16, false);
+    /// assert_eq!(mesh.no_boundary_loops(), 2);
+    /// ```
+    ///
+    pub fn cylinder(radius: f64, height: f64, radial_segments: usize, caps: bool) -> Self {
+        if caps {
+            Self::revolve(
+                &[(0.0, 0.0), (radius, 0.0), (radius, height), (0.0, height)],
+                false,
+                radial_segments,
+            )
+        } else {
+            Self::revolve(&[(radius, 0.0), (radius, height)], false, radial_segments)
+        }
+    }
+
+    ///
+    /// Constructs a cone with the circular base in the xz-plane with center at the origin and the apex at `(0, height, 0)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::cone(1.0, 2.0, 16);
+    /// assert!(mesh.is_closed());
+    /// assert_eq!(mesh.no_vertices(), 18);
+    /// ```
+    ///
+    pub fn cone(radius: f64, height: f64, radial_segments: usize) -> Self {
+        Self::revolve(
+            &[(0.0, 0.0), (radius, 0.0), (0.0, height)],
+            false,
+            radial_segments,
+        )
+    }
+
+    ///
+    /// Constructs a capsule, ie. a cylinder with a hemisphere at each end, with center at the origin.
+    /// The cylindrical part goes from `-0.5 * height` to `0.5 * height` along the y-axis, so the total height is `height + 2.0 * radius`.
+    /// Each hemisphere is divided into `rings` segments from the equator to the pole.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::capsule(0.5, 2.0, 16, 8);
+    /// assert!(mesh.is_closed());
+    /// ```
+    ///
+    pub fn capsule(radius: f64, height: f64, radial_segments: usize, rings: usize) -> Self {
+        let rings = rings.max(1);
+        let mut profile = vec![(0.0, -0.5 * height - radius)];
+        for i in 1..=rings {
+            let angle = -0.5 * std::f64::consts::PI * (1.0 - i as f64 / rings as f64);
+            profile.push((radius * angle.cos(), -0.5 * height + radius * angle.sin()));
+        }
+        for i in 0..rings {
+            let angle = 0.5 * std::f64::consts::PI * i as f64 / rings as f64;
+            profile.push((radius * angle.cos(), 0.5 * height + radius * angle.sin()));
+        }
+        profile.push((0.0, 0.5 * height + radius));
+        Self::revolve(&profile, false, radial_segments)
+    }
+
+    ///
+    /// Constructs a tube, ie. a hollow cylinder, with the bottom in the xz-plane with center at the origin and the top at `y = height`.
+    /// The tube is closed by a flat ring at each end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::tube(0.5, 1.0, 2.0, 16);
+    /// assert!(mesh.is_closed());
+    /// assert_eq!(mesh.genus(), 1);
+    /// ```
+    ///
+    pub fn tube(inner_radius: f64, outer_radius: f64, height: f64, radial_segments: usize) -> Self {
+        Self::revolve(
+            &[
+                (inner_radius, 0.0),
+                (outer_radius, 0.0),
+                (outer_radius, height),
+                (inner_radius, height),
+            ],
+            true,
+            radial_segments,
+        )
+    }
+
+    ///
+    /// Constructs a sphere with radius 1 and center at the origin by subdividing an icosahedron the given number of times and projecting the vertices onto the sphere.
+    /// Each subdivision splits every face into four, so the sphere has `20 * 4^subdivisions` faces of almost equal size and shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::icosphere(2);
+    /// assert_eq!(mesh.no_faces(), 320);
+    /// for vertex_id in mesh.vertex_iter() {
+    ///     assert!((mesh.vertex_position(vertex_id).magnitude() - 1.0).abs() < 0.000001);
+    /// }
+    /// ```
+    ///
+    pub fn icosphere(subdivisions: u32) -> Self {
+        let t = 0.5 * (1.0 + 5.0f64.sqrt());
+        let mut positions = vec![
+            vec3(-1.0, t, 0.0),
+            vec3(1.0, t, 0.0),
+            vec3(-1.0, -t, 0.0),
+            vec3(1.0, -t, 0.0),
+            vec3(0.0, -1.0, t),
+            vec3(0.0, 1.0, t),
+            vec3(0.0, -1.0, -t),
+            vec3(0.0, 1.0, -t),
+            vec3(t, 0.0, -1.0),
+            vec3(t, 0.0, 1.0),
+            vec3(-t, 0.0, -1.0),
+            vec3(-t, 0.0, 1.0),
+        ];
+        let mut indices: Vec<u32> = vec![
+            0, 11, 5, 0, 5, 1, 0, 1, 7, 0, 7, 10, 0, 10, 11, 1, 5, 9, 5, 11, 4, 11, 10, 2, 10, 7,
+            6, 7, 1, 8, 3, 9, 4, 3, 4, 2, 3, 2, 6, 3, 6, 8, 3, 8, 9, 4, 9, 5, 2, 4, 11, 6, 2, 10,
+            8, 6, 7, 9, 8, 1,
+        ];
+        for _ in 0..subdivisions {
+            let mut midpoints = HashMap::new();
+            let mut midpoint = |a: u32, b: u32| {
+                *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                    positions.push(0.5 * (positions[a as usize] + positions[b as usize]));
+                    positions.len() as u32 - 1
+                })
+            };
+            let mut new_indices = Vec::with_capacity(4 * indices.len());
+            for face in indices.chunks(3) {
+                let (a, b, c) = (face[0], face[1], face[2]);
+                let ab = midpoint(a, b);
+                let bc = midpoint(b, c);
+                let ca = midpoint(c, a);
+                new_indices.extend([a, ab, ca, b, bc, ab, c, ca, bc, ab, bc, ca]);
+            }
+            indices = new_indices;
+        }
+        for p in positions.iter_mut() {
+            *p = p.normalize();
+        }
+        Self::from_positions_and_indices(&positions, &indices)
+    }
+
+    ///
+    /// Constructs a rectangle with the given center, which is subdivided into a grid of `u_segments` times `v_segments` cells, each divided into two triangles.
+    /// The sides of the rectangle are given by the `u` and `v` vectors, so the rectangle lies in the plane spanned by these two vectors and the normal of the faces is `u.cross(v)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// // A 10 by 10 grid in the xz-plane with normals pointing up
+    /// let mesh = Mesh::grid(vec3(0.0, 0.0, 0.0), vec3(0.0, 0.0, 2.0), vec3(2.0, 0.0, 0.0), 10, 10);
+    /// assert_eq!(mesh.no_vertices(), 121);
+    /// assert_eq!(mesh.no_faces(), 200);
+    /// assert_eq!(mesh.face_normal(mesh.face_iter().next().unwrap()), vec3(0.0, 1.0, 0.0));
+    /// ```
+    ///
+    pub fn grid(center: Vec3, u: Vec3, v: Vec3, u_segments: usize, v_segments: usize) -> Self {
+        let u_segments = u_segments.max(1);
+        let v_segments = v_segments.max(1);
+        let corner = center - 0.5 * u - 0.5 * v;
+        let mut positions = Vec::with_capacity((u_segments + 1) * (v_segments + 1));
+        for j in 0..=v_segments {
+            for i in 0..=u_segments {
+                positions.push(
+                    corner
+                        + (i as f64 / u_segments as f64) * u
+                        + (j as f64 / v_segments as f64) * v,
+                );
+            }
+        }
+        let mut indices = Vec::with_capacity(6 * u_segments * v_segments);
+        for j in 0..v_segments {
+            for i in 0..u_segments {
+                let a = (j * (u_segments + 1) + i) as u32;
+                let b = a + 1;
+                let c = a + u_segments as u32 + 1;
+                let d = c + 1;
+                indices.extend([a, b, c, c, b, d]);
+            }
+        }
+        Self::from_positions_and_indices(&positions, &indices)
+    }
+
+    ///
+    /// Constructs a surface of revolution by rotating the profile, given as `(radius, y)` pairs, around the y-axis.
+    /// Profile points with zero radius become a single vertex on the axis.
+    /// The profile should go counterclockwise when seen with the radius pointing right and y up, for the normals to point outwards.
+    ///
+    fn revolve(profile: &[(f64, f64)], is_closed_profile: bool, segments: usize) -> Self {
+        let segments = segments.max(3);
+        let mut positions = Vec::new();
+        let mut rings = Vec::with_capacity(profile.len());
+        for (radius, y) in profile.iter() {
+            let start = positions.len() as u32;
+            if *radius == 0.0 {
+                positions.push(vec3(0.0, *y, 0.0));
+                rings.push(vec![start; segments]);
+            } else {
+                for j in 0..segments {
+                    let angle = 2.0 * std::f64::consts::PI * j as f64 / segments as f64;
+                    positions.push(vec3(radius * angle.cos(), *y, radius * angle.sin()));
+                }
+                rings.push((start..start + segments as u32).collect());
+            }
+        }
+
+        let mut indices = Vec::new();
+        let no_bands = if is_closed_profile {
+            profile.len()
+        } else {
+            profile.len() - 1
+        };
+        for i in 0..no_bands {
+            let ring0 = &rings[i];
+            let ring1 = &rings[(i + 1) % profile.len()];
+            for j in 0..segments {
+                let a = ring0[j];
+                let b = ring0[(j + 1) % segments];
+                let c = ring1[j];
+                let d = ring1[(j + 1) % segments];
+                if a != b {
+                    indices.extend([a, c, b]);
+                }
+                if c != d {
+                    indices.extend([b, c, d]);
+                }
+            }
+        }
+        Self::from_positions_and_indices(&positions, &indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn assert_closed_shape(
+        shape: impl Fn(usize) -> Mesh,
+        euler_characteristic: i64,
+        expected_volume: f64,
+    ) {
+        // Mesh::is_valid is slow, so the validity is only checked on a coarse version of the shape
+        shape(6).is_valid().unwrap();
+        let mesh = shape(64);
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.euler_characteristic(), euler_characteristic);
+        let volume = mesh.volume().unwrap();
+        assert!(
+            volume > 0.0 && (volume - expected_volume).abs() < 0.02 * expected_volume,
+            "{} != {}",
+            volume,
+            expected_volume
+        );
+    }
+
+    #[test]
+    fn test_icosphere() {
+        Mesh::icosphere(1).is_valid().unwrap();
+        let mesh = Mesh::icosphere(4);
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.euler_characteristic(), 2);
+        assert_eq!(mesh.no_vertices(), 10 * 4usize.pow(4) + 2);
+        let volume = mesh.volume().unwrap();
+        assert!(volume > 0.0 && (volume - 4.0 / 3.0 * PI).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_grid() {
+        let mesh = Mesh::grid(
+            vec3(1.0, 2.0, 3.0),
+            vec3(4.0, 0.0, 0.0),
+            vec3(0.0, 0.0, -2.0),
+            8,
+            3,
+        );
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_faces(), 48);
+        assert_eq!(mesh.no_boundary_loops(), 1);
+        let area: f64 = mesh.face_iter().map(|f| mesh.face_area(f)).sum();
+        assert!((area - 8.0).abs() < 0.000001);
+        for face_id in mesh.face_iter() {
+            assert!((mesh.face_normal(face_id) - vec3(0.0, 1.0, 0.0)).magnitude() < 0.000001);
+        }
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            assert!(p.x >= -1.0 && p.x <= 3.0 && p.y == 2.0 && p.z >= 2.0 && p.z <= 4.0);
+        }
+    }
+
+    #[test]
+    fn test_torus() {
+        assert_closed_shape(
+            |n| Mesh::torus(1.0, 0.25, n, n / 2),
+            0,
+            2.0 * PI * PI * 1.0 * 0.25 * 0.25,
+        );
+    }
+
+    #[test]
+    fn test_cylinder() {
+        assert_closed_shape(|n| Mesh::cylinder(0.5, 2.0, n, true), 2, PI * 0.25 * 2.0);
+
+        let mesh = Mesh::cylinder(0.5, 2.0, 6, false);
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_boundary_loops(), 2);
+        assert_eq!(mesh.no_faces(), 12);
+    }
+
+    #[test]
+    fn test_cone() {
+        assert_closed_shape(|n| Mesh::cone(1.0, 2.0, n), 2, PI * 2.0 / 3.0);
+    }
+
+    #[test]
+    fn test_capsule() {
+        assert_closed_shape(
+            |n| Mesh::capsule(0.5, 2.0, n, n / 4),
+            2,
+            PI * 0.25 * 2.0 + 4.0 / 3.0 * PI * 0.125,
+        );
+    }
+
+    #[test]
+    fn test_tube() {
+        assert_closed_shape(|n| Mesh::tube(0.5, 1.0, 2.0, n), 0, PI * (1.0 - 0.25) * 2.0);
+    }
+}
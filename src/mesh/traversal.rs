@@ -18,6 +18,7 @@ impl Mesh {
 
     /// Creates a [Walker] at the given half-edge.
     pub fn walker_from_halfedge(&self, halfedge_id: HalfEdgeID) -> Walker {
+        self.connectivity_info.check_halfedge(halfedge_id);
         self.walker().into_halfedge_walker(halfedge_id)
     }
 
@@ -104,9 +105,9 @@ impl Mesh {
 /// # let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
 /// // Compute the average edge length
 /// let mut avg_edge_length = 0.0f64;
-/// for halfedge_id in mesh.edge_iter()
+/// for edge_id in mesh.edge_iter()
 /// {
-///     let mut walker = mesh.walker_from_halfedge(halfedge_id);
+///     let mut walker = mesh.walker_from_halfedge(edge_id.into());
 ///     let p0 = mesh.vertex_position(walker.vertex_id().unwrap());
 ///     let p1 = mesh.vertex_position(walker.as_twin().vertex_id().unwrap());
 ///     avg_edge_length += (p0 - p1).magnitude();
@@ -202,6 +203,23 @@ impl<'a> Walker<'a> {
         self.as_next().as_next()
     }
 
+    /// Walk to the next half-edge along the boundary of the mesh, ie. the next half-edge without an adjacent face.
+    /// If the current half-edge has an adjacent face, this is the same as [Walker::as_next].
+    pub fn as_next_on_boundary(&mut self) -> &mut Self {
+        if self.current_info.is_none() || self.face_id().is_some() {
+            return self.as_next();
+        }
+        let start = self.twin_id();
+        self.as_twin();
+        while self.face_id().is_some() {
+            self.as_previous().as_twin();
+            if self.current == start {
+                self.set_current(None);
+            }
+        }
+        self
+    }
+
     /// Walk to the twin half-edge.
     pub fn as_twin(&mut self) -> &mut Self {
         let halfedge_id = match self.current_info {
@@ -27,4 +27,57 @@ impl Mesh {
     pub fn no_faces(&self) -> usize {
         self.connectivity_info.no_faces()
     }
+
+    ///
+    /// Returns whether the given vertex is in the mesh, ie. false if the vertex has been removed.
+    /// Using the id of a removed vertex, ie. a stale id, panics, also in release builds, so use this method to test ids that might have been removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let halfedge_id = mesh.halfedge_iter().next().unwrap();
+    /// let (_, dying_vertex_id) = mesh.edge_vertices(halfedge_id);
+    /// let surviving_vertex_id = mesh.collapse_edge(halfedge_id);
+    /// assert!(mesh.contains_vertex(surviving_vertex_id));
+    /// assert!(!mesh.contains_vertex(dying_vertex_id));
+    /// ```
+    ///
+    pub fn contains_vertex(&self, vertex_id: VertexID) -> bool {
+        self.connectivity_info.contains_vertex(vertex_id)
+    }
+
+    ///
+    /// Returns whether the given half-edge is in the mesh, ie. false if the half-edge has been removed.
+    /// See [Mesh::contains_vertex].
+    ///
+    pub fn contains_halfedge(&self, halfedge_id: HalfEdgeID) -> bool {
+        self.connectivity_info.contains_halfedge(halfedge_id)
+    }
+
+    ///
+    /// Returns whether the given face is in the mesh, ie. false if the face has been removed.
+    /// See [Mesh::contains_vertex].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let face_id = mesh.face_iter().next().unwrap();
+    /// let (v0, v1, v2) = mesh.face_vertices(face_id);
+    /// mesh.remove_face(face_id);
+    /// assert!(!mesh.contains_face(face_id));
+    ///
+    /// // The index of the removed face is reused, but the id is not
+    /// let new_face_id = mesh.add_face(v0, v1, v2).unwrap();
+    /// assert_eq!(*new_face_id, *face_id);
+    /// assert_ne!(new_face_id, face_id);
+    /// assert!(mesh.contains_face(new_face_id));
+    /// ```
+    ///
+    pub fn contains_face(&self, face_id: FaceID) -> bool {
+        self.connectivity_info.contains_face(face_id)
+    }
 }
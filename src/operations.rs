@@ -11,23 +11,110 @@ mod edge_measures;
 
 mod face_measures;
 
+mod mesh_measures;
+
 mod transformations;
 
+mod deform;
+#[doc(inline)]
+pub use deform::*;
+
+mod lattice;
+#[doc(inline)]
+pub use lattice::*;
+
 mod bounding_box;
 #[doc(inline)]
 pub use bounding_box::*;
 
 mod validity;
+#[doc(inline)]
+pub use validity::*;
 
 // Advanced
+mod bvh;
+
+mod curvature;
+#[doc(inline)]
+pub use curvature::*;
+
+mod principal_axes;
+#[doc(inline)]
+pub use principal_axes::*;
+
 mod quality;
 
+mod quality_report;
+#[doc(inline)]
+pub use quality_report::*;
+
+mod decimation;
+#[doc(inline)]
+pub use decimation::*;
+
+mod statistics;
+#[doc(inline)]
+pub use statistics::*;
+
+mod vertex_cache;
+
 mod connected_components;
+#[doc(inline)]
+pub use connected_components::*;
+
+mod segmentation;
+
+mod selection;
+#[doc(inline)]
+pub use selection::*;
+
+mod geodesic;
+
+mod distance;
+#[doc(inline)]
+pub use distance::*;
+
+mod sampling;
+#[doc(inline)]
+pub use sampling::*;
+
+#[cfg(feature = "solver")]
+mod heat;
+
+mod laplacian;
+
+#[cfg(feature = "solver")]
+mod parameterization;
+#[cfg(feature = "solver")]
+#[doc(inline)]
+pub use parameterization::*;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+
+#[cfg(feature = "robust")]
+mod predicates;
 
 mod intersection;
 #[doc(inline)]
 pub use intersection::*;
 
+mod spatial_queries;
+#[doc(inline)]
+pub use spatial_queries::*;
+
 mod merge;
 
+mod bevel;
+
 mod split;
+#[doc(inline)]
+pub use split::*;
+
+mod cut;
+#[doc(inline)]
+pub use cut::*;
+
+mod triangulation;
+#[doc(inline)]
+pub use triangulation::*;
@@ -0,0 +1,411 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::Error;
+use std::collections::{HashMap, HashSet};
+
+/// The vertex of a corner of a face after a bevel, which is either an existing vertex or one of the new vertices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Corner {
+    Old(VertexID),
+    New(usize),
+}
+
+/// # Bevel
+impl Mesh {
+    ///
+    /// Bevels the given edges, ie. replaces each edge with a thin strip of faces.
+    /// The vertices of a beveled edge are split into one vertex for each region between the beveled edges around the vertex
+    /// and each new vertex is moved the given distance into its region, along the non-beveled edges in that region if there are any.
+    /// Where three or more beveled edges meet, the hole between the new vertices is closed with a polygon.
+    /// An interior vertex with only one beveled edge is split into a vertex on each of the two edges next to the beveled edge,
+    /// so the strip ends in an edge.
+    ///
+    /// **Note:** Edges on the boundary cannot be beveled and are ignored.
+    /// The mesh is changed in place, so the ids, the [face groups](#face-groups) and the [creases](#creases) of the vertices and faces away from the beveled edges are kept
+    /// and the bevel is recorded in the [journal](#journal).
+    /// The split vertices and the faces around them are replaced, the new faces are assigned to the face group of the face they replace
+    /// and the strip of a beveled crease is bounded by two creases.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the new faces cannot be added to the mesh, see [Mesh::add_faces].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// // Bevel the edges around a vertex
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// let edges: Vec<EdgeID> = mesh.vertex_halfedge_iter(vertex_id).map(|halfedge_id| mesh.edge_id(halfedge_id)).collect();
+    /// mesh.bevel_edges(&edges, 0.01).unwrap();
+    /// assert!(mesh.is_closed());
+    /// ```
+    ///
+    pub fn bevel_edges(&mut self, edges: &[EdgeID], width: f64) -> Result<(), Error> {
+        let is_beveled: HashSet<HalfEdgeID> = edges
+            .iter()
+            .filter(|edge_id| !self.is_edge_on_boundary((**edge_id).into()))
+            .flat_map(|edge_id| {
+                let (h0, h1) = self.edge_halfedges(*edge_id);
+                [h0, h1]
+            })
+            .collect();
+
+        // Split the vertices of the beveled edges into a new vertex for each region around the vertex
+        let mut new_vertices: Vec<(VertexID, Vec3)> = Vec::new();
+        let mut corners: HashMap<(VertexID, FaceID), Corner> = HashMap::new();
+        let mut caps = Vec::new();
+        let mut seams = Vec::new();
+        for vertex_id in self.vertex_iter() {
+            let p = self.vertex_position(vertex_id);
+            let ring = self.ordered_vertex_halfedges(vertex_id);
+            let is_boundary = ring
+                .last()
+                .map(|h| self.walker_from_halfedge(*h).face_id().is_none())
+                .unwrap_or(false);
+            let no_beveled = ring.iter().filter(|h| is_beveled.contains(h)).count();
+            if no_beveled == 0 {
+                continue;
+            }
+            let direction = |halfedge_id: HalfEdgeID| {
+                let neighbour = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                (self.vertex_position(neighbour) - p).normalize()
+            };
+
+            if no_beveled == 1 && !is_boundary {
+                // Split the vertex into a vertex on each of the two edges next to the beveled edge,
+                // where the first is used by all faces except the last and the seam between them is closed with a face
+                let start = ring.iter().position(|h| is_beveled.contains(h)).unwrap();
+                let halfedge = |i: usize| ring[(start + i) % ring.len()];
+                let last = halfedge(ring.len() - 1);
+                new_vertices.push((vertex_id, p + width * direction(halfedge(1))));
+                new_vertices.push((vertex_id, p + width * direction(last)));
+                let first_corner = Corner::New(new_vertices.len() - 2);
+                let last_corner = Corner::New(new_vertices.len() - 1);
+                for i in 0..ring.len() {
+                    let face_id = self.walker_from_halfedge(halfedge(i)).face_id().unwrap();
+                    let corner = if i + 1 < ring.len() {
+                        first_corner
+                    } else {
+                        last_corner
+                    };
+                    corners.insert((vertex_id, face_id), corner);
+                }
+                let walker = self.walker_from_halfedge(last);
+                let (neighbour, face_id) = (walker.vertex_id().unwrap(), walker.face_id().unwrap());
+                seams.push((vertex_id, neighbour, face_id, [first_corner, last_corner]));
+                continue;
+            }
+
+            // Divide the one-ring into regions starting at each beveled edge (and at the boundary)
+            let start = if is_boundary {
+                0
+            } else {
+                ring.iter().position(|h| is_beveled.contains(h)).unwrap()
+            };
+            let mut regions: Vec<Vec<HalfEdgeID>> = Vec::new();
+            for i in 0..ring.len() {
+                let halfedge_id = ring[(start + i) % ring.len()];
+                if i == 0 || is_beveled.contains(&halfedge_id) {
+                    regions.push(Vec::new());
+                }
+                regions.last_mut().unwrap().push(halfedge_id);
+            }
+
+            let mut cap = Vec::new();
+            for (i, region) in regions.iter().enumerate() {
+                let mut offset = region
+                    .iter()
+                    .filter(|h| !is_beveled.contains(h))
+                    .map(|h| direction(*h))
+                    .fold(Vec3::zero(), |a, b| a + b);
+                if offset.magnitude2() < 0.000001 {
+                    // Move into the face between two beveled edges
+                    let next = regions[(i + 1) % regions.len()][0];
+                    offset = direction(region[0]) + direction(next);
+                }
+                new_vertices.push((vertex_id, p + width * offset.normalize()));
+                let corner = Corner::New(new_vertices.len() - 1);
+                for halfedge_id in region.iter() {
+                    if let Some(face_id) = self.walker_from_halfedge(*halfedge_id).face_id() {
+                        corners.insert((vertex_id, face_id), corner);
+                    }
+                }
+                cap.push(corner);
+            }
+            if !is_boundary && cap.len() > 2 {
+                caps.push(cap);
+            }
+        }
+        if new_vertices.is_empty() {
+            return Ok(());
+        }
+        let corner = |vertex_id: VertexID, face_id: FaceID| {
+            corners
+                .get(&(vertex_id, face_id))
+                .copied()
+                .unwrap_or(Corner::Old(vertex_id))
+        };
+
+        // Replace the faces around the split vertices and add a strip of faces along each beveled edge and a cap where three or more beveled edges meet
+        let replaced_faces: HashSet<FaceID> = corners.keys().map(|(_, face_id)| *face_id).collect();
+        let mut faces = Vec::new();
+        let mut crease_replacements = Vec::new();
+        for (vertex_id, neighbour, face_id, [first_corner, last_corner]) in seams {
+            let n = corner(neighbour, face_id);
+            faces.push((
+                [first_corner, n, last_corner],
+                self.face_group(face_id).map(|name| name.to_string()),
+            ));
+            crease_replacements.push((
+                (vertex_id, neighbour),
+                vec![(first_corner, n), (last_corner, n)],
+            ));
+        }
+        for face_id in replaced_faces.iter() {
+            let (v0, v1, v2) = self.face_vertices(*face_id);
+            let group = self.face_group(*face_id).map(|name| name.to_string());
+            faces.push((
+                [
+                    corner(v0, *face_id),
+                    corner(v1, *face_id),
+                    corner(v2, *face_id),
+                ],
+                group,
+            ));
+            for (a, b) in [(v0, v1), (v1, v2), (v2, v0)] {
+                let halfedge_id = self.connecting_edge(a, b).unwrap();
+                if !is_beveled.contains(&halfedge_id) {
+                    crease_replacements
+                        .push(((a, b), vec![(corner(a, *face_id), corner(b, *face_id))]));
+                }
+            }
+        }
+        for halfedge_id in is_beveled.iter() {
+            let mut walker = self.walker_from_halfedge(*halfedge_id);
+            let face_id = walker.face_id().unwrap();
+            let w = walker.vertex_id().unwrap();
+            walker.as_twin();
+            let twin_face_id = walker.face_id().unwrap();
+            let v = walker.vertex_id().unwrap();
+            if v > w {
+                continue;
+            }
+            let group = self
+                .face_group(face_id)
+                .filter(|name| self.face_group(twin_face_id) == Some(*name))
+                .map(|name| name.to_string());
+            let (v1, w1) = (corner(v, face_id), corner(w, face_id));
+            let (v2, w2) = (corner(v, twin_face_id), corner(w, twin_face_id));
+            if v1 != v2 {
+                faces.push(([w1, v1, v2], group.clone()));
+            }
+            if w1 != w2 {
+                faces.push(([w1, v2, w2], group));
+            }
+            crease_replacements.push(((v, w), vec![(v1, w1), (v2, w2)]));
+        }
+        for cap in caps {
+            for i in 1..cap.len() - 1 {
+                faces.push(([cap[0], cap[i], cap[i + 1]], None));
+            }
+        }
+
+        // Vertices which are not split, but where all faces are replaced, are removed together with the faces and therefore added again
+        let positions: HashMap<VertexID, Vec3> = faces
+            .iter()
+            .flat_map(|(face, _)| face.iter())
+            .filter_map(|corner| match corner {
+                Corner::Old(vertex_id) => Some((*vertex_id, self.vertex_position(*vertex_id))),
+                Corner::New(_) => None,
+            })
+            .collect();
+        self.remove_faces(&replaced_faces);
+        let mut old_vertices = HashMap::new();
+        for (vertex_id, position) in positions {
+            let new_vertex_id = if self.contains_vertex(vertex_id) {
+                vertex_id
+            } else {
+                let new_vertex_id = self.add_vertex(position);
+                self.copy_vertex_attributes(vertex_id, new_vertex_id);
+                new_vertex_id
+            };
+            old_vertices.insert(vertex_id, new_vertex_id);
+        }
+        let new_vertices: Vec<VertexID> = new_vertices
+            .into_iter()
+            .map(|(vertex_id, position)| {
+                let new_vertex_id = self.add_vertex(position);
+                self.copy_vertex_attributes(vertex_id, new_vertex_id);
+                new_vertex_id
+            })
+            .collect();
+        let resolve = |corner: Corner| match corner {
+            Corner::Old(vertex_id) => old_vertices[&vertex_id],
+            Corner::New(i) => new_vertices[i],
+        };
+
+        let face_ids = self.add_faces(
+            &faces
+                .iter()
+                .map(|([c0, c1, c2], _)| (resolve(*c0), resolve(*c1), resolve(*c2)))
+                .collect::<Vec<_>>(),
+        )?;
+        for (face_id, (_, group)) in face_ids.into_iter().zip(faces) {
+            if let Some(group) = group {
+                self.assign_group(face_id, group);
+            }
+        }
+        for ((a, b), to) in crease_replacements {
+            self.replace_crease(
+                (a, b),
+                to.into_iter()
+                    .map(|(c0, c1)| (resolve(c0), resolve(c1)))
+                    .collect::<Vec<_>>(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the half-edges starting in the given vertex in counter-clockwise order,
+    /// starting right after the boundary if the vertex is on the boundary.
+    fn ordered_vertex_halfedges(&self, vertex_id: VertexID) -> Vec<HalfEdgeID> {
+        self.vertex_vertex_iter(vertex_id)
+            .map(|neighbour| self.connecting_edge(vertex_id, neighbour).unwrap())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bevel_all_cube_edges() {
+        let mut mesh = crate::test_utility::cube();
+        let edges: Vec<EdgeID> = mesh
+            .edge_iter()
+            .filter(|edge_id| mesh.edge_sqr_length((*edge_id).into()) < 4.1)
+            .collect();
+        assert_eq!(edges.len(), 12);
+
+        mesh.bevel_edges(&edges, 0.1).unwrap();
+
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        // Each corner of the cube is split into three vertices
+        assert_eq!(mesh.no_vertices(), 24);
+        // 12 faces + 2 faces for each of the 12 strips + 1 face for each of the 8 corners
+        assert_eq!(mesh.no_faces(), 12 + 24 + 8);
+        assert!(mesh.volume().unwrap() < 8.0);
+        assert!(mesh.volume().unwrap() > 7.0);
+    }
+
+    #[test]
+    fn test_bevel_one_edge() {
+        let mut mesh = crate::test_utility::subdivided_triangle();
+        let edge_id = mesh
+            .edge_iter()
+            .find(|edge_id| !mesh.is_edge_on_boundary((*edge_id).into()))
+            .unwrap();
+
+        mesh.bevel_edges(&[edge_id], 0.1).unwrap();
+
+        mesh.is_valid().unwrap();
+        // The interior vertex and the boundary vertex are both split into two vertices
+        assert_eq!(mesh.no_vertices(), 6);
+        // 3 faces + 2 faces for the strip + 1 face for the seam at the interior vertex
+        assert_eq!(mesh.no_faces(), 6);
+    }
+
+    #[test]
+    fn test_bevel_one_edge_of_closed_mesh() {
+        let mut mesh = crate::test_utility::cube();
+        let edge_id = mesh
+            .edge_iter()
+            .find(|edge_id| mesh.edge_sqr_length((*edge_id).into()) < 4.1)
+            .unwrap();
+
+        mesh.bevel_edges(&[edge_id], 0.1).unwrap();
+
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        // Both vertices of the edge are split into two vertices
+        assert_eq!(mesh.no_vertices(), 10);
+        // 12 faces + 2 faces for the strip + 1 face for the seam at each end of the strip
+        assert_eq!(mesh.no_faces(), 12 + 2 + 2);
+        assert!(mesh.volume().unwrap() < 8.0);
+        assert!(mesh.volume().unwrap() > 7.8);
+    }
+
+    #[test]
+    fn test_bevel_keeps_state_away_from_the_bevel() {
+        let mut mesh = crate::test_utility::cube();
+        let find_vertex = |mesh: &Mesh, p: Vec3| {
+            mesh.vertex_iter()
+                .find(|v| mesh.vertex_position(*v) == p)
+                .unwrap()
+        };
+        let corner = find_vertex(&mesh, vec3(1.0, 1.0, 1.0));
+        let opposite = find_vertex(&mesh, vec3(-1.0, -1.0, -1.0));
+        // A face at the opposite corner which is not next to the vertices at the ends of the strips
+        let far_halfedge = mesh
+            .vertex_halfedge_iter(opposite)
+            .find(|halfedge_id| {
+                mesh.walker_from_halfedge(*halfedge_id)
+                    .face_id()
+                    .map(|face_id| {
+                        let (v0, v1, v2) = mesh.face_positions(face_id);
+                        [v0, v1, v2].iter().all(|p| p.x + p.y + p.z < 0.0)
+                    })
+                    .unwrap_or(false)
+            })
+            .unwrap();
+        let far_face = mesh.walker_from_halfedge(far_halfedge).face_id().unwrap();
+        let near_face = mesh
+            .walker_from_vertex(corner)
+            .face_id()
+            .unwrap_or_else(|| {
+                mesh.walker_from_vertex(corner)
+                    .into_twin()
+                    .face_id()
+                    .unwrap()
+            });
+        mesh.assign_group(far_face, "far");
+        mesh.assign_group(near_face, "near");
+        mesh.set_crease(far_halfedge, true);
+        let edges: Vec<EdgeID> = mesh
+            .vertex_halfedge_iter(corner)
+            .filter(|halfedge_id| mesh.edge_sqr_length(*halfedge_id) < 4.1)
+            .map(|halfedge_id| mesh.edge_id(halfedge_id))
+            .collect();
+        assert_eq!(edges.len(), 3);
+        mesh.start_journal();
+
+        mesh.bevel_edges(&edges, 0.1).unwrap();
+        mesh.finish_step();
+
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        // The corner is split into three vertices and the other end of each strip into two vertices
+        assert_eq!(mesh.no_vertices(), 13);
+        assert!(!mesh.contains_vertex(corner));
+        assert!(mesh.contains_vertex(opposite));
+        assert_eq!(mesh.face_group(far_face), Some("far"));
+        assert!(mesh.is_crease(far_halfedge));
+        assert!(mesh
+            .face_iter()
+            .any(|face_id| mesh.face_group(face_id) == Some("near")));
+
+        // The bevel is recorded in the journal
+        assert!(mesh.undo());
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_vertices(), 8);
+        assert!(mesh.contains_vertex(corner));
+        assert!(mesh.redo());
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.no_vertices(), 13);
+    }
+}
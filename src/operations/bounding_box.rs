@@ -4,6 +4,127 @@ use crate::mesh::*;
 
 pub use three_d_asset::AxisAlignedBoundingBox;
 
+///
+/// An axis aligned box in double precision, as opposed to [AxisAlignedBoundingBox], which is used for the bounding box of the mesh (see [Mesh::aabb]).
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    /// The minimum corner
+    pub min: Vec3,
+    /// The maximum corner
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// Constructs a new box from its minimum and maximum corners.
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    /// Constructs the smallest box which contains all of the given positions. Returns `None` if no positions are given.
+    pub fn new_with_positions(positions: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut positions = positions.into_iter();
+        let first = positions.next()?;
+        let mut aabb = Self::new(first, first);
+        for p in positions {
+            aabb.expand(p);
+        }
+        Some(aabb)
+    }
+
+    /// Expands the box such that it contains the given position.
+    pub fn expand(&mut self, position: Vec3) {
+        for i in 0..3 {
+            self.min[i] = self.min[i].min(position[i]);
+            self.max[i] = self.max[i].max(position[i]);
+        }
+    }
+
+    /// Returns the center of the box.
+    pub fn center(&self) -> Vec3 {
+        0.5 * (self.min + self.max)
+    }
+
+    /// Returns the size of the box along each axis.
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    /// Returns whether or not the given position is inside or on the boundary of the box.
+    pub fn contains_point(&self, position: Vec3) -> bool {
+        (0..3).all(|i| self.min[i] <= position[i] && position[i] <= self.max[i])
+    }
+
+    /// Returns whether or not the other box is entirely inside this box.
+    pub fn contains(&self, other: &Aabb) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    /// Returns whether or not the two boxes overlap, including if they only touch.
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        (0..3).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+
+    /// Returns the box where the two boxes overlap or `None` if they do not overlap.
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        if self.intersects(other) {
+            Some(Aabb::new(
+                vec3(
+                    self.min.x.max(other.min.x),
+                    self.min.y.max(other.min.y),
+                    self.min.z.max(other.min.z),
+                ),
+                vec3(
+                    self.max.x.min(other.max.x),
+                    self.max.y.min(other.max.y),
+                    self.max.z.min(other.max.z),
+                ),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+///
+/// A sphere which contains the entire mesh, see [Mesh::bounding_sphere].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingSphere {
+    /// The center of the sphere
+    pub center: Vec3,
+    /// The radius of the sphere
+    pub radius: f64,
+}
+
+impl BoundingSphere {
+    /// Returns whether or not the given position is inside or on the boundary of the sphere.
+    pub fn contains_point(&self, position: Vec3) -> bool {
+        (position - self.center).magnitude2() <= self.radius * self.radius
+    }
+}
+
+///
+/// A box with arbitrary orientation which contains the entire mesh, see [Mesh::oriented_bounding_box].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrientedBoundingBox {
+    /// The center of the box
+    pub center: Vec3,
+    /// The orthonormal axes of the box
+    pub axes: [Vec3; 3],
+    /// Half the size of the box along each of the axes
+    pub half_extents: Vec3,
+}
+
+impl OrientedBoundingBox {
+    /// Returns whether or not the given position is inside or on the boundary of the box.
+    pub fn contains_point(&self, position: Vec3) -> bool {
+        let d = position - self.center;
+        (0..3).all(|i| d.dot(self.axes[i]).abs() <= self.half_extents[i])
+    }
+}
+
 /// # Bounding box
 impl Mesh {
     /// Returns the smallest axis aligned box which contains the entire mesh, ie. the axis aligned bounding box.
@@ -15,6 +136,107 @@ impl Mesh {
                 .collect::<Vec<_>>(),
         )
     }
+
+    ///
+    /// Returns the smallest axis aligned box which contains the entire mesh in double precision or `None` if the mesh has no vertices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let aabb = mesh.aabb().unwrap();
+    /// assert_eq!(aabb.min, vec3(-1.0, -1.0, -1.0));
+    /// assert_eq!(aabb.max, vec3(1.0, 1.0, 1.0));
+    /// assert!(aabb.contains_point(vec3(0.5, 0.0, -0.5)));
+    /// ```
+    ///
+    pub fn aabb(&self) -> Option<Aabb> {
+        Aabb::new_with_positions(self.vertex_iter().map(|v| self.vertex_position(v)))
+    }
+
+    ///
+    /// Returns a sphere which contains the entire mesh computed using Ritter's algorithm.
+    /// The sphere is not necessarily the smallest enclosing sphere, but it is usually at most a few percent larger.
+    /// Returns `None` if the mesh has no vertices.
+    /// If a vertex position is not finite, for example NaN, the returned sphere is not finite either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// let sphere = mesh.bounding_sphere().unwrap();
+    /// assert!(sphere.center.magnitude() < 0.1);
+    /// assert!(sphere.radius < 1.1);
+    /// ```
+    ///
+    pub fn bounding_sphere(&self) -> Option<BoundingSphere> {
+        let positions: Vec<Vec3> = self
+            .vertex_iter()
+            .map(|v| self.vertex_position(v))
+            .collect();
+        let farthest_from = |p: Vec3| {
+            *positions
+                .iter()
+                .max_by(|a, b| (*a - p).magnitude2().total_cmp(&(*b - p).magnitude2()))
+                .unwrap()
+        };
+        let x = *positions.first()?;
+        let y = farthest_from(x);
+        let z = farthest_from(y);
+        let mut center = 0.5 * (y + z);
+        let mut radius = 0.5 * (z - y).magnitude();
+        for p in positions.iter() {
+            let distance = (p - center).magnitude();
+            if distance > radius {
+                let new_radius = 0.5 * (radius + distance);
+                center += (new_radius - radius) / distance * (p - center);
+                radius = new_radius;
+            }
+        }
+        Some(BoundingSphere { center, radius })
+    }
+
+    ///
+    /// Returns a box which contains the entire mesh and is aligned with the principal axes of the mesh (see [Mesh::principal_axes]).
+    /// For elongated meshes which are not aligned with the coordinate axes, this box is usually a lot tighter than the axis aligned bounding box.
+    /// Returns `None` if the mesh has no vertices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::cube().into();
+    /// mesh.non_uniform_scale(3.0, 1.0, 0.5);
+    /// mesh.rotate(Mat3::from_angle_z(degrees(30.0)));
+    /// let obb = mesh.oriented_bounding_box().unwrap();
+    /// assert!((obb.half_extents.x - 3.0).abs() < 0.000001);
+    /// assert!((obb.half_extents.y - 1.0).abs() < 0.000001);
+    /// assert!((obb.half_extents.z - 0.5).abs() < 0.000001);
+    /// ```
+    ///
+    pub fn oriented_bounding_box(&self) -> Option<OrientedBoundingBox> {
+        let principal_axes = self.principal_axes();
+        let axes = principal_axes.axes;
+        let mut vertices = self.vertex_iter();
+        let first = self.vertex_position(vertices.next()?) - principal_axes.center;
+        let mut min = vec3(first.dot(axes[0]), first.dot(axes[1]), first.dot(axes[2]));
+        let mut max = min;
+        for vertex_id in vertices {
+            let d = self.vertex_position(vertex_id) - principal_axes.center;
+            for i in 0..3 {
+                min[i] = min[i].min(d.dot(axes[i]));
+                max[i] = max[i].max(d.dot(axes[i]));
+            }
+        }
+        let mid = 0.5 * (min + max);
+        Some(OrientedBoundingBox {
+            center: principal_axes.center + mid.x * axes[0] + mid.y * axes[1] + mid.z * axes[2],
+            axes,
+            half_extents: 0.5 * (max - min),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -41,4 +263,55 @@ mod tests {
         assert_eq!(bb.min(), Vector3::new(-1.0, -1.0, -1.0));
         assert_eq!(bb.max(), Vector3::new(1.0, 1.0, 1.0));
     }
+
+    #[test]
+    fn test_aabb() {
+        let a = Aabb::new(vec3(0.0, 0.0, 0.0), vec3(2.0, 2.0, 2.0));
+        let b = Aabb::new(vec3(1.0, 1.0, 1.0), vec3(3.0, 3.0, 3.0));
+        let c = Aabb::new(vec3(0.5, 0.5, 0.5), vec3(1.0, 1.0, 1.0));
+        let d = Aabb::new(vec3(2.5, 0.0, 0.0), vec3(3.0, 1.0, 1.0));
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&d));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Aabb::new(vec3(1.0, 1.0, 1.0), vec3(2.0, 2.0, 2.0)))
+        );
+        assert_eq!(a.intersection(&d), None);
+        assert!(a.contains(&c));
+        assert!(!a.contains(&b));
+        assert_eq!(a.center(), vec3(1.0, 1.0, 1.0));
+        assert_eq!(Mesh::new(&TriMesh::default()).aabb(), None);
+    }
+
+    #[test]
+    fn test_bounding_volumes_contain_mesh() {
+        let mut mesh: Mesh = TriMesh::cylinder(16).into();
+        mesh.non_uniform_scale(4.5, 0.1, 2.0);
+        mesh.rotate(Mat3::from_angle_y(degrees(20.0)));
+        mesh.translate(vec3(-1.5, 3.7, 9.1));
+
+        let sphere = mesh.bounding_sphere().unwrap();
+        let obb = mesh.oriented_bounding_box().unwrap();
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            assert!((p - sphere.center).magnitude() <= sphere.radius + 0.000001);
+            let d = p - obb.center;
+            for i in 0..3 {
+                assert!(d.dot(obb.axes[i]).abs() <= obb.half_extents[i] + 0.000001);
+            }
+        }
+        let aabb = mesh.aabb().unwrap();
+        let obb_volume = 8.0 * obb.half_extents.x * obb.half_extents.y * obb.half_extents.z;
+        let aabb_volume = aabb.size().x * aabb.size().y * aabb.size().z;
+        assert!(obb_volume < aabb_volume);
+    }
+
+    #[test]
+    fn test_bounding_sphere_with_nan_position() {
+        let mut mesh: Mesh = TriMesh::sphere(4).into();
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        mesh.move_vertex_to(vertex_id, vec3(f64::NAN, 0.0, 0.0));
+        let sphere = mesh.bounding_sphere().unwrap();
+        assert!(!sphere.radius.is_finite());
+    }
 }
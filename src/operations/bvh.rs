@@ -0,0 +1,344 @@
+//! A bounding volume hierarchy over the faces of a [Mesh](crate::Mesh) used to accelerate spatial queries.
+
+use crate::mesh::*;
+
+const MAX_FACES_IN_LEAF: usize = 4;
+const MIN_FACES_FOR_PARALLEL_BUILD: usize = 1024;
+
+/// An axis aligned box given by its minimum and maximum corner.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Bounds {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Bounds {
+    pub fn from_points(points: &[Vec3]) -> Self {
+        let mut bounds = Bounds {
+            min: vec3(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: vec3(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        };
+        for p in points {
+            bounds.expand_with_point(p);
+        }
+        bounds
+    }
+
+    pub fn expand_with_point(&mut self, p: &Vec3) {
+        self.min = vec3(
+            self.min.x.min(p.x),
+            self.min.y.min(p.y),
+            self.min.z.min(p.z),
+        );
+        self.max = vec3(
+            self.max.x.max(p.x),
+            self.max.y.max(p.y),
+            self.max.z.max(p.z),
+        );
+    }
+
+    pub fn expand_with_bounds(&mut self, other: &Bounds) {
+        self.expand_with_point(&other.min);
+        self.expand_with_point(&other.max);
+    }
+
+    pub fn center(&self) -> Vec3 {
+        0.5 * (self.min + self.max)
+    }
+
+    pub fn overlaps(&self, other: &Bounds, margin: f64) -> bool {
+        self.min.x <= other.max.x + margin
+            && other.min.x <= self.max.x + margin
+            && self.min.y <= other.max.y + margin
+            && other.min.y <= self.max.y + margin
+            && self.min.z <= other.max.z + margin
+            && other.min.z <= self.max.z + margin
+    }
+
+    /// Returns the squared distance from the point to the closest point inside the bounds.
+    pub fn distance2_to_point(&self, p: &Vec3) -> f64 {
+        let mut distance2 = 0.0;
+        for axis in 0..3 {
+            let d = (self.min[axis] - p[axis])
+                .max(p[axis] - self.max[axis])
+                .max(0.0);
+            distance2 += d * d;
+        }
+        distance2
+    }
+
+    /// Returns whether the ray, enlarged by the margin, hits the bounds somewhere in front of the start point.
+    pub fn intersects_ray(&self, start_point: &Vec3, direction: &Vec3, margin: f64) -> bool {
+        let mut t_min: f64 = 0.0;
+        let mut t_max = f64::INFINITY;
+        for axis in 0..3 {
+            let min = self.min[axis] - margin;
+            let max = self.max[axis] + margin;
+            if direction[axis].abs() < f64::EPSILON {
+                if start_point[axis] < min || start_point[axis] > max {
+                    return false;
+                }
+            } else {
+                let t0 = (min - start_point[axis]) / direction[axis];
+                let t1 = (max - start_point[axis]) / direction[axis];
+                t_min = t_min.max(t0.min(t1));
+                t_max = t_max.min(t0.max(t1));
+                if t_min > t_max {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+enum Node {
+    Leaf {
+        bounds: Bounds,
+        start: usize,
+        end: usize,
+    },
+    Internal {
+        bounds: Bounds,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl Node {
+    fn bounds(&self) -> &Bounds {
+        match self {
+            Node::Leaf { bounds, .. } | Node::Internal { bounds, .. } => bounds,
+        }
+    }
+
+    /// Returns the node with the indices of the child nodes moved by the given offset.
+    fn offset(self, offset: usize) -> Self {
+        match self {
+            Node::Internal {
+                bounds,
+                left,
+                right,
+            } => Node::Internal {
+                bounds,
+                left: left + offset,
+                right: right + offset,
+            },
+            leaf => leaf,
+        }
+    }
+}
+
+/// A bounding volume hierarchy over a set of faces which is built by splitting at the median of the longest axis.
+pub(crate) struct Bvh {
+    nodes: Vec<Node>,
+    faces: Vec<(FaceID, Bounds)>,
+}
+
+impl Bvh {
+    /// Builds a hierarchy over all the faces of the mesh.
+    /// The hierarchy is built using multiple threads if the `rayon` feature is enabled.
+    pub fn new(mesh: &Mesh) -> Self {
+        let faces = face_bounds(mesh);
+        Self::from_faces(faces)
+    }
+
+    /// Builds a hierarchy over the given faces and their bounds.
+    pub fn from_faces(mut faces: Vec<(FaceID, Bounds)>) -> Self {
+        let nodes = if faces.is_empty() {
+            Vec::new()
+        } else {
+            build(&mut faces, 0)
+        };
+        Bvh { nodes, faces }
+    }
+
+    /// Calls the callback for each face whose bounds overlaps the given bounds enlarged by the margin.
+    pub fn for_each_overlapping(
+        &self,
+        bounds: &Bounds,
+        margin: f64,
+        callback: &mut dyn FnMut(FaceID),
+    ) {
+        self.traverse(&mut |b| b.overlaps(bounds, margin), callback);
+    }
+
+    /// Calls the callback for each face whose bounds, enlarged by the margin, are hit by the given ray.
+    pub fn for_each_on_ray(
+        &self,
+        start_point: &Vec3,
+        direction: &Vec3,
+        margin: f64,
+        callback: &mut dyn FnMut(FaceID),
+    ) {
+        self.traverse(
+            &mut |b| b.intersects_ray(start_point, direction, margin),
+            callback,
+        );
+    }
+
+    /// Calls the callback for each face whose bounds are accepted by the given test,
+    /// only visiting the subtrees where the test accepts the bounds of the subtree.
+    pub fn traverse(
+        &self,
+        test: &mut dyn FnMut(&Bounds) -> bool,
+        callback: &mut dyn FnMut(FaceID),
+    ) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !test(node.bounds()) {
+                continue;
+            }
+            match node {
+                Node::Leaf { start, end, .. } => {
+                    for (face_id, face_bounds) in self.faces[*start..*end].iter() {
+                        if test(face_bounds) {
+                            callback(*face_id);
+                        }
+                    }
+                }
+                Node::Internal { left, right, .. } => {
+                    stack.push(*left);
+                    stack.push(*right);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn face_bounds(mesh: &Mesh) -> Vec<(FaceID, Bounds)> {
+    use rayon::prelude::*;
+    mesh.par_face_iter()
+        .map(|face_id| {
+            let (p0, p1, p2) = mesh.face_positions(face_id);
+            (face_id, Bounds::from_points(&[p0, p1, p2]))
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "rayon"))]
+fn face_bounds(mesh: &Mesh) -> Vec<(FaceID, Bounds)> {
+    mesh.face_iter()
+        .map(|face_id| {
+            let (p0, p1, p2) = mesh.face_positions(face_id);
+            (face_id, Bounds::from_points(&[p0, p1, p2]))
+        })
+        .collect()
+}
+
+/// Builds the nodes of the subtree containing the given faces, where `start` is the index of the first of the faces.
+/// The root of the subtree is the first node and the children of a node are stored after the node itself.
+fn build(faces: &mut [(FaceID, Bounds)], start: usize) -> Vec<Node> {
+    let mut bounds = faces[0].1;
+    faces[1..]
+        .iter()
+        .for_each(|(_, b)| bounds.expand_with_bounds(b));
+
+    let end = start + faces.len();
+    if faces.len() <= MAX_FACES_IN_LEAF {
+        return vec![Node::Leaf { bounds, start, end }];
+    }
+
+    let extent = bounds.max - bounds.min;
+    let axis = if extent.x >= extent.y && extent.x >= extent.z {
+        0
+    } else if extent.y >= extent.z {
+        1
+    } else {
+        2
+    };
+    let mid = faces.len() / 2;
+    faces.select_nth_unstable_by(mid, |(_, a), (_, b)| {
+        a.center()[axis].total_cmp(&b.center()[axis])
+    });
+
+    let (left_faces, right_faces) = faces.split_at_mut(mid);
+    let (left, right) = if left_faces.len() >= MIN_FACES_FOR_PARALLEL_BUILD {
+        join(
+            || build(left_faces, start),
+            || build(right_faces, start + mid),
+        )
+    } else {
+        (build(left_faces, start), build(right_faces, start + mid))
+    };
+
+    let mut nodes = Vec::with_capacity(1 + left.len() + right.len());
+    nodes.push(Node::Internal {
+        bounds,
+        left: 1,
+        right: 1 + left.len(),
+    });
+    let right_offset = 1 + left.len();
+    nodes.extend(left.into_iter().map(|node| node.offset(1)));
+    nodes.extend(right.into_iter().map(|node| node.offset(right_offset)));
+    nodes
+}
+
+#[cfg(feature = "rayon")]
+fn join<A, B>(a: impl FnOnce() -> A + Send, b: impl FnOnce() -> B + Send) -> (A, B)
+where
+    A: Send,
+    B: Send,
+{
+    rayon::join(a, b)
+}
+
+#[cfg(not(feature = "rayon"))]
+fn join<A, B>(a: impl FnOnce() -> A, b: impl FnOnce() -> B) -> (A, B) {
+    (a(), b())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    #[test]
+    fn test_overlapping_faces() {
+        let mesh: Mesh = TriMesh::sphere(8).into();
+        let bvh = Bvh::new(&mesh);
+        let query = Bounds::from_points(&[vec3(0.9, -0.1, -0.1), vec3(1.1, 0.1, 0.1)]);
+
+        let mut found = Vec::new();
+        bvh.for_each_overlapping(&query, 0.0, &mut |face_id| found.push(face_id));
+
+        let expected: Vec<FaceID> = mesh
+            .face_iter()
+            .filter(|face_id| {
+                let (p0, p1, p2) = mesh.face_positions(*face_id);
+                Bounds::from_points(&[p0, p1, p2]).overlaps(&query, 0.0)
+            })
+            .collect();
+        found.sort();
+        assert!(!expected.is_empty());
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_large_hierarchy() {
+        let mesh: Mesh = TriMesh::sphere(64).into();
+        assert!(mesh.no_faces() > 2 * MIN_FACES_FOR_PARALLEL_BUILD);
+        let bvh = Bvh::new(&mesh);
+
+        let mut found = Vec::new();
+        bvh.for_each_on_ray(
+            &vec3(0.0, 0.0, 0.0),
+            &vec3(1.0, 0.0, 0.0),
+            0.0,
+            &mut |face_id| found.push(face_id),
+        );
+        assert!(!found.is_empty());
+        for face_id in found {
+            assert!(mesh.face_center(face_id).x > 0.9);
+        }
+
+        let mut count = 0;
+        bvh.traverse(&mut |_| true, &mut |_| count += 1);
+        assert_eq!(count, mesh.no_faces());
+    }
+}
@@ -3,6 +3,17 @@
 use crate::mesh::*;
 use std::collections::HashSet;
 
+///
+/// The size of a connected component, see [Mesh::remove_small_components].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ComponentSize {
+    /// The number of faces in the component.
+    Faces(usize),
+    /// The total area of the faces in the component.
+    Area(f64),
+}
+
 /// # Connected components
 impl Mesh {
     ///
@@ -19,6 +30,129 @@ impl Mesh {
         self.connected_components_with_limit(&|_| false)
     }
 
+    ///
+    /// Returns the index of the connected component that each face belongs to,
+    /// where the indices are consecutive starting from zero and correspond to the order of [Mesh::connected_components].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let component_ids = mesh.face_component_ids();
+    /// assert!(mesh.face_iter().all(|face_id| component_ids[&face_id] == 0));
+    /// ```
+    ///
+    pub fn face_component_ids(&self) -> FaceProperty<usize> {
+        self.face_component_ids_with_limit(&|_| false)
+    }
+
+    ///
+    /// Returns the index of the connected component, limited by the given limit function, that each face belongs to,
+    /// where the indices are consecutive starting from zero and correspond to the order of [Mesh::connected_components_with_limit].
+    ///
+    pub fn face_component_ids_with_limit(
+        &self,
+        limit: &dyn Fn(HalfEdgeID) -> bool,
+    ) -> FaceProperty<usize> {
+        let mut component_ids = FaceProperty::new();
+        let mut no_components = 0;
+        for face_id in self.face_iter() {
+            if !component_ids.contains_key(&face_id) {
+                for f in self.connected_component_with_limit(face_id, limit) {
+                    component_ids.insert(f, no_components);
+                }
+                no_components += 1;
+            }
+        }
+        component_ids
+    }
+
+    ///
+    /// Removes all connected components except the one with the most faces, for example to remove the floating junk of a scanned mesh.
+    /// Returns the number of removed components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(3);
+    /// let mut junk = Mesh::icosphere(0);
+    /// junk.translate(vec3(5.0, 0.0, 0.0));
+    /// mesh.append(&junk);
+    ///
+    /// assert_eq!(mesh.keep_largest_component(), 1);
+    /// assert_eq!(mesh.no_faces(), 1280);
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn keep_largest_component(&mut self) -> usize {
+        let component_ids = self.face_component_ids();
+        let mut sizes = Vec::new();
+        for id in component_ids.values() {
+            if *id >= sizes.len() {
+                sizes.resize(id + 1, 0);
+            }
+            sizes[*id] += 1;
+        }
+        let Some(largest) = (0..sizes.len()).max_by_key(|id| (sizes[*id], usize::MAX - id)) else {
+            return 0;
+        };
+        self.remove_faces(
+            &component_ids
+                .into_iter()
+                .filter(|(_, id)| *id != largest)
+                .map(|(face_id, _)| face_id)
+                .collect(),
+        );
+        sizes.len() - 1
+    }
+
+    ///
+    /// Removes the connected components which are smaller than the given size, measured either as the number of faces or the area.
+    /// Returns the number of removed components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(3);
+    /// let mut junk = Mesh::icosphere(0);
+    /// junk.scale(0.1);
+    /// junk.translate(vec3(5.0, 0.0, 0.0));
+    /// mesh.append(&junk);
+    ///
+    /// assert_eq!(mesh.remove_small_components(ComponentSize::Area(1.0)), 1);
+    /// assert_eq!(mesh.no_faces(), 1280);
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn remove_small_components(&mut self, min_size: ComponentSize) -> usize {
+        let component_ids = self.face_component_ids();
+        let mut sizes = Vec::new();
+        for (face_id, id) in component_ids.iter() {
+            if *id >= sizes.len() {
+                sizes.resize(id + 1, 0.0);
+            }
+            sizes[*id] += match min_size {
+                ComponentSize::Faces(_) => 1.0,
+                ComponentSize::Area(_) => self.face_area(*face_id),
+            };
+        }
+        let min_size = match min_size {
+            ComponentSize::Faces(no_faces) => no_faces as f64,
+            ComponentSize::Area(area) => area,
+        };
+        self.remove_faces(
+            &component_ids
+                .into_iter()
+                .filter(|(_, id)| sizes[*id] < min_size)
+                .map(|(face_id, _)| face_id)
+                .collect(),
+        );
+        sizes.iter().filter(|size| **size < min_size).count()
+    }
+
     ///
     /// Finds the connected set of faces starting from the given face and limited by the given limit function.
     ///
@@ -73,6 +207,35 @@ mod tests {
     use super::*;
     use three_d_asset::{Indices, Positions, TriMesh};
 
+    #[test]
+    fn test_remove_small_components() {
+        let mut mesh = Mesh::icosphere(2);
+        for i in 1..4 {
+            let mut junk = Mesh::icosphere(0);
+            junk.translate(vec3(3.0 * i as f64, 0.0, 0.0));
+            mesh.append(&junk);
+        }
+        let mut square = crate::test_utility::square();
+        square.translate(vec3(0.0, 3.0, 0.0));
+        mesh.append(&square);
+
+        assert_eq!(mesh.remove_small_components(ComponentSize::Faces(3)), 1);
+        assert_eq!(mesh.no_faces(), 320 + 3 * 20);
+        mesh.is_valid().unwrap();
+
+        assert_eq!(mesh.keep_largest_component(), 3);
+        assert_eq!(mesh.no_faces(), 320);
+        assert_eq!(mesh.no_vertices(), 162);
+        assert_eq!(mesh.no_halfedges(), 960);
+        mesh.is_valid().unwrap();
+
+        assert_eq!(mesh.remove_small_components(ComponentSize::Area(1.0)), 0);
+        assert_eq!(mesh.remove_small_components(ComponentSize::Area(100.0)), 1);
+        assert_eq!(mesh.no_vertices(), 0);
+        assert_eq!(mesh.no_halfedges(), 0);
+        assert_eq!(mesh.keep_largest_component(), 0);
+    }
+
     #[test]
     fn test_one_connected_component() {
         let mesh = create_connected_test_object();
@@ -93,6 +256,18 @@ mod tests {
         assert!(cc.iter().find(|vec| vec.len() == 1).is_some());
     }
 
+    #[test]
+    fn test_face_component_ids() {
+        let mesh = create_unconnected_test_object();
+        let components = mesh.connected_components();
+        let component_ids = mesh.face_component_ids();
+
+        assert_eq!(component_ids.len(), mesh.no_faces());
+        for (i, component) in components.iter().enumerate() {
+            assert!(component.iter().all(|face_id| component_ids[face_id] == i));
+        }
+    }
+
     fn create_connected_test_object() -> Mesh {
         TriMesh {
             positions: Positions::F64(vec![
@@ -1,18 +1,65 @@
 //! See [Mesh](crate::mesh::Mesh).
 
 use crate::mesh::*;
+use crate::operations::VertexSet;
+use std::collections::{hash_map::Entry, HashSet};
 
 /// # Connectivity
 impl Mesh {
     /// Returns whether or not the mesh is closed, ie. contains no holes.
     pub fn is_closed(&self) -> bool {
-        for halfedge_id in self.edge_iter() {
-            if self.is_edge_on_boundary(halfedge_id) {
+        for edge_id in self.edge_iter() {
+            if self.is_edge_on_boundary(edge_id.into()) {
                 return false;
             }
         }
         true
     }
+
+    ///
+    /// Returns the Euler characteristic of the mesh, ie. `V - E + F`, where `V` is the number of vertices, `E` the number of edges and `F` the number of faces.
+    ///
+    pub fn euler_characteristic(&self) -> i64 {
+        self.no_vertices() as i64 - self.no_edges() as i64 + self.no_faces() as i64
+    }
+
+    ///
+    /// Returns the number of boundary loops, ie. the number of holes in the mesh.
+    ///
+    pub fn no_boundary_loops(&self) -> usize {
+        let mut visited = HashSet::new();
+        let mut count = 0;
+        for halfedge_id in self.halfedge_iter() {
+            if visited.contains(&halfedge_id)
+                || self.walker_from_halfedge(halfedge_id).face_id().is_some()
+            {
+                continue;
+            }
+            count += 1;
+            visited.extend(self.boundary_loop_iter(halfedge_id));
+        }
+        count
+    }
+
+    ///
+    /// Returns the genus of the mesh, ie. the number of handles, computed from the Euler characteristic, the number of connected components and the number of boundary loops.
+    /// The mesh is assumed to be orientable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// assert_eq!(mesh.genus(), 0);
+    /// ```
+    ///
+    pub fn genus(&self) -> usize {
+        let no_components = self.connected_components().len() as i64;
+        let genus =
+            (2 * no_components - self.no_boundary_loops() as i64 - self.euler_characteristic()) / 2;
+        genus.max(0) as usize
+    }
+
     ///
     /// Returns the connecting edge between the two vertices or `None` if no edge is found.
     ///
@@ -32,6 +79,60 @@ impl Mesh {
         None
     }
 
+    ///
+    /// Returns the vertices which can be reached from the given vertex by walking at most `k` edges, including the given vertex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// assert_eq!(mesh.vertex_k_ring(vertex_id, 0).len(), 1);
+    /// assert_eq!(mesh.vertex_k_ring(vertex_id, 1).len(), 1 + mesh.vertex_halfedge_iter(vertex_id).count());
+    /// ```
+    ///
+    pub fn vertex_k_ring(&self, vertex_id: VertexID, k: usize) -> HashSet<VertexID> {
+        let mut ring = HashSet::new();
+        ring.insert(vertex_id);
+        let mut front = vec![vertex_id];
+        for _ in 0..k {
+            let mut next_front = Vec::new();
+            for v in front {
+                for halfedge_id in self.vertex_halfedge_iter(v) {
+                    let neighbour = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                    if ring.insert(neighbour) {
+                        next_front.push(neighbour);
+                    }
+                }
+            }
+            front = next_front;
+        }
+        ring
+    }
+
+    ///
+    /// Returns the faces which can be reached from the given face by crossing at most `k` edges, including the given face.
+    /// For `k = 1`, these are the given face and its neighbours (see [Mesh::face_neighbours]).
+    ///
+    pub fn face_k_ring(&self, face_id: FaceID, k: usize) -> HashSet<FaceID> {
+        let mut ring = HashSet::new();
+        ring.insert(face_id);
+        let mut front = vec![face_id];
+        for _ in 0..k {
+            let mut next_front = Vec::new();
+            for f in front {
+                for neighbour in self.face_neighbours(f) {
+                    if ring.insert(neighbour) {
+                        next_front.push(neighbour);
+                    }
+                }
+            }
+            front = next_front;
+        }
+        ring
+    }
+
     /// Returns whether or not the vertex is on a boundary.
     pub fn is_vertex_on_boundary(&self, vertex_id: VertexID) -> bool {
         for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
@@ -43,12 +144,150 @@ impl Mesh {
         false
     }
 
+    ///
+    /// Returns the valence of the vertex, ie. the number of edges connected to the vertex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::icosphere(0);
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// assert_eq!(mesh.vertex_valence(vertex_id), 5);
+    /// ```
+    ///
+    pub fn vertex_valence(&self, vertex_id: VertexID) -> usize {
+        self.vertex_halfedge_iter(vertex_id).count()
+    }
+
+    ///
+    /// Returns whether the vertex is regular, ie. whether it has the valence of a vertex in a regular triangle grid,
+    /// which is six for an interior vertex and four for a vertex on the boundary.
+    ///
+    pub fn is_regular_vertex(&self, vertex_id: VertexID) -> bool {
+        let regular_valence = if self.is_vertex_on_boundary(vertex_id) {
+            4
+        } else {
+            6
+        };
+        self.vertex_valence(vertex_id) == regular_valence
+    }
+
+    ///
+    /// Iterator over the vertices which are not regular (see [Mesh::is_regular_vertex]), for example the extraordinary vertices of a subdivision surface.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// // The twelve vertices of the original icosahedron have valence five while all other vertices have valence six
+    /// let mesh = Mesh::icosphere(2);
+    /// assert_eq!(mesh.irregular_vertex_iter().count(), 12);
+    /// ```
+    ///
+    pub fn irregular_vertex_iter(&self) -> impl Iterator<Item = VertexID> + '_ {
+        self.vertex_iter()
+            .filter(move |vertex_id| !self.is_regular_vertex(*vertex_id))
+    }
+
+    ///
+    /// Returns the vertices on the boundary (see [Mesh::is_vertex_on_boundary]), found in one pass over the half-edges instead of testing each vertex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::grid(vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), 4, 4);
+    /// assert_eq!(mesh.boundary_vertices().len(), 16);
+    /// ```
+    ///
+    pub fn boundary_vertices(&self) -> VertexSet {
+        self.boundary_edges()
+            .into_iter()
+            .map(|halfedge_id| self.walker_from_halfedge(halfedge_id).vertex_id().unwrap())
+            .collect()
+    }
+
+    ///
+    /// Returns the half-edges without an adjacent face, ie. one half-edge for each edge on the boundary (see [Mesh::is_edge_on_boundary])
+    /// oriented along the boundary loop it is part of (see [Mesh::boundary_loop_iter]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::cylinder(16).into();
+    /// // The two ends of the cylinder are open
+    /// assert_eq!(mesh.boundary_edges().len(), 2 * 16);
+    /// ```
+    ///
+    pub fn boundary_edges(&self) -> Vec<HalfEdgeID> {
+        self.halfedge_iter()
+            .filter(|halfedge_id| self.walker_from_halfedge(*halfedge_id).face_id().is_none())
+            .collect()
+    }
+
+    ///
+    /// Returns the topological distance from each vertex to the boundary, ie. the smallest number of edges to cross to reach a vertex on the boundary,
+    /// which is zero for the vertices on the boundary, one for their neighbours and so on.
+    /// Vertices which cannot reach the boundary, for example the vertices of a closed mesh, are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::grid(vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), 4, 4);
+    /// let distances = mesh.distance_to_boundary();
+    /// let center = mesh.vertex_iter().find(|v| mesh.vertex_position(*v) == vec3(0.0, 0.0, 0.0)).unwrap();
+    /// assert_eq!(distances[&center], 2);
+    /// ```
+    ///
+    pub fn distance_to_boundary(&self) -> VertexProperty<usize> {
+        let mut distances: VertexProperty<usize> = self
+            .boundary_vertices()
+            .iter()
+            .map(|vertex_id| (vertex_id, 0))
+            .collect();
+        let mut front: Vec<VertexID> = distances.keys().copied().collect();
+        let mut distance = 0;
+        while !front.is_empty() {
+            distance += 1;
+            let mut next_front = Vec::new();
+            for vertex_id in front {
+                for neighbour in self.vertex_vertex_iter(vertex_id) {
+                    if let Entry::Vacant(entry) = distances.entry(neighbour) {
+                        entry.insert(distance);
+                        next_front.push(neighbour);
+                    }
+                }
+            }
+            front = next_front;
+        }
+        distances
+    }
+
     /// Returns whether or not the edge is on a boundary.
     pub fn is_edge_on_boundary(&self, halfedge_id: HalfEdgeID) -> bool {
         let mut walker = self.walker_from_halfedge(halfedge_id);
         walker.face_id().is_none() || walker.as_twin().face_id().is_none()
     }
 
+    /// Returns the id of the edge that the given half-edge is a part of.
+    pub fn edge_id(&self, halfedge_id: HalfEdgeID) -> EdgeID {
+        let twin_id = self.walker_from_halfedge(halfedge_id).twin_id().unwrap();
+        let halfedge_id = halfedge_id.min(twin_id);
+        unsafe { EdgeID::new_with_generation(*halfedge_id, halfedge_id.generation()) }
+    }
+
+    /// Returns the two half-edges of the given edge.
+    pub fn edge_halfedges(&self, edge_id: EdgeID) -> (HalfEdgeID, HalfEdgeID) {
+        let halfedge_id = HalfEdgeID::from(edge_id);
+        (
+            halfedge_id,
+            self.walker_from_halfedge(halfedge_id).twin_id().unwrap(),
+        )
+    }
+
     /// Returns the vertex id of the two adjacent vertices to the given edge.
     pub fn edge_vertices(&self, halfedge_id: HalfEdgeID) -> (VertexID, VertexID) {
         let mut walker = self.walker_from_halfedge(halfedge_id);
@@ -117,13 +356,179 @@ impl Mesh {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use three_d_asset::TriMesh;
+    use three_d_asset::{Indices, Positions, TriMesh};
+
+    #[test]
+    #[should_panic(expected = "stale")]
+    fn test_stale_vertex_id_is_reported() {
+        let mut mesh: Mesh = TriMesh::sphere(4).into();
+        let halfedge_id = mesh.halfedge_iter().next().unwrap();
+        let (_, dying_vertex_id) = mesh.edge_vertices(halfedge_id);
+        mesh.collapse_edge(halfedge_id);
+        mesh.add_vertex(vec3(0.0, 0.0, 0.0));
+        mesh.vertex_position(dying_vertex_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "stale")]
+    fn test_stale_face_id_is_reported() {
+        let mut mesh: Mesh = TriMesh::sphere(4).into();
+        let face_id = mesh.face_iter().next().unwrap();
+        let (v0, v1, v2) = mesh.face_vertices(face_id);
+        mesh.remove_face(face_id);
+        mesh.add_face(v0, v1, v2).unwrap();
+        mesh.face_normal(face_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "stale")]
+    fn test_stale_halfedge_id_is_reported() {
+        let mut mesh = crate::test_utility::triangle();
+        let face_id = mesh.face_iter().next().unwrap();
+        let halfedge_id = mesh.walker_from_face(face_id).halfedge_id().unwrap();
+        mesh.remove_face(face_id);
+        let v0 = mesh.add_vertex(vec3(0.0, 0.0, 0.0));
+        let v1 = mesh.add_vertex(vec3(1.0, 0.0, 0.0));
+        let v2 = mesh.add_vertex(vec3(0.0, 1.0, 0.0));
+        mesh.add_face(v0, v1, v2).unwrap();
+        mesh.edge_vertices(halfedge_id);
+    }
+
+    #[test]
+    fn test_k_rings() {
+        let mesh = crate::test_utility::triangle_strip();
+        let vertex_id = unsafe { VertexID::new(0) };
+        assert_eq!(mesh.vertex_k_ring(vertex_id, 1).len(), 3);
+        assert_eq!(mesh.vertex_k_ring(vertex_id, 2).len(), 5);
+        assert_eq!(mesh.vertex_k_ring(vertex_id, 100).len(), mesh.no_vertices());
+
+        let face_id = mesh.face_iter().next().unwrap();
+        assert_eq!(mesh.face_k_ring(face_id, 0).len(), 1);
+        assert_eq!(mesh.face_k_ring(face_id, 100).len(), mesh.no_faces());
+        let ring = mesh.face_k_ring(face_id, 1);
+        for halfedge_id in mesh.face_halfedge_iter(face_id) {
+            if let Some(f) = mesh.walker_from_halfedge(halfedge_id).as_twin().face_id() {
+                assert!(ring.contains(&f));
+            }
+        }
+    }
+
+    #[test]
+    fn test_vertex_valence() {
+        let mesh = crate::test_utility::subdivided_triangle();
+        let center = unsafe { VertexID::new(0) };
+        assert_eq!(mesh.vertex_valence(center), 3);
+        assert!(!mesh.is_regular_vertex(center));
+
+        // Only the four corners of a grid are irregular
+        let mesh = Mesh::grid(
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            4,
+            4,
+        );
+        let irregular: Vec<VertexID> = mesh.irregular_vertex_iter().collect();
+        assert_eq!(irregular.len(), 4);
+        for vertex_id in irregular {
+            let p = mesh.vertex_position(vertex_id);
+            assert_eq!(p.x.abs(), 0.5);
+            assert_eq!(p.y.abs(), 0.5);
+        }
+    }
+
+    #[test]
+    fn test_boundary_queries() {
+        let mut mesh = Mesh::icosphere(2);
+        assert!(mesh.boundary_edges().is_empty());
+        assert!(mesh.boundary_vertices().is_empty());
+        assert!(mesh.distance_to_boundary().is_empty());
+
+        let face_id = mesh.face_iter().next().unwrap();
+        let (v0, v1, v2) = mesh.face_vertices(face_id);
+        mesh.remove_face(face_id);
+        let boundary_edges = mesh.boundary_edges();
+        assert_eq!(boundary_edges.len(), 3);
+        assert!(boundary_edges
+            .iter()
+            .all(|halfedge_id| mesh.is_edge_on_boundary(*halfedge_id)));
+        let boundary_vertices = mesh.boundary_vertices();
+        assert_eq!(boundary_vertices, [v0, v1, v2].into_iter().collect());
+        assert!(mesh
+            .vertex_iter()
+            .all(|v| boundary_vertices.contains(v) == mesh.is_vertex_on_boundary(v)));
+
+        let distances = mesh.distance_to_boundary();
+        assert_eq!(distances.len(), mesh.no_vertices());
+        for vertex_id in mesh.vertex_iter() {
+            let distance = distances[&vertex_id];
+            assert_eq!(distance == 0, boundary_vertices.contains(vertex_id));
+            if distance > 0 {
+                assert_eq!(
+                    mesh.vertex_vertex_iter(vertex_id)
+                        .map(|v| distances[&v])
+                        .min(),
+                    Some(distance - 1)
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_is_closed_when_not_closed() {
         let mesh = crate::test_utility::subdivided_triangle();
         assert!(!mesh.is_closed());
     }
 
+    #[test]
+    fn test_topology_of_sphere() {
+        let mesh: Mesh = TriMesh::sphere(4).into();
+        assert_eq!(mesh.euler_characteristic(), 2);
+        assert_eq!(mesh.no_boundary_loops(), 0);
+        assert_eq!(mesh.genus(), 0);
+    }
+
+    #[test]
+    fn test_topology_of_open_mesh() {
+        let mut mesh = crate::test_utility::subdivided_triangle();
+        assert_eq!(mesh.euler_characteristic(), 1);
+        assert_eq!(mesh.no_boundary_loops(), 1);
+        assert_eq!(mesh.genus(), 0);
+
+        mesh.append(&crate::test_utility::square());
+        assert_eq!(mesh.euler_characteristic(), 2);
+        assert_eq!(mesh.no_boundary_loops(), 2);
+        assert_eq!(mesh.genus(), 0);
+    }
+
+    #[test]
+    fn test_genus_of_torus() {
+        let mut positions = Vec::new();
+        let mut indices = Vec::new();
+        let (n, m) = (8u32, 6u32);
+        for i in 0..n {
+            for j in 0..m {
+                let u = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+                let v = 2.0 * std::f64::consts::PI * j as f64 / m as f64;
+                let r = 2.0 + 0.5 * v.cos();
+                positions.push(vec3(r * u.cos(), 0.5 * v.sin(), r * u.sin()));
+                let (i1, j1) = ((i + 1) % n, (j + 1) % m);
+                indices.extend([i * m + j, i1 * m + j, i1 * m + j1]);
+                indices.extend([i * m + j, i1 * m + j1, i * m + j1]);
+            }
+        }
+        let mesh: Mesh = TriMesh {
+            indices: Indices::U32(indices),
+            positions: Positions::F64(positions),
+            ..Default::default()
+        }
+        .into();
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.euler_characteristic(), 0);
+        assert_eq!(mesh.no_boundary_loops(), 0);
+        assert_eq!(mesh.genus(), 1);
+    }
+
     #[test]
     fn test_is_closed_when_closed() {
         let mesh: Mesh = TriMesh::sphere(4).into();
@@ -0,0 +1,341 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+///
+/// The principal curvatures and principal directions at a vertex, see [Mesh::principal_curvatures].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrincipalCurvatures {
+    /// The maximum curvature.
+    pub k1: f64,
+    /// The minimum curvature.
+    pub k2: f64,
+    /// The unit tangent direction in which the curvature is maximal.
+    pub direction1: Vec3,
+    /// The unit tangent direction in which the curvature is minimal.
+    pub direction2: Vec3,
+}
+
+/// # Curvature
+impl Mesh {
+    ///
+    /// Returns the Gaussian curvature at the given vertex computed as the angle deficit divided by the mixed Voronoi area of the vertex.
+    /// For a vertex on the boundary, the angle deficit is measured relative to `π` instead of `2π`.
+    /// Returns zero if the vertex is not part of any face, since the curvature is not defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(32).into();
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// assert!((mesh.gaussian_curvature(vertex_id) - 1.0).abs() < 0.1);
+    /// ```
+    ///
+    pub fn gaussian_curvature(&self, vertex_id: VertexID) -> f64 {
+        if !self.is_vertex_in_face(vertex_id) {
+            return 0.0;
+        }
+        let mut angle_sum = 0.0;
+        for (p, p1, p2) in self.vertex_corners(vertex_id) {
+            angle_sum += (p1 - p).angle(p2 - p).0;
+        }
+        let full_angle = if self.is_vertex_on_boundary(vertex_id) {
+            std::f64::consts::PI
+        } else {
+            2.0 * std::f64::consts::PI
+        };
+        (full_angle - angle_sum) / self.mixed_voronoi_area(vertex_id)
+    }
+
+    ///
+    /// Returns the Gaussian curvature (see [Mesh::gaussian_curvature]) of all vertices
+    /// in the same order as the vertices are visited by [Mesh::vertex_iter], ie. the same order as the positions of the exported mesh.
+    ///
+    pub fn gaussian_curvature_buffer(&self) -> Vec<f64> {
+        self.vertex_iter()
+            .map(|vertex_id| self.gaussian_curvature(vertex_id))
+            .collect()
+    }
+
+    ///
+    /// Returns the mean curvature at the given vertex computed using the cotangent discretization of the Laplace-Beltrami operator.
+    /// The mean curvature is positive where the surface curves away from the normal direction, for example everywhere on a sphere with outward pointing normals.
+    /// Returns zero if the vertex is not part of any face, since the curvature is not defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(32).into();
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// assert!((mesh.mean_curvature(vertex_id) - 1.0).abs() < 0.1);
+    /// ```
+    ///
+    pub fn mean_curvature(&self, vertex_id: VertexID) -> f64 {
+        if !self.is_vertex_in_face(vertex_id) {
+            return 0.0;
+        }
+        let laplacian = self.laplace_beltrami(vertex_id);
+        let sign = if laplacian.dot(self.vertex_normal(vertex_id)) > 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        sign * 0.5 * laplacian.magnitude()
+    }
+
+    ///
+    /// Returns the mean curvature (see [Mesh::mean_curvature]) of all vertices
+    /// in the same order as the vertices are visited by [Mesh::vertex_iter], ie. the same order as the positions of the exported mesh.
+    ///
+    pub fn mean_curvature_buffer(&self) -> Vec<f64> {
+        self.vertex_iter()
+            .map(|vertex_id| self.mean_curvature(vertex_id))
+            .collect()
+    }
+
+    ///
+    /// Returns the principal curvatures and principal directions at the given vertex.
+    /// The principal curvatures are computed from the mean curvature `H` and the Gaussian curvature `K` as `H ± sqrt(H² - K)`
+    /// and the principal directions are found as the eigenvectors of a curvature tensor fitted to the normal curvatures along the adjacent edges.
+    /// Returns `None` if the vertex is not part of any face, since the curvature is not defined.
+    ///
+    pub fn principal_curvatures(&self, vertex_id: VertexID) -> Option<PrincipalCurvatures> {
+        if !self.is_vertex_in_face(vertex_id) {
+            return None;
+        }
+        let h = self.mean_curvature(vertex_id);
+        let k = self.gaussian_curvature(vertex_id);
+        let d = (h * h - k).max(0.0).sqrt();
+
+        let p = self.vertex_position(vertex_id);
+        let normal = self.vertex_normal(vertex_id);
+        let neighbours: Vec<Vec3> = self
+            .vertex_halfedge_iter(vertex_id)
+            .map(|halfedge_id| {
+                self.vertex_position(self.walker_from_halfedge(halfedge_id).vertex_id().unwrap())
+                    - p
+            })
+            .collect();
+        let first = neighbours[0];
+        let u = (first - first.dot(normal) * normal).normalize();
+        let v = normal.cross(u);
+
+        // Least squares fit of the curvature tensor [[a, b], [b, c]] to the normal curvatures
+        let mut ata = Mat3::zero();
+        let mut atb = Vec3::zero();
+        for d in neighbours.iter() {
+            let tangent = (d - d.dot(normal) * normal).normalize();
+            let (x, y) = (tangent.dot(u), tangent.dot(v));
+            let row = vec3(x * x, 2.0 * x * y, y * y);
+            let normal_curvature = -2.0 * d.dot(normal) / d.magnitude2();
+            ata += Mat3::from_cols(row * row.x, row * row.y, row * row.z);
+            atb += row * normal_curvature;
+        }
+        let angle = ata
+            .invert()
+            .map(|inverse| {
+                let tensor = inverse * atb;
+                0.5 * (2.0 * tensor.y).atan2(tensor.x - tensor.z)
+            })
+            .unwrap_or(0.0);
+        let direction1 = angle.cos() * u + angle.sin() * v;
+        Some(PrincipalCurvatures {
+            k1: h + d,
+            k2: h - d,
+            direction1,
+            direction2: normal.cross(direction1),
+        })
+    }
+
+    /// Returns whether the given vertex is part of at least one face.
+    fn is_vertex_in_face(&self, vertex_id: VertexID) -> bool {
+        self.vertex_halfedge_iter(vertex_id)
+            .any(|halfedge_id| self.walker_from_halfedge(halfedge_id).face_id().is_some())
+    }
+
+    /// Returns the Laplace-Beltrami operator applied to the vertex positions at the given vertex using the cotangent weights.
+    pub(crate) fn laplace_beltrami(&self, vertex_id: VertexID) -> Vec3 {
+        let p = self.vertex_position(vertex_id);
+        let mut sum = Vec3::zero();
+        for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
+            let neighbour = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+            sum += self.cotangent_weight(halfedge_id) * (self.vertex_position(neighbour) - p);
+        }
+        sum / self.mixed_voronoi_area(vertex_id)
+    }
+
+    /// Returns half the sum of the cotangents of the two angles opposite to the given edge.
+    pub(crate) fn cotangent_weight(&self, halfedge_id: HalfEdgeID) -> f64 {
+        let mut weight = 0.0;
+        let mut walker = self.walker_from_halfedge(halfedge_id);
+        for _ in 0..2 {
+            if walker.face_id().is_some() {
+                let p0 = self.vertex_position(walker.vertex_id().unwrap());
+                let p2 = self.vertex_position(walker.as_next().vertex_id().unwrap());
+                let p1 = self.vertex_position(walker.as_next().vertex_id().unwrap());
+                weight += 0.5 * cotangent(p0 - p2, p1 - p2);
+                walker.as_next();
+            }
+            walker.as_twin();
+        }
+        weight
+    }
+
+    ///
+    /// Returns the mixed Voronoi area of the given vertex, ie. the area of the Voronoi region of the vertex restricted to the adjacent faces
+    /// and where the circumcenter is replaced by the midpoint of the opposite edge for obtuse triangles.
+    ///
+    pub fn mixed_voronoi_area(&self, vertex_id: VertexID) -> f64 {
+        let mut area = 0.0;
+        for (p, p1, p2) in self.vertex_corners(vertex_id) {
+            let face_area = 0.5 * (p1 - p).cross(p2 - p).magnitude();
+            if (p1 - p).dot(p2 - p) < 0.0 {
+                area += 0.5 * face_area;
+            } else if (p - p1).dot(p2 - p1) < 0.0 || (p - p2).dot(p1 - p2) < 0.0 {
+                area += 0.25 * face_area;
+            } else {
+                area += 0.125
+                    * ((p1 - p).magnitude2() * cotangent(p - p2, p1 - p2)
+                        + (p2 - p).magnitude2() * cotangent(p - p1, p2 - p1));
+            }
+        }
+        area
+    }
+
+    /// Returns the positions of the corners of the faces adjacent to the given vertex,
+    /// where the first position is the position of the vertex and the corners are ordered according to the face orientation.
+    pub(crate) fn vertex_corners(&self, vertex_id: VertexID) -> Vec<(Vec3, Vec3, Vec3)> {
+        let p = self.vertex_position(vertex_id);
+        self.vertex_halfedge_iter(vertex_id)
+            .filter_map(|halfedge_id| {
+                let mut walker = self.walker_from_halfedge(halfedge_id);
+                walker.face_id()?;
+                let p1 = self.vertex_position(walker.vertex_id().unwrap());
+                let p2 = self.vertex_position(walker.as_next().vertex_id().unwrap());
+                Some((p, p1, p2))
+            })
+            .collect()
+    }
+}
+
+/// Returns the cotangent of the angle between the two vectors.
+pub(crate) fn cotangent(v0: Vec3, v1: Vec3) -> f64 {
+    v0.dot(v1) / v0.cross(v1).magnitude()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    #[test]
+    fn test_gaussian_curvature_of_plane() {
+        let mesh = crate::test_utility::subdivided_triangle();
+        let vertex_id = mesh
+            .vertex_iter()
+            .find(|v| !mesh.is_vertex_on_boundary(*v))
+            .unwrap();
+        assert!(mesh.gaussian_curvature(vertex_id).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_gaussian_curvature_of_sphere() {
+        let mut mesh: Mesh = TriMesh::sphere(32).into();
+        mesh.scale(2.0);
+        let curvatures = mesh.gaussian_curvature_buffer();
+        assert_eq!(curvatures.len(), mesh.no_vertices());
+        let total: f64 = mesh
+            .vertex_iter()
+            .zip(curvatures.iter())
+            .map(|(v, k)| k * mesh.mixed_voronoi_area(v))
+            .sum();
+        // Gauss-Bonnet: The total curvature of a sphere is 4π
+        assert!((total - 4.0 * std::f64::consts::PI).abs() < 0.000001);
+        let average = curvatures.iter().sum::<f64>() / curvatures.len() as f64;
+        assert!((average - 0.25).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_mean_curvature_of_sphere() {
+        let mut mesh: Mesh = TriMesh::sphere(32).into();
+        mesh.scale(2.0);
+        let curvatures = mesh.mean_curvature_buffer();
+        assert!(curvatures.iter().all(|h| (h - 0.5).abs() < 0.05));
+
+        mesh.flip_orientation();
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        assert!((mesh.mean_curvature(vertex_id) + 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_mean_curvature_of_plane() {
+        let mesh = crate::test_utility::subdivided_triangle();
+        let vertex_id = mesh
+            .vertex_iter()
+            .find(|v| !mesh.is_vertex_on_boundary(*v))
+            .unwrap();
+        assert!(mesh.mean_curvature(vertex_id).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_principal_curvatures_of_cylinder() {
+        let (rings, segments) = (9u32, 64u32);
+        let mut positions = Vec::new();
+        let mut indices = Vec::new();
+        for i in 0..rings {
+            for j in 0..segments {
+                let angle = 2.0 * std::f64::consts::PI * j as f64 / segments as f64;
+                positions.push(vec3(0.1 * i as f64, angle.cos(), angle.sin()));
+                if i + 1 < rings {
+                    let j1 = (j + 1) % segments;
+                    let (a, b) = (i * segments + j, i * segments + j1);
+                    let (c, d) = (a + segments, b + segments);
+                    indices.extend([a, b, d, a, d, c]);
+                }
+            }
+        }
+        let mesh: Mesh = TriMesh {
+            indices: three_d_asset::Indices::U32(indices),
+            positions: three_d_asset::Positions::F64(positions),
+            ..Default::default()
+        }
+        .into();
+        let vertex_id = unsafe { VertexID::new(4 * segments) };
+        let curvatures = mesh.principal_curvatures(vertex_id).unwrap();
+        assert!((curvatures.k1.abs() - 1.0).abs() < 0.1);
+        assert!(curvatures.k2.abs() < 0.1);
+        assert!(curvatures.direction1.x.abs() < 0.1);
+        assert!(curvatures.direction2.x.abs() > 0.9);
+    }
+
+    #[test]
+    fn test_principal_curvatures_of_isolated_vertex() {
+        let mut mesh = crate::test_utility::triangle();
+        let vertex_id = mesh.add_vertex(vec3(0.0, 0.0, 1.0));
+        assert_eq!(mesh.principal_curvatures(vertex_id), None);
+    }
+
+    #[test]
+    fn test_curvature_of_isolated_vertex() {
+        let mut mesh = crate::test_utility::triangle();
+        let vertex_id = mesh.add_vertex(vec3(0.0, 0.0, 1.0));
+        assert_eq!(mesh.gaussian_curvature(vertex_id), 0.0);
+        assert_eq!(mesh.mean_curvature(vertex_id), 0.0);
+        assert!(mesh
+            .gaussian_curvature_buffer()
+            .iter()
+            .all(|k| k.is_finite()));
+        assert!(mesh.mean_curvature_buffer().iter().all(|h| h.is_finite()));
+    }
+
+    #[test]
+    fn test_mixed_voronoi_area() {
+        let mesh: Mesh = TriMesh::sphere(16).into();
+        let total: f64 = mesh.vertex_iter().map(|v| mesh.mixed_voronoi_area(v)).sum();
+        let surface_area: f64 = mesh.face_iter().map(|f| mesh.face_area(f)).sum();
+        assert!((total - surface_area).abs() < 0.000001);
+    }
+}
@@ -0,0 +1,628 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::operations::triangulate_polygon;
+use crate::operations::triangulation::{is_inside_polygon, signed_area};
+use crate::Error;
+use std::collections::{HashMap, HashSet};
+
+/// The maximum distance from the plane for a vertex to be considered to lie in the plane.
+const PLANE_MARGIN: f64 = 0.0000001;
+
+///
+/// A curve in 3D given by a sequence of points connected by straight line segments as returned by [Mesh::plane_section].
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polyline3 {
+    /// The points of the polyline in order
+    pub points: Vec<Vec3>,
+    /// Whether the last point is connected to the first point, in which case the first point is not repeated at the end of the points
+    pub is_closed: bool,
+}
+
+/// A point on the intersection between a mesh and a plane, which is either a vertex in the plane or a point on an edge crossing the plane.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+enum SectionPoint {
+    Vertex(VertexID),
+    Edge(EdgeID),
+}
+
+/// # Cut
+impl Mesh {
+    ///
+    /// Cuts the mesh into two by the plane given by a point in the plane and the normal of the plane.
+    /// Returns the part behind the plane, ie. on the opposite side of the normal, and the part in front of the plane.
+    /// The faces crossing the plane are split such that the new edges lie in the plane, see [Mesh::split_at_isovalue].
+    /// Faces lying in the plane belong to the part behind the plane if they face the same way as the normal, ie. if they close the part behind the plane,
+    /// and otherwise to the part in front of the plane.
+    ///
+    /// If `cap` is true, the holes in each part that are created by the cut are filled with faces lying in the plane,
+    /// so that cutting a closed mesh results in two closed meshes.
+    /// Cross sections with holes, for example when cutting a hollow object, are capped with the holes left open,
+    /// such that the inner and outer surfaces are connected by the cap.
+    /// Holes bordering faces lying in the plane are not capped, since those faces already close the hole in the other part.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the normal is zero, or if `cap` is true and a cross section cannot be triangulated (see [triangulate_polygon]),
+    /// for example if it is self-intersecting, or if capping it results in a non-manifold mesh.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let (behind, in_front) = mesh.cut_by_plane(vec3(0.0, 0.3, 0.0), vec3(0.0, 1.0, 0.0), true).unwrap();
+    /// assert!(behind.is_closed());
+    /// assert!(in_front.is_closed());
+    /// assert!(behind.vertex_iter().all(|v| behind.vertex_position(v).y <= 0.3));
+    /// assert!(in_front.vertex_iter().all(|v| in_front.vertex_position(v).y >= 0.3));
+    /// ```
+    ///
+    pub fn cut_by_plane(
+        &self,
+        point: Vec3,
+        normal: Vec3,
+        cap: bool,
+    ) -> Result<(Mesh, Mesh), Error> {
+        if normal.magnitude2() == 0.0 {
+            return Err(Error::ActionWillResultInInvalidMesh(
+                "the normal of the cutting plane is zero".to_string(),
+            ));
+        }
+        let normal = normal.normalize();
+        let mut mesh = self.clone();
+        let distance =
+            |mesh: &Mesh, vertex_id| (mesh.vertex_position(vertex_id) - point).dot(normal);
+        let mut field = VertexProperty::new();
+        for vertex_id in mesh.vertex_iter() {
+            field.insert(vertex_id, distance(&mesh, vertex_id));
+        }
+        let crossings: Vec<_> = mesh
+            .edge_iter()
+            .filter_map(|edge_id| {
+                let halfedge_id = edge_id.into();
+                let (v0, v1) = mesh.edge_vertices(halfedge_id);
+                let (f0, f1) = (field[&v0], field[&v1]);
+                if f0.abs() > PLANE_MARGIN && f1.abs() > PLANE_MARGIN && f0 * f1 < 0.0 {
+                    let t = f0 / (f0 - f1);
+                    let position = mesh.vertex_position(v0)
+                        + t * (mesh.vertex_position(v1) - mesh.vertex_position(v0));
+                    Some((halfedge_id, position))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        for (halfedge_id, position) in crossings {
+            let vertex_id = mesh.split_edge(halfedge_id, position);
+            field.insert(vertex_id, 0.0);
+        }
+
+        let is_in_plane = |vertex_id: VertexID| field[&vertex_id].abs() <= PLANE_MARGIN;
+        let is_face_in_plane = |mesh: &Mesh, face_id: FaceID| {
+            let (v0, v1, v2) = mesh.face_vertices(face_id);
+            is_in_plane(v0) && is_in_plane(v1) && is_in_plane(v2)
+        };
+        let is_behind = |mesh: &Mesh, face_id: FaceID| {
+            if is_face_in_plane(mesh, face_id) {
+                mesh.face_normal(face_id).dot(normal) > 0.0
+            } else {
+                let (v0, v1, v2) = mesh.face_vertices(face_id);
+                field[&v0] + field[&v1] + field[&v2] < 0.0
+            }
+        };
+        let mut behind = mesh.clone_subset(&|mesh, face_id| is_behind(mesh, face_id));
+        let mut in_front = mesh.clone_subset(&|mesh, face_id| !is_behind(mesh, face_id));
+        if cap {
+            let closed_edges: HashSet<(VertexID, VertexID)> = mesh
+                .face_iter()
+                .filter(|face_id| is_face_in_plane(&mesh, *face_id))
+                .flat_map(|face_id| {
+                    let (v0, v1, v2) = mesh.face_vertices(face_id);
+                    [(v0, v1), (v1, v2), (v2, v0)]
+                })
+                .map(|(a, b)| if a < b { (a, b) } else { (b, a) })
+                .collect();
+            behind.cap_holes_in_plane(normal, &is_in_plane, &closed_edges)?;
+            in_front.cap_holes_in_plane(normal, &is_in_plane, &closed_edges)?;
+        }
+        Ok((behind, in_front))
+    }
+
+    ///
+    /// Returns a copy of the mesh which is cut open along the given edges, ie. the vertices along the seam are duplicated such that the faces on each side of a seam edge are no longer connected.
+    /// A vertex is only duplicated if the seam edges separate the faces around it, so a seam path which does not reach the boundary of the mesh opens up from the first to the last vertex of the path.
+    /// For example, cutting a closed mesh with genus zero along a tree of edges results in a topological disk, which can be parameterized using [Mesh::parameterize_lscm] (requires the `solver` feature).
+    ///
+    /// The vertex attributes (see [Vertex attributes](#vertex-attributes)) and face groups (see [Face groups](#face-groups)) are copied to the new mesh, but vertices which are not part of a face are not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::cylinder(1.0, 2.0, 16, false);
+    /// // Cut the tube open along one of the lines from the bottom to the top
+    /// let seam: Vec<HalfEdgeID> = mesh
+    ///     .edge_iter()
+    ///     .map(|edge_id| edge_id.into())
+    ///     .filter(|halfedge_id| {
+    ///         let (p0, p1) = mesh.edge_positions(*halfedge_id);
+    ///         p0.x == 1.0 && p1.x == 1.0
+    ///     })
+    ///     .collect();
+    /// let cut = mesh.cut_along(&seam);
+    /// assert_eq!(cut.no_boundary_loops(), 1);
+    /// assert_eq!(cut.no_vertices(), mesh.no_vertices() + 2);
+    /// ```
+    ///
+    pub fn cut_along(&self, edges: &[HalfEdgeID]) -> Mesh {
+        use three_d_asset::{Indices, Positions, TriMesh};
+        let seams: HashSet<HalfEdgeID> = edges
+            .iter()
+            .flat_map(|halfedge_id| {
+                let twin_id = self.walker_from_halfedge(*halfedge_id).twin_id();
+                std::iter::once(*halfedge_id).chain(twin_id)
+            })
+            .collect();
+
+        // Each set of faces around a vertex which are connected without crossing a seam get their own copy of the vertex
+        let vertex_indices = self.vertex_index_map();
+        let mut origin = Vec::new();
+        let mut corner_indices: HashMap<(VertexID, FaceID), u32> = HashMap::new();
+        for vertex_id in self.vertex_iter() {
+            let mut parents: HashMap<FaceID, FaceID> = HashMap::new();
+            fn find(parents: &HashMap<FaceID, FaceID>, face_id: FaceID) -> FaceID {
+                let mut root = face_id;
+                while parents[&root] != root {
+                    root = parents[&root];
+                }
+                root
+            }
+            for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
+                if let Some(face_id) = self.walker_from_halfedge(halfedge_id).face_id() {
+                    parents.insert(face_id, face_id);
+                }
+            }
+            for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
+                if seams.contains(&halfedge_id) {
+                    continue;
+                }
+                let mut walker = self.walker_from_halfedge(halfedge_id);
+                if let (Some(f0), Some(f1)) = (walker.face_id(), walker.as_twin().face_id()) {
+                    let (r0, r1) = (find(&parents, f0), find(&parents, f1));
+                    parents.insert(r0, r1);
+                }
+            }
+            let mut copies = HashMap::new();
+            for face_id in parents.keys() {
+                let index = *copies.entry(find(&parents, *face_id)).or_insert_with(|| {
+                    origin.push(vertex_indices[&vertex_id]);
+                    origin.len() as u32 - 1
+                });
+                corner_indices.insert((vertex_id, *face_id), index);
+            }
+        }
+
+        let mut indices = Vec::with_capacity(3 * self.no_faces());
+        for face_id in self.face_iter() {
+            for halfedge_id in self.face_halfedge_iter(face_id) {
+                let vertex_id = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                indices.push(corner_indices[&(vertex_id, face_id)]);
+            }
+        }
+        let exported = self.export();
+        fn duplicate<T: Copy>(
+            values: Option<Vec<T>>,
+            is_stored: bool,
+            origin: &[usize],
+        ) -> Option<Vec<T>> {
+            values
+                .filter(|_| is_stored)
+                .map(|values| origin.iter().map(|i| values[*i]).collect())
+        }
+        let positions = exported.positions.to_f64();
+        let mut mesh = Mesh::new(&TriMesh {
+            indices: Indices::U32(indices),
+            positions: Positions::F64(origin.iter().map(|i| positions[*i]).collect()),
+            normals: duplicate(
+                exported.normals,
+                self.vertex_iter()
+                    .any(|vertex_id| self.stored_vertex_normal(vertex_id).is_some()),
+                &origin,
+            ),
+            tangents: duplicate(exported.tangents, true, &origin),
+            uvs: duplicate(exported.uvs, true, &origin),
+            colors: duplicate(exported.colors, true, &origin),
+        });
+        let groups: Vec<Option<String>> = self
+            .face_iter()
+            .map(|face_id| self.face_group(face_id).map(|name| name.to_string()))
+            .collect();
+        for (face_id, group) in mesh.face_iter().collect::<Vec<_>>().into_iter().zip(groups) {
+            if let Some(name) = group {
+                mesh.assign_group(face_id, name);
+            }
+        }
+        mesh
+    }
+
+    ///
+    /// Returns the curves where the mesh intersects the plane given by a point in the plane and the normal of the plane, without modifying the mesh.
+    /// The curves are closed if the mesh is closed and otherwise some of them might end at the boundary of the mesh.
+    /// Faces lying entirely in the plane are ignored and no curves are returned if the normal is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// let section = mesh.plane_section(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0));
+    /// assert_eq!(section.len(), 1);
+    /// assert!(section[0].is_closed);
+    /// assert!(section[0].points.iter().all(|p| p.y.abs() < 0.000001));
+    /// ```
+    ///
+    pub fn plane_section(&self, point: Vec3, normal: Vec3) -> Vec<Polyline3> {
+        if normal.magnitude2() == 0.0 {
+            return Vec::new();
+        }
+        let normal = normal.normalize();
+        let distance = |vertex_id| {
+            let d = (self.vertex_position(vertex_id) - point).dot(normal);
+            if d.abs() <= PLANE_MARGIN {
+                0.0
+            } else {
+                d
+            }
+        };
+
+        // Find the line segments where each face crosses the plane
+        let mut positions = HashMap::new();
+        let mut segments = HashSet::new();
+        for face_id in self.face_iter() {
+            let mut section_points = Vec::new();
+            for halfedge_id in self.face_halfedge_iter(face_id) {
+                let (v0, v1) = self.edge_vertices(halfedge_id);
+                let (d0, d1) = (distance(v0), distance(v1));
+                if d0 == 0.0 {
+                    positions.insert(SectionPoint::Vertex(v0), self.vertex_position(v0));
+                    section_points.push(SectionPoint::Vertex(v0));
+                } else if d0 * d1 < 0.0 {
+                    let p = self.vertex_position(v0)
+                        + d0 / (d0 - d1) * (self.vertex_position(v1) - self.vertex_position(v0));
+                    let section_point = SectionPoint::Edge(self.edge_id(halfedge_id));
+                    positions.insert(section_point, p);
+                    section_points.push(section_point);
+                }
+            }
+            if let [a, b] = section_points[..] {
+                segments.insert(if a.cmp_key() < b.cmp_key() {
+                    (a, b)
+                } else {
+                    (b, a)
+                });
+            }
+        }
+
+        // Connect the line segments into polylines
+        let mut neighbours: HashMap<SectionPoint, Vec<SectionPoint>> = HashMap::new();
+        for (a, b) in segments {
+            neighbours.entry(a).or_default().push(b);
+            neighbours.entry(b).or_default().push(a);
+        }
+        let mut starts: Vec<SectionPoint> = neighbours.keys().copied().collect();
+        starts.sort_by_key(|p| (neighbours[p].len() != 1, p.cmp_key()));
+        let mut visited = HashSet::new();
+        let mut polylines = Vec::new();
+        for start in starts {
+            if visited.contains(&start) {
+                continue;
+            }
+            let mut points = vec![positions[&start]];
+            visited.insert(start);
+            let mut current = start;
+            while let Some(next) = neighbours[&current]
+                .iter()
+                .find(|p| !visited.contains(*p))
+                .copied()
+            {
+                visited.insert(next);
+                points.push(positions[&next]);
+                current = next;
+            }
+            let is_closed = points.len() > 2 && neighbours[&current].contains(&start);
+            polylines.push(Polyline3 { points, is_closed });
+        }
+        polylines
+    }
+
+    ///
+    /// Fills the holes where all the vertices on the boundary lie in the plane with the given unit normal,
+    /// except the holes with an edge among the given edges, which are already closed by other faces.
+    /// A boundary loop inside another boundary loop with the opposite orientation is left open as a hole in the cap of the outer loop.
+    ///
+    fn cap_holes_in_plane(
+        &mut self,
+        normal: Vec3,
+        is_in_plane: &dyn Fn(VertexID) -> bool,
+        closed_edges: &HashSet<(VertexID, VertexID)>,
+    ) -> Result<(), Error> {
+        let u = normal
+            .cross(if normal.x.abs() < 0.9 {
+                Vec3::unit_x()
+            } else {
+                Vec3::unit_y()
+            })
+            .normalize();
+        let v = normal.cross(u);
+
+        let mut visited = HashSet::new();
+        let mut loops = Vec::new();
+        for halfedge_id in self.halfedge_iter() {
+            if visited.contains(&halfedge_id)
+                || self.walker_from_halfedge(halfedge_id).face_id().is_some()
+            {
+                continue;
+            }
+            let boundary_loop: Vec<VertexID> = self
+                .boundary_loop_iter(halfedge_id)
+                .map(|halfedge_id| {
+                    visited.insert(halfedge_id);
+                    self.walker_from_halfedge(halfedge_id).vertex_id().unwrap()
+                })
+                .collect();
+            let is_closed = (0..boundary_loop.len()).any(|i| {
+                let (a, b) = (
+                    boundary_loop[i],
+                    boundary_loop[(i + 1) % boundary_loop.len()],
+                );
+                closed_edges.contains(&if a < b { (a, b) } else { (b, a) })
+            });
+            if !is_closed
+                && boundary_loop
+                    .iter()
+                    .all(|vertex_id| is_in_plane(*vertex_id))
+            {
+                let points: Vec<(f64, f64)> = boundary_loop
+                    .iter()
+                    .map(|vertex_id| {
+                        let p = self.vertex_position(*vertex_id);
+                        (p.dot(u), p.dot(v))
+                    })
+                    .collect();
+                let area = signed_area(&points);
+                loops.push((boundary_loop, points, area));
+            }
+        }
+
+        // The loops with the same orientation as the largest loop are the outlines of the caps and the others are holes in the caps.
+        let Some(largest) = loops
+            .iter()
+            .max_by(|a, b| a.2.abs().total_cmp(&b.2.abs()))
+            .map(|l| l.2)
+        else {
+            return Ok(());
+        };
+        let (outlines, holes): (Vec<_>, Vec<_>) =
+            loops.into_iter().partition(|l| l.2 * largest > 0.0);
+        let mut holes_in_outline = vec![Vec::new(); outlines.len()];
+        for hole in holes {
+            // A hole belongs to the smallest outline containing it
+            let outline = outlines
+                .iter()
+                .enumerate()
+                .filter(|(_, outline)| is_inside_polygon(&outline.1, hole.1[0]))
+                .min_by(|(_, a), (_, b)| a.2.abs().total_cmp(&b.2.abs()))
+                .map(|(i, _)| i)
+                .ok_or_else(|| {
+                    Error::TriangulationFailed(
+                        "a hole in the cross section is not inside an outline".to_string(),
+                    )
+                })?;
+            holes_in_outline[outline].push(hole);
+        }
+
+        for ((boundary_loop, points, _), holes) in outlines.into_iter().zip(holes_in_outline) {
+            let hole_points: Vec<Vec<(f64, f64)>> =
+                holes.iter().map(|hole| hole.1.clone()).collect();
+            let vertices: Vec<VertexID> = boundary_loop
+                .into_iter()
+                .chain(holes.into_iter().flat_map(|hole| hole.0))
+                .collect();
+            let faces: Vec<_> = triangulate_polygon(&points, &hole_points)?
+                .into_iter()
+                .map(|[a, b, c]| (vertices[a], vertices[b], vertices[c]))
+                .collect();
+            self.add_faces(&faces)?;
+        }
+        Ok(())
+    }
+}
+
+impl SectionPoint {
+    /// Returns a key which gives a deterministic order of the points.
+    fn cmp_key(&self) -> (u8, u32) {
+        match self {
+            SectionPoint::Vertex(vertex_id) => (0, **vertex_id),
+            SectionPoint::Edge(edge_id) => (1, **edge_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plane_section() {
+        let mesh = crate::test_utility::cube();
+
+        // Through four of the vertices and the diagonals of two of the faces
+        let section = mesh.plane_section(vec3(0.0, 0.0, 0.0), vec3(1.0, -1.0, 0.0));
+        assert_eq!(section.len(), 1);
+        assert!(section[0].is_closed);
+        assert_eq!(section[0].points.len(), 5);
+
+        let section = mesh.plane_section(vec3(0.0, 0.0, 0.5), vec3(0.2, 0.1, 1.0));
+        assert_eq!(section.len(), 1);
+        assert!(section[0].is_closed);
+        let length: f64 = (0..section[0].points.len())
+            .map(|i| {
+                section[0].points[i].distance(section[0].points[(i + 1) % section[0].points.len()])
+            })
+            .sum();
+        assert!(length > 8.0);
+
+        let mesh = crate::test_utility::square();
+        let section = mesh.plane_section(vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0));
+        assert_eq!(section.len(), 1);
+        assert!(!section[0].is_closed);
+        assert_eq!(section[0].points.len(), 3);
+
+        // The margin for a vertex to lie in the plane does not depend on the length of the normal
+        let mesh = crate::test_utility::cube();
+        for scale in [0.000000001, 1.0, 1000000000.0] {
+            let section =
+                mesh.plane_section(vec3(0.0, 1.0 + 0.00000001, 0.0), vec3(0.0, scale, 0.0));
+            assert_eq!(section.len(), 1);
+            assert!(section[0].is_closed);
+        }
+        assert!(mesh
+            .plane_section(vec3(0.0, 0.0, 0.0), vec3(0.0, 0.0, 0.0))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_cut_along() {
+        // An open path of three edges on the cube opens up like a slit
+        let mesh = crate::test_utility::cube();
+        let vertices: Vec<VertexID> = mesh.vertex_iter().collect();
+        let seam: Vec<HalfEdgeID> = [(0, 1), (1, 5), (5, 6)]
+            .iter()
+            .map(|(a, b)| mesh.connecting_edge(vertices[*a], vertices[*b]).unwrap())
+            .collect();
+        let cut = mesh.cut_along(&seam);
+        cut.is_valid().unwrap();
+        assert_eq!(cut.no_vertices(), 10);
+        assert_eq!(cut.no_faces(), mesh.no_faces());
+        assert_eq!(cut.no_boundary_loops(), 1);
+        assert_eq!(cut.euler_characteristic(), 1);
+
+        // A closed loop separates the mesh into two parts
+        let mut mesh = Mesh::cylinder(1.0, 2.0, 8, true);
+        let face_id = mesh.face_iter().next().unwrap();
+        mesh.assign_group(face_id, "first");
+        let rim: Vec<HalfEdgeID> = mesh
+            .edge_iter()
+            .map(|edge_id| edge_id.into())
+            .filter(|halfedge_id| {
+                let (p0, p1) = mesh.edge_positions(*halfedge_id);
+                p0.y > 1.999
+                    && p1.y > 1.999
+                    && mesh.edge_dihedral_angle(*halfedge_id).unwrap()
+                        > Radians::from(degrees(45.0))
+            })
+            .collect();
+        assert_eq!(rim.len(), 8);
+        let cut = mesh.cut_along(&rim);
+        cut.is_valid().unwrap();
+        assert_eq!(cut.connected_components().len(), 2);
+        assert_eq!(cut.no_boundary_loops(), 2);
+        assert_eq!(cut.no_vertices(), mesh.no_vertices() + 8);
+        assert_eq!(cut.group_names(), vec!["first".to_string()]);
+    }
+
+    #[test]
+    fn test_cut_by_plane() {
+        let mesh = crate::test_utility::cube();
+        let (behind, in_front) = mesh
+            .cut_by_plane(vec3(0.0, 0.0, 0.5), vec3(0.2, 0.1, 1.0), true)
+            .unwrap();
+        behind.is_valid().unwrap();
+        in_front.is_valid().unwrap();
+        assert!(behind.is_closed());
+        assert!(in_front.is_closed());
+        let volume = behind.volume().unwrap() + in_front.volume().unwrap();
+        assert!((volume - 8.0).abs() < 0.000001);
+
+        let (behind, in_front) = mesh
+            .cut_by_plane(vec3(0.0, 0.0, 0.5), vec3(0.2, 0.1, 1.0), false)
+            .unwrap();
+        assert_eq!(behind.no_boundary_loops(), 1);
+        assert_eq!(in_front.no_boundary_loops(), 1);
+        for halfedge_id in behind.halfedge_iter() {
+            if behind.is_edge_on_boundary(halfedge_id) {
+                let (p0, p1) = behind.edge_positions(halfedge_id);
+                assert!((p0 - vec3(0.0, 0.0, 0.5)).dot(vec3(0.2, 0.1, 1.0)).abs() < 0.000001);
+                assert!((p1 - vec3(0.0, 0.0, 0.5)).dot(vec3(0.2, 0.1, 1.0)).abs() < 0.000001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cut_by_plane_with_hole() {
+        // A hollow cube, ie. a cube with an inverted smaller cube inside
+        let mut mesh = crate::test_utility::cube();
+        let mut inner = crate::test_utility::cube();
+        inner.scale(0.5);
+        inner.flip_orientation();
+        mesh.append(&inner);
+
+        let (behind, in_front) = mesh
+            .cut_by_plane(vec3(0.0, 0.0, 0.1), vec3(0.1, 0.2, 1.0), true)
+            .unwrap();
+        behind.is_valid().unwrap();
+        in_front.is_valid().unwrap();
+        assert!(behind.is_closed());
+        assert!(in_front.is_closed());
+        assert_eq!(behind.connected_components().len(), 1);
+        assert_eq!(in_front.connected_components().len(), 1);
+        let volume = behind.volume().unwrap() + in_front.volume().unwrap();
+        assert!((volume - 7.0).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_cut_by_plane_with_faces_in_plane() {
+        // The top cap closes the part behind the plane and the bottom cap the part in front of the plane
+        let mesh = Mesh::cylinder(1.0, 2.0, 16, true);
+        let (behind, in_front) = mesh
+            .cut_by_plane(vec3(0.0, 2.0, 0.0), vec3(0.0, 1.0, 0.0), true)
+            .unwrap();
+        assert_eq!(behind.no_faces(), mesh.no_faces());
+        assert!(behind.is_closed());
+        assert_eq!(in_front.no_faces(), 0);
+        let (behind, in_front) = mesh
+            .cut_by_plane(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), true)
+            .unwrap();
+        assert_eq!(behind.no_faces(), 0);
+        assert_eq!(in_front.no_faces(), mesh.no_faces());
+        assert!(in_front.is_closed());
+
+        // A hole closed by the faces in the plane is not capped
+        let mesh = crate::test_utility::square();
+        let (behind, in_front) = mesh
+            .cut_by_plane(vec3(0.0, 0.0, 0.0), vec3(0.0, 0.0, 1.0), true)
+            .unwrap();
+        assert_eq!(behind.no_faces(), 2);
+        assert_eq!(behind.no_boundary_loops(), 1);
+        assert_eq!(in_front.no_faces(), 0);
+    }
+
+    #[test]
+    fn test_cut_by_plane_with_unnormalized_normal() {
+        let mesh = crate::test_utility::cube();
+        for scale in [0.000000001, 1.0, 1000000000.0] {
+            let (behind, in_front) = mesh
+                .cut_by_plane(vec3(0.0, 0.5, 0.0), vec3(0.0, scale, 0.0), true)
+                .unwrap();
+            behind.is_valid().unwrap();
+            in_front.is_valid().unwrap();
+            assert!((behind.volume().unwrap() - 6.0).abs() < 0.000001);
+            assert!((in_front.volume().unwrap() - 2.0).abs() < 0.000001);
+        }
+        assert!(mesh
+            .cut_by_plane(vec3(0.0, 0.5, 0.0), vec3(0.0, 0.0, 0.0), true)
+            .is_err());
+    }
+}
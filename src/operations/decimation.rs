@@ -0,0 +1,504 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// The weight of the planes perpendicular to the boundary relative to the planes of the faces, which keeps the boundary in place unless it is locked.
+const BOUNDARY_WEIGHT: f64 = 10.0;
+
+///
+/// Constraints used by [Mesh::decimate_with_constraints] to preserve the features of the mesh, for example the silhouette of a CAD model.
+/// The default is no constraints.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DecimationConstraints {
+    /// Whether the vertices on the boundary are kept at their position, such that the boundary loops are unchanged.
+    pub lock_boundary: bool,
+    /// If specified, the vertices of the edges with a dihedral angle (see [Mesh::edge_dihedral_angle]) larger than this angle are kept at their position, such that the sharp features are unchanged.
+    pub feature_angle: Option<Radians>,
+    /// If specified, an edge is not collapsed if that changes the normal of any of the remaining faces by more than this angle.
+    pub max_normal_deviation: Option<Radians>,
+}
+
+/// # Decimation
+impl Mesh {
+    ///
+    /// Reduces the number of faces to the given target by collapsing edges, see [Mesh::decimate_with_constraints].
+    /// Returns the number of collapsed edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(3);
+    /// mesh.decimate(320);
+    /// assert_eq!(mesh.no_faces(), 320);
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn decimate(&mut self, target_no_faces: usize) -> usize {
+        self.decimate_with_constraints(target_no_faces, &DecimationConstraints::default())
+    }
+
+    ///
+    /// Reduces the number of faces to the given target by collapsing edges, where the edge which changes the shape of the mesh the least is collapsed first
+    /// as measured by the quadric error metrics described by Michael Garland and Paul S. Heckbert in "Surface Simplification Using Quadric Error Metrics".
+    /// The given constraints are used to preserve the boundary and the sharp features of the mesh, see [DecimationConstraints].
+    /// The vertices of the [creases](Mesh#creases) are always kept at their position.
+    /// Returns the number of collapsed edges.
+    ///
+    /// An edge is only collapsed if the mesh stays manifold and no face is flipped, so the decimation stops before reaching the target
+    /// if no more edges can be collapsed, for example because of the constraints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::grid(vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), 10, 10);
+    /// let no_boundary_vertices = mesh.boundary_vertices().len();
+    /// mesh.decimate_with_constraints(
+    ///     10,
+    ///     &DecimationConstraints {
+    ///         lock_boundary: true,
+    ///         ..Default::default()
+    ///     },
+    /// );
+    /// assert!(mesh.no_faces() < 200);
+    /// assert_eq!(mesh.boundary_vertices().len(), no_boundary_vertices);
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn decimate_with_constraints(
+        &mut self,
+        target_no_faces: usize,
+        constraints: &DecimationConstraints,
+    ) -> usize {
+        let mut fixed = HashSet::new();
+        for halfedge_id in self.edge_iter().map(HalfEdgeID::from) {
+            let is_feature = self.is_crease(halfedge_id)
+                || constraints.feature_angle.is_some_and(|feature_angle| {
+                    self.edge_dihedral_angle(halfedge_id)
+                        .is_some_and(|angle| angle > feature_angle)
+                })
+                || constraints.lock_boundary && self.is_edge_on_boundary(halfedge_id);
+            if is_feature {
+                let (v0, v1) = self.edge_vertices(halfedge_id);
+                fixed.insert(v0);
+                fixed.insert(v1);
+            }
+        }
+        let mut quadrics = self.quadrics();
+
+        let mut heap = BinaryHeap::new();
+        let mut stamps = HashMap::new();
+        for halfedge_id in self.edge_iter().map(HalfEdgeID::from) {
+            let (v0, v1) = self.edge_vertices(halfedge_id);
+            if let Some(candidate) = Candidate::new(self, &quadrics, &fixed, v0, v1, stamps.len()) {
+                stamps.insert(candidate.key(), candidate.stamp);
+                heap.push(candidate);
+            }
+        }
+
+        let mut no_collapses = 0;
+        let mut next_stamp = stamps.len();
+        while self.no_faces() > target_no_faces {
+            let Some(candidate) = heap.pop() else {
+                break;
+            };
+            // Skip the candidates computed before one of the vertices was changed
+            if stamps.get(&candidate.key()) != Some(&candidate.stamp) {
+                continue;
+            }
+            stamps.remove(&candidate.key());
+            let (surviving_vertex_id, dying_vertex_id) =
+                (candidate.surviving_vertex_id, candidate.dying_vertex_id);
+            let Some(halfedge_id) = self.connecting_edge(dying_vertex_id, surviving_vertex_id)
+            else {
+                continue;
+            };
+            if !self.collapse_will_keep_manifold(halfedge_id)
+                || self.collapse_will_leave_too_few_neighbours(halfedge_id)
+                || self.collapse_will_fold_faces(
+                    halfedge_id,
+                    candidate.position,
+                    constraints.max_normal_deviation,
+                )
+            {
+                continue;
+            }
+
+            for vertex_id in self.vertex_vertex_iter(dying_vertex_id).collect::<Vec<_>>() {
+                stamps.remove(&ordered(dying_vertex_id, vertex_id));
+            }
+            self.collapse_edge(halfedge_id);
+            self.set_vertex_position(surviving_vertex_id, candidate.position);
+            let quadric = quadrics[&surviving_vertex_id] + quadrics[&dying_vertex_id];
+            quadrics.insert(surviving_vertex_id, quadric);
+            quadrics.remove(&dying_vertex_id);
+            no_collapses += 1;
+
+            for vertex_id in self
+                .vertex_vertex_iter(surviving_vertex_id)
+                .collect::<Vec<_>>()
+            {
+                stamps.remove(&ordered(surviving_vertex_id, vertex_id));
+                if let Some(candidate) = Candidate::new(
+                    self,
+                    &quadrics,
+                    &fixed,
+                    surviving_vertex_id,
+                    vertex_id,
+                    next_stamp,
+                ) {
+                    next_stamp += 1;
+                    stamps.insert(candidate.key(), candidate.stamp);
+                    heap.push(candidate);
+                }
+            }
+        }
+        no_collapses
+    }
+
+    /// Returns the quadric of each vertex, ie. the sum of the squared distances to the planes of the adjacent faces weighted by their area
+    /// and to the planes perpendicular to the adjacent boundary edges.
+    fn quadrics(&self) -> HashMap<VertexID, Quadric> {
+        let mut quadrics: HashMap<VertexID, Quadric> = self
+            .vertex_iter()
+            .map(|vertex_id| (vertex_id, Quadric::default()))
+            .collect();
+        for face_id in self.face_iter() {
+            let area = self.face_area(face_id);
+            if area > 0.0 {
+                let (v0, v1, v2) = self.face_vertices(face_id);
+                let quadric =
+                    Quadric::plane(self.face_normal(face_id), self.vertex_position(v0), area);
+                for vertex_id in [v0, v1, v2] {
+                    *quadrics.get_mut(&vertex_id).unwrap() += quadric;
+                }
+            }
+        }
+        for halfedge_id in self.halfedge_iter() {
+            let mut walker = self.walker_from_halfedge(halfedge_id);
+            if walker.face_id().is_some() {
+                continue;
+            }
+            let Some(face_id) = walker.as_twin().face_id() else {
+                continue;
+            };
+            let (v0, v1) = self.edge_vertices(halfedge_id);
+            let (p0, p1) = (self.vertex_position(v0), self.vertex_position(v1));
+            let normal = (p1 - p0).cross(self.face_normal(face_id));
+            if normal.magnitude2() > 0.0 {
+                let quadric = Quadric::plane(
+                    normal.normalize(),
+                    p0,
+                    BOUNDARY_WEIGHT * (p1 - p0).magnitude2(),
+                );
+                for vertex_id in [v0, v1] {
+                    *quadrics.get_mut(&vertex_id).unwrap() += quadric;
+                }
+            }
+        }
+        quadrics
+    }
+
+    // The merged vertex must be connected to at least three other vertices, otherwise the collapse results in two faces on top of each other, for example when collapsing an edge of a tetrahedron
+    fn collapse_will_leave_too_few_neighbours(&self, halfedge_id: HalfEdgeID) -> bool {
+        let (v0, v1) = self.edge_vertices(halfedge_id);
+        let neighbours: HashSet<VertexID> = self
+            .vertex_vertex_iter(v0)
+            .chain(self.vertex_vertex_iter(v1))
+            .filter(|vertex_id| *vertex_id != v0 && *vertex_id != v1)
+            .collect();
+        neighbours.len() < 3
+    }
+
+    // Returns whether moving the end points of the edge to the given position flips one of the faces which are not removed by the collapse
+    // or rotates its normal more than the given angle
+    fn collapse_will_fold_faces(
+        &self,
+        halfedge_id: HalfEdgeID,
+        position: Vec3,
+        max_normal_deviation: Option<Radians>,
+    ) -> bool {
+        let (v0, v1) = self.edge_vertices(halfedge_id);
+        let moved_position = |vertex_id: VertexID| {
+            if vertex_id == v0 || vertex_id == v1 {
+                position
+            } else {
+                self.vertex_position(vertex_id)
+            }
+        };
+        for vertex_id in [v0, v1] {
+            for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
+                let Some(face_id) = self.walker_from_halfedge(halfedge_id).face_id() else {
+                    continue;
+                };
+                let (a, b, c) = self.face_vertices(face_id);
+                if [a, b, c].contains(&v0) && [a, b, c].contains(&v1) {
+                    continue;
+                }
+                let (p0, p1, p2) = (moved_position(a), moved_position(b), moved_position(c));
+                let direction = (p1 - p0).cross(p2 - p0);
+                if direction.magnitude2() == 0.0 {
+                    return true;
+                }
+                let normal = self.face_normal(face_id);
+                let new_normal = direction.normalize();
+                if normal.dot(new_normal) <= 0.0
+                    || max_normal_deviation
+                        .is_some_and(|max_angle| normal.angle(new_normal) > max_angle)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+///
+/// A collapse of the dying vertex into the surviving vertex which is moved to the given position, ordered by the cost such that the cheapest collapse is popped first from a [BinaryHeap].
+///
+struct Candidate {
+    cost: f64,
+    position: Vec3,
+    surviving_vertex_id: VertexID,
+    dying_vertex_id: VertexID,
+    stamp: usize,
+}
+
+impl Candidate {
+    fn new(
+        mesh: &Mesh,
+        quadrics: &HashMap<VertexID, Quadric>,
+        fixed: &HashSet<VertexID>,
+        v0: VertexID,
+        v1: VertexID,
+        stamp: usize,
+    ) -> Option<Self> {
+        let quadric = quadrics[&v0] + quadrics[&v1];
+        let (p0, p1) = (mesh.vertex_position(v0), mesh.vertex_position(v1));
+        let (surviving_vertex_id, dying_vertex_id, position) =
+            match (fixed.contains(&v0), fixed.contains(&v1)) {
+                (true, true) => return None,
+                (true, false) => (v0, v1, p0),
+                (false, true) => (v1, v0, p1),
+                (false, false) => {
+                    // Use the optimal position if it is well defined, otherwise the best of the end points and the midpoint
+                    let position = quadric.minimizer().unwrap_or_else(|| {
+                        [p0, p1, 0.5 * (p0 + p1)]
+                            .into_iter()
+                            .min_by(|a, b| quadric.error(*a).total_cmp(&quadric.error(*b)))
+                            .unwrap()
+                    });
+                    (v0, v1, position)
+                }
+            };
+        Some(Self {
+            cost: quadric.error(position).max(0.0),
+            position,
+            surviving_vertex_id,
+            dying_vertex_id,
+            stamp,
+        })
+    }
+
+    fn key(&self) -> (VertexID, VertexID) {
+        ordered(self.surviving_vertex_id, self.dying_vertex_id)
+    }
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.stamp.cmp(&self.stamp))
+    }
+}
+
+fn ordered(v0: VertexID, v1: VertexID) -> (VertexID, VertexID) {
+    (v0.min(v1), v0.max(v1))
+}
+
+///
+/// The sum of squared distances to a set of planes given as `x^T a x + 2 b^T x + c` for a point `x`.
+///
+#[derive(Clone, Copy, Debug)]
+struct Quadric {
+    a: Mat3,
+    b: Vec3,
+    c: f64,
+}
+
+impl Quadric {
+    /// The squared distance to the plane through the point with the given unit normal multiplied by the weight.
+    fn plane(normal: Vec3, point: Vec3, weight: f64) -> Self {
+        let d = -normal.dot(point);
+        Self {
+            a: Mat3::from_cols(normal * normal.x, normal * normal.y, normal * normal.z) * weight,
+            b: normal * (d * weight),
+            c: d * d * weight,
+        }
+    }
+
+    fn error(&self, point: Vec3) -> f64 {
+        point.dot(self.a * point) + 2.0 * self.b.dot(point) + self.c
+    }
+
+    /// Returns the point with the smallest error or `None` if the point is not well defined, for example if all planes are parallel.
+    fn minimizer(&self) -> Option<Vec3> {
+        let scale = self.a.trace();
+        if self.a.determinant().abs() <= 1e-10 * scale * scale * scale {
+            return None;
+        }
+        self.a.invert().map(|inverse| -(inverse * self.b))
+    }
+}
+
+impl Default for Quadric {
+    fn default() -> Self {
+        Self {
+            a: Mat3::zero(),
+            b: Vec3::zero(),
+            c: 0.0,
+        }
+    }
+}
+
+impl std::ops::Add for Quadric {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self {
+            a: self.a + other.a,
+            b: self.b + other.b,
+            c: self.c + other.c,
+        }
+    }
+}
+
+impl std::ops::AddAssign for Quadric {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimate_sphere() {
+        let mut mesh = Mesh::icosphere(3);
+        let no_collapses = mesh.decimate(100);
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        assert!(mesh.no_faces() <= 100);
+        assert_eq!(mesh.no_faces(), 1280 - 2 * no_collapses);
+        for vertex_id in mesh.vertex_iter() {
+            assert!((mesh.vertex_position(vertex_id).magnitude() - 1.0).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn test_decimate_preserves_features() {
+        let mut mesh: Mesh = three_d_asset::TriMesh::cube().into();
+        mesh.merge_overlapping_primitives();
+        mesh.split_long_edges(0.3);
+        let sharp_vertices: Vec<(VertexID, Vec3)> = mesh
+            .vertex_iter()
+            .filter(|vertex_id| {
+                let p = mesh.vertex_position(*vertex_id);
+                [p.x, p.y, p.z].iter().filter(|c| c.abs() == 1.0).count() >= 2
+            })
+            .map(|vertex_id| (vertex_id, mesh.vertex_position(vertex_id)))
+            .collect();
+        let no_faces = mesh.no_faces();
+
+        mesh.decimate_with_constraints(
+            12,
+            &DecimationConstraints {
+                feature_angle: Some(degrees(30.0).into()),
+                ..Default::default()
+            },
+        );
+
+        mesh.is_valid().unwrap();
+        assert!(mesh.no_faces() < no_faces);
+        for (vertex_id, position) in sharp_vertices {
+            assert_eq!(mesh.vertex_position(vertex_id), position);
+        }
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            assert!((p.x.abs().max(p.y.abs()).max(p.z.abs()) - 1.0).abs() < 0.000001);
+        }
+    }
+
+    #[test]
+    fn test_decimate_with_locked_boundary() {
+        let grid = Mesh::grid(
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            10,
+            10,
+        );
+        let boundary: Vec<(VertexID, Vec3)> = grid
+            .boundary_vertices()
+            .iter()
+            .map(|vertex_id| (vertex_id, grid.vertex_position(vertex_id)))
+            .collect();
+
+        let mut mesh = grid.clone();
+        mesh.decimate_with_constraints(
+            10,
+            &DecimationConstraints {
+                lock_boundary: true,
+                ..Default::default()
+            },
+        );
+        mesh.is_valid().unwrap();
+        assert!(mesh.no_faces() < grid.no_faces());
+        for (vertex_id, position) in boundary.iter() {
+            assert_eq!(mesh.vertex_position(*vertex_id), *position);
+        }
+
+        // The straight boundary is simplified when it is not locked
+        let mut mesh = grid.clone();
+        mesh.decimate(10);
+        mesh.is_valid().unwrap();
+        assert!(mesh.boundary_vertices().len() < boundary.len());
+    }
+
+    #[test]
+    fn test_decimate_with_max_normal_deviation() {
+        let mut mesh = Mesh::icosphere(3);
+        mesh.decimate(20);
+        let mut constrained = Mesh::icosphere(3);
+        constrained.decimate_with_constraints(
+            20,
+            &DecimationConstraints {
+                max_normal_deviation: Some(degrees(5.0).into()),
+                ..Default::default()
+            },
+        );
+        constrained.is_valid().unwrap();
+        assert!(constrained.no_faces() > mesh.no_faces());
+    }
+}
@@ -0,0 +1,205 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+///
+/// The shape of the falloff of the weights of a soft selection, see [Mesh::soft_selection].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Falloff {
+    /// The weight decreases linearly with the distance.
+    Linear,
+    /// The weight decreases smoothly, ie. with zero slope at the center and at the radius.
+    Smooth,
+    /// The weight follows a sphere, ie. it is flat at the center and falls off steeply at the radius.
+    Sphere,
+    /// The weight decreases quadratically, ie. steeply near the center and flat at the radius.
+    Sharp,
+}
+
+impl Falloff {
+    /// Returns the weight at the given distance relative to the radius, ie. between zero and one.
+    fn weight(&self, t: f64) -> f64 {
+        match self {
+            Falloff::Linear => 1.0 - t,
+            Falloff::Smooth => 1.0 - t * t * (3.0 - 2.0 * t),
+            Falloff::Sphere => (1.0 - t * t).sqrt(),
+            Falloff::Sharp => (1.0 - t) * (1.0 - t),
+        }
+    }
+}
+
+/// # Deform
+impl Mesh {
+    ///
+    /// Moves each vertex along its normal (see [Mesh::vertex_normal]) by the value of the field at that vertex, for example to apply a height map or a displacement texture.
+    /// The normals are computed before any vertex is moved and vertices without a value in the field are not moved.
+    ///
+    /// If `clamp` is true, the displacement of each vertex is limited to half the length of the shortest edge connected to the vertex,
+    /// which avoids that large displacements fold the surface locally and thereby create self-intersections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let field: VertexProperty<f64> = mesh.vertex_iter().map(|v| (v, 0.5)).collect();
+    /// mesh.displace(&field, false);
+    /// for vertex_id in mesh.vertex_iter() {
+    ///     assert!((mesh.vertex_position(vertex_id).magnitude() - 1.5).abs() < 0.01);
+    /// }
+    /// ```
+    ///
+    pub fn displace(&mut self, field: &VertexProperty<f64>, clamp: bool) {
+        let displacements: Vec<(VertexID, Vec3)> = self
+            .vertex_iter()
+            .filter_map(|vertex_id| {
+                let mut value = *field.get(&vertex_id)?;
+                if clamp {
+                    let max = 0.5
+                        * self
+                            .vertex_halfedge_iter(vertex_id)
+                            .map(|halfedge_id| self.edge_length(halfedge_id))
+                            .fold(f64::INFINITY, f64::min);
+                    value = value.clamp(-max, max);
+                }
+                Some((vertex_id, value * self.vertex_normal(vertex_id)))
+            })
+            .collect();
+        for (vertex_id, displacement) in displacements {
+            self.move_vertex_by(vertex_id, displacement);
+        }
+    }
+
+    ///
+    /// Returns a weight for each vertex within the given radius of the center vertex, which is one at the center and decreases to zero at the radius following the given falloff.
+    /// The weights can for example be used to move a region of the mesh smoothly or be given to [Mesh::displace].
+    /// Vertices further away than the radius are not contained in the result.
+    ///
+    /// If `geodesic` is true, the distance is measured along the edges of the mesh (see [Mesh::geodesic_distances]), so that for example the other side of a thin part is not selected,
+    /// otherwise the distance is measured in a straight line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::grid(vec3(0.0, 0.0, 0.0), vec3(4.0, 0.0, 0.0), vec3(0.0, 4.0, 0.0), 16, 16);
+    /// let center = mesh.vertex_iter().find(|v| mesh.vertex_position(*v).magnitude() < 0.001).unwrap();
+    /// let weights = mesh.soft_selection(center, 1.0, Falloff::Smooth, false);
+    /// assert_eq!(weights[&center], 1.0);
+    ///
+    /// // Pull up a smooth bump
+    /// for (vertex_id, weight) in weights {
+    ///     mesh.move_vertex_by(vertex_id, vec3(0.0, 0.0, 0.5 * weight));
+    /// }
+    /// ```
+    ///
+    pub fn soft_selection(
+        &self,
+        center_vertex: VertexID,
+        radius: f64,
+        falloff: Falloff,
+        geodesic: bool,
+    ) -> VertexProperty<f64> {
+        let distances = if geodesic {
+            self.geodesic_distances(center_vertex)
+        } else {
+            let center = self.vertex_position(center_vertex);
+            self.vertex_iter()
+                .map(|vertex_id| (vertex_id, self.vertex_position(vertex_id).distance(center)))
+                .collect()
+        };
+        distances
+            .into_iter()
+            .filter(|(_, distance)| *distance < radius)
+            .map(|(vertex_id, distance)| (vertex_id, falloff.weight(distance / radius)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_displace() {
+        let mut mesh = crate::test_utility::subdivided_triangle();
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        let normal = mesh.vertex_normal(vertex_id);
+        let position = mesh.vertex_position(vertex_id);
+        let min_edge_length = mesh
+            .vertex_halfedge_iter(vertex_id)
+            .map(|halfedge_id| mesh.edge_length(halfedge_id))
+            .fold(f64::INFINITY, f64::min);
+        let mut field = VertexProperty::new();
+        field.insert(vertex_id, -10.0);
+
+        let mut clamped = mesh.clone();
+        clamped.displace(&field, true);
+        mesh.displace(&field, false);
+
+        assert!(
+            (mesh.vertex_position(vertex_id) - (position - 10.0 * normal)).magnitude() < 0.000001
+        );
+        assert!(
+            (clamped.vertex_position(vertex_id) - (position - 0.5 * min_edge_length * normal))
+                .magnitude()
+                < 0.000001
+        );
+        for other in mesh.vertex_iter().filter(|v| *v != vertex_id) {
+            assert_eq!(mesh.vertex_position(other), clamped.vertex_position(other));
+        }
+    }
+
+    #[test]
+    fn test_soft_selection() {
+        // A thin sheet folded such that the two halves are close
+        let mut mesh = Mesh::grid(
+            vec3(0.0, 0.0, 0.0),
+            vec3(4.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            8,
+            2,
+        );
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            if p.x > 0.0 {
+                mesh.set_vertex_position(vertex_id, vec3(-p.x, p.y, 0.1));
+            }
+        }
+        let center = mesh
+            .vertex_iter()
+            .find(|v| (mesh.vertex_position(*v) - vec3(-1.0, 0.0, 0.0)).magnitude() < 0.001)
+            .unwrap();
+        let other_side = mesh
+            .vertex_iter()
+            .find(|v| (mesh.vertex_position(*v) - vec3(-1.0, 0.0, 0.1)).magnitude() < 0.001)
+            .unwrap();
+
+        for falloff in [
+            Falloff::Linear,
+            Falloff::Smooth,
+            Falloff::Sphere,
+            Falloff::Sharp,
+        ] {
+            let weights = mesh.soft_selection(center, 1.0, falloff, false);
+            assert_eq!(weights[&center], 1.0);
+            assert!(weights[&other_side] > 0.0 && weights[&other_side] < 1.0);
+            assert!(weights.values().all(|w| *w > 0.0 && *w <= 1.0));
+
+            let weights = mesh.soft_selection(center, 1.0, falloff, true);
+            assert!(!weights.contains_key(&other_side));
+            let neighbour = mesh
+                .vertex_iter()
+                .find(|v| (mesh.vertex_position(*v) - vec3(-1.5, 0.0, 0.0)).magnitude() < 0.001)
+                .unwrap();
+            let expected = match falloff {
+                Falloff::Linear => 0.5,
+                Falloff::Smooth => 0.5,
+                Falloff::Sphere => 0.75f64.sqrt(),
+                Falloff::Sharp => 0.25,
+            };
+            assert!((weights[&neighbour] - expected).abs() < 0.000001);
+        }
+    }
+}
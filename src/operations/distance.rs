@@ -0,0 +1,282 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::operations::SpatialIndex;
+
+/// The maximum number of times a face is subdivided when computing the distance from the face to another mesh, see [Mesh::distance_to].
+const MAX_SUBDIVISIONS: usize = 8;
+
+/// The tolerance of the distances computed by [Mesh::distance_to] relative to the diagonal of the bounding box of the two meshes.
+const RELATIVE_TOLERANCE: f64 = 0.000001;
+
+///
+/// Distances between two meshes as returned by [Mesh::distance_to].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshDistance {
+    /// The one-sided Hausdorff distance from this mesh to the other mesh, ie. the maximum distance from a point on this mesh to the other mesh.
+    pub hausdorff_to_other: f64,
+    /// The one-sided Hausdorff distance from the other mesh to this mesh, ie. the maximum distance from a point on the other mesh to this mesh.
+    pub hausdorff_from_other: f64,
+    /// The symmetric Hausdorff distance, ie. the maximum of the two one-sided Hausdorff distances.
+    pub hausdorff: f64,
+    /// The root mean square of the distance from the points on this mesh to the other mesh.
+    pub rms_to_other: f64,
+    /// The root mean square of the distance from the points on the other mesh to this mesh.
+    pub rms_from_other: f64,
+    /// The root mean square of the distance from the points on both meshes to the other mesh.
+    pub rms: f64,
+}
+
+/// # Distance
+impl Mesh {
+    ///
+    /// Computes the distance between this mesh and the other mesh, for example to quantify the error after simplification or remeshing.
+    /// Returns `None` if one of the meshes has no faces, since the distance to an empty mesh is not defined.
+    ///
+    /// The distance from a point to the other mesh is the exact distance to the closest point on the faces of the other mesh (see [Mesh::closest_point]).
+    /// The Hausdorff distances are found using branch and bound, where a face is subdivided as long as the distance somewhere on the face might exceed the largest distance found so far,
+    /// which is bounded by the distance from the corners of the face to a face of the other mesh, since the distance to a face is convex.
+    /// So the Hausdorff distances are exact up to a small tolerance, unless the maximum number of subdivisions is reached.
+    /// The root mean square distances are integrated over the subdivided faces using the edge midpoints, which is exact where the closest point is inside a single face of the other mesh,
+    /// and weighted by the area of the faces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// let mut other = mesh.clone();
+    /// other.scale(1.1);
+    /// let distance = mesh.distance_to(&other).unwrap();
+    /// assert!((distance.hausdorff - 0.1).abs() < 0.01);
+    /// assert!(distance.rms <= distance.hausdorff);
+    ///
+    /// assert!(mesh.distance_to(&Mesh::new(&three_d_asset::TriMesh::default())).is_none());
+    /// ```
+    ///
+    pub fn distance_to(&self, other: &Mesh) -> Option<MeshDistance> {
+        if self.no_faces() == 0 || other.no_faces() == 0 {
+            return None;
+        }
+        let mut aabb = self.aabb()?;
+        let other_aabb = other.aabb()?;
+        aabb.expand(other_aabb.min);
+        aabb.expand(other_aabb.max);
+        let tolerance = RELATIVE_TOLERANCE * aabb.size().magnitude();
+
+        let (hausdorff_to_other, rms_to_other, area) =
+            self.one_sided_distance(&other.spatial_index(), tolerance);
+        let (hausdorff_from_other, rms_from_other, other_area) =
+            other.one_sided_distance(&self.spatial_index(), tolerance);
+        let total_area = area + other_area;
+        let rms = if total_area > 0.0 {
+            ((rms_to_other * rms_to_other * area + rms_from_other * rms_from_other * other_area)
+                / total_area)
+                .sqrt()
+        } else {
+            0.0
+        };
+        Some(MeshDistance {
+            hausdorff_to_other,
+            hausdorff_from_other,
+            hausdorff: hausdorff_to_other.max(hausdorff_from_other),
+            rms_to_other,
+            rms_from_other,
+            rms,
+        })
+    }
+
+    ///
+    /// Returns the closest point on the mesh to the given point together with the face that contains the closest point
+    /// or `None` if the mesh has no faces.
+    /// This builds a [SpatialIndex] for each call, so use [SpatialIndex::closest_point] when finding the closest point to many points.
+    ///
+    pub fn closest_point(&self, point: Vec3) -> Option<(FaceID, Vec3)> {
+        self.spatial_index().closest_point(point)
+    }
+
+    /// Returns the maximum and the area weighted root mean square distance from this mesh to the mesh of the index and the area of this mesh.
+    /// The mesh of the index must have at least one face.
+    fn one_sided_distance(&self, other: &SpatialIndex, tolerance: f64) -> (f64, f64, f64) {
+        let other_mesh = other.mesh();
+        let distance = |p: Vec3| {
+            let (face_id, closest) = other.closest_point(p).unwrap();
+            (face_id, closest.distance(p))
+        };
+        let face_distance = |face_id: FaceID, p: Vec3| {
+            let (p0, p1, p2) = other_mesh.face_positions(face_id);
+            closest_point_on_triangle(p, p0, p1, p2).distance(p)
+        };
+
+        // Start with the maximum distance at the vertices, so only the faces where the distance might exceed that are subdivided
+        let mut max = self
+            .vertex_iter()
+            .map(|vertex_id| distance(self.vertex_position(vertex_id)).1)
+            .fold(0.0, f64::max);
+        let mut sum = 0.0;
+        let mut area = 0.0;
+        for face_id in self.face_iter() {
+            let (p0, p1, p2) = self.face_positions(face_id);
+            let mut stack = vec![([p0, p1, p2], 0)];
+            while let Some((corners, depth)) = stack.pop() {
+                let center = (corners[0] + corners[1] + corners[2]) / 3.0;
+                let samples = [
+                    distance(corners[0]),
+                    distance(corners[1]),
+                    distance(corners[2]),
+                    distance(center),
+                ];
+                max = samples.iter().fold(max, |max, (_, d)| max.max(*d));
+
+                // The distance to a face is convex, so the maximum distance to a face over the triangle is the maximum at the corners,
+                // which is an upper bound of the distance to the mesh over the triangle
+                let upper_bound = samples
+                    .iter()
+                    .map(|(candidate, _)| {
+                        corners
+                            .iter()
+                            .map(|corner| face_distance(*candidate, *corner))
+                            .fold(0.0, f64::max)
+                    })
+                    .fold(f64::INFINITY, f64::min);
+                if upper_bound > max + tolerance && depth < MAX_SUBDIVISIONS {
+                    let [a, b, c] = corners;
+                    let (ab, bc, ca) = (0.5 * (a + b), 0.5 * (b + c), 0.5 * (c + a));
+                    stack.push(([a, ab, ca], depth + 1));
+                    stack.push(([ab, b, bc], depth + 1));
+                    stack.push(([ca, bc, c], depth + 1));
+                    stack.push(([ab, bc, ca], depth + 1));
+                    continue;
+                }
+
+                let triangle_area = 0.5
+                    * (corners[1] - corners[0])
+                        .cross(corners[2] - corners[0])
+                        .magnitude();
+                let mut triangle_sum = 0.0;
+                for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+                    let (_, d) = distance(0.5 * (corners[i] + corners[j]));
+                    max = max.max(d);
+                    triangle_sum += d * d;
+                }
+                sum += triangle_area * triangle_sum / 3.0;
+                area += triangle_area;
+            }
+        }
+        let rms = if area > 0.0 { (sum / area).sqrt() } else { 0.0 };
+        (max, rms, area)
+    }
+}
+
+/// Returns the point on the triangle spanned by `a`, `b` and `c` that is closest to the point `p`.
+pub(crate) fn closest_point_on_triangle(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + d1 / (d1 - d3) * ab;
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + d2 / (d2 - d6) * ac;
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (d4 - d3) / ((d4 - d3) + (d5 - d6)) * (c - b);
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    #[test]
+    fn test_closest_point_on_triangle() {
+        let (a, b, c) = (
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+        );
+        let closest = |p| closest_point_on_triangle(p, a, b, c);
+        assert_eq!(closest(vec3(0.25, 0.25, 1.0)), vec3(0.25, 0.25, 0.0));
+        assert_eq!(closest(vec3(-1.0, -1.0, 0.0)), a);
+        assert_eq!(closest(vec3(2.0, -1.0, 0.5)), b);
+        assert_eq!(closest(vec3(0.5, -1.0, 0.0)), vec3(0.5, 0.0, 0.0));
+        assert_eq!(closest(vec3(1.0, 1.0, 0.0)), vec3(0.5, 0.5, 0.0));
+    }
+
+    #[test]
+    fn test_distance_to() {
+        let mesh = crate::test_utility::cube();
+        let distance = mesh.distance_to(&mesh).unwrap();
+        assert!(distance.hausdorff < 0.000001);
+        assert!(distance.rms < 0.000001);
+
+        let mut other = mesh.clone();
+        other.translate(vec3(0.0, 0.5, 0.0));
+        let distance = mesh.distance_to(&other).unwrap();
+        assert!((distance.hausdorff_to_other - 0.5).abs() < 0.000001);
+        assert!((distance.hausdorff_from_other - 0.5).abs() < 0.000001);
+        assert!(distance.rms > 0.0 && distance.rms < 0.5);
+
+        let sphere: Mesh = TriMesh::sphere(8).into();
+        let distance = sphere.distance_to(&mesh).unwrap();
+        assert!(distance.hausdorff_to_other < distance.hausdorff_from_other);
+    }
+
+    #[test]
+    fn test_distance_to_is_largest_inside_face() {
+        // The distance from the triangle to the surrounding cube is largest at the origin, which is inside the triangle
+        // and not at a corner, an edge midpoint or the center of the triangle
+        let triangle: Mesh = TriMesh {
+            positions: three_d_asset::Positions::F64(vec![
+                vec3(-0.5, -0.5, 0.0),
+                vec3(0.5, -0.5, 0.0),
+                vec3(0.0, 0.5, 0.0),
+            ]),
+            ..Default::default()
+        }
+        .into();
+        let cube = crate::test_utility::cube();
+        let distance = triangle.distance_to(&cube).unwrap();
+        assert!((distance.hausdorff_to_other - 1.0).abs() < 0.01);
+        assert!((distance.hausdorff_from_other - 1.5).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_distance_to_empty_mesh() {
+        let mesh = crate::test_utility::cube();
+        let empty = Mesh::new(&TriMesh::default());
+        assert!(mesh.distance_to(&empty).is_none());
+        assert!(empty.distance_to(&mesh).is_none());
+        assert!(empty.closest_point(vec3(0.0, 0.0, 0.0)).is_none());
+    }
+}
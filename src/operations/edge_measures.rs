@@ -30,4 +30,73 @@ impl Mesh {
         let (p0, p1) = self.edge_positions(halfedge_id);
         (p0 - p1).magnitude2()
     }
+
+    ///
+    /// Returns the dihedral angle of the specified edge measured as the angle between the normals of the two adjacent faces,
+    /// ie. zero if the two faces are in the same plane and increasing as the edge becomes sharper.
+    /// Returns `None` if the edge is on the boundary.
+    ///
+    pub fn edge_dihedral_angle(&self, halfedge_id: HalfEdgeID) -> Option<Radians> {
+        let mut walker = self.walker_from_halfedge(halfedge_id);
+        let face_id0 = walker.face_id()?;
+        let face_id1 = walker.as_twin().face_id()?;
+        Some(self.face_normal(face_id0).angle(self.face_normal(face_id1)))
+    }
+
+    ///
+    /// Returns one of the half-edges of each edge whose dihedral angle (see [Mesh::edge_dihedral_angle]) is larger than the given threshold, in the order of [Mesh::edge_iter].
+    /// These are the sharp edges of the mesh which for example should be shaded with split normals or be preserved when smoothing, see also [Mesh::set_crease].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::cube().into();
+    /// mesh.merge_overlapping_primitives();
+    /// // The 12 edges of the cube, but not the diagonals of the sides
+    /// assert_eq!(mesh.sharp_edges(degrees(30.0)).len(), 12);
+    /// ```
+    ///
+    pub fn sharp_edges(&self, angle_threshold: impl Into<Radians>) -> Vec<HalfEdgeID> {
+        let angle_threshold = angle_threshold.into();
+        self.edge_iter()
+            .map(HalfEdgeID::from)
+            .filter(|halfedge_id| match self.edge_dihedral_angle(*halfedge_id) {
+                Some(angle) => angle > angle_threshold,
+                None => false,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_dihedral_angle() {
+        let mut mesh = crate::test_utility::square();
+        let halfedge_id = mesh
+            .halfedge_iter()
+            .find(|h| !mesh.is_edge_on_boundary(*h))
+            .unwrap();
+        let boundary_halfedge_id = mesh
+            .halfedge_iter()
+            .find(|h| mesh.is_edge_on_boundary(*h))
+            .unwrap();
+        assert!(mesh.edge_dihedral_angle(halfedge_id).unwrap().0.abs() < 0.000001);
+        assert_eq!(mesh.edge_dihedral_angle(boundary_halfedge_id), None);
+        assert!(mesh.sharp_edges(degrees(10.0)).is_empty());
+
+        // Fold the square 90 degrees around the diagonal
+        let vertex_id = mesh
+            .vertex_iter()
+            .find(|v| mesh.vertex_position(*v) == vec3(1.0, 1.0, 0.0))
+            .unwrap();
+        mesh.move_vertex_to(vertex_id, vec3(0.0, 0.0, 2.0f64.sqrt()));
+        let angle = mesh.edge_dihedral_angle(halfedge_id).unwrap();
+        assert!((angle - Radians::from(degrees(90.0))).0.abs() < 0.000001);
+        assert_eq!(mesh.sharp_edges(degrees(80.0)).len(), 1);
+        assert!(mesh.sharp_edges(degrees(100.0)).is_empty());
+    }
 }
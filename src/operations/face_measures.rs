@@ -47,10 +47,112 @@ impl Mesh {
 
         (p0 + p1 + p2) / 3.0
     }
+
+    /// Returns the interior angles of the face at the three vertices in the same order as [Mesh::face_positions].
+    pub fn face_angles(&self, face_id: FaceID) -> (Radians, Radians, Radians) {
+        let (p0, p1, p2) = self.face_positions(face_id);
+        (
+            (p1 - p0).angle(p2 - p0),
+            (p2 - p1).angle(p0 - p1),
+            (p0 - p2).angle(p1 - p2),
+        )
+    }
+
+    /// Returns the smallest interior angle of the face, which is 60 degrees for an equilateral triangle and zero for a degenerate triangle.
+    pub fn face_min_angle(&self, face_id: FaceID) -> Radians {
+        let (a0, a1, a2) = self.face_angles(face_id);
+        Rad(a0.0.min(a1.0).min(a2.0))
+    }
+
+    /// Returns the largest interior angle of the face, which is 60 degrees for an equilateral triangle and 180 degrees for a degenerate triangle.
+    pub fn face_max_angle(&self, face_id: FaceID) -> Radians {
+        let (a0, a1, a2) = self.face_angles(face_id);
+        Rad(a0.0.max(a1.0).max(a2.0))
+    }
+
+    ///
+    /// Returns the aspect ratio of the face given as the length of the longest edge divided by the diameter of the inscribed circle and normalized such that
+    /// the aspect ratio of an equilateral triangle is one. The aspect ratio increases as the triangle becomes more elongated and is infinite for a degenerate triangle.
+    ///
+    pub fn face_aspect_ratio(&self, face_id: FaceID) -> f64 {
+        let (p0, p1, p2) = self.face_positions(face_id);
+        let (a, b, c) = (p0.distance(p1), p1.distance(p2), p2.distance(p0));
+        let inradius = self.face_area(face_id) / (0.5 * (a + b + c));
+        a.max(b).max(c) / (2.0 * 3.0f64.sqrt() * inradius)
+    }
+
+    ///
+    /// Returns the radius ratio of the face given as the radius of the inscribed circle divided by the radius of the circumscribed circle and normalized such that
+    /// the radius ratio of an equilateral triangle is one. The radius ratio decreases towards zero as the triangle becomes degenerate.
+    ///
+    pub fn face_radius_ratio(&self, face_id: FaceID) -> f64 {
+        let (p0, p1, p2) = self.face_positions(face_id);
+        let (a, b, c) = (p0.distance(p1), p1.distance(p2), p2.distance(p0));
+        let area = self.face_area(face_id);
+        if area <= 0.0 {
+            return 0.0;
+        }
+        let inradius = area / (0.5 * (a + b + c));
+        let circumradius = a * b * c / (4.0 * area);
+        2.0 * inradius / circumradius
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn test_face_quality_measures() {
+        let mut mesh: Mesh = three_d_asset::TriMesh {
+            positions: three_d_asset::Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.5, 0.75f64.sqrt(), 0.0),
+            ]),
+            ..Default::default()
+        }
+        .into();
+        let face_id = mesh.face_iter().next().unwrap();
+        assert!((mesh.face_aspect_ratio(face_id) - 1.0).abs() < 0.000001);
+        assert!((mesh.face_radius_ratio(face_id) - 1.0).abs() < 0.000001);
+        assert!(
+            (mesh.face_min_angle(face_id) - Rad::from(degrees(60.0)))
+                .0
+                .abs()
+                < 0.000001
+        );
+        assert!(
+            (mesh.face_max_angle(face_id) - Rad::from(degrees(60.0)))
+                .0
+                .abs()
+                < 0.000001
+        );
+
+        // A right isosceles triangle
+        let vertex_id = mesh
+            .vertex_iter()
+            .find(|v| mesh.vertex_position(*v).y > 0.0)
+            .unwrap();
+        mesh.move_vertex_to(vertex_id, vec3(0.0, 1.0, 0.0));
+        let (a0, a1, a2) = mesh.face_angles(face_id);
+        assert!((a0 + a1 + a2 - Rad::from(degrees(180.0))).0.abs() < 0.000001);
+        assert!(
+            (mesh.face_min_angle(face_id) - Rad::from(degrees(45.0)))
+                .0
+                .abs()
+                < 0.000001
+        );
+        assert!(
+            (mesh.face_max_angle(face_id) - Rad::from(degrees(90.0)))
+                .0
+                .abs()
+                < 0.000001
+        );
+        assert!(mesh.face_aspect_ratio(face_id) > 1.0);
+        assert!(mesh.face_radius_ratio(face_id) < 1.0);
+    }
+
     #[test]
     fn test_face_area() {
         let mesh = crate::test_utility::triangle();
@@ -0,0 +1,178 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// # Geodesics
+impl Mesh {
+    ///
+    /// Returns the shortest distance from the source vertex to each vertex in the mesh when only walking along the edges of the mesh.
+    /// Vertices which cannot be reached from the source vertex are not contained in the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let source = mesh.vertex_iter().next().unwrap();
+    /// let distances = mesh.geodesic_distances(source);
+    /// assert_eq!(distances[&source], 0.0);
+    /// assert_eq!(distances.len(), mesh.no_vertices());
+    /// ```
+    ///
+    pub fn geodesic_distances(&self, source: VertexID) -> VertexProperty<f64> {
+        self.dijkstra(source, None).0
+    }
+
+    ///
+    /// Returns the sequence of half-edges that forms the shortest path between the two vertices when only walking along the edges of the mesh,
+    /// or `None` if there is no path between the two vertices.
+    /// The first half-edge starts in the `start` vertex and the last half-edge points to the `end` vertex.
+    ///
+    pub fn shortest_edge_path(&self, start: VertexID, end: VertexID) -> Option<Vec<HalfEdgeID>> {
+        let (_, previous) = self.dijkstra(start, Some(end));
+        let mut path = Vec::new();
+        let mut vertex_id = end;
+        while vertex_id != start {
+            let halfedge_id = *previous.get(&vertex_id)?;
+            path.push(halfedge_id);
+            vertex_id = self
+                .walker_from_halfedge(halfedge_id)
+                .as_twin()
+                .vertex_id()
+                .unwrap();
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Runs Dijkstra's algorithm from the source vertex, optionally stopping when the target vertex is reached.
+    /// Returns the distances and the half-edge used to reach each vertex.
+    fn dijkstra(
+        &self,
+        source: VertexID,
+        target: Option<VertexID>,
+    ) -> (VertexProperty<f64>, VertexProperty<HalfEdgeID>) {
+        let mut distances = VertexProperty::new();
+        let mut previous = VertexProperty::new();
+        let mut queue = BinaryHeap::new();
+        distances.insert(source, 0.0);
+        queue.push(QueueItem {
+            distance: 0.0,
+            vertex_id: source,
+        });
+        while let Some(QueueItem {
+            distance,
+            vertex_id,
+        }) = queue.pop()
+        {
+            if distance > distances[&vertex_id] {
+                continue;
+            }
+            if Some(vertex_id) == target {
+                break;
+            }
+            for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
+                let neighbour = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                let new_distance = distance + self.edge_length(halfedge_id);
+                if distances
+                    .get(&neighbour)
+                    .map(|d| new_distance < *d)
+                    .unwrap_or(true)
+                {
+                    distances.insert(neighbour, new_distance);
+                    previous.insert(neighbour, halfedge_id);
+                    queue.push(QueueItem {
+                        distance: new_distance,
+                        vertex_id: neighbour,
+                    });
+                }
+            }
+        }
+        (distances, previous)
+    }
+}
+
+struct QueueItem {
+    distance: f64,
+    vertex_id: VertexID,
+}
+
+impl PartialEq for QueueItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueueItem {}
+
+impl PartialOrd for QueueItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueueItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed such that the binary heap pops the smallest distance first
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then_with(|| other.vertex_id.cmp(&self.vertex_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    #[test]
+    fn test_geodesic_distances() {
+        let mesh = crate::test_utility::triangle_strip();
+        let source = unsafe { VertexID::new(0) };
+        let distances = mesh.geodesic_distances(source);
+
+        assert_eq!(distances.len(), mesh.no_vertices());
+        assert_eq!(distances[&source], 0.0);
+        assert_eq!(distances[&unsafe { VertexID::new(1) }], 1.0);
+        assert_eq!(distances[&unsafe { VertexID::new(4) }], 2.0);
+        assert_eq!(distances[&unsafe { VertexID::new(5) }], 2.5);
+    }
+
+    #[test]
+    fn test_geodesic_distances_of_unconnected_mesh() {
+        let mut mesh = crate::test_utility::triangle();
+        mesh.append(&crate::test_utility::square());
+        let distances = mesh.geodesic_distances(mesh.vertex_iter().next().unwrap());
+        assert_eq!(distances.len(), 3);
+    }
+
+    #[test]
+    fn test_shortest_edge_path() {
+        let mesh: Mesh = TriMesh::sphere(8).into();
+        let start = mesh.vertex_iter().next().unwrap();
+        let distances = mesh.geodesic_distances(start);
+        let (end, distance) = distances.iter().max_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+
+        let path = mesh.shortest_edge_path(start, *end).unwrap();
+
+        let mut walker = mesh.walker_from_halfedge(path[0]);
+        assert_eq!(walker.as_twin().vertex_id(), Some(start));
+        for window in path.windows(2) {
+            let v = mesh.walker_from_halfedge(window[0]).vertex_id();
+            assert_eq!(
+                mesh.walker_from_halfedge(window[1]).as_twin().vertex_id(),
+                v
+            );
+        }
+        assert_eq!(
+            mesh.walker_from_halfedge(*path.last().unwrap()).vertex_id(),
+            Some(*end)
+        );
+        let length: f64 = path.iter().map(|h| mesh.edge_length(*h)).sum();
+        assert!((length - distance).abs() < 0.000001);
+        assert_eq!(mesh.shortest_edge_path(start, start), Some(Vec::new()));
+    }
+}
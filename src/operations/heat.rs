@@ -0,0 +1,214 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use super::curvature::cotangent;
+use crate::mesh::*;
+use crate::Error;
+use nalgebra::DMatrix;
+use nalgebra_sparse::factorization::CscCholesky;
+use nalgebra_sparse::{CooMatrix, CscMatrix};
+
+/// # Heat method
+impl Mesh {
+    ///
+    /// Returns the geodesic distance from the source vertex to each vertex in the mesh computed using the heat method
+    /// (Crane et al., "Geodesics in Heat").
+    /// Compared to [Mesh::geodesic_distances], the distances are not restricted to paths along the edges of the mesh and are therefore smoother,
+    /// but they are approximate and the source vertex is not guaranteed to have a distance of exactly zero.
+    /// Vertices which cannot be reached from the source vertex are not contained in the result.
+    ///
+    /// **Note:** Requires the `solver` feature.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if one of the linear systems cannot be solved, for example if the mesh contains degenerate faces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// let source = mesh.vertex_iter().next().unwrap();
+    /// let distances = mesh.heat_geodesic_distances(source).unwrap();
+    /// assert_eq!(distances.len(), mesh.no_vertices());
+    /// ```
+    ///
+    pub fn heat_geodesic_distances(&self, source: VertexID) -> Result<VertexProperty<f64>, Error> {
+        let vertices: Vec<VertexID> = self.geodesic_distances(source).into_keys().collect();
+        let indices: VertexProperty<usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, vertex_id)| (*vertex_id, i))
+            .collect();
+        let n = vertices.len();
+        if n == 1 {
+            return Ok(indices.keys().map(|vertex_id| (*vertex_id, 0.0)).collect());
+        }
+
+        // Integrate the heat flow for a short time (M + t L) u = δ
+        let mean_edge_length = self
+            .edge_iter()
+            .map(|edge_id| self.edge_length(edge_id.into()))
+            .sum::<f64>()
+            / self.no_edges() as f64;
+        let time = mean_edge_length * mean_edge_length;
+        let mut heat_system = self.heat_laplacian(&indices, time, None);
+        for (i, vertex_id) in vertices.iter().enumerate() {
+            heat_system.push(i, i, self.mixed_voronoi_area(*vertex_id));
+        }
+        let mut delta = DMatrix::zeros(n, 1);
+        delta[indices[&source]] = 1.0;
+        let heat = solve(heat_system, delta)?;
+
+        // Evaluate the normalized gradient field X = -∇u / |∇u| and its integrated divergence
+        let mut divergence = DMatrix::<f64>::zeros(n, 1);
+        for face_id in self.face_iter() {
+            let (v0, v1, v2) = self.face_vertices(face_id);
+            let (Some(i0), Some(i1), Some(i2)) =
+                (indices.get(&v0), indices.get(&v1), indices.get(&v2))
+            else {
+                continue;
+            };
+            let (p0, p1, p2) = (
+                self.vertex_position(v0),
+                self.vertex_position(v1),
+                self.vertex_position(v2),
+            );
+            let normal = (p1 - p0).cross(p2 - p0);
+            let gradient = normal.cross(p2 - p1) * heat[*i0]
+                + normal.cross(p0 - p2) * heat[*i1]
+                + normal.cross(p1 - p0) * heat[*i2];
+            let magnitude = gradient.magnitude();
+            if magnitude < f64::EPSILON {
+                continue;
+            }
+            let x = -gradient / magnitude;
+            for (i, p, pa, pb) in [(i0, p0, p1, p2), (i1, p1, p2, p0), (i2, p2, p0, p1)] {
+                let ea = pa - p;
+                let eb = pb - p;
+                divergence[*i] += 0.5
+                    * (cotangent(pa - pb, p - pb) * ea.dot(x)
+                        + cotangent(pb - pa, p - pa) * eb.dot(x));
+            }
+        }
+
+        // Recover the distance by solving the Poisson equation L φ = -∇·X with the source vertex fixed at zero
+        let source_index = indices[&source];
+        let pinned = |i: usize| if i > source_index { i - 1 } else { i };
+        let poisson_system = self.heat_laplacian(&indices, 1.0, Some(source_index));
+        let mut rhs = DMatrix::zeros(n - 1, 1);
+        for i in (0..n).filter(|i| *i != source_index) {
+            rhs[pinned(i)] = -divergence[i];
+        }
+        let distance = solve(poisson_system, rhs)?;
+
+        Ok(indices
+            .iter()
+            .map(|(vertex_id, i)| {
+                let d = if *i == source_index {
+                    0.0
+                } else {
+                    distance[pinned(*i)]
+                };
+                (*vertex_id, d)
+            })
+            .collect())
+    }
+
+    /// Returns the cotangent Laplacian scaled by the given factor for the vertices with the given indices.
+    /// If a pinned index is given, the corresponding row and column is removed.
+    fn heat_laplacian(
+        &self,
+        indices: &VertexProperty<usize>,
+        scale: f64,
+        pinned: Option<usize>,
+    ) -> CooMatrix<f64> {
+        let n = indices.len() - pinned.map(|_| 1).unwrap_or(0);
+        let triplets = self.cotan_laplacian_triplets(&|vertex_id| {
+            let i = *indices.get(&vertex_id)?;
+            match pinned {
+                Some(p) if i == p => None,
+                Some(p) if i > p => Some(i - 1),
+                _ => Some(i),
+            }
+        });
+        let mut matrix = CooMatrix::new(n, n);
+        for (row, column, value) in triplets {
+            matrix.push(row, column, scale * value);
+        }
+        matrix
+    }
+}
+
+/// Solves the symmetric positive definite system using a Cholesky factorization.
+/// Returns an error if the system is not positive definite, for example because of degenerate faces.
+pub(super) fn solve(matrix: CooMatrix<f64>, rhs: DMatrix<f64>) -> Result<DMatrix<f64>, Error> {
+    Ok(CscCholesky::factor(&CscMatrix::from(&matrix))
+        .map_err(|e| Error::SolverFailed(e.to_string()))?
+        .solve(&rhs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    #[test]
+    fn test_heat_geodesic_distances_on_sphere() {
+        let mesh: Mesh = TriMesh::sphere(16).into();
+        let source = mesh.vertex_iter().next().unwrap();
+        let p = mesh.vertex_position(source);
+        let distances = mesh.heat_geodesic_distances(source).unwrap();
+
+        assert_eq!(distances.len(), mesh.no_vertices());
+        let mut error = 0.0;
+        for vertex_id in mesh.vertex_iter() {
+            let exact = p
+                .normalize()
+                .dot(mesh.vertex_position(vertex_id).normalize())
+                .clamp(-1.0, 1.0)
+                .acos();
+            error += (distances[&vertex_id] - exact).abs();
+        }
+        error /= mesh.no_vertices() as f64;
+        assert!(error < 0.05, "mean error {}", error);
+    }
+
+    #[test]
+    fn test_heat_geodesic_distances_on_plane() {
+        let size = 21;
+        let mut positions = Vec::new();
+        let mut indices = Vec::new();
+        for j in 0..size {
+            for i in 0..size {
+                positions.push(vec3(i as f64, j as f64, 0.0) / (size - 1) as f64);
+                if i + 1 < size && j + 1 < size {
+                    let v = (j * size + i) as u32;
+                    let s = size as u32;
+                    indices.extend([v, v + 1, v + s + 1, v, v + s + 1, v + s]);
+                }
+            }
+        }
+        let mesh: Mesh = TriMesh {
+            indices: three_d_asset::Indices::U32(indices),
+            positions: three_d_asset::Positions::F64(positions),
+            ..Default::default()
+        }
+        .into();
+        let source = mesh
+            .vertex_iter()
+            .find(|v| mesh.vertex_position(*v) == vec3(0.5, 0.5, 0.0))
+            .unwrap();
+        let distances = mesh.heat_geodesic_distances(source).unwrap();
+
+        assert_eq!(distances.len(), mesh.no_vertices());
+        for vertex_id in mesh.vertex_iter() {
+            let exact = (mesh.vertex_position(vertex_id) - vec3(0.5, 0.5, 0.0)).magnitude();
+            assert!(
+                (distances[&vertex_id] - exact).abs() < 0.05,
+                "{} {}",
+                distances[&vertex_id],
+                exact
+            );
+        }
+    }
+}
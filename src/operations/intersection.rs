@@ -1,6 +1,9 @@
 //! See [Mesh](crate::mesh::Mesh).
 
 use crate::mesh::*;
+use crate::operations::bvh::*;
+#[cfg(feature = "robust")]
+use crate::operations::predicates::*;
 use utility::*;
 
 ///
@@ -41,6 +44,54 @@ pub enum Intersection {
     },
 }
 
+///
+/// The result of a ray hitting a face in the mesh as returned by for example [Mesh::ray_hit].
+/// Can be converted into an [Intersection].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// The face that is hit
+    pub face_id: FaceID,
+    /// The [Primitive] (vertex, edge or face) that is hit, ie. a vertex or an edge of the face if the ray hits the face at the boundary of the face
+    pub primitive: Primitive,
+    /// The point where the ray hits the face
+    pub point: Vec3,
+    /// The distance from the start point of the ray to the point where the ray hits the face
+    pub distance: f64,
+    /// The barycentric coordinates of the point with respect to the vertices of the face in the order returned by [Mesh::face_vertices]
+    pub barycentric: (f64, f64, f64),
+}
+
+impl From<RayHit> for Intersection {
+    fn from(hit: RayHit) -> Self {
+        Intersection::Point {
+            primitive: hit.primitive,
+            point: hit.point,
+        }
+    }
+}
+
+///
+/// A ray given by a start point and a direction.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    /// The point where the ray starts
+    pub start_point: Vec3,
+    /// The direction of the ray
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Constructs a new ray starting at the given point and going in the given direction.
+    pub fn new(start_point: Vec3, direction: Vec3) -> Self {
+        Self {
+            start_point,
+            direction,
+        }
+    }
+}
+
 /// # Intersection
 impl Mesh {
     ///
@@ -53,24 +104,216 @@ impl Mesh {
         ray_start_point: &Vec3,
         ray_direction: &Vec3,
     ) -> Option<Intersection> {
-        let mut current: Option<Intersection> = None;
+        self.ray_hit(ray_start_point, ray_direction)
+            .map(Intersection::from)
+    }
+
+    ///
+    /// Find the [RayHit] between any face in the mesh and the given ray, which in addition to the [Intersection] contains the face, the distance and the barycentric coordinates.
+    /// If the ray hits multiple faces, the hit closest to the starting point in the direction of the ray is returned.
+    /// If no faces are hit, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let hit = mesh.ray_hit(&vec3(0.0, 0.0, 3.0), &vec3(0.0, 0.0, -1.0)).unwrap();
+    /// assert!((hit.distance - 2.0).abs() < 0.000001);
+    ///
+    /// let (v0, v1, v2) = mesh.face_vertices(hit.face_id);
+    /// let (b0, b1, b2) = hit.barycentric;
+    /// let point = b0 * mesh.vertex_position(v0) + b1 * mesh.vertex_position(v1) + b2 * mesh.vertex_position(v2);
+    /// assert!(point.distance(hit.point) < 0.000001);
+    /// ```
+    ///
+    pub fn ray_hit(&self, ray_start_point: &Vec3, ray_direction: &Vec3) -> Option<RayHit> {
+        let mut current: Option<RayHit> = None;
         for face_id in self.face_iter() {
-            let new_intersection =
-                self.face_ray_intersection(face_id, ray_start_point, ray_direction);
-            if let Some(Intersection::Point { point, .. }) = new_intersection {
-                let new_point = point;
-                if let Some(Intersection::Point { point, .. }) = current {
-                    if point.distance2(*ray_start_point) > new_point.distance2(*ray_start_point) {
-                        current = new_intersection;
-                    }
-                } else {
-                    current = new_intersection;
+            if let Some(hit) = self.face_ray_hit(face_id, ray_start_point, ray_direction) {
+                let is_closer = match &current {
+                    Some(current) => hit.distance < current.distance,
+                    None => true,
+                };
+                if is_closer {
+                    current = Some(hit);
                 }
             }
         }
         current
     }
 
+    ///
+    /// Finds the [Primitive] picked by the given ray, for example when the user clicks on the mesh in an editor.
+    /// If the ray hits the mesh, the vertex of the hit face closest to the hit point is returned if it is within the given `radius` of the hit point,
+    /// otherwise the closest edge of the hit face is returned if it is within the `radius` of the hit point and otherwise the hit face is returned.
+    /// The `radius` is given in world space, so it should typically be the size of a few pixels at the distance of the mesh.
+    /// If the ray does not hit the mesh, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// let p = mesh.vertex_position(vertex_id);
+    /// let ray = Ray::new(3.0 * p + vec3(0.01, 0.0, 0.0), -p);
+    /// assert_eq!(mesh.pick(&ray, 0.05), Some(Primitive::Vertex(vertex_id)));
+    /// ```
+    ///
+    pub fn pick(&self, ray: &Ray, radius: f64) -> Option<Primitive> {
+        let hit = self.ray_hit(&ray.start_point, &ray.direction)?;
+        if let Primitive::Vertex(_) = hit.primitive {
+            return Some(hit.primitive);
+        }
+        let (v0, v1, v2) = self.face_vertices(hit.face_id);
+        let closest_vertex = [v0, v1, v2]
+            .into_iter()
+            .map(|vertex_id| {
+                (
+                    self.vertex_position(vertex_id).distance(hit.point),
+                    vertex_id,
+                )
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))?;
+        if closest_vertex.0 <= radius {
+            return Some(Primitive::Vertex(closest_vertex.1));
+        }
+        if let Primitive::Edge(_) = hit.primitive {
+            return Some(hit.primitive);
+        }
+        let closest_edge = self
+            .face_halfedge_iter(hit.face_id)
+            .map(|halfedge_id| {
+                let (p0, p1) = self.edge_positions(halfedge_id);
+                (
+                    point_line_segment_distance(&hit.point, &p0, &p1),
+                    halfedge_id,
+                )
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))?;
+        if closest_edge.0 <= radius {
+            let halfedge_id = closest_edge.1;
+            let twin_id = self.walker_from_halfedge(halfedge_id).twin_id().unwrap();
+            return Some(Primitive::Edge(halfedge_id.min(twin_id)));
+        }
+        Some(hit.primitive)
+    }
+
+    ///
+    /// Finds all the places where the given ray hits the mesh ordered by the distance from the start point of the ray,
+    /// for example to count the number of times a ray crosses the surface to determine whether a point is inside a closed mesh.
+    ///
+    /// If the ray hits the mesh at an edge or a vertex, the hit is only reported once, even though the ray hits all the faces adjacent to the edge or vertex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let hits = mesh.ray_intersections(&vec3(0.1, 0.2, 3.0), &vec3(0.0, 0.0, -1.0));
+    /// assert_eq!(hits.len(), 2);
+    /// assert!(hits[0].point.z > 0.0);
+    /// assert!(hits[1].point.z < 0.0);
+    /// ```
+    ///
+    pub fn ray_intersections(&self, ray_start_point: &Vec3, ray_direction: &Vec3) -> Vec<RayHit> {
+        let mut hits: Vec<RayHit> = self
+            .face_iter()
+            .filter_map(|face_id| self.face_ray_hit(face_id, ray_start_point, ray_direction))
+            .collect();
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        let mut hit_primitives = std::collections::HashSet::new();
+        hits.retain(|hit| match hit.primitive {
+            Primitive::Face(_) => true,
+            primitive => hit_primitives.insert(primitive),
+        });
+        hits
+    }
+
+    ///
+    /// Find the [Intersection] between the mesh and each of the given rays, see [Mesh::ray_intersection].
+    /// The result at a given index is the intersection of the ray at that index.
+    ///
+    /// The faces are organised in a bounding volume hierarchy before casting the rays, so this is considerably faster than calling
+    /// [Mesh::ray_intersection] for each ray when casting many rays. If the `rayon` feature is enabled, the rays are cast in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let rays = [
+    ///     Ray::new(vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0)),
+    ///     Ray::new(vec3(2.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0)),
+    /// ];
+    /// let intersections = mesh.ray_intersections_batch(&rays);
+    /// assert!(intersections[0].is_some());
+    /// assert!(intersections[1].is_none());
+    /// ```
+    ///
+    pub fn ray_intersections_batch(&self, rays: &[Ray]) -> Vec<Option<Intersection>> {
+        let bvh = Bvh::new(self);
+        let cast = |ray: &Ray| self.bvh_ray_intersection(&bvh, ray);
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            rays.par_iter().map(cast).collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            rays.iter().map(cast).collect()
+        }
+    }
+
+    fn bvh_ray_intersection(&self, bvh: &Bvh, ray: &Ray) -> Option<Intersection> {
+        let mut current: Option<RayHit> = None;
+        bvh.for_each_on_ray(&ray.start_point, &ray.direction, MARGIN, &mut |face_id| {
+            if let Some(hit) = self.face_ray_hit(face_id, &ray.start_point, &ray.direction) {
+                let is_closer = match &current {
+                    Some(current) => hit.distance < current.distance,
+                    None => true,
+                };
+                if is_closer {
+                    current = Some(hit);
+                }
+            }
+        });
+        current.map(Intersection::from)
+    }
+
+    ///
+    /// Finds all pairs of faces in the mesh that intersect each other, ie. where the mesh intersects itself.
+    /// Each pair is ordered such that the first face id is smaller than the second.
+    ///
+    /// Faces sharing an edge are never reported and faces sharing a vertex are only reported
+    /// if they also intersect somewhere else than at the shared vertex.
+    /// Coplanar faces are reported if they overlap, also when none of their vertices are inside the other face.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// assert!(mesh.self_intersections().is_empty());
+    /// ```
+    ///
+    pub fn self_intersections(&self) -> Vec<(FaceID, FaceID)> {
+        let bvh = Bvh::new(self);
+        let mut pairs = Vec::new();
+        for face_id in self.face_iter() {
+            let (p0, p1, p2) = self.face_positions(face_id);
+            let bounds = Bounds::from_points(&[p0, p1, p2]);
+            bvh.for_each_overlapping(&bounds, MARGIN, &mut |other_face_id| {
+                if face_id < other_face_id && self.face_face_intersects(face_id, other_face_id) {
+                    pairs.push((face_id, other_face_id));
+                }
+            });
+        }
+        pairs.sort();
+        pairs
+    }
+
     ///
     /// Find the [Intersection] between the given face and ray.
     /// If the face is not intersected by the ray, None is returned.
@@ -92,11 +335,43 @@ impl Mesh {
         })
     }
 
+    ///
+    /// Find the [RayHit] between the given face and ray.
+    /// If the face is not hit by the ray, None is returned.
+    ///
+    pub fn face_ray_hit(
+        &self,
+        face_id: FaceID,
+        ray_start_point: &Vec3,
+        ray_direction: &Vec3,
+    ) -> Option<RayHit> {
+        match self.face_ray_intersection(face_id, ray_start_point, ray_direction)? {
+            Intersection::Point { primitive, point } => {
+                let (v0, v1, v2) = self.face_vertices(face_id);
+                Some(RayHit {
+                    face_id,
+                    primitive,
+                    point,
+                    distance: point.distance(*ray_start_point),
+                    barycentric: barycentric(
+                        &point,
+                        &self.vertex_position(v0),
+                        &self.vertex_position(v1),
+                        &self.vertex_position(v2),
+                    ),
+                })
+            }
+            Intersection::LinePiece { .. } => None,
+        }
+    }
+
     ///
     /// Find the [Intersection] between the given face and line piece.
     /// If the face is not intersected by the line piece, None is returned.
     ///
     /// Note: Intersections, where the line piece is in the plane spanned by the face, are not yet fully handled.
+    /// The end points of the line piece are classified against the plane of the face using a small margin,
+    /// unless the `robust` feature is enabled, in which case they are classified using exact predicates, ie. an end point is only in the plane if it is exactly in the plane.
     ///
     pub fn face_line_piece_intersection(
         &self,
@@ -104,10 +379,19 @@ impl Mesh {
         point0: &Vec3,
         point1: &Vec3,
     ) -> Option<Intersection> {
-        let p = self.vertex_position(self.walker_from_face(face_id).vertex_id().unwrap());
-        let n = self.face_direction(face_id);
+        #[cfg(feature = "robust")]
+        let intersection = {
+            let (a, b, c) = self.face_positions(face_id);
+            triangle_line_piece_intersection(point0, point1, &a, &b, &c)
+        };
+        #[cfg(not(feature = "robust"))]
+        let intersection = {
+            let p = self.vertex_position(self.walker_from_face(face_id).vertex_id().unwrap());
+            let n = self.face_direction(face_id);
+            plane_line_piece_intersection(point0, point1, &p, &n)
+        };
 
-        plane_line_piece_intersection(&point0, &point1, &p, &n).and_then(|intersection| {
+        intersection.and_then(|intersection| {
             match intersection {
                 PlaneLinepieceIntersectionResult::LineInPlane => {
                     let intersection0 =
@@ -174,6 +458,7 @@ impl Mesh {
     ///
     /// Find the [Intersection] (the primitive is either a vertex or edge) between the given edge and the point.
     /// If the edge is not close to the point, None is returned.
+    /// The point is considered to be at the edge if the distance is below a small margin, also when the `robust` feature is enabled.
     ///
     pub fn edge_point_intersection(
         &self,
@@ -208,6 +493,8 @@ impl Mesh {
     ///
     /// Find the [Intersection] (the primitive is either a vertex, edge or face) between the given face and the point.
     /// If the face is not close to the point, None is returned.
+    /// Enable the `robust` feature to test whether the point is inside the face using exact predicates,
+    /// the distance to the plane, the edges and the vertices of the face are still compared to a small margin.
     ///
     pub fn face_point_intersection(&self, face_id: FaceID, point: &Vec3) -> Option<Intersection> {
         let p = self.vertex_position(self.walker_from_face(face_id).vertex_id().unwrap());
@@ -219,6 +506,52 @@ impl Mesh {
         self.face_point_intersection_when_point_in_plane(face_id, point)
     }
 
+    /// Returns whether the two faces intersect anywhere else than at shared vertices.
+    fn face_face_intersects(&self, face_id1: FaceID, face_id2: FaceID) -> bool {
+        let (a0, a1, a2) = self.face_vertices(face_id1);
+        let (b0, b1, b2) = self.face_vertices(face_id2);
+        let shared: Vec<VertexID> = [a0, a1, a2]
+            .into_iter()
+            .filter(|v| *v == b0 || *v == b1 || *v == b2)
+            .collect();
+        if shared.len() > 1 {
+            return false;
+        }
+        let (p0, p1, p2) = self.face_positions(face_id1);
+        let (q0, q1, q2) = self.face_positions(face_id2);
+        #[cfg(feature = "robust")]
+        let is_coplanar = [q0, q1, q2]
+            .iter()
+            .all(|q| orient3d(&p0, &p1, &p2, q) == 0.0);
+        #[cfg(not(feature = "robust"))]
+        let is_coplanar = {
+            let n = self.face_normal(face_id1);
+            [q0, q1, q2].iter().all(|q| n.dot(q - p0).abs() < MARGIN)
+        };
+        if is_coplanar {
+            // The edges of coplanar faces can cross without any end point being inside the other face
+            return coplanar_triangles_overlap(
+                [p0, p1, p2],
+                [q0, q1, q2],
+                &self.face_normal(face_id1),
+            );
+        }
+        let edges_intersect_face = |edge_face_id: FaceID, face_id: FaceID| {
+            self.face_halfedge_iter(edge_face_id).any(|halfedge_id| {
+                let (p0, p1) = self.edge_positions(halfedge_id);
+                match self.face_line_piece_intersection(face_id, &p0, &p1) {
+                    Some(Intersection::Point {
+                        primitive: Primitive::Vertex(vertex_id),
+                        ..
+                    }) => !shared.contains(&vertex_id),
+                    Some(_) => true,
+                    None => false,
+                }
+            })
+        };
+        edges_intersect_face(face_id1, face_id2) || edges_intersect_face(face_id2, face_id1)
+    }
+
     /// Assumes that the point lies in the plane spanned by the face
     fn face_point_intersection_when_point_in_plane(
         &self,
@@ -234,14 +567,19 @@ impl Mesh {
 
         // Test whether the intersection point is located inside the face
         let (a, b, c) = self.face_positions(face_id);
-        let coords = barycentric(point, &a, &b, &c);
-        if 0.0 < coords.0
-            && coords.0 < 1.0
-            && 0.0 < coords.1
-            && coords.1 < 1.0
-            && 0.0 < coords.2
-            && coords.2 < 1.0
-        {
+        #[cfg(feature = "robust")]
+        let is_inside = is_point_in_triangle(point, &a, &b, &c, &self.face_direction(face_id));
+        #[cfg(not(feature = "robust"))]
+        let is_inside = {
+            let coords = barycentric(point, &a, &b, &c);
+            0.0 < coords.0
+                && coords.0 < 1.0
+                && 0.0 < coords.1
+                && coords.1 < 1.0
+                && 0.0 < coords.2
+                && coords.2 < 1.0
+        };
+        if is_inside {
             Some(Intersection::Point {
                 primitive: Primitive::Face(face_id),
                 point: *point,
@@ -255,7 +593,7 @@ impl Mesh {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use three_d_asset::{Positions, TriMesh};
+    use three_d_asset::{Indices, Positions, TriMesh};
 
     #[test]
     fn test_face_point_intersection_when_point_in_plane() {
@@ -342,6 +680,207 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "robust")]
+    #[test]
+    fn test_face_line_piece_intersection_exact_plane_classification() {
+        let mesh: Mesh = TriMesh {
+            positions: Positions::F64(vec![
+                vec3(1025.0, 1024.0, 1024.0),
+                vec3(1024.0, 1025.0, 1024.0),
+                vec3(1024.0, 1024.0, 1025.0),
+            ]),
+            ..Default::default()
+        }
+        .into();
+        let face_id = mesh.face_iter().next().unwrap();
+        let center = vec3(3073.0 / 3.0, 3073.0 / 3.0, 3073.0 / 3.0);
+        let normal = vec3(1.0, 1.0, 1.0).normalize();
+
+        // Both end points are slightly above the plane, which is within the margin, but not in the plane
+        let above = center + 0.1 * MARGIN * normal;
+        assert_eq!(
+            mesh.face_line_piece_intersection(face_id, &above, &(above + normal)),
+            None
+        );
+
+        // The end points are on each side of the plane, but one of them is within the margin of the plane
+        let result = mesh.face_line_piece_intersection(face_id, &above, &(center - normal));
+        match result {
+            Some(Intersection::Point {
+                primitive: Primitive::Face(id),
+                point,
+            }) => {
+                assert_eq!(id, face_id);
+                assert!(point.distance(center) < MARGIN);
+            }
+            _ => panic!("expected a face intersection, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_self_intersections() {
+        let mut mesh = crate::test_utility::cube();
+        assert!(mesh.self_intersections().is_empty());
+
+        let mut other = crate::test_utility::cube();
+        other.translate(vec3(0.5, 0.5, 0.5));
+        mesh.append(&other);
+
+        let intersections = mesh.self_intersections();
+        assert!(!intersections.is_empty());
+        for (face_id1, face_id2) in intersections {
+            assert!(face_id1 < face_id2);
+            let (v0, v1, v2) = mesh.face_vertices(face_id1);
+            let (v3, v4, v5) = mesh.face_vertices(face_id2);
+            assert!([v0, v1, v2]
+                .iter()
+                .all(|v| *v != v3 && *v != v4 && *v != v5));
+        }
+    }
+
+    #[test]
+    fn test_self_intersections_of_coplanar_faces() {
+        let mesh: Mesh = TriMesh {
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(2.0, 0.0, 0.0),
+                vec3(1.0, 1.5, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(2.0, 1.0, 0.0),
+                vec3(1.0, -0.5, 0.0),
+            ]),
+            indices: Indices::U32(vec![0, 1, 2, 3, 5, 4]),
+            ..Default::default()
+        }
+        .into();
+        assert_eq!(mesh.self_intersections().len(), 1);
+
+        // Coplanar faces sharing a vertex and overlapping
+        let mesh: Mesh = TriMesh {
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(2.0, 0.0, 0.0),
+                vec3(2.0, 1.0, 0.0),
+                vec3(2.0, -1.0, 0.0),
+                vec3(2.0, 0.5, 0.0),
+            ]),
+            indices: Indices::U32(vec![0, 1, 2, 0, 3, 4]),
+            ..Default::default()
+        }
+        .into();
+        assert_eq!(mesh.self_intersections().len(), 1);
+
+        // Coplanar faces only touching at a vertex or an edge
+        assert!(crate::test_utility::square()
+            .self_intersections()
+            .is_empty());
+        let mesh: Mesh = TriMesh {
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(-1.0, 0.0, 0.0),
+                vec3(0.0, -1.0, 0.0),
+                vec3(0.5, 0.5, 0.0),
+                vec3(1.0, 1.0, 0.0),
+            ]),
+            indices: Indices::U32(vec![0, 1, 2, 0, 3, 4, 5, 6, 2]),
+            ..Default::default()
+        }
+        .into();
+        assert!(mesh.self_intersections().is_empty());
+    }
+
+    #[test]
+    fn test_ray_hit() {
+        let mesh = crate::test_utility::square();
+        let start = vec3(0.2, -0.3, 2.0);
+        let direction = vec3(0.0, 0.0, -2.0);
+
+        let hit = mesh.ray_hit(&start, &direction).unwrap();
+
+        assert_eq!(hit.primitive, Primitive::Face(hit.face_id));
+        assert!(hit.point.distance(vec3(0.2, -0.3, 0.0)) < 0.000001);
+        assert!((hit.distance - 2.0).abs() < 0.000001);
+        let (b0, b1, b2) = hit.barycentric;
+        assert!((b0 + b1 + b2 - 1.0).abs() < 0.000001);
+        assert!(b0 > 0.0 && b1 > 0.0 && b2 > 0.0);
+        assert_eq!(
+            Some(Intersection::from(hit)),
+            mesh.ray_intersection(&start, &direction)
+        );
+        assert!(mesh.ray_hit(&start, &(-direction)).is_none());
+    }
+
+    #[test]
+    fn test_pick() {
+        let mesh = crate::test_utility::square();
+        let direction = vec3(0.0, 0.0, -1.0);
+        let pick = |x, y| mesh.pick(&Ray::new(vec3(x, y, 1.0), direction), 0.1);
+
+        let vertex_id = mesh
+            .vertex_iter()
+            .find(|v| mesh.vertex_position(*v) == vec3(1.0, 1.0, 0.0))
+            .unwrap();
+        assert_eq!(pick(0.95, 0.95), Some(Primitive::Vertex(vertex_id)));
+        match pick(0.0, 0.95) {
+            Some(Primitive::Edge(halfedge_id)) => {
+                let (p0, p1) = mesh.edge_positions(halfedge_id);
+                assert_eq!(p0.y, 1.0);
+                assert_eq!(p1.y, 1.0);
+            }
+            _ => panic!("expected an edge"),
+        }
+        assert!(matches!(pick(0.5, -0.2), Some(Primitive::Face(_))));
+        assert_eq!(pick(1.5, 0.0), None);
+    }
+
+    #[test]
+    fn test_ray_intersections() {
+        let mesh = crate::test_utility::cube();
+        let hits = mesh.ray_intersections(&vec3(0.3, 0.2, -3.0), &vec3(0.0, 0.0, 1.0));
+        assert_eq!(hits.len(), 2);
+        assert!(hits[0].distance < hits[1].distance);
+        assert!((hits[0].point.z + 1.0).abs() < 0.000001);
+        assert!((hits[1].point.z - 1.0).abs() < 0.000001);
+
+        // Through the diagonal edges of the faces and the corners of the cube
+        let hits = mesh.ray_intersections(&vec3(0.0, 0.0, -3.0), &vec3(0.0, 0.0, 1.0));
+        assert_eq!(hits.len(), 2);
+        let hits = mesh.ray_intersections(&vec3(-2.0, -2.0, -2.0), &vec3(1.0, 1.0, 1.0));
+        assert_eq!(hits.len(), 2);
+        assert!(hits
+            .iter()
+            .all(|hit| matches!(hit.primitive, Primitive::Vertex(_))));
+    }
+
+    #[test]
+    fn test_ray_intersections_batch() {
+        let mesh: Mesh = TriMesh::sphere(8).into();
+        let rays: Vec<Ray> = (0..50)
+            .map(|i| {
+                let angle = i as f64 * 0.4;
+                let spread = 0.3 + 0.02 * i as f64;
+                Ray::new(
+                    vec3(0.1 * angle.cos(), 2.0, 0.1 * angle.sin()),
+                    vec3(spread * angle.sin(), -1.0, spread * angle.cos()),
+                )
+            })
+            .collect();
+
+        let intersections = mesh.ray_intersections_batch(&rays);
+
+        assert_eq!(intersections.len(), rays.len());
+        assert!(intersections.iter().any(|i| i.is_some()));
+        assert!(intersections.iter().any(|i| i.is_none()));
+        for (ray, intersection) in rays.iter().zip(intersections) {
+            assert_eq!(
+                intersection,
+                mesh.ray_intersection(&ray.start_point, &ray.direction)
+            );
+        }
+    }
+
     #[test]
     fn test_edge_point_intersection() {
         let mesh: Mesh = TriMesh {
@@ -594,7 +1133,7 @@ mod tests {
     }
 }
 
-mod utility {
+pub(super) mod utility {
     use crate::math::*;
 
     pub const MARGIN: f64 = 0.0000001;
@@ -642,6 +1181,41 @@ mod utility {
         }
     }
 
+    /// Classifies the end points of the line piece against the plane through the triangle (a, b, c) using exact predicates,
+    /// ie. an end point is only in the plane if it is exactly in the plane.
+    #[cfg(feature = "robust")]
+    pub fn triangle_line_piece_intersection(
+        p0: &Vec3,
+        p1: &Vec3,
+        a: &Vec3,
+        b: &Vec3,
+        c: &Vec3,
+    ) -> Option<PlaneLinepieceIntersectionResult> {
+        use crate::operations::predicates::orient3d;
+        let d0 = orient3d(a, b, c, p0);
+        let d1 = orient3d(a, b, c, p1);
+        if d0 == 0.0 && d1 == 0.0 {
+            Some(PlaneLinepieceIntersectionResult::LineInPlane)
+        } else if d0 == 0.0 {
+            Some(PlaneLinepieceIntersectionResult::P0InPlane)
+        } else if d1 == 0.0 {
+            Some(PlaneLinepieceIntersectionResult::P1InPlane)
+        } else if d0.signum() != d1.signum() {
+            // The end points are strictly on each side, so the intersection point is computed in floating point and kept on the line piece
+            let n = (b - a).cross(c - a);
+            let p01 = p1 - p0;
+            let t = n.dot(a - p0) / n.dot(p01);
+            let t = if t.is_finite() {
+                t.clamp(0.0, 1.0)
+            } else {
+                0.5
+            };
+            Some(PlaneLinepieceIntersectionResult::Intersection(p0 + p01 * t))
+        } else {
+            None
+        }
+    }
+
     pub fn plane_ray_intersection(
         ray_start_point: &Vec3,
         ray_direction: &Vec3,
@@ -679,6 +1253,25 @@ mod utility {
         (u, v, w)
     }
 
+    /// Returns whether the interiors of the two triangles overlap, assuming that the triangles lie in the same plane with the given normal.
+    /// Triangles that only touch at an edge or a vertex do not overlap.
+    pub fn coplanar_triangles_overlap(a: [Vec3; 3], b: [Vec3; 3], normal: &Vec3) -> bool {
+        // The triangles are convex, so they do not overlap if and only if one of the edges separates them
+        let is_separated_by_edge = |t: &[Vec3; 3], other: &[Vec3; 3]| {
+            let sign = normal.dot((t[1] - t[0]).cross(t[2] - t[0])).signum();
+            (0..3).any(|i| {
+                let p0 = t[i];
+                let e = t[(i + 1) % 3] - p0;
+                let length = e.magnitude();
+                length > MARGIN
+                    && other
+                        .iter()
+                        .all(|q| sign * normal.dot(e.cross(q - p0)) / length < MARGIN)
+            })
+        };
+        !is_separated_by_edge(&a, &b) && !is_separated_by_edge(&b, &a)
+    }
+
     pub fn point_line_segment_distance(point: &Vec3, p0: &Vec3, p1: &Vec3) -> f64 {
         let v = p1 - p0;
         let w = point - p0;
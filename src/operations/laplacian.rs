@@ -0,0 +1,108 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+/// # Laplacian
+///
+/// The matrices are returned as a list of `(row, column, value)` triplets where each row and column appears at most once
+/// and where row and column `i` corresponds to the `i`'th vertex returned by [Mesh::vertex_iter].
+///
+impl Mesh {
+    ///
+    /// Returns the cotangent Laplacian as a sparse list of `(row, column, value)` triplets.
+    /// The off-diagonal entry of two connected vertices is minus half the sum of the cotangents of the angles opposite to the connecting edge
+    /// and the diagonal entry is minus the sum of the off-diagonal entries in that row, ie. the matrix is symmetric and positive semi-definite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let laplacian = mesh.cotan_laplacian();
+    /// let row_sum: f64 = laplacian.iter().filter(|(row, _, _)| *row == 0).map(|(_, _, value)| value).sum();
+    /// assert!(row_sum.abs() < 0.000001);
+    /// ```
+    ///
+    pub fn cotan_laplacian(&self) -> Vec<(usize, usize, f64)> {
+        let indices = self.vertex_index_map();
+        self.cotan_laplacian_triplets(&|vertex_id| Some(indices[&vertex_id]))
+    }
+
+    ///
+    /// Returns the lumped mass matrix as a sparse list of `(row, column, value)` triplets.
+    /// The matrix is diagonal and the diagonal entry of a vertex is its [mixed Voronoi area](Mesh::mixed_voronoi_area).
+    ///
+    pub fn mass_matrix(&self) -> Vec<(usize, usize, f64)> {
+        self.vertex_iter()
+            .enumerate()
+            .map(|(i, vertex_id)| (i, i, self.mixed_voronoi_area(vertex_id)))
+            .collect()
+    }
+
+    /// Returns the cotangent Laplacian restricted to the vertices for which the index function returns an index.
+    pub(crate) fn cotan_laplacian_triplets(
+        &self,
+        index: &dyn Fn(VertexID) -> Option<usize>,
+    ) -> Vec<(usize, usize, f64)> {
+        let mut triplets = Vec::new();
+        for vertex_id in self.vertex_iter() {
+            if let Some(i) = index(vertex_id) {
+                let diagonal = self
+                    .vertex_halfedge_iter(vertex_id)
+                    .map(|halfedge_id| self.cotangent_weight(halfedge_id))
+                    .sum();
+                triplets.push((i, i, diagonal));
+            }
+        }
+        for edge_id in self.edge_iter() {
+            let halfedge_id = edge_id.into();
+            let (v0, v1) = self.edge_vertices(halfedge_id);
+            if let (Some(i0), Some(i1)) = (index(v0), index(v1)) {
+                let weight = self.cotangent_weight(halfedge_id);
+                triplets.push((i0, i1, -weight));
+                triplets.push((i1, i0, -weight));
+            }
+        }
+        triplets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    #[test]
+    fn test_cotan_laplacian() {
+        let mesh: Mesh = TriMesh::sphere(8).into();
+        let laplacian = mesh.cotan_laplacian();
+        let n = mesh.no_vertices();
+
+        let mut dense = vec![vec![0.0; n]; n];
+        for (row, column, value) in laplacian.iter() {
+            assert_eq!(dense[*row][*column], 0.0);
+            dense[*row][*column] = *value;
+        }
+        for (i, row) in dense.iter().enumerate() {
+            assert!(row.iter().sum::<f64>().abs() < 0.000001);
+            assert!(row[i] > 0.0);
+            for (j, value) in row.iter().enumerate() {
+                assert_eq!(*value, dense[j][i]);
+            }
+        }
+        assert_eq!(laplacian.len(), n + 2 * mesh.no_edges());
+    }
+
+    #[test]
+    fn test_mass_matrix() {
+        let mesh: Mesh = TriMesh::sphere(8).into();
+        let area: f64 = mesh
+            .face_iter()
+            .map(|face_id| mesh.face_area(face_id))
+            .sum();
+        let mass = mesh.mass_matrix();
+        assert_eq!(mass.len(), mesh.no_vertices());
+        assert!(mass.iter().all(|(row, column, _)| row == column));
+        assert!((mass.iter().map(|(_, _, value)| value).sum::<f64>() - area).abs() < 0.000001);
+    }
+}
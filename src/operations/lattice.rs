@@ -0,0 +1,320 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::operations::Aabb;
+
+///
+/// The interpolation used to deform a position by a [Lattice].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LatticeInterpolation {
+    /// Trilinear interpolation of the eight lattice points around the position, so moving a lattice point only affects the positions in the adjacent cells.
+    Trilinear,
+    /// Bézier interpolation of all lattice points, ie. classic free-form deformation, which is smooth but where moving a lattice point affects all positions.
+    Bezier,
+}
+
+///
+/// A control lattice for free-form deformation, ie. a regular grid of points embedding a box, which deforms the space inside the box when the lattice points are moved.
+/// Construct the lattice around a mesh, move the lattice points using [Lattice::set_point] and then deform the mesh using [Mesh::deform_by_lattice].
+///
+#[derive(Clone, Debug)]
+pub struct Lattice {
+    aabb: Aabb,
+    resolution: [usize; 3],
+    points: Vec<Vec3>,
+}
+
+impl Lattice {
+    ///
+    /// Constructs a lattice with the given number of points along each axis, which is at least two, evenly distributed in the given box.
+    /// If the box has no extent along an axis, for example if it is the bounding box of a flat mesh, the box is padded slightly along that axis so that it has a volume.
+    ///
+    pub fn new(aabb: Aabb, resolution: [usize; 3]) -> Self {
+        let resolution = resolution.map(|r| r.max(2));
+        let padding = 0.001 * aabb.size().magnitude().max(1.0);
+        let mut aabb = aabb;
+        for axis in 0..3 {
+            if aabb.max[axis] - aabb.min[axis] <= 0.0 {
+                aabb.min[axis] -= padding;
+                aabb.max[axis] += padding;
+            }
+        }
+        let size = aabb.size();
+        let mut points = Vec::with_capacity(resolution[0] * resolution[1] * resolution[2]);
+        for k in 0..resolution[2] {
+            for j in 0..resolution[1] {
+                for i in 0..resolution[0] {
+                    let t = vec3(
+                        i as f64 / (resolution[0] - 1) as f64,
+                        j as f64 / (resolution[1] - 1) as f64,
+                        k as f64 / (resolution[2] - 1) as f64,
+                    );
+                    points.push(aabb.min + vec3(t.x * size.x, t.y * size.y, t.z * size.z));
+                }
+            }
+        }
+        Self {
+            aabb,
+            resolution,
+            points,
+        }
+    }
+
+    ///
+    /// Constructs a lattice with the given number of points along each axis around the axis aligned bounding box of the mesh.
+    /// The box is padded slightly, so that it also has a volume if the mesh is flat.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::cylinder(0.5, 2.0, 16, true);
+    /// let mut lattice = Lattice::new_around(&mesh, [2, 3, 2]);
+    /// // Bulge the middle of the cylinder
+    /// for i in 0..2 {
+    ///     for k in 0..2 {
+    ///         let p = lattice.point(i, 1, k);
+    ///         lattice.set_point(i, 1, k, vec3(2.0 * p.x, p.y, 2.0 * p.z));
+    ///     }
+    /// }
+    /// mesh.deform_by_lattice(&lattice, LatticeInterpolation::Bezier);
+    /// let aabb = mesh.aabb().unwrap();
+    /// assert!(aabb.size().x > 1.0);
+    /// ```
+    ///
+    pub fn new_around(mesh: &Mesh, resolution: [usize; 3]) -> Self {
+        let aabb = mesh
+            .aabb()
+            .unwrap_or(Aabb::new(vec3(0.0, 0.0, 0.0), vec3(0.0, 0.0, 0.0)));
+        let padding = 0.001 * aabb.size().magnitude().max(1.0);
+        let padding = vec3(padding, padding, padding);
+        Self::new(
+            Aabb::new(aabb.min - padding, aabb.max + padding),
+            resolution,
+        )
+    }
+
+    /// Returns the number of lattice points along each axis.
+    pub fn resolution(&self) -> [usize; 3] {
+        self.resolution
+    }
+
+    /// Returns the position of the lattice point with the given indices along each axis.
+    /// Panics if an index is not less than the [resolution](Lattice::resolution) along its axis.
+    pub fn point(&self, i: usize, j: usize, k: usize) -> Vec3 {
+        self.points[self.index(i, j, k)]
+    }
+
+    /// Moves the lattice point with the given indices along each axis to the given position.
+    /// Panics if an index is not less than the [resolution](Lattice::resolution) along its axis.
+    pub fn set_point(&mut self, i: usize, j: usize, k: usize, position: Vec3) {
+        let index = self.index(i, j, k);
+        self.points[index] = position;
+    }
+
+    ///
+    /// Returns the deformed position of the given position, which is unchanged if no lattice points are moved.
+    /// Positions outside the box of the lattice are deformed by extrapolation.
+    ///
+    pub fn deform(&self, position: Vec3, interpolation: LatticeInterpolation) -> Vec3 {
+        let size = self.aabb.size();
+        let t = position - self.aabb.min;
+        let t = [t.x / size.x, t.y / size.y, t.z / size.z];
+        match interpolation {
+            LatticeInterpolation::Trilinear => {
+                // Find the cell and the local coordinates in the cell along each axis
+                let mut cell = [0; 3];
+                let mut local = [0.0; 3];
+                for axis in 0..3 {
+                    let no_cells = self.resolution[axis] - 1;
+                    let x = t[axis] * no_cells as f64;
+                    cell[axis] = (x.floor().max(0.0) as usize).min(no_cells - 1);
+                    local[axis] = x - cell[axis] as f64;
+                }
+                let mut result = vec3(0.0, 0.0, 0.0);
+                for corner in 0..8 {
+                    let offset = [corner & 1, (corner >> 1) & 1, corner >> 2];
+                    let mut weight = 1.0;
+                    for axis in 0..3 {
+                        weight *= if offset[axis] == 1 {
+                            local[axis]
+                        } else {
+                            1.0 - local[axis]
+                        };
+                    }
+                    result += weight
+                        * self.point(
+                            cell[0] + offset[0],
+                            cell[1] + offset[1],
+                            cell[2] + offset[2],
+                        );
+                }
+                result
+            }
+            LatticeInterpolation::Bezier => {
+                let weights: Vec<Vec<f64>> = (0..3)
+                    .map(|axis| bernstein(self.resolution[axis] - 1, t[axis]))
+                    .collect();
+                let mut result = vec3(0.0, 0.0, 0.0);
+                for k in 0..self.resolution[2] {
+                    for j in 0..self.resolution[1] {
+                        for i in 0..self.resolution[0] {
+                            result +=
+                                weights[0][i] * weights[1][j] * weights[2][k] * self.point(i, j, k);
+                        }
+                    }
+                }
+                result
+            }
+        }
+    }
+
+    fn index(&self, i: usize, j: usize, k: usize) -> usize {
+        assert!(
+            i < self.resolution[0] && j < self.resolution[1] && k < self.resolution[2],
+            "the lattice point ({}, {}, {}) is outside a lattice with resolution {:?}",
+            i,
+            j,
+            k,
+            self.resolution
+        );
+        i + self.resolution[0] * (j + self.resolution[1] * k)
+    }
+}
+
+///
+/// Returns the Bernstein polynomials of the given degree evaluated at `t`.
+///
+fn bernstein(degree: usize, t: f64) -> Vec<f64> {
+    let mut binomial = 1.0;
+    (0..=degree)
+        .map(|i| {
+            if i > 0 {
+                binomial *= (degree - i + 1) as f64 / i as f64;
+            }
+            binomial * t.powi(i as i32) * (1.0 - t).powi((degree - i) as i32)
+        })
+        .collect()
+}
+
+/// # Lattice deformation
+impl Mesh {
+    ///
+    /// Deforms the mesh by moving each vertex to its deformed position given by the lattice (see [Lattice::deform]), ie. free-form deformation.
+    ///
+    pub fn deform_by_lattice(&mut self, lattice: &Lattice, interpolation: LatticeInterpolation) {
+        for vertex_id in self.vertex_iter() {
+            let position = lattice.deform(self.vertex_position(vertex_id), interpolation);
+            self.set_vertex_position(vertex_id, position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undeformed_lattice() {
+        let mesh = Mesh::icosphere(1);
+        let lattice = Lattice::new_around(&mesh, [3, 4, 5]);
+        for interpolation in [
+            LatticeInterpolation::Trilinear,
+            LatticeInterpolation::Bezier,
+        ] {
+            let mut deformed = mesh.clone();
+            deformed.deform_by_lattice(&lattice, interpolation);
+            for vertex_id in mesh.vertex_iter() {
+                assert!(
+                    (deformed.vertex_position(vertex_id) - mesh.vertex_position(vertex_id))
+                        .magnitude()
+                        < 0.000001
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lattice_around_flat_mesh() {
+        let mesh = crate::test_utility::square();
+        let lattice = Lattice::new(mesh.aabb().unwrap(), [3, 3, 3]);
+        let mut moved = lattice.clone();
+        moved.set_point(1, 1, 2, lattice.point(1, 1, 2) + vec3(0.0, 0.0, 1.0));
+        for interpolation in [
+            LatticeInterpolation::Trilinear,
+            LatticeInterpolation::Bezier,
+        ] {
+            let mut deformed = mesh.clone();
+            deformed.deform_by_lattice(&lattice, interpolation);
+            for vertex_id in mesh.vertex_iter() {
+                assert!(
+                    (deformed.vertex_position(vertex_id) - mesh.vertex_position(vertex_id))
+                        .magnitude()
+                        < 0.000001
+                );
+            }
+
+            let mut deformed = mesh.clone();
+            deformed.deform_by_lattice(&moved, interpolation);
+            for vertex_id in deformed.vertex_iter() {
+                let p = deformed.vertex_position(vertex_id);
+                assert!(p.x.is_finite() && p.y.is_finite() && p.z.is_finite());
+            }
+        }
+    }
+
+    #[test]
+    fn test_deform_by_lattice() {
+        let lattice = Lattice::new(
+            Aabb::new(vec3(0.0, 0.0, 0.0), vec3(2.0, 2.0, 2.0)),
+            [3, 3, 3],
+        );
+        let mut translated = lattice.clone();
+        let mut moved = lattice.clone();
+        for k in 0..3 {
+            for j in 0..3 {
+                for i in 0..3 {
+                    translated.set_point(i, j, k, lattice.point(i, j, k) + vec3(1.0, 2.0, 3.0));
+                }
+            }
+        }
+        moved.set_point(2, 2, 2, vec3(3.0, 3.0, 3.0));
+
+        for interpolation in [
+            LatticeInterpolation::Trilinear,
+            LatticeInterpolation::Bezier,
+        ] {
+            let p = vec3(0.3, 1.2, 1.7);
+            assert!(
+                (translated.deform(p, interpolation) - (p + vec3(1.0, 2.0, 3.0))).magnitude()
+                    < 0.000001
+            );
+            // The corners follow the lattice points
+            assert!(
+                (moved.deform(vec3(2.0, 2.0, 2.0), interpolation) - vec3(3.0, 3.0, 3.0))
+                    .magnitude()
+                    < 0.000001
+            );
+        }
+
+        // Trilinear interpolation is local, Bézier interpolation is global
+        let p = vec3(0.5, 0.5, 0.5);
+        assert_eq!(moved.deform(p, LatticeInterpolation::Trilinear), p);
+        assert!(moved.deform(p, LatticeInterpolation::Bezier).x > p.x);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a lattice")]
+    fn test_point_outside_lattice() {
+        let lattice = Lattice::new_around(&Mesh::icosphere(1), [2, 2, 2]);
+        lattice.point(2, 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a lattice")]
+    fn test_set_point_outside_lattice() {
+        let mut lattice = Lattice::new_around(&Mesh::icosphere(1), [2, 2, 2]);
+        lattice.set_point(0, 0, 2, vec3(0.0, 0.0, 0.0));
+    }
+}
@@ -1,6 +1,7 @@
 //! See [Mesh](crate::mesh::Mesh).
 
 use crate::mesh::*;
+use std::collections::HashMap;
 
 /// # Merge
 impl Mesh {
@@ -8,10 +9,44 @@ impl Mesh {
     /// Merges the mesh together with the `other` mesh.
     /// The `other` mesh primitives are copied to the current mesh (and `other` is therefore not changed)
     /// followed by merging of overlapping primitives.
+    /// Returns the mapping from the ids of the vertices and faces of the `other` mesh to the ids in this mesh after the merge,
+    /// where an overlapping vertex or face is mapped to the vertex or face of this mesh it is merged with.
     ///
-    pub fn merge_with(&mut self, other: &Self) {
-        self.append(other);
-        self.merge_overlapping_primitives();
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::grid(vec3(0.0, 0.0, 0.0), vec3(2.0, 0.0, 0.0), vec3(0.0, 2.0, 0.0), 1, 1);
+    /// let other = Mesh::grid(vec3(2.0, 0.0, 0.0), vec3(2.0, 0.0, 0.0), vec3(0.0, 2.0, 0.0), 1, 1);
+    ///
+    /// let (vertices, faces) = mesh.merge_with(&other);
+    ///
+    /// assert_eq!(mesh.no_vertices(), 6);
+    /// for vertex_id in other.vertex_iter() {
+    ///     assert_eq!(mesh.vertex_position(vertices[&vertex_id]), other.vertex_position(vertex_id));
+    /// }
+    /// # assert_eq!(faces.len(), 2);
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn merge_with(
+        &mut self,
+        other: &Self,
+    ) -> (HashMap<VertexID, VertexID>, HashMap<FaceID, FaceID>) {
+        let (mut vertices, mut faces) = self.append(other);
+        let (merged_vertices, merged_faces) =
+            self.merge_overlapping_primitives_internal(self.merge_epsilon());
+        for vertex_id in vertices.values_mut() {
+            if let Some(surviving_vertex_id) = merged_vertices.get(vertex_id) {
+                *vertex_id = *surviving_vertex_id;
+            }
+        }
+        for face_id in faces.values_mut() {
+            if let Some(surviving_face_id) = merged_faces.get(face_id) {
+                *face_id = *surviving_face_id;
+            }
+        }
+        (vertices, faces)
     }
 }
 
@@ -57,7 +92,7 @@ mod tests {
         let mut mesh2 = crate::test_utility::cube();
         mesh2.translate(vec3(0.5, 0.5, 0.5));
 
-        let (meshes1, meshes2) = mesh1.split_at_intersection(&mut mesh2);
+        let (meshes1, meshes2) = mesh1.split_at_intersection(&mut mesh2).unwrap();
 
         let mut result = meshes1.first().unwrap().clone();
         result.merge_with(meshes2.first().unwrap());
@@ -0,0 +1,54 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::Error;
+
+/// # Mesh measures
+impl Mesh {
+    ///
+    /// Returns the volume enclosed by the mesh computed as the sum of the signed volumes of the tetrahedra spanned by origo and each face.
+    /// The volume is positive if the face normals point outwards and negative otherwise (see [Mesh::orient_consistently]).
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the mesh is not closed, since the enclosed volume is then not well-defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(32).into();
+    /// let volume = mesh.volume().unwrap();
+    /// assert!((volume - 4.0 / 3.0 * std::f64::consts::PI).abs() < 0.05);
+    /// ```
+    ///
+    pub fn volume(&self) -> Result<f64, Error> {
+        if !self.is_closed() {
+            return Err(Error::MeshIsNotClosed("volume".to_string()));
+        }
+        Ok(self.signed_volume_of_faces(self.face_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_of_cube() {
+        let mut mesh = crate::test_utility::cube();
+        assert!((mesh.volume().unwrap() - 8.0).abs() < 0.000001);
+
+        mesh.translate(vec3(3.0, -2.0, 1.0));
+        assert!((mesh.volume().unwrap() - 8.0).abs() < 0.000001);
+
+        mesh.flip_orientation();
+        assert!((mesh.volume().unwrap() + 8.0).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_volume_of_open_mesh() {
+        let mesh = crate::test_utility::square();
+        assert!(mesh.volume().is_err());
+    }
+}
@@ -0,0 +1,117 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use rayon::prelude::*;
+
+/// # Parallel
+///
+/// Parallel iterators and parallel versions of methods that compute a value for each vertex or face.
+/// The methods are only available when the `rayon` feature is enabled.
+///
+impl Mesh {
+    ///
+    /// Returns a parallel iterator over the vertices, visiting the same vertices as [Mesh::vertex_iter].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// use rayon::prelude::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let max_distance = mesh
+    ///     .par_vertex_iter()
+    ///     .map(|vertex_id| mesh.vertex_position(vertex_id).magnitude())
+    ///     .reduce(|| 0.0, f64::max);
+    /// assert!((max_distance - 1.0).abs() < 0.000001);
+    /// ```
+    ///
+    pub fn par_vertex_iter(&self) -> rayon::vec::IntoIter<VertexID> {
+        self.vertex_iter().collect::<Vec<_>>().into_par_iter()
+    }
+
+    ///
+    /// Returns a parallel iterator over the faces, visiting the same faces as [Mesh::face_iter].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// use rayon::prelude::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let area: f64 = mesh.par_face_iter().map(|face_id| mesh.face_area(face_id)).sum();
+    /// let sequential_area: f64 = mesh.face_iter().map(|face_id| mesh.face_area(face_id)).sum();
+    /// assert!((area - sequential_area).abs() < 0.000001);
+    /// ```
+    ///
+    pub fn par_face_iter(&self) -> rayon::vec::IntoIter<FaceID> {
+        self.face_iter().collect::<Vec<_>>().into_par_iter()
+    }
+
+    ///
+    /// Returns the normal (see [Mesh::vertex_normal]) of all vertices computed in parallel
+    /// in the same order as the vertices are visited by [Mesh::vertex_iter], ie. the same order as the positions of the exported mesh.
+    ///
+    pub fn par_vertex_normal_buffer(&self) -> Vec<Vec3> {
+        self.par_vertex_iter()
+            .map(|vertex_id| self.vertex_normal(vertex_id))
+            .collect()
+    }
+
+    ///
+    /// Returns the normal (see [Mesh::face_normal]) of all faces computed in parallel
+    /// in the same order as the faces are visited by [Mesh::face_iter].
+    ///
+    pub fn par_face_normal_buffer(&self) -> Vec<Vec3> {
+        self.par_face_iter()
+            .map(|face_id| self.face_normal(face_id))
+            .collect()
+    }
+
+    ///
+    /// Returns the Gaussian curvature of all vertices computed in parallel, see [Mesh::gaussian_curvature_buffer].
+    ///
+    pub fn par_gaussian_curvature_buffer(&self) -> Vec<f64> {
+        self.par_vertex_iter()
+            .map(|vertex_id| self.gaussian_curvature(vertex_id))
+            .collect()
+    }
+
+    ///
+    /// Returns the mean curvature of all vertices computed in parallel, see [Mesh::mean_curvature_buffer].
+    ///
+    pub fn par_mean_curvature_buffer(&self) -> Vec<f64> {
+        self.par_vertex_iter()
+            .map(|vertex_id| self.mean_curvature(vertex_id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    #[test]
+    fn test_parallel_buffers_equal_sequential() {
+        let mesh: Mesh = TriMesh::sphere(8).into();
+
+        let normals: Vec<Vec3> = mesh
+            .vertex_iter()
+            .map(|vertex_id| mesh.vertex_normal(vertex_id))
+            .collect();
+        assert_eq!(mesh.par_vertex_normal_buffer(), normals);
+        let normals: Vec<Vec3> = mesh
+            .face_iter()
+            .map(|face_id| mesh.face_normal(face_id))
+            .collect();
+        assert_eq!(mesh.par_face_normal_buffer(), normals);
+        assert_eq!(
+            mesh.par_gaussian_curvature_buffer(),
+            mesh.gaussian_curvature_buffer()
+        );
+        assert_eq!(
+            mesh.par_mean_curvature_buffer(),
+            mesh.mean_curvature_buffer()
+        );
+    }
+}
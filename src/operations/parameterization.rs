@@ -0,0 +1,577 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use super::heat::solve;
+use crate::mesh::*;
+use crate::Error;
+use nalgebra::DMatrix;
+use nalgebra_sparse::CooMatrix;
+
+///
+/// The shape that the boundary of a mesh is mapped to by [Mesh::parameterize_harmonic].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryMapping {
+    /// The boundary is mapped to the circle inscribed in the unit square, ie. with center `(0.5, 0.5)` and radius `0.5`.
+    Circle,
+    /// The boundary is mapped to the boundary of the unit square.
+    /// The first corner of the square is the boundary vertex with the smallest interior angle and the other three corners are the boundary vertices closest to dividing the boundary into four parts of equal length.
+    Square,
+}
+
+/// # Parameterization
+impl Mesh {
+    ///
+    /// Maps the mesh to the plane by fixing the boundary vertices to the given shape and solving the Laplace equation with cotangent weights for the interior vertices,
+    /// ie. the interior vertices are placed such that the map is harmonic and therefore as smooth as possible.
+    /// The boundary vertices are distributed along the shape according to the length of the boundary edges and the faces keep their orientation,
+    /// so the returned positions can for example be used as uv coordinates (see [Mesh::set_vertex_uv]).
+    ///
+    /// The map is guaranteed to be without fold-overs for meshes without obtuse angles, but it can contain flipped faces if the mesh has very obtuse angles.
+    ///
+    /// **Note:** Requires the `solver` feature.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the mesh is not a topological disk, ie. connected with exactly one boundary loop and no handles,
+    /// or if the linear system cannot be solved, for example if the mesh contains degenerate faces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// let (hemisphere, _) = mesh.cut_by_plane(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), false).unwrap();
+    /// let uvs = hemisphere.parameterize_harmonic(BoundaryMapping::Circle).unwrap();
+    /// assert_eq!(uvs.len(), hemisphere.no_vertices());
+    /// ```
+    ///
+    pub fn parameterize_harmonic(
+        &self,
+        boundary_mapping: BoundaryMapping,
+    ) -> Result<VertexProperty<Vec2>, Error> {
+        self.check_is_disk("harmonic parameterization")?;
+        let mut uvs = self.map_boundary(boundary_mapping);
+
+        let interior: Vec<VertexID> = self
+            .vertex_iter()
+            .filter(|vertex_id| !uvs.contains_key(vertex_id))
+            .collect();
+        if interior.is_empty() {
+            return Ok(uvs);
+        }
+        let indices: VertexProperty<usize> = interior
+            .iter()
+            .enumerate()
+            .map(|(i, vertex_id)| (*vertex_id, i))
+            .collect();
+
+        // Solve L_II x_I = -L_IB x_B for the interior vertices I given the fixed boundary vertices B
+        let n = interior.len();
+        let mut system = CooMatrix::new(n, n);
+        for (row, column, value) in
+            self.cotan_laplacian_triplets(&|vertex_id| indices.get(&vertex_id).copied())
+        {
+            system.push(row, column, value);
+        }
+        let mut rhs = DMatrix::zeros(n, 2);
+        for (i, vertex_id) in interior.iter().enumerate() {
+            for halfedge_id in self.vertex_halfedge_iter(*vertex_id) {
+                let neighbour = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                if let Some(uv) = uvs.get(&neighbour) {
+                    let weight = self.cotangent_weight(halfedge_id);
+                    rhs[(i, 0)] += weight * uv.x;
+                    rhs[(i, 1)] += weight * uv.y;
+                }
+            }
+        }
+        let solution = solve(system, rhs)?;
+        for (i, vertex_id) in interior.iter().enumerate() {
+            uvs.insert(*vertex_id, vec2(solution[(i, 0)], solution[(i, 1)]));
+        }
+        Ok(uvs)
+    }
+
+    ///
+    /// Maps the mesh to the plane using least squares conformal maps (Lévy et al., "Least Squares Conformal Maps for Automatic Texture Atlas Generation"),
+    /// ie. the map which preserves the angles of the faces as well as possible.
+    /// In contrast to [Mesh::parameterize_harmonic], the boundary is free, which results in less distortion.
+    /// Two boundary vertices far from each other are fixed at `(0, 0)` and `(d, 0)`, where `d` is the distance between them, so the scale of the map is similar to the scale of the mesh.
+    /// The faces keep their orientation in the map.
+    ///
+    /// **Note:** Requires the `solver` feature.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the mesh is not a topological disk, ie. connected with exactly one boundary loop and no handles,
+    /// or if the linear system cannot be solved, for example if the mesh contains degenerate faces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// let (hemisphere, _) = mesh.cut_by_plane(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), false).unwrap();
+    /// let uvs = hemisphere.parameterize_lscm().unwrap();
+    /// assert_eq!(uvs.len(), hemisphere.no_vertices());
+    /// ```
+    ///
+    pub fn parameterize_lscm(&self) -> Result<VertexProperty<Vec2>, Error> {
+        self.check_is_disk("least squares conformal maps")?;
+        let indices = self.vertex_index_map();
+        let n = indices.len();
+
+        // Fix two boundary vertices which are approximately the farthest apart
+        let boundary: Vec<VertexID> = self
+            .vertex_iter()
+            .filter(|vertex_id| self.is_vertex_on_boundary(*vertex_id))
+            .collect();
+        let farthest = |from: VertexID| {
+            let p = self.vertex_position(from);
+            *boundary
+                .iter()
+                .max_by(|a, b| {
+                    p.distance2(self.vertex_position(**a))
+                        .total_cmp(&p.distance2(self.vertex_position(**b)))
+                })
+                .unwrap()
+        };
+        let pin1 = farthest(boundary[0]);
+        let pin0 = farthest(pin1);
+        let distance = self
+            .vertex_position(pin0)
+            .distance(self.vertex_position(pin1));
+        let pinned = [(pin0, vec2(0.0, 0.0)), (pin1, vec2(distance, 0.0))];
+
+        // The conformal energy is the Dirichlet energy minus the area of the map, where the area is given by the boundary edges.
+        // Row and column 2i and 2i + 1 corresponds to the u and v coordinate of the i'th vertex.
+        let mut triplets = Vec::new();
+        for (row, column, value) in
+            self.cotan_laplacian_triplets(&|vertex_id| Some(indices[&vertex_id]))
+        {
+            triplets.push((2 * row, 2 * column, value));
+            triplets.push((2 * row + 1, 2 * column + 1, value));
+        }
+        for halfedge_id in self.halfedge_iter() {
+            let mut walker = self.walker_from_halfedge(halfedge_id);
+            if walker.face_id().is_some() && walker.as_twin().face_id().is_none() {
+                let (v1, v0) = self.edge_vertices(halfedge_id);
+                let (i, j) = (indices[&v0], indices[&v1]);
+                for (row, column, value) in [
+                    (2 * i, 2 * j + 1, -0.5),
+                    (2 * j + 1, 2 * i, -0.5),
+                    (2 * j, 2 * i + 1, 0.5),
+                    (2 * i + 1, 2 * j, 0.5),
+                ] {
+                    triplets.push((row, column, value));
+                }
+            }
+        }
+
+        // Minimize the energy with respect to the coordinates of the vertices which are not fixed
+        let mut fixed = vec![None; 2 * n];
+        for (vertex_id, uv) in pinned {
+            fixed[2 * indices[&vertex_id]] = Some(uv.x);
+            fixed[2 * indices[&vertex_id] + 1] = Some(uv.y);
+        }
+        let mut free_indices = vec![0; 2 * n];
+        let mut no_free = 0;
+        for (i, value) in fixed.iter().enumerate() {
+            if value.is_none() {
+                free_indices[i] = no_free;
+                no_free += 1;
+            }
+        }
+        let mut system = CooMatrix::new(no_free, no_free);
+        let mut rhs = DMatrix::zeros(no_free, 1);
+        for (row, column, value) in triplets {
+            if fixed[row].is_some() {
+                continue;
+            }
+            match fixed[column] {
+                Some(x) => rhs[free_indices[row]] -= value * x,
+                None => system.push(free_indices[row], free_indices[column], value),
+            }
+        }
+        let solution = solve(system, rhs)?;
+        let coordinate = |i: usize| fixed[i].unwrap_or_else(|| solution[free_indices[i]]);
+        Ok(self
+            .vertex_iter()
+            .map(|vertex_id| {
+                let i = indices[&vertex_id];
+                (vertex_id, vec2(coordinate(2 * i), coordinate(2 * i + 1)))
+            })
+            .collect())
+    }
+
+    ///
+    /// Computes uv coordinates using [Mesh::parameterize_lscm] and stores them as the uv coordinates of the vertices (see [Mesh::set_vertex_uv]), so they are exported with the mesh.
+    /// The uv coordinates are translated and uniformly scaled to fit inside the unit square.
+    ///
+    /// **Note:** Requires the `solver` feature.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the mesh is not a topological disk or if the linear system cannot be solved, in which case the stored uv coordinates are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::grid(vec3(0.0, 0.0, 0.0), vec3(2.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), 4, 2);
+    /// mesh.generate_uvs().unwrap();
+    /// let uvs = mesh.export().uvs.unwrap();
+    /// assert!(uvs.iter().all(|uv| uv.x >= 0.0 && uv.x <= 1.0 && uv.y >= 0.0 && uv.y <= 1.0));
+    /// ```
+    ///
+    pub fn generate_uvs(&mut self) -> Result<(), Error> {
+        let uvs = self.parameterize_lscm()?;
+        let mut min = vec2(f64::INFINITY, f64::INFINITY);
+        let mut max = vec2(f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for uv in uvs.values() {
+            min = vec2(min.x.min(uv.x), min.y.min(uv.y));
+            max = vec2(max.x.max(uv.x), max.y.max(uv.y));
+        }
+        let size = (max.x - min.x).max(max.y - min.y).max(f64::EPSILON);
+        for (vertex_id, uv) in uvs {
+            let uv = (uv - min) / size;
+            self.set_vertex_uv(vertex_id, three_d_asset::vec2(uv.x as f32, uv.y as f32));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Maps a closed mesh with genus zero onto the unit sphere centered at the origin, which is for example useful for morphing between two meshes or for spherical harmonics analysis.
+    /// The mesh is flowed towards a sphere using conformalized mean curvature flow (Kazhdan et al., "Can Mean-Curvature Flow Be Made Non-Singular?"),
+    /// where the mesh is normalized to the area of the unit sphere after each step, and the vertices are then projected onto the sphere.
+    /// Since the flow is close to conformal, the angles of the faces are approximately preserved.
+    ///
+    /// **Note:** Requires the `solver` feature.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the mesh is not a topological sphere, ie. connected and closed with no handles,
+    /// or if the linear system cannot be solved, for example if the mesh contains degenerate faces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::cylinder(1.0, 4.0, 16, true);
+    /// let positions = mesh.parameterize_spherical().unwrap();
+    /// assert!(positions.values().all(|p| (p.magnitude() - 1.0).abs() < 0.000001));
+    /// ```
+    ///
+    pub fn parameterize_spherical(&self) -> Result<VertexProperty<Vec3>, Error> {
+        if self.no_faces() == 0
+            || !self.is_closed()
+            || self.euler_characteristic() != 2
+            || self.connected_components().len() != 1
+        {
+            return Err(Error::MeshIsNotSphere(
+                "spherical parameterization".to_string(),
+            ));
+        }
+        const STEP: f64 = 0.05;
+        const MAX_ITERATIONS: usize = 100;
+        const TOLERANCE: f64 = 0.001;
+
+        let mut mesh = self.clone();
+        mesh.normalize_to_unit_sphere_area();
+        // The stiffness matrix is kept fixed while the mass matrix is updated, which is what keeps the flow from developing singularities
+        let stiffness = mesh.cotan_laplacian();
+        let n = mesh.no_vertices();
+        for _ in 0..MAX_ITERATIONS {
+            let vertices: Vec<VertexID> = mesh.vertex_iter().collect();
+            let masses: Vec<f64> = vertices
+                .iter()
+                .map(|vertex_id| mesh.mixed_voronoi_area(*vertex_id))
+                .collect();
+
+            // Solve (M + t L) x' = M x
+            let mut system = CooMatrix::new(n, n);
+            for (row, column, value) in stiffness.iter() {
+                system.push(*row, *column, STEP * value);
+            }
+            let mut rhs = DMatrix::zeros(n, 3);
+            for (i, vertex_id) in vertices.iter().enumerate() {
+                system.push(i, i, masses[i]);
+                let p = mesh.vertex_position(*vertex_id);
+                for k in 0..3 {
+                    rhs[(i, k)] = masses[i] * p[k];
+                }
+            }
+            let solution = solve(system, rhs)?;
+            for (i, vertex_id) in vertices.iter().enumerate() {
+                mesh.set_vertex_position(
+                    *vertex_id,
+                    vec3(solution[(i, 0)], solution[(i, 1)], solution[(i, 2)]),
+                );
+            }
+            mesh.normalize_to_unit_sphere_area();
+            if mesh.vertex_iter().all(|vertex_id| {
+                (mesh.vertex_position(vertex_id).magnitude() - 1.0).abs() < TOLERANCE
+            }) {
+                break;
+            }
+        }
+        Ok(mesh
+            .vertex_iter()
+            .map(|vertex_id| (vertex_id, mesh.vertex_position(vertex_id).normalize()))
+            .collect())
+    }
+
+    /// Translates the mesh such that the area weighted center is at the origin and scales it to have the same area as the unit sphere.
+    fn normalize_to_unit_sphere_area(&mut self) {
+        let mut center = Vec3::zero();
+        let mut area = 0.0;
+        for vertex_id in self.vertex_iter() {
+            let vertex_area = self.mixed_voronoi_area(vertex_id);
+            center += vertex_area * self.vertex_position(vertex_id);
+            area += vertex_area;
+        }
+        let center = center / area;
+        let scale = (4.0 * std::f64::consts::PI / area).sqrt();
+        for vertex_id in self.vertex_iter() {
+            let p = self.vertex_position(vertex_id);
+            self.set_vertex_position(vertex_id, scale * (p - center));
+        }
+    }
+
+    /// Returns an error if the mesh is not connected with exactly one boundary loop and no handles.
+    fn check_is_disk(&self, operation: &str) -> Result<(), Error> {
+        if self.no_faces() == 0
+            || self.euler_characteristic() != 1
+            || self.no_boundary_loops() != 1
+            || self.connected_components().len() != 1
+        {
+            return Err(Error::MeshIsNotDisk(operation.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Maps the vertices on the boundary of a mesh with a single boundary loop to the given shape.
+    fn map_boundary(&self, boundary_mapping: BoundaryMapping) -> VertexProperty<Vec2> {
+        let start = self
+            .halfedge_iter()
+            .find(|halfedge_id| self.walker_from_halfedge(*halfedge_id).face_id().is_none())
+            .unwrap();
+        // The boundary half-edges go clockwise around the mesh, so the boundary vertices are reversed to go counterclockwise
+        let mut boundary: Vec<VertexID> = self
+            .boundary_loop_iter(start)
+            .map(|halfedge_id| self.walker_from_halfedge(halfedge_id).vertex_id().unwrap())
+            .collect();
+        boundary.reverse();
+        if boundary_mapping == BoundaryMapping::Square {
+            let angle_sum = |vertex_id: VertexID| -> f64 {
+                self.vertex_corners(vertex_id)
+                    .into_iter()
+                    .map(|(p, p1, p2)| (p1 - p).angle(p2 - p).0)
+                    .sum()
+            };
+            let sharpest = (0..boundary.len())
+                .min_by(|a, b| angle_sum(boundary[*a]).total_cmp(&angle_sum(boundary[*b])))
+                .unwrap();
+            boundary.rotate_left(sharpest);
+        }
+        let n = boundary.len();
+        let mut lengths = vec![0.0; n + 1];
+        for i in 0..n {
+            lengths[i + 1] = lengths[i]
+                + self
+                    .vertex_position(boundary[i])
+                    .distance(self.vertex_position(boundary[(i + 1) % n]));
+        }
+        let total_length = lengths[n];
+
+        let parameters: Vec<f64> = match boundary_mapping {
+            BoundaryMapping::Circle => lengths[..n].iter().map(|l| l / total_length).collect(),
+            BoundaryMapping::Square if n < 4 => {
+                lengths[..n].iter().map(|l| l / total_length).collect()
+            }
+            BoundaryMapping::Square => {
+                // Place the corners at the vertices closest to a quarter of the boundary length apart
+                let mut corners = [0, 0, 0, 0, n];
+                for k in 1..4 {
+                    let target = k as f64 * 0.25 * total_length;
+                    let closest = (0..n)
+                        .min_by(|a, b| {
+                            (lengths[*a] - target)
+                                .abs()
+                                .total_cmp(&(lengths[*b] - target).abs())
+                        })
+                        .unwrap();
+                    corners[k] = closest.clamp(corners[k - 1] + 1, n + k - 4);
+                }
+                let mut parameters = Vec::with_capacity(n);
+                for k in 0..4 {
+                    let (l0, l1) = (lengths[corners[k]], lengths[corners[k + 1]]);
+                    for length in lengths[corners[k]..corners[k + 1]].iter() {
+                        parameters.push(0.25 * (k as f64 + (length - l0) / (l1 - l0)));
+                    }
+                }
+                parameters
+            }
+        };
+
+        boundary
+            .into_iter()
+            .zip(parameters)
+            .map(|(vertex_id, t)| {
+                let uv = match boundary_mapping {
+                    BoundaryMapping::Circle => {
+                        let angle = 2.0 * std::f64::consts::PI * t;
+                        vec2(0.5 + 0.5 * angle.cos(), 0.5 + 0.5 * angle.sin())
+                    }
+                    BoundaryMapping::Square => {
+                        let s = 4.0 * t;
+                        match s as usize {
+                            0 => vec2(s, 0.0),
+                            1 => vec2(1.0, s - 1.0),
+                            2 => vec2(3.0 - s, 1.0),
+                            _ => vec2(0.0, 4.0 - s),
+                        }
+                    }
+                };
+                (vertex_id, uv)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the signed area of the face in the parameterization.
+    fn uv_area(mesh: &Mesh, uvs: &VertexProperty<Vec2>, face_id: FaceID) -> f64 {
+        let (v0, v1, v2) = mesh.face_vertices(face_id);
+        let (a, b) = (uvs[&v1] - uvs[&v0], uvs[&v2] - uvs[&v0]);
+        0.5 * (a.x * b.y - a.y * b.x)
+    }
+
+    #[test]
+    fn test_parameterize_grid_to_square() {
+        let mesh = Mesh::grid(
+            vec3(0.0, 0.0, 0.0),
+            vec3(2.0, 0.0, 0.0),
+            vec3(0.0, 0.0, -2.0),
+            8,
+            8,
+        );
+        let uvs = mesh.parameterize_harmonic(BoundaryMapping::Square).unwrap();
+        // A regular grid is reproduced exactly
+        for face_id in mesh.face_iter() {
+            assert!((uv_area(&mesh, &uvs, face_id) - 0.5 / 64.0).abs() < 0.000001);
+        }
+    }
+
+    #[test]
+    fn test_parameterize_hemisphere_to_circle() {
+        let (hemisphere, _) = Mesh::icosphere(3)
+            .cut_by_plane(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), false)
+            .unwrap();
+        let uvs = hemisphere
+            .parameterize_harmonic(BoundaryMapping::Circle)
+            .unwrap();
+        assert_eq!(uvs.len(), hemisphere.no_vertices());
+        for vertex_id in hemisphere.vertex_iter() {
+            let distance = (uvs[&vertex_id] - vec2(0.5, 0.5)).magnitude();
+            if hemisphere.is_vertex_on_boundary(vertex_id) {
+                assert!((distance - 0.5).abs() < 0.000001);
+            } else {
+                assert!(distance < 0.5);
+            }
+        }
+        let area: f64 = hemisphere
+            .face_iter()
+            .map(|face_id| {
+                let area = uv_area(&hemisphere, &uvs, face_id);
+                assert!(area > 0.0);
+                area
+            })
+            .sum();
+        // The area of the polygon inscribed in the circle
+        assert!(area < 0.25 * std::f64::consts::PI && area > 0.24 * std::f64::consts::PI);
+    }
+
+    #[test]
+    fn test_parameterize_lscm() {
+        // A planar mesh is mapped without distortion
+        let mut mesh = crate::test_utility::subdivided_triangle();
+        mesh.split_face(mesh.face_iter().next().unwrap(), vec3(0.5, 0.5, 0.0));
+        let uvs = mesh.parameterize_lscm().unwrap();
+        for face_id in mesh.face_iter() {
+            let (v0, v1, v2) = mesh.face_vertices(face_id);
+            let area = mesh.face_area(face_id);
+            assert!((uv_area(&mesh, &uvs, face_id) - area).abs() < 0.000001);
+            for (a, b) in [(v0, v1), (v1, v2), (v2, v0)] {
+                let length = mesh.vertex_position(a).distance(mesh.vertex_position(b));
+                assert!(((uvs[&a] - uvs[&b]).magnitude() - length).abs() < 0.000001);
+            }
+        }
+
+        let (hemisphere, _) = Mesh::icosphere(3)
+            .cut_by_plane(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), false)
+            .unwrap();
+        let uvs = hemisphere.parameterize_lscm().unwrap();
+        assert_eq!(uvs.len(), hemisphere.no_vertices());
+        for face_id in hemisphere.face_iter() {
+            assert!(uv_area(&hemisphere, &uvs, face_id) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_generate_uvs() {
+        let (mut hemisphere, _) = Mesh::icosphere(2)
+            .cut_by_plane(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), false)
+            .unwrap();
+        hemisphere.generate_uvs().unwrap();
+        let mut max = 0.0f32;
+        for vertex_id in hemisphere.vertex_iter() {
+            let uv = hemisphere.vertex_uv(vertex_id).unwrap();
+            assert!(uv.x >= 0.0 && uv.y >= 0.0 && uv.x <= 1.0 && uv.y <= 1.0);
+            max = max.max(uv.x).max(uv.y);
+        }
+        assert!((max - 1.0).abs() < 0.00001);
+    }
+
+    #[test]
+    fn test_parameterize_spherical() {
+        let mut mesh = Mesh::capsule(0.5, 3.0, 16, 4);
+        // Push in the side to make the mesh non-convex
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            if p.x > 0.3 && p.y.abs() < 1.0 {
+                mesh.set_vertex_position(vertex_id, vec3(-0.2, p.y, p.z));
+            }
+        }
+        let positions = mesh.parameterize_spherical().unwrap();
+        assert_eq!(positions.len(), mesh.no_vertices());
+        for face_id in mesh.face_iter() {
+            let (v0, v1, v2) = mesh.face_vertices(face_id);
+            let (p0, p1, p2) = (positions[&v0], positions[&v1], positions[&v2]);
+            assert!((p1 - p0).cross(p2 - p0).dot(p0 + p1 + p2) > 0.0);
+        }
+        for p in positions.values() {
+            assert!((p.magnitude() - 1.0).abs() < 0.000001);
+        }
+
+        assert!(Mesh::cylinder(1.0, 2.0, 8, false)
+            .parameterize_spherical()
+            .is_err());
+        assert!(Mesh::torus(1.0, 0.25, 16, 8)
+            .parameterize_spherical()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parameterize_non_disk() {
+        let mut mesh = Mesh::icosphere(1);
+        assert!(mesh.parameterize_harmonic(BoundaryMapping::Circle).is_err());
+        assert!(mesh.generate_uvs().is_err());
+        assert!(mesh.vertex_uv(mesh.vertex_iter().next().unwrap()).is_none());
+        let mesh = Mesh::cylinder(1.0, 2.0, 8, false);
+        assert!(mesh.parameterize_harmonic(BoundaryMapping::Square).is_err());
+        assert!(mesh.parameterize_lscm().is_err());
+    }
+}
@@ -0,0 +1,255 @@
+//! Geometric predicates with an exact sign used by the intersection tests when the `robust` feature is enabled.
+//! The exact predicates are used to classify the end points of an edge against the plane of a face and to test whether a point in the plane of a face is inside the face,
+//! whereas the tests of whether a point is at an edge or a vertex of a face use a small margin, such that nearby intersection points are snapped to the edge or vertex.
+//!
+//! The predicates are first evaluated with floating point arithmetic and, if the result is too close to zero to be trusted, evaluated again
+//! using exact expansion arithmetic as described by Jonathan Shewchuk in "Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates".
+
+use crate::math::*;
+
+///
+/// Returns a positive value if the points a, b and c occur in counterclockwise order, a negative value if they occur in clockwise order and zero if they are collinear.
+///
+pub fn orient2d(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    let det_left = (a.0 - c.0) * (b.1 - c.1);
+    let det_right = (a.1 - c.1) * (b.0 - c.0);
+    let det = det_left - det_right;
+
+    const EPSILON: f64 = f64::EPSILON * 0.5;
+    const ERROR_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+    let det_sum = det_left.abs() + det_right.abs();
+    if det.abs() > ERROR_BOUND * det_sum {
+        det
+    } else {
+        exact::orient2d(a, b, c)
+    }
+}
+
+///
+/// Returns a positive value if the point d is below the plane through the points a, b and c, where above is the side from which a, b and c appear in counterclockwise order,
+/// a negative value if d is above the plane and zero if the four points are coplanar.
+///
+pub fn orient3d(a: &Vec3, b: &Vec3, c: &Vec3, d: &Vec3) -> f64 {
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+    let bdxcdy = bd.x * cd.y;
+    let cdxbdy = cd.x * bd.y;
+    let cdxady = cd.x * ad.y;
+    let adxcdy = ad.x * cd.y;
+    let adxbdy = ad.x * bd.y;
+    let bdxady = bd.x * ad.y;
+    let det = ad.z * (bdxcdy - cdxbdy) + bd.z * (cdxady - adxcdy) + cd.z * (adxbdy - bdxady);
+
+    const EPSILON: f64 = f64::EPSILON * 0.5;
+    const ERROR_BOUND: f64 = (7.0 + 56.0 * EPSILON) * EPSILON;
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * ad.z.abs()
+        + (cdxady.abs() + adxcdy.abs()) * bd.z.abs()
+        + (adxbdy.abs() + bdxady.abs()) * cd.z.abs();
+    if det.abs() > ERROR_BOUND * permanent {
+        det
+    } else {
+        exact::orient3d(a, b, c, d)
+    }
+}
+
+///
+/// Returns whether the point is strictly inside the triangle (a, b, c) when projected onto the plane with the given normal.
+///
+pub fn is_point_in_triangle(point: &Vec3, a: &Vec3, b: &Vec3, c: &Vec3, normal: &Vec3) -> bool {
+    // Drop the coordinate where the normal is largest, this projection preserves the orientation up to a sign
+    let (nx, ny, nz) = (normal.x.abs(), normal.y.abs(), normal.z.abs());
+    let project = |p: &Vec3| {
+        if nx >= ny && nx >= nz {
+            (p.y, p.z)
+        } else if ny >= nz {
+            (p.z, p.x)
+        } else {
+            (p.x, p.y)
+        }
+    };
+    let (p, a, b, c) = (project(point), project(a), project(b), project(c));
+    let d0 = orient2d(a, b, p);
+    let d1 = orient2d(b, c, p);
+    let d2 = orient2d(c, a, p);
+    (d0 > 0.0 && d1 > 0.0 && d2 > 0.0) || (d0 < 0.0 && d1 < 0.0 && d2 < 0.0)
+}
+
+mod exact {
+    use crate::math::*;
+
+    ///
+    /// An expansion is a sum of floating point numbers which are ordered by increasing magnitude and do not overlap,
+    /// which means that the sign of the sum is the sign of the last component.
+    ///
+    type Expansion = Vec<f64>;
+
+    /// Returns (x, y) such that x = fl(a + b) and x + y = a + b exactly.
+    fn two_sum(a: f64, b: f64) -> (f64, f64) {
+        let x = a + b;
+        let b_virtual = x - a;
+        let a_virtual = x - b_virtual;
+        (x, (a - a_virtual) + (b - b_virtual))
+    }
+
+    /// Returns (x, y) such that x = fl(a * b) and x + y = a * b exactly.
+    fn two_product(a: f64, b: f64) -> (f64, f64) {
+        let x = a * b;
+        (x, a.mul_add(b, -x))
+    }
+
+    /// Returns a - b as an expansion.
+    fn difference(a: f64, b: f64) -> Expansion {
+        let (x, y) = two_sum(a, -b);
+        [y, x].into_iter().filter(|c| *c != 0.0).collect()
+    }
+
+    /// Adds the number to the expansion (Shewchuk's Grow-Expansion with zero elimination).
+    fn grow(expansion: &[f64], b: f64) -> Expansion {
+        let mut result = Vec::with_capacity(expansion.len() + 1);
+        let mut q = b;
+        for e in expansion {
+            let (sum, error) = two_sum(q, *e);
+            if error != 0.0 {
+                result.push(error);
+            }
+            q = sum;
+        }
+        if q != 0.0 {
+            result.push(q);
+        }
+        result
+    }
+
+    fn sum(e: &[f64], f: &[f64]) -> Expansion {
+        f.iter().fold(e.to_vec(), |result, c| grow(&result, *c))
+    }
+
+    fn product(e: &[f64], f: &[f64]) -> Expansion {
+        let mut result = Vec::new();
+        for a in e {
+            for b in f {
+                let (x, y) = two_product(*a, *b);
+                result = grow(&grow(&result, y), x);
+            }
+        }
+        result
+    }
+
+    fn negate(e: &[f64]) -> Expansion {
+        e.iter().map(|c| -c).collect()
+    }
+
+    fn sign(expansion: &[f64]) -> f64 {
+        expansion.last().copied().unwrap_or(0.0)
+    }
+
+    pub fn orient2d(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+        let left = product(&difference(a.0, c.0), &difference(b.1, c.1));
+        let right = product(&difference(a.1, c.1), &difference(b.0, c.0));
+        sign(&sum(&left, &negate(&right)))
+    }
+
+    pub fn orient3d(a: &Vec3, b: &Vec3, c: &Vec3, d: &Vec3) -> f64 {
+        let (adx, ady, adz) = (
+            difference(a.x, d.x),
+            difference(a.y, d.y),
+            difference(a.z, d.z),
+        );
+        let (bdx, bdy, bdz) = (
+            difference(b.x, d.x),
+            difference(b.y, d.y),
+            difference(b.z, d.z),
+        );
+        let (cdx, cdy, cdz) = (
+            difference(c.x, d.x),
+            difference(c.y, d.y),
+            difference(c.z, d.z),
+        );
+        let minor = |x0: &[f64], y1: &[f64], x1: &[f64], y0: &[f64]| {
+            sum(&product(x0, y1), &negate(&product(x1, y0)))
+        };
+        let det = sum(
+            &sum(
+                &product(&adz, &minor(&bdx, &cdy, &cdx, &bdy)),
+                &product(&bdz, &minor(&cdx, &ady, &adx, &cdy)),
+            ),
+            &product(&cdz, &minor(&adx, &bdy, &bdx, &ady)),
+        );
+        sign(&det)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orient2d() {
+        assert!(orient2d((0.0, 0.0), (1.0, 0.0), (0.0, 1.0)) > 0.0);
+        assert!(orient2d((0.0, 0.0), (0.0, 1.0), (1.0, 0.0)) < 0.0);
+        assert_eq!(orient2d((0.0, 0.0), (1.0, 1.0), (2.0, 2.0)), 0.0);
+    }
+
+    #[test]
+    fn test_orient2d_near_degenerate() {
+        // Points close to the line y = x, where the floating point evaluation returns the wrong sign for some of them
+        let b = (12.0, 12.0);
+        let c = (24.0, 24.0);
+        for i in 0..64 {
+            for j in 0..64 {
+                let p = (0.5 + i as f64 * f64::EPSILON, 0.5 + j as f64 * f64::EPSILON);
+                let det = orient2d(p, b, c);
+                if i == j {
+                    assert_eq!(det, 0.0);
+                } else {
+                    assert_eq!(det > 0.0, j > i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_orient3d() {
+        let a = vec3(0.0, 0.0, 0.0);
+        let b = vec3(1.0, 0.0, 0.0);
+        let c = vec3(0.0, 1.0, 0.0);
+        assert!(orient3d(&a, &b, &c, &vec3(0.2, 0.2, -1.0)) > 0.0);
+        assert!(orient3d(&a, &b, &c, &vec3(0.2, 0.2, 1.0)) < 0.0);
+        assert_eq!(orient3d(&a, &b, &c, &vec3(5.0, -3.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_orient3d_near_degenerate() {
+        // Points far from the triangle and one or a few units in the last place from the plane x + y + z = 3073 through the triangle,
+        // where the floating point evaluation is not reliable
+        let a = vec3(1025.0, 1024.0, 1024.0);
+        let b = vec3(1024.0, 1025.0, 1024.0);
+        let c = vec3(1024.0, 1024.0, 1025.0);
+        let s = 1048576.0;
+        let d = vec3(1025.0 - 2.0 * s, 1024.0 + s, 1024.0 + s);
+        let ulp = s * f64::EPSILON;
+        assert_eq!(orient3d(&a, &b, &c, &d), 0.0);
+        for i in 1..32 {
+            let offset = vec3(0.0, 0.0, i as f64 * ulp);
+            assert!(orient3d(&a, &b, &c, &(d + offset)) < 0.0);
+            assert!(orient3d(&a, &b, &c, &(d - offset)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_is_point_in_triangle() {
+        let a = vec3(0.0, 0.0, 0.0);
+        let b = vec3(1.0, 0.0, 0.0);
+        let c = vec3(0.0, 0.0, 1.0);
+        let n = vec3(0.0, 1.0, 0.0);
+        assert!(is_point_in_triangle(&vec3(0.25, 0.0, 0.25), &a, &b, &c, &n));
+        assert!(!is_point_in_triangle(&vec3(0.5, 0.0, 0.5), &a, &b, &c, &n));
+        assert!(!is_point_in_triangle(
+            &vec3(0.75, 0.0, 0.75),
+            &a,
+            &b,
+            &c,
+            &n
+        ));
+        assert!(!is_point_in_triangle(&vec3(-0.1, 0.0, 0.5), &a, &b, &c, &n));
+    }
+}
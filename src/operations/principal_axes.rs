@@ -0,0 +1,189 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+///
+/// The principal axes of the surface of a mesh as returned by [Mesh::principal_axes].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrincipalAxes {
+    /// The center of the surface, ie. the area weighted average of the points on the surface.
+    pub center: Vec3,
+    /// The principal axes ordered by decreasing variance. The axes are orthonormal and form a right-handed coordinate system.
+    pub axes: [Vec3; 3],
+    /// The variance of the points on the surface along each of the principal axes.
+    pub variances: [f64; 3],
+}
+
+/// # Principal axes
+impl Mesh {
+    ///
+    /// Computes the principal axes of the surface, ie. the eigenvectors of the covariance matrix of the points on the surface.
+    /// The covariance is computed by integrating over the faces, so the result does not depend on how the surface is triangulated.
+    ///
+    /// The sign of the first two axes is chosen such that the surface extends furthest in the positive direction of the axis (measured by the third moment),
+    /// so the axes are canonical for a given shape unless the shape is symmetric.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// mesh.non_uniform_scale(1.0, 3.0, 2.0);
+    /// let principal_axes = mesh.principal_axes();
+    /// assert!(principal_axes.axes[0].y.abs() > 0.99);
+    /// assert!(principal_axes.axes[1].z.abs() > 0.99);
+    /// assert!(principal_axes.axes[2].x.abs() > 0.99);
+    /// ```
+    ///
+    pub fn principal_axes(&self) -> PrincipalAxes {
+        let mut area = 0.0;
+        let mut first_moment = Vec3::zero();
+        let mut second_moment = Mat3::zero();
+        for face_id in self.face_iter() {
+            let (p0, p1, p2) = self.face_positions(face_id);
+            let a = self.face_area(face_id);
+            let sum = p0 + p1 + p2;
+            area += a;
+            first_moment += a * sum / 3.0;
+            // The integral of x * x^T over the triangle
+            second_moment +=
+                (outer(p0, p0) + outer(p1, p1) + outer(p2, p2) + outer(sum, sum)) * (a / 12.0);
+        }
+        if area <= 0.0 {
+            return PrincipalAxes {
+                center: Vec3::zero(),
+                axes: [Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()],
+                variances: [0.0; 3],
+            };
+        }
+        let center = first_moment / area;
+        let covariance = second_moment / area - outer(center, center);
+        let (mut variances, mut axes) = symmetric_eigen(covariance);
+
+        // Sort by decreasing variance
+        let mut order = [0, 1, 2];
+        order.sort_by(|a, b| variances[*b].total_cmp(&variances[*a]));
+        variances = [
+            variances[order[0]],
+            variances[order[1]],
+            variances[order[2]],
+        ];
+        axes = [axes[order[0]], axes[order[1]], axes[order[2]]];
+
+        // Choose the signs of the axes based on the third moment
+        for axis in axes.iter_mut().take(2) {
+            let mut third_moment = 0.0;
+            for face_id in self.face_iter() {
+                let (p0, p1, p2) = self.face_positions(face_id);
+                let d = ((p0 + p1 + p2) / 3.0 - center).dot(*axis);
+                third_moment += self.face_area(face_id) * d * d * d;
+            }
+            if third_moment < 0.0 {
+                *axis = -*axis;
+            }
+        }
+        axes[2] = axes[0].cross(axes[1]);
+
+        PrincipalAxes {
+            center,
+            axes,
+            variances,
+        }
+    }
+
+    ///
+    /// Moves and rotates the mesh such that the center of the surface is at origo and the principal axes (see [Mesh::principal_axes]) are aligned with the x, y and z axes in that order,
+    /// ie. such that the mesh extends the most along the x axis and the least along the z axis.
+    /// Returns the applied transformation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// mesh.non_uniform_scale(1.0, 3.0, 2.0);
+    /// mesh.translate(vec3(1.0, 2.0, 3.0));
+    ///
+    /// mesh.align_to_principal_axes();
+    ///
+    /// let principal_axes = mesh.principal_axes();
+    /// assert!(principal_axes.center.magnitude() < 0.000001);
+    /// assert!(principal_axes.axes[0].x.abs() > 0.999999);
+    /// assert!(principal_axes.axes[1].y.abs() > 0.999999);
+    /// ```
+    ///
+    pub fn align_to_principal_axes(&mut self) -> Mat4 {
+        let principal_axes = self.principal_axes();
+        let [x, y, z] = principal_axes.axes;
+        let rotation = Mat4::from(Mat3::from_cols(x, y, z).transpose());
+        let transformation = rotation * Mat4::from_translation(-principal_axes.center);
+        self.apply_transformation(transformation);
+        transformation
+    }
+}
+
+fn outer(a: Vec3, b: Vec3) -> Mat3 {
+    Mat3::from_cols(a * b.x, a * b.y, a * b.z)
+}
+
+/// Returns the eigenvalues and the corresponding normalized eigenvectors of the given symmetric matrix using the Jacobi eigenvalue algorithm.
+fn symmetric_eigen(matrix: Mat3) -> ([f64; 3], [Vec3; 3]) {
+    let mut a = matrix;
+    let mut v = Mat3::identity();
+    for _ in 0..50 {
+        // Find the largest off-diagonal element
+        let (mut p, mut q) = (0, 1);
+        for (i, j) in [(0, 2), (1, 2)] {
+            if a[j][i].abs() > a[q][p].abs() {
+                p = i;
+                q = j;
+            }
+        }
+        if a[q][p].abs() < 1e-15 {
+            break;
+        }
+        // Rotate to eliminate the element
+        let theta = 0.5 * (2.0 * a[q][p]).atan2(a[p][p] - a[q][q]);
+        let (s, c) = theta.sin_cos();
+        let mut rotation = Mat3::identity();
+        rotation[p][p] = c;
+        rotation[q][q] = c;
+        rotation[q][p] = -s;
+        rotation[p][q] = s;
+        a = rotation.transpose() * a * rotation;
+        v = v * rotation;
+    }
+    ([a[0][0], a[1][1], a[2][2]], [v.x, v.y, v.z])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let matrix = Mat3::new(4.0, 1.0, 2.0, 1.0, 3.0, 0.5, 2.0, 0.5, 1.0);
+        let (values, vectors) = symmetric_eigen(matrix);
+        for i in 0..3 {
+            assert!((vectors[i].magnitude() - 1.0).abs() < 0.000001);
+            assert!((matrix * vectors[i] - values[i] * vectors[i]).magnitude() < 0.000001);
+        }
+    }
+
+    #[test]
+    fn test_principal_axes_of_box() {
+        let mut mesh = crate::test_utility::cube();
+        mesh.non_uniform_scale(1.0, 2.0, 3.0);
+        mesh.rotate(Mat3::from_angle_x(degrees(30.0)));
+
+        let principal_axes = mesh.principal_axes();
+
+        assert!(principal_axes.center.magnitude() < 0.000001);
+        assert!(principal_axes.variances[0] > principal_axes.variances[1]);
+        assert!(principal_axes.variances[1] > principal_axes.variances[2]);
+        let expected_z = Mat3::from_angle_x(degrees(30.0)) * Vec3::unit_z();
+        assert!(principal_axes.axes[0].dot(expected_z).abs() > 0.999999);
+        assert!(principal_axes.axes[2].x.abs() > 0.999999);
+    }
+}
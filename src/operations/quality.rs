@@ -7,12 +7,21 @@ use std::collections::{HashMap, HashSet};
 impl Mesh {
     /// Moves the vertices to `pos + factor * (avg_pos - pos)` where `pos` is the current position
     /// and `avg_pos` is the average position of the neighbouring vertices.
+    /// Vertices on a [crease](Mesh#creases) are only moved along the crease, ie. `avg_pos` is the average position of the two neighbouring vertices on the crease,
+    /// and vertices where more than two creases meet are not moved.
     pub fn smooth_vertices(&mut self, factor: f64) {
         let mut map = HashMap::new();
         for vertex_id in self.vertex_iter() {
+            let no_creases = self.vertex_crease_count(vertex_id);
+            if no_creases > 2 {
+                continue;
+            }
             let mut avg_pos = vec3(0.0, 0.0, 0.0);
             let mut i = 0;
             for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
+                if no_creases == 2 && !self.is_crease(halfedge_id) {
+                    continue;
+                }
                 let vid = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
                 avg_pos = avg_pos + self.vertex_position(vid);
                 i = i + 1;
@@ -22,12 +31,49 @@ impl Mesh {
             map.insert(vertex_id, p + factor * (avg_pos - p));
         }
 
-        for vertex_id in self.vertex_iter() {
-            self.move_vertex_to(vertex_id, *map.get(&vertex_id).unwrap());
+        for (vertex_id, position) in map {
+            self.move_vertex_to(vertex_id, position);
+        }
+    }
+
+    ///
+    /// Smooths the mesh by moving each vertex along the Laplace-Beltrami operator of the surface (see [Mesh::mean_curvature]), ie. with a speed proportional to the mean curvature,
+    /// which irons out bumps and wrinkles much more aggressively than [Mesh::smooth_vertices] and makes closed meshes converge to a sphere-like shape that shrinks towards a point.
+    /// Vertices on the boundary are not moved.
+    ///
+    /// The flow is integrated explicitly using the given time `step`, which must be small compared to the squared length of the shortest edge for the flow to be stable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(2);
+    /// // Make the mesh bumpy
+    /// for vertex_id in mesh.vertex_iter() {
+    ///     let p = mesh.vertex_position(vertex_id);
+    ///     mesh.set_vertex_position(vertex_id, p * (1.0 + 0.1 * (10.0 * p.x).sin()));
+    /// }
+    /// mesh.mean_curvature_flow(0.001, 10);
+    /// ```
+    ///
+    pub fn mean_curvature_flow(&mut self, step: f64, iterations: usize) {
+        for _ in 0..iterations {
+            let positions: Vec<(VertexID, Vec3)> = self
+                .vertex_iter()
+                .filter(|vertex_id| !self.is_vertex_on_boundary(*vertex_id))
+                .map(|vertex_id| {
+                    let p = self.vertex_position(vertex_id);
+                    (vertex_id, p + step * self.laplace_beltrami(vertex_id))
+                })
+                .collect();
+            for (vertex_id, position) in positions {
+                self.set_vertex_position(vertex_id, position);
+            }
         }
     }
 
     /// Collapse an edge of faces which has an area smaller than `area_threshold`.
+    /// Edges are not collapsed if it will move a vertex away from a [crease](Mesh#creases).
     pub fn collapse_small_faces(&mut self, area_threshold: f64) {
         let mut faces_to_test = HashSet::new();
         self.face_iter().for_each(|f| {
@@ -42,7 +88,52 @@ impl Mesh {
                     faces_to_test.remove(&twin_face_id);
                 }
                 let twin_id = walker.twin_id().unwrap();
-                self.collapse_edge(twin_id);
+                if self.collapse_will_preserve_creases(twin_id) {
+                    self.collapse_edge(twin_id);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Splits each edge which is longer than `max_length` at its midpoint, starting with the longest edges, and repeats until no edge is longer than `max_length`.
+    /// This is the counterpart of [Mesh::collapse_small_faces] and, together with [Mesh::flip_edges] and [Mesh::smooth_vertices], a building block for remeshing.
+    /// Returns the number of edges that were split. Nothing is split if `max_length` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(1);
+    /// mesh.split_long_edges(0.3);
+    /// assert!(mesh.edge_iter().all(|halfedge_id| mesh.edge_length(halfedge_id.into()) <= 0.3));
+    /// ```
+    ///
+    pub fn split_long_edges(&mut self, max_length: f64) -> usize {
+        if max_length <= 0.0 {
+            return 0;
+        }
+        let max_sqr_length = max_length * max_length;
+        let mut no_splits = 0;
+        loop {
+            let mut long_edges: Vec<(HalfEdgeID, f64)> = self
+                .edge_iter()
+                .map(|edge| {
+                    let halfedge_id = HalfEdgeID::from(edge);
+                    (halfedge_id, self.edge_sqr_length(halfedge_id))
+                })
+                .filter(|(_, sqr_length)| *sqr_length > max_sqr_length)
+                .collect();
+            if long_edges.is_empty() {
+                return no_splits;
+            }
+            long_edges.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+            for (halfedge_id, _) in long_edges {
+                // A previous split in this round might have shortened the edge
+                if self.edge_sqr_length(halfedge_id) > max_sqr_length {
+                    self.split_edge_at(halfedge_id, 0.5);
+                    no_splits += 1;
+                }
             }
         }
     }
@@ -55,6 +146,7 @@ impl Mesh {
     /// * where the dot product between the normals of the adjacent faces is smaller than `flattness_threshold`
     /// (1: Completely flat, 0: 90 degrees angle between normals)
     /// * where the flip will not result in inverted triangles
+    /// * which is not a [crease](Mesh#creases)
     ///
     pub fn flip_edges(&mut self, flatness_threshold: f64) {
         let insert_or_remove =
@@ -109,11 +201,23 @@ impl Mesh {
 
     fn should_flip(&self, halfedge_id: HalfEdgeID, flatness_threshold: f64) -> bool {
         !self.is_edge_on_boundary(halfedge_id)
+            && !self.is_crease(halfedge_id)
             && self.flatness(halfedge_id) > flatness_threshold
             && !self.flip_will_invert_triangle(halfedge_id)
             && self.flip_will_improve_quality(halfedge_id)
     }
 
+    // A crease is only collapsed along the crease and other edges only if they are not connected to a crease
+    fn collapse_will_preserve_creases(&self, halfedge_id: HalfEdgeID) -> bool {
+        let (v0, v1) = self.edge_vertices(halfedge_id);
+        let (c0, c1) = (self.vertex_crease_count(v0), self.vertex_crease_count(v1));
+        if self.is_crease(halfedge_id) {
+            c0 == 2 && c1 == 2
+        } else {
+            c0 == 0 && c1 == 0
+        }
+    }
+
     // 1 = Completely flat, 0 = 90 degrees angle between normals
     fn flatness(&self, haledge_id: HalfEdgeID) -> f64 {
         let mut walker = self.walker_from_halfedge(haledge_id);
@@ -161,6 +265,42 @@ mod tests {
     use super::*;
     use three_d_asset::{Indices, Positions, TriMesh};
 
+    #[test]
+    fn test_mean_curvature_flow() {
+        let mut mesh = Mesh::icosphere(3);
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            let noise = 0.05 * (13.0 * p.x).sin() * (7.0 * p.y).cos();
+            mesh.set_vertex_position(vertex_id, p * (1.0 + noise));
+        }
+        let roundness = |mesh: &Mesh| {
+            let radii: Vec<f64> = mesh
+                .vertex_iter()
+                .map(|v| mesh.vertex_position(v).magnitude())
+                .collect();
+            let min = radii.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = radii.iter().copied().fold(0.0, f64::max);
+            min / max
+        };
+        let before = roundness(&mesh);
+        let volume = mesh.volume().unwrap();
+        mesh.mean_curvature_flow(0.004, 20);
+        assert!(roundness(&mesh) > before + 0.5 * (1.0 - before));
+        assert!(mesh.volume().unwrap() < volume);
+
+        // The boundary is fixed
+        let mut mesh = crate::test_utility::subdivided_triangle();
+        let boundary: Vec<(VertexID, Vec3)> = mesh
+            .vertex_iter()
+            .filter(|v| mesh.is_vertex_on_boundary(*v))
+            .map(|v| (v, mesh.vertex_position(v)))
+            .collect();
+        mesh.mean_curvature_flow(0.01, 5);
+        for (vertex_id, position) in boundary {
+            assert_eq!(mesh.vertex_position(vertex_id), position);
+        }
+    }
+
     #[test]
     fn test_collapse_small_faces() {
         let mut mesh: Mesh = TriMesh {
@@ -178,4 +318,34 @@ mod tests {
         mesh.collapse_small_faces(0.2);
         mesh.is_valid().unwrap();
     }
+
+    #[test]
+    fn test_split_long_edges() {
+        let mut mesh = crate::test_utility::square();
+        let vertices: Vec<VertexID> = mesh.vertex_iter().collect();
+        let halfedge_id = mesh.connecting_edge(vertices[0], vertices[1]).unwrap();
+        mesh.set_crease(halfedge_id, true);
+        let area = mesh.face_iter().map(|f| mesh.face_area(f)).sum::<f64>();
+
+        let no_splits = mesh.split_long_edges(0.5);
+
+        mesh.is_valid().unwrap();
+        assert!(no_splits > 0);
+        assert_eq!(mesh.no_vertices(), 4 + no_splits);
+        assert!(mesh
+            .edge_iter()
+            .all(|halfedge_id| mesh.edge_length(halfedge_id.into()) <= 0.5));
+        let split_area = mesh.face_iter().map(|f| mesh.face_area(f)).sum::<f64>();
+        assert!((area - split_area).abs() < 0.000001);
+        // The crease is split into four crease edges
+        assert_eq!(
+            mesh.edge_iter()
+                .filter(|halfedge_id| mesh.is_crease((*halfedge_id).into()))
+                .count(),
+            4
+        );
+
+        assert_eq!(mesh.split_long_edges(0.5), 0);
+        assert_eq!(mesh.split_long_edges(0.0), 0);
+    }
 }
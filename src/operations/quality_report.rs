@@ -0,0 +1,185 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+/// The number of bins in the histograms of the [QualityReport].
+const NO_BINS: usize = 10;
+
+///
+/// A histogram of a set of values where the range from `min` to `max` is divided into bins of equal size.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// The start of the first bin
+    pub min: f64,
+    /// The end of the last bin
+    pub max: f64,
+    /// The number of values in each bin. Values outside the range are counted in the first or last bin.
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Constructs a histogram with the given number of bins between `min` and `max` of the given values.
+    /// The histogram has at least one bin, even if the given number of bins is zero.
+    pub fn new(values: &[f64], min: f64, max: f64, no_bins: usize) -> Self {
+        let no_bins = no_bins.max(1);
+        let mut counts = vec![0; no_bins];
+        for value in values {
+            let t = if max > min {
+                (value - min) / (max - min)
+            } else {
+                0.0
+            };
+            let bin = ((t * no_bins as f64).floor().max(0.0) as usize).min(no_bins - 1);
+            counts[bin] += 1;
+        }
+        Self { min, max, counts }
+    }
+
+    /// Returns the start and end of the bin with the given index.
+    pub fn bin_range(&self, index: usize) -> (f64, f64) {
+        let size = (self.max - self.min) / self.counts.len() as f64;
+        (
+            self.min + index as f64 * size,
+            self.min + (index + 1) as f64 * size,
+        )
+    }
+}
+
+///
+/// A summary of a measure evaluated on a set of primitives, for example the aspect ratio of all faces.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasureSummary {
+    /// The smallest value
+    pub min: f64,
+    /// The average value
+    pub mean: f64,
+    /// The largest value
+    pub max: f64,
+    /// The distribution of the values
+    pub histogram: Histogram,
+}
+
+impl MeasureSummary {
+    /// Summarizes the given values using a histogram with the given number of bins between `min` and `max`.
+    pub fn new(values: &[f64], min: f64, max: f64, no_bins: usize) -> Self {
+        let (value_min, value_max) = values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), v| {
+                (a.min(*v), b.max(*v))
+            });
+        Self {
+            min: if values.is_empty() { 0.0 } else { value_min },
+            mean: if values.is_empty() {
+                0.0
+            } else {
+                values.iter().sum::<f64>() / values.len() as f64
+            },
+            max: if values.is_empty() { 0.0 } else { value_max },
+            histogram: Histogram::new(values, min, max, no_bins),
+        }
+    }
+
+    /// Summarizes the given values using a histogram with the given number of bins between the smallest and largest value.
+    pub fn new_with_value_range(values: &[f64], no_bins: usize) -> Self {
+        let mut summary = Self::new(values, 0.0, 0.0, no_bins);
+        summary.histogram = Histogram::new(values, summary.min, summary.max, no_bins);
+        summary
+    }
+}
+
+///
+/// A summary of the quality of the faces of a mesh as returned by [Mesh::quality_report].
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityReport {
+    /// The number of faces
+    pub no_faces: usize,
+    /// The aspect ratio of the faces (see [Mesh::face_aspect_ratio]) with a histogram from 1 to 10.
+    pub aspect_ratio: MeasureSummary,
+    /// The radius ratio of the faces (see [Mesh::face_radius_ratio]) with a histogram from 0 to 1.
+    pub radius_ratio: MeasureSummary,
+    /// The smallest angle of the faces in degrees (see [Mesh::face_min_angle]) with a histogram from 0 to 60 degrees.
+    pub min_angle: MeasureSummary,
+    /// The largest angle of the faces in degrees (see [Mesh::face_max_angle]) with a histogram from 60 to 180 degrees.
+    pub max_angle: MeasureSummary,
+}
+
+/// # Quality report
+impl Mesh {
+    ///
+    /// Evaluates the quality measures of all the faces, for example to evaluate the result of remeshing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// let report = mesh.quality_report();
+    /// assert_eq!(report.no_faces, mesh.no_faces());
+    /// assert_eq!(report.min_angle.histogram.counts.iter().sum::<usize>(), mesh.no_faces());
+    /// assert!(report.radius_ratio.max <= 1.0);
+    /// println!("{:#?}", report);
+    /// ```
+    ///
+    pub fn quality_report(&self) -> QualityReport {
+        let measure = |f: &dyn Fn(FaceID) -> f64| -> Vec<f64> { self.face_iter().map(f).collect() };
+        QualityReport {
+            no_faces: self.no_faces(),
+            aspect_ratio: MeasureSummary::new(
+                &measure(&|face_id| self.face_aspect_ratio(face_id)),
+                1.0,
+                10.0,
+                NO_BINS,
+            ),
+            radius_ratio: MeasureSummary::new(
+                &measure(&|face_id| self.face_radius_ratio(face_id)),
+                0.0,
+                1.0,
+                NO_BINS,
+            ),
+            min_angle: MeasureSummary::new(
+                &measure(&|face_id| Degrees::from(self.face_min_angle(face_id)).0),
+                0.0,
+                60.0,
+                NO_BINS,
+            ),
+            max_angle: MeasureSummary::new(
+                &measure(&|face_id| Degrees::from(self.face_max_angle(face_id)).0),
+                60.0,
+                180.0,
+                NO_BINS,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram() {
+        let histogram = Histogram::new(&[-1.0, 0.0, 0.5, 2.5, 3.9, 4.0, 7.0], 0.0, 4.0, 4);
+        assert_eq!(histogram.counts, vec![3, 0, 1, 3]);
+        assert_eq!(histogram.bin_range(1), (1.0, 2.0));
+
+        let histogram = Histogram::new(&[-1.0, 0.0, 0.5, 2.5, 3.9, 4.0, 7.0], 0.0, 4.0, 0);
+        assert_eq!(histogram.counts, vec![7]);
+        assert_eq!(histogram.bin_range(0), (0.0, 4.0));
+    }
+
+    #[test]
+    fn test_quality_report() {
+        let mesh = crate::test_utility::cube();
+        let report = mesh.quality_report();
+        assert_eq!(report.no_faces, 12);
+        // All faces are right isosceles triangles
+        assert!((report.min_angle.min - 45.0).abs() < 0.000001);
+        assert!((report.min_angle.max - 45.0).abs() < 0.000001);
+        assert!((report.max_angle.mean - 90.0).abs() < 0.000001);
+        assert_eq!(report.max_angle.histogram.counts[2], 12);
+        assert_eq!(report.min_angle.histogram.counts[7], 12);
+    }
+}
@@ -0,0 +1,303 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use std::collections::HashMap;
+
+/// The number of rejected candidates per accepted sample after which [Mesh::sample_poisson_disk] stops drawing new candidates.
+pub const POISSON_DISK_MAX_ATTEMPTS: usize = 30;
+
+/// # Sampling
+///
+/// Methods for sampling points on the surface of the mesh.
+/// The sampling is deterministic given a seed, so the same seed always produces the same points for the same mesh.
+///
+impl Mesh {
+    ///
+    /// Returns `count` points sampled uniformly at random on the surface of the mesh, ie. the probability of a face being sampled is proportional to its area.
+    /// Each point is returned together with the face it lies on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let samples = mesh.sample_uniform(100, 42);
+    /// assert_eq!(samples.len(), 100);
+    /// let index = mesh.spatial_index();
+    /// for (_, point) in samples {
+    ///     let distance = index.closest_point(point).unwrap().1.distance(point);
+    ///     assert!(distance < 0.000001);
+    /// }
+    /// ```
+    ///
+    pub fn sample_uniform(&self, count: usize, seed: u64) -> Vec<(FaceID, Vec3)> {
+        let mut random = Random::new(seed);
+        let Some(sampler) = AreaSampler::new(self) else {
+            return Vec::new();
+        };
+        (0..count)
+            .map(|_| sampler.sample(self, &mut random))
+            .collect()
+    }
+
+    ///
+    /// Returns points sampled on the surface of the mesh such that no two points are closer than `min_distance` (Poisson-disk or blue-noise sampling),
+    /// for example for scattering instances or as seeds for remeshing.
+    /// Each point is returned together with the face it lies on.
+    ///
+    /// The points are found by drawing uniform samples (see [Mesh::sample_uniform]) one at a time and accepting each sample if it is not within `min_distance` of an already accepted sample.
+    /// Similar to the `k` tries per point in Bridson's algorithm, the sampling stops when [POISSON_DISK_MAX_ATTEMPTS] samples per accepted sample have been rejected,
+    /// so the number of drawn samples is bounded by the number of accepted samples and a small part of the surface may be further than `min_distance` from a sample.
+    /// The distance is the Euclidean distance and not the geodesic distance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let samples = mesh.sample_poisson_disk(0.2, 42);
+    /// assert!(samples.len() > 50);
+    /// for (i, (_, p0)) in samples.iter().enumerate() {
+    ///     for (_, p1) in samples.iter().skip(i + 1) {
+    ///         assert!(p0.distance(*p1) >= 0.2);
+    ///     }
+    /// }
+    /// ```
+    ///
+    pub fn sample_poisson_disk(&self, min_distance: f64, seed: u64) -> Vec<(FaceID, Vec3)> {
+        if min_distance <= 0.0 {
+            return Vec::new();
+        }
+        let mut random = Random::new(seed);
+        let Some(sampler) = AreaSampler::new(self) else {
+            return Vec::new();
+        };
+
+        let cell = |p: Vec3| {
+            (
+                (p.x / min_distance).floor() as i64,
+                (p.y / min_distance).floor() as i64,
+                (p.z / min_distance).floor() as i64,
+            )
+        };
+        let mut grid: HashMap<(i64, i64, i64), Vec<Vec3>> = HashMap::new();
+        let mut samples = Vec::new();
+        let mut rejected = 0;
+        while rejected < POISSON_DISK_MAX_ATTEMPTS * (samples.len() + 1) {
+            let (face_id, point) = sampler.sample(self, &mut random);
+            let (x, y, z) = cell(point);
+            let is_free = (x - 1..=x + 1).all(|i| {
+                (y - 1..=y + 1).all(|j| {
+                    (z - 1..=z + 1).all(|k| match grid.get(&(i, j, k)) {
+                        Some(points) => points.iter().all(|p| p.distance(point) >= min_distance),
+                        None => true,
+                    })
+                })
+            });
+            if is_free {
+                grid.entry((x, y, z)).or_default().push(point);
+                samples.push((face_id, point));
+            } else {
+                rejected += 1;
+            }
+        }
+        samples
+    }
+
+    ///
+    /// Returns the value of the given vertex property at the point on the face with the given barycentric coordinates,
+    /// ie. the values at the vertices of the face weighted by the barycentric coordinates with respect to the vertices in the order returned by [Mesh::face_vertices].
+    /// This can for example be used to look up a smoothly interpolated value where a ray hits the mesh, see [RayHit::barycentric](crate::operations::RayHit::barycentric).
+    ///
+    /// # Panics
+    ///
+    /// If the property does not contain a value for each of the vertices of the face.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let normals: VertexProperty<Vec3> = mesh
+    ///     .vertex_iter()
+    ///     .map(|vertex_id| (vertex_id, mesh.vertex_normal(vertex_id)))
+    ///     .collect();
+    ///
+    /// let hit = mesh.ray_hit(&vec3(0.0, 0.0, 3.0), &vec3(0.0, 0.0, -1.0)).unwrap();
+    /// let normal = mesh.interpolate_at(hit.face_id, hit.barycentric, &normals);
+    /// assert!(normal.normalize().distance(vec3(0.0, 0.0, 1.0)) < 0.1);
+    /// ```
+    ///
+    pub fn interpolate_at<T>(
+        &self,
+        face_id: FaceID,
+        barycentric: (f64, f64, f64),
+        property: &VertexProperty<T>,
+    ) -> T
+    where
+        T: Clone + std::ops::Mul<f64, Output = T> + std::ops::Add<Output = T>,
+    {
+        let (v0, v1, v2) = self.face_vertices(face_id);
+        property[&v0].clone() * barycentric.0
+            + property[&v1].clone() * barycentric.1
+            + property[&v2].clone() * barycentric.2
+    }
+
+    ///
+    /// Returns the position of the point on the face with the given barycentric coordinates with respect to the vertices in the order returned by [Mesh::face_vertices],
+    /// see also [Mesh::interpolate_at].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let hit = mesh.ray_hit(&vec3(0.0, 0.0, 3.0), &vec3(0.0, 0.0, -1.0)).unwrap();
+    /// assert!(mesh.position_at(hit.face_id, hit.barycentric).distance(hit.point) < 0.000001);
+    /// ```
+    ///
+    pub fn position_at(&self, face_id: FaceID, barycentric: (f64, f64, f64)) -> Vec3 {
+        let (v0, v1, v2) = self.face_vertices(face_id);
+        self.vertex_position(v0) * barycentric.0
+            + self.vertex_position(v1) * barycentric.1
+            + self.vertex_position(v2) * barycentric.2
+    }
+
+    fn random_point_on_face(&self, face_id: FaceID, random: &mut Random) -> Vec3 {
+        let (p0, p1, p2) = self.face_positions(face_id);
+        let r0 = random.next().sqrt();
+        let r1 = random.next();
+        p0 * (1.0 - r0) + p1 * (r0 * (1.0 - r1)) + p2 * (r0 * r1)
+    }
+}
+
+/// Draws points uniformly on the surface of a mesh, ie. the probability of a face being sampled is proportional to its area.
+struct AreaSampler {
+    faces: Vec<FaceID>,
+    cumulative_areas: Vec<f64>,
+    area: f64,
+}
+
+impl AreaSampler {
+    /// Returns `None` if the mesh has no area to sample.
+    fn new(mesh: &Mesh) -> Option<Self> {
+        let faces: Vec<FaceID> = mesh.face_iter().collect();
+        let mut cumulative_areas = Vec::with_capacity(faces.len());
+        let mut area = 0.0;
+        for face_id in faces.iter() {
+            area += mesh.face_area(*face_id);
+            cumulative_areas.push(area);
+        }
+        (area > 0.0).then_some(Self {
+            faces,
+            cumulative_areas,
+            area,
+        })
+    }
+
+    fn sample(&self, mesh: &Mesh, random: &mut Random) -> (FaceID, Vec3) {
+        let target = random.next() * self.area;
+        let index = self
+            .cumulative_areas
+            .partition_point(|a| *a < target)
+            .min(self.faces.len() - 1);
+        let face_id = self.faces[index];
+        (face_id, mesh.random_point_on_face(face_id, random))
+    }
+}
+
+/// A small pseudo random number generator (SplitMix64) to avoid a dependency.
+struct Random(u64);
+
+impl Random {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Returns a number in the range [0, 1).
+    fn next(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_uniform_is_deterministic_and_area_weighted() {
+        let mut mesh = crate::test_utility::square();
+        let samples = mesh.sample_uniform(1000, 7);
+        assert_eq!(samples, mesh.sample_uniform(1000, 7));
+
+        // Make the faces differ in area
+        let vertex_id = mesh
+            .vertex_iter()
+            .find(|v| mesh.vertex_position(*v) == vec3(1.0, 1.0, 0.0))
+            .unwrap();
+        mesh.move_vertex_to(vertex_id, vec3(3.0, 3.0, 0.0));
+        let samples = mesh.sample_uniform(4000, 7);
+        let (face_id, _) = samples[0];
+        let area_fraction =
+            mesh.face_area(face_id) / mesh.face_iter().map(|f| mesh.face_area(f)).sum::<f64>();
+        let fraction =
+            samples.iter().filter(|(f, _)| *f == face_id).count() as f64 / samples.len() as f64;
+        assert!((fraction - area_fraction).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_sample_poisson_disk() {
+        let mesh = crate::test_utility::square();
+        let samples = mesh.sample_poisson_disk(0.1, 3);
+        for (i, (_, p0)) in samples.iter().enumerate() {
+            for (_, p1) in samples.iter().skip(i + 1) {
+                assert!(p0.distance(*p1) >= 0.1);
+            }
+        }
+        // The samples should almost cover the square such that few points are further than the minimum distance from a sample
+        let uncovered = mesh
+            .sample_uniform(500, 5)
+            .into_iter()
+            .filter(|(_, p)| samples.iter().all(|(_, s)| s.distance(*p) >= 0.1))
+            .count();
+        assert!(uncovered < 10);
+    }
+
+    #[test]
+    fn test_interpolate_at() {
+        let mesh = crate::test_utility::square();
+        let face_id = mesh.face_iter().next().unwrap();
+        let (v0, v1, v2) = mesh.face_vertices(face_id);
+        let values: VertexProperty<f64> = mesh
+            .vertex_iter()
+            .map(|vertex_id| (vertex_id, mesh.vertex_position(vertex_id).x))
+            .collect();
+
+        assert_eq!(
+            mesh.interpolate_at(face_id, (1.0, 0.0, 0.0), &values),
+            values[&v0]
+        );
+        assert_eq!(
+            mesh.interpolate_at(face_id, (0.0, 1.0, 0.0), &values),
+            values[&v1]
+        );
+        assert_eq!(
+            mesh.interpolate_at(face_id, (0.0, 0.0, 1.0), &values),
+            values[&v2]
+        );
+        assert_eq!(
+            mesh.position_at(face_id, (0.0, 0.0, 1.0)),
+            mesh.vertex_position(v2)
+        );
+
+        // The values are linear in x, so the interpolated value is the x coordinate of the point
+        let barycentric = (0.2, 0.3, 0.5);
+        let point = mesh.position_at(face_id, barycentric);
+        assert!((mesh.interpolate_at(face_id, barycentric, &values) - point.x).abs() < 0.000001);
+    }
+}
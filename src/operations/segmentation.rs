@@ -0,0 +1,194 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use std::collections::HashMap;
+
+/// # Segmentation
+impl Mesh {
+    ///
+    /// Segments the mesh into smooth regions which are separated by sharp edges, ie. edges where the dihedral angle (see [Mesh::edge_dihedral_angle]) is larger than the given threshold.
+    /// Returns the index of the region that each face belongs to, where the indices are consecutive starting from zero.
+    ///
+    /// Note that a region can contain sharp edges, if the faces on each side of the edge are connected through a smooth path around the edge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::cube().into();
+    /// mesh.merge_overlapping_primitives();
+    /// let regions = mesh.segment_by_angle(degrees(30.0));
+    /// // The six sides of the cube
+    /// assert_eq!(regions.values().max(), Some(&5));
+    /// ```
+    ///
+    pub fn segment_by_angle(&self, angle_threshold: impl Into<Radians>) -> FaceProperty<usize> {
+        let angle_threshold = angle_threshold.into();
+        let is_sharp = |halfedge_id| match self.edge_dihedral_angle(halfedge_id) {
+            Some(angle) => angle > angle_threshold,
+            None => true,
+        };
+        self.face_component_ids_with_limit(&is_sharp)
+    }
+
+    ///
+    /// Segments the mesh into convex patches which are separated by concave regions, for example to find the parts of a model or the charts of a UV map.
+    /// Returns the index of the patch that each face belongs to, where the indices are consecutive starting from zero.
+    ///
+    /// The segmentation is computed using the watershed algorithm on the concavity of each face,
+    /// which is the negated minimum principal curvature (see [Mesh::principal_curvatures]) averaged over the vertices of the face.
+    /// Each patch is grown from a face with locally minimal concavity until it meets another patch.
+    /// Two patches are merged if the concavity where they meet is less than `depth_threshold` above the minimum concavity of one of the patches,
+    /// so a larger threshold results in fewer patches.
+    /// The threshold is measured in the same unit as the curvature, ie. one over the unit of the positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// // A convex shape is one patch
+    /// let mesh = Mesh::icosphere(2);
+    /// let patches = mesh.segment_by_curvature(0.1);
+    /// assert!(mesh.face_iter().all(|face_id| patches[&face_id] == 0));
+    /// ```
+    ///
+    pub fn segment_by_curvature(&self, depth_threshold: f64) -> FaceProperty<usize> {
+        let concavity: VertexProperty<f64> = self
+            .vertex_iter()
+            .map(|vertex_id| {
+                let h = self.mean_curvature(vertex_id);
+                let k = self.gaussian_curvature(vertex_id);
+                (vertex_id, (h * h - k).max(0.0).sqrt() - h)
+            })
+            .collect();
+        let mut faces: Vec<(FaceID, f64)> = self
+            .face_iter()
+            .map(|face_id| {
+                let (v0, v1, v2) = self.face_vertices(face_id);
+                let height = (concavity[&v0] + concavity[&v1] + concavity[&v2]) / 3.0;
+                (face_id, height)
+            })
+            .collect();
+        faces.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        // Flood the faces in order of increasing concavity, where each face joins the patch of the lowest neighbour which is already flooded,
+        // or starts a new patch if it is a local minimum
+        let mut labels = FaceProperty::new();
+        let mut parents: Vec<usize> = Vec::new();
+        let mut minimum_heights: Vec<f64> = Vec::new();
+        fn find(parents: &mut [usize], label: usize) -> usize {
+            let mut root = label;
+            while parents[root] != root {
+                root = parents[root];
+            }
+            parents[label] = root;
+            root
+        }
+        for (face_id, height) in faces {
+            let mut neighbours = Vec::new();
+            for halfedge_id in self.face_halfedge_iter(face_id) {
+                if let Some(neighbour_id) =
+                    self.walker_from_halfedge(halfedge_id).as_twin().face_id()
+                {
+                    if let Some(label) = labels.get(&neighbour_id) {
+                        let root = find(&mut parents, *label);
+                        if !neighbours.contains(&root) {
+                            neighbours.push(root);
+                        }
+                    }
+                }
+            }
+            let label = match neighbours
+                .iter()
+                .copied()
+                .min_by(|a, b| minimum_heights[*a].total_cmp(&minimum_heights[*b]))
+            {
+                Some(deepest) => {
+                    for root in neighbours {
+                        if root != deepest && height - minimum_heights[root] < depth_threshold {
+                            parents[root] = deepest;
+                        }
+                    }
+                    deepest
+                }
+                None => {
+                    parents.push(parents.len());
+                    minimum_heights.push(height);
+                    parents.len() - 1
+                }
+            };
+            labels.insert(face_id, label);
+        }
+
+        // Number the patches consecutively in the order of the faces
+        let mut patch_ids = HashMap::new();
+        self.face_iter()
+            .map(|face_id| {
+                let root = find(&mut parents, labels[&face_id]);
+                let no_patches = patch_ids.len();
+                (face_id, *patch_ids.entry(root).or_insert(no_patches))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    #[test]
+    fn test_segment_by_angle() {
+        let mut mesh: Mesh = TriMesh::cube().into();
+        mesh.merge_overlapping_primitives();
+        let regions = mesh.segment_by_angle(degrees(30.0));
+        for region in 0..6 {
+            let faces: Vec<FaceID> = mesh
+                .face_iter()
+                .filter(|face_id| regions[face_id] == region)
+                .collect();
+            assert_eq!(faces.len(), 2);
+            assert!(mesh.face_normal(faces[0]).dot(mesh.face_normal(faces[1])) > 0.999999);
+        }
+
+        let regions = mesh.segment_by_angle(degrees(100.0));
+        assert!(mesh.face_iter().all(|face_id| regions[&face_id] == 0));
+
+        let mesh: Mesh = TriMesh::sphere(16).into();
+        let regions = mesh.segment_by_angle(degrees(30.0));
+        assert!(mesh.face_iter().all(|face_id| regions[&face_id] == 0));
+    }
+
+    #[test]
+    fn test_segment_by_curvature() {
+        // A peanut shape with a concave waist
+        let mut mesh = Mesh::icosphere(3);
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            let radius = 0.4 + p.y * p.y;
+            mesh.set_vertex_position(vertex_id, vec3(radius * p.x, 2.0 * p.y, radius * p.z));
+        }
+        let patches = mesh.segment_by_curvature(1.0);
+        let top = mesh
+            .face_iter()
+            .find(|f| mesh.face_center(*f).y > 1.0)
+            .unwrap();
+        let bottom = mesh
+            .face_iter()
+            .find(|f| mesh.face_center(*f).y < -1.0)
+            .unwrap();
+        assert_ne!(patches[&top], patches[&bottom]);
+        for face_id in mesh.face_iter() {
+            let y = mesh.face_center(face_id).y;
+            if y > 0.5 {
+                assert_eq!(patches[&face_id], patches[&top]);
+            } else if y < -0.5 {
+                assert_eq!(patches[&face_id], patches[&bottom]);
+            }
+        }
+        assert_eq!(patches.values().max(), Some(&1));
+
+        let patches = mesh.segment_by_curvature(100.0);
+        assert!(mesh.face_iter().all(|face_id| patches[&face_id] == 0));
+    }
+}
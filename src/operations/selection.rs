@@ -0,0 +1,339 @@
+//! Selections of faces and vertices, see [FaceSet] and [VertexSet].
+
+use crate::mesh::*;
+use std::collections::BTreeSet;
+
+///
+/// A set of primitives of a [Mesh], for example a selection of the faces or vertices that an operation should be applied to.
+/// See [FaceSet] and [VertexSet] for the operations that depend on the connectivity of the mesh.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selection<T: ID> {
+    ids: BTreeSet<T>,
+}
+
+///
+/// A selection of faces, see [Selection].
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::*;
+/// let mesh = Mesh::icosphere(2);
+/// // Select the faces near the top and clone them into a new mesh
+/// let top: FaceSet = mesh
+///     .face_iter()
+///     .filter(|face_id| mesh.face_center(*face_id).y > 0.9)
+///     .collect();
+/// let selection = top.grow(&mesh, 1);
+/// let clone = mesh.clone_subset(&|_, face_id| selection.contains(face_id));
+/// assert_eq!(clone.no_faces(), selection.len());
+/// ```
+///
+pub type FaceSet = Selection<FaceID>;
+
+///
+/// A selection of vertices, see [Selection].
+///
+pub type VertexSet = Selection<VertexID>;
+
+impl<T: ID> Selection<T> {
+    /// Constructs an empty selection.
+    pub fn new() -> Self {
+        Self {
+            ids: BTreeSet::new(),
+        }
+    }
+
+    /// Adds the primitive to the selection and returns whether it was not already selected.
+    pub fn insert(&mut self, id: T) -> bool {
+        self.ids.insert(id)
+    }
+
+    /// Removes the primitive from the selection and returns whether it was selected.
+    pub fn remove(&mut self, id: T) -> bool {
+        self.ids.remove(&id)
+    }
+
+    /// Returns whether the primitive is selected.
+    pub fn contains(&self, id: T) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// Returns the number of selected primitives.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns whether no primitives are selected.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Iterates over the selected primitives in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.ids.iter().copied()
+    }
+
+    /// Returns the primitives that are selected in this or the other selection.
+    pub fn union(&self, other: &Self) -> Self {
+        self.ids.union(&other.ids).copied().collect()
+    }
+
+    /// Returns the primitives that are selected in both this and the other selection.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.ids.intersection(&other.ids).copied().collect()
+    }
+
+    /// Returns the primitives that are selected in this selection but not in the other selection.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.ids.difference(&other.ids).copied().collect()
+    }
+}
+
+impl<T: ID> Default for Selection<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ID> FromIterator<T> for Selection<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            ids: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: ID> Extend<T> for Selection<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.ids.extend(iter);
+    }
+}
+
+impl<T: ID> IntoIterator for Selection<T> {
+    type Item = T;
+    type IntoIter = std::collections::btree_set::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.ids.into_iter()
+    }
+}
+
+impl Selection<FaceID> {
+    ///
+    /// Returns the selection grown `n` times, where each time the faces that share a vertex with a selected face are added.
+    ///
+    pub fn grow(&self, mesh: &Mesh, n: usize) -> Self {
+        let mut selection = self.clone();
+        for _ in 0..n {
+            let no_faces = selection.len();
+            let vertices = selection.vertices(mesh);
+            selection.extend(vertices.iter().flat_map(|v| vertex_faces(mesh, v)));
+            if selection.len() == no_faces {
+                break;
+            }
+        }
+        selection
+    }
+
+    ///
+    /// Returns the selection shrunk `n` times, where each time the faces that share a vertex with a face which is not selected are removed.
+    /// The faces at the boundary of the mesh are not removed unless they share a vertex with a face which is not selected.
+    ///
+    pub fn shrink(&self, mesh: &Mesh, n: usize) -> Self {
+        let mut selection = self.clone();
+        for _ in 0..n {
+            let border: VertexSet = selection
+                .vertices(mesh)
+                .iter()
+                .filter(|v| vertex_faces(mesh, *v).any(|f| !selection.contains(f)))
+                .collect();
+            selection = selection
+                .iter()
+                .filter(|face_id| {
+                    let (v0, v1, v2) = mesh.face_vertices(*face_id);
+                    !border.contains(v0) && !border.contains(v1) && !border.contains(v2)
+                })
+                .collect();
+        }
+        selection
+    }
+
+    ///
+    /// Returns the half-edges of the selected faces which are adjacent to a face which is not selected or which are on the boundary of the mesh.
+    /// The half-edges are oriented in the same direction as the selected faces, ie. they go counterclockwise around the selection.
+    ///
+    pub fn boundary(&self, mesh: &Mesh) -> Vec<HalfEdgeID> {
+        self.iter()
+            .flat_map(|face_id| mesh.face_halfedge_iter(face_id))
+            .filter(|halfedge_id| {
+                match mesh.walker_from_halfedge(*halfedge_id).as_twin().face_id() {
+                    Some(face_id) => !self.contains(face_id),
+                    None => true,
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns the vertices of the selected faces.
+    ///
+    pub fn vertices(&self, mesh: &Mesh) -> VertexSet {
+        self.iter()
+            .flat_map(|face_id| {
+                let (v0, v1, v2) = mesh.face_vertices(face_id);
+                [v0, v1, v2]
+            })
+            .collect()
+    }
+}
+
+impl Selection<VertexID> {
+    ///
+    /// Returns the selection grown `n` times, where each time the vertices that are connected by an edge to a selected vertex are added.
+    ///
+    pub fn grow(&self, mesh: &Mesh, n: usize) -> Self {
+        let mut selection = self.clone();
+        for _ in 0..n {
+            let neighbours: Vec<VertexID> = selection
+                .iter()
+                .flat_map(|v| vertex_neighbours(mesh, v))
+                .collect();
+            let no_vertices = selection.len();
+            selection.extend(neighbours);
+            if selection.len() == no_vertices {
+                break;
+            }
+        }
+        selection
+    }
+
+    ///
+    /// Returns the selection shrunk `n` times, where each time the vertices that are connected by an edge to a vertex which is not selected are removed.
+    ///
+    pub fn shrink(&self, mesh: &Mesh, n: usize) -> Self {
+        let mut selection = self.clone();
+        for _ in 0..n {
+            selection = selection
+                .iter()
+                .filter(|v| {
+                    vertex_neighbours(mesh, *v).all(|neighbour| selection.contains(neighbour))
+                })
+                .collect();
+        }
+        selection
+    }
+
+    ///
+    /// Returns the selected vertices that are connected by an edge to a vertex which is not selected.
+    ///
+    pub fn boundary(&self, mesh: &Mesh) -> VertexSet {
+        self.iter()
+            .filter(|v| vertex_neighbours(mesh, *v).any(|neighbour| !self.contains(neighbour)))
+            .collect()
+    }
+
+    ///
+    /// Returns the faces where all three vertices are selected.
+    ///
+    pub fn faces(&self, mesh: &Mesh) -> FaceSet {
+        self.iter()
+            .flat_map(|v| vertex_faces(mesh, v))
+            .filter(|face_id| {
+                let (v0, v1, v2) = mesh.face_vertices(*face_id);
+                self.contains(v0) && self.contains(v1) && self.contains(v2)
+            })
+            .collect()
+    }
+}
+
+/// Iterates over the faces adjacent to the vertex.
+fn vertex_faces(mesh: &Mesh, vertex_id: VertexID) -> impl Iterator<Item = FaceID> + '_ {
+    mesh.vertex_halfedge_iter(vertex_id)
+        .filter_map(|halfedge_id| mesh.walker_from_halfedge(halfedge_id).face_id())
+}
+
+/// Iterates over the vertices connected to the vertex by an edge.
+fn vertex_neighbours(mesh: &Mesh, vertex_id: VertexID) -> impl Iterator<Item = VertexID> + '_ {
+    mesh.vertex_halfedge_iter(vertex_id)
+        .filter_map(|halfedge_id| mesh.walker_from_halfedge(halfedge_id).vertex_id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_operations() {
+        let mesh = crate::test_utility::subdivided_triangle();
+        let faces: Vec<FaceID> = mesh.face_iter().collect();
+        let a: FaceSet = faces[..2].iter().copied().collect();
+        let b: FaceSet = faces[1..].iter().copied().collect();
+        assert_eq!(a.union(&b).len(), faces.len());
+        assert_eq!(
+            a.intersection(&b).iter().collect::<Vec<_>>(),
+            vec![faces[1]]
+        );
+        assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), vec![faces[0]]);
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_grow_and_shrink_faces() {
+        let mesh = Mesh::grid(
+            vec3(0.0, 0.0, 0.0),
+            vec3(8.0, 0.0, 0.0),
+            vec3(0.0, 8.0, 0.0),
+            8,
+            8,
+        );
+        let cell: FaceSet = mesh
+            .face_iter()
+            .filter(|face_id| {
+                let p = mesh.face_center(*face_id);
+                p.x > 0.0 && p.x < 1.0 && p.y > 0.0 && p.y < 1.0
+            })
+            .collect();
+        assert_eq!(cell.len(), 2);
+
+        let grown = cell.grow(&mesh, 1);
+        assert!(grown.len() > cell.len());
+        assert!(cell.difference(&grown).is_empty());
+        assert_eq!(grown.shrink(&mesh, 1), cell);
+
+        let all = cell.grow(&mesh, 100);
+        assert_eq!(all.len(), mesh.no_faces());
+        assert_eq!(all.boundary(&mesh).len(), 4 * 8);
+        assert_eq!(all.shrink(&mesh, 1).len(), mesh.no_faces());
+
+        // The boundary of the two faces goes around the square which they cover
+        let boundary = cell.boundary(&mesh);
+        assert_eq!(boundary.len(), 4);
+        let length: f64 = boundary.iter().map(|h| mesh.edge_length(*h)).sum();
+        assert!((length - 4.0).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_vertex_selection() {
+        let mesh = Mesh::grid(
+            vec3(0.0, 0.0, 0.0),
+            vec3(4.0, 0.0, 0.0),
+            vec3(0.0, 4.0, 0.0),
+            4,
+            4,
+        );
+        let center: VertexSet = mesh
+            .vertex_iter()
+            .filter(|v| mesh.vertex_position(*v).magnitude() < 0.1)
+            .collect();
+        assert_eq!(center.len(), 1);
+        assert!(center.faces(&mesh).is_empty());
+
+        let grown = center.grow(&mesh, 1);
+        assert_eq!(grown.len(), 7);
+        assert_eq!(grown.boundary(&mesh).len(), 6);
+        assert_eq!(grown.shrink(&mesh, 1), center);
+        assert_eq!(grown.faces(&mesh).len(), 6);
+        assert_eq!(grown.faces(&mesh).vertices(&mesh), grown);
+    }
+}
@@ -0,0 +1,395 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::operations::bvh::*;
+use crate::operations::distance::closest_point_on_triangle;
+use std::cell::Cell;
+
+///
+/// A plane given by a normal and a signed distance such that the points `p` for which `normal.dot(p) + distance` is zero lie in the plane.
+/// The side of the plane that the normal points towards is called the positive side.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    /// The normal of the plane, which does not need to be normalized
+    pub normal: Vec3,
+    /// The signed distance from origo to the plane along the normal scaled by the length of the normal
+    pub distance: f64,
+}
+
+impl Plane {
+    /// Constructs a new plane from a point in the plane and the normal of the plane.
+    pub fn new(point: Vec3, normal: Vec3) -> Self {
+        Self {
+            normal,
+            distance: -normal.dot(point),
+        }
+    }
+
+    /// Returns the signed distance from the plane to the point scaled by the length of the normal, ie. positive on the positive side of the plane.
+    pub fn signed_distance(&self, point: Vec3) -> f64 {
+        self.normal.dot(point) + self.distance
+    }
+}
+
+///
+/// A spatial index over the faces of a [Mesh], which is built once and can then be used for any number of [spatial queries](Mesh#spatial-queries),
+/// for example when selecting the faces under a brush each frame. Construct it with [Mesh::spatial_index].
+///
+/// The index borrows the mesh, so the mesh cannot be changed while the index is in use, ie. the index is never out of date.
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::*;
+/// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+/// let index = mesh.spatial_index();
+/// for i in 0..10 {
+///     let center = vec3(0.0, 1.0, 0.1 * i as f64);
+///     assert_eq!(index.faces_within_sphere(center, 0.3), mesh.faces_within_sphere(center, 0.3));
+/// }
+/// ```
+///
+pub struct SpatialIndex<'a> {
+    mesh: &'a Mesh,
+    bvh: Bvh,
+}
+
+impl<'a> SpatialIndex<'a> {
+    /// Returns the mesh that the index is built over.
+    pub fn mesh(&self) -> &'a Mesh {
+        self.mesh
+    }
+
+    ///
+    /// Returns the faces which intersect the axis aligned box given by the minimum and maximum corner, ordered by id, see [Mesh::faces_in_aabb].
+    ///
+    pub fn faces_in_aabb(&self, min: Vec3, max: Vec3) -> Vec<FaceID> {
+        let bounds = Bounds { min, max };
+        let mut faces = Vec::new();
+        self.bvh.for_each_overlapping(&bounds, 0.0, &mut |face_id| {
+            if self.mesh.face_intersects_aabb(face_id, &bounds) {
+                faces.push(face_id);
+            }
+        });
+        faces.sort();
+        faces
+    }
+
+    ///
+    /// Returns the faces which are possibly inside the frustum given by six planes whose normals point towards the inside of the frustum, ordered by id, see [Mesh::faces_in_frustum].
+    ///
+    pub fn faces_in_frustum(&self, planes: &[Plane; 6]) -> Vec<FaceID> {
+        let mut faces = Vec::new();
+        self.bvh.traverse(
+            &mut |bounds| {
+                planes.iter().all(|plane| {
+                    // The corner of the bounds furthest in the direction of the normal
+                    let corner = vec3(
+                        if plane.normal.x >= 0.0 {
+                            bounds.max.x
+                        } else {
+                            bounds.min.x
+                        },
+                        if plane.normal.y >= 0.0 {
+                            bounds.max.y
+                        } else {
+                            bounds.min.y
+                        },
+                        if plane.normal.z >= 0.0 {
+                            bounds.max.z
+                        } else {
+                            bounds.min.z
+                        },
+                    );
+                    plane.signed_distance(corner) >= 0.0
+                })
+            },
+            &mut |face_id| {
+                let (p0, p1, p2) = self.mesh.face_positions(face_id);
+                if planes.iter().all(|plane| {
+                    plane.signed_distance(p0) >= 0.0
+                        || plane.signed_distance(p1) >= 0.0
+                        || plane.signed_distance(p2) >= 0.0
+                }) {
+                    faces.push(face_id);
+                }
+            },
+        );
+        faces.sort();
+        faces
+    }
+
+    ///
+    /// Returns the closest point on the mesh to the given point together with the face that contains the closest point
+    /// or `None` if the mesh has no faces, see [Mesh::closest_point].
+    ///
+    pub fn closest_point(&self, point: Vec3) -> Option<(FaceID, Vec3)> {
+        let best: Cell<Option<(f64, FaceID, Vec3)>> = Cell::new(None);
+        let within_best = |distance2: f64| match best.get() {
+            Some((best_distance2, _, _)) => distance2 < best_distance2,
+            None => true,
+        };
+        self.bvh.traverse(
+            &mut |bounds| within_best(bounds.distance2_to_point(&point)),
+            &mut |face_id| {
+                let (p0, p1, p2) = self.mesh.face_positions(face_id);
+                let closest = closest_point_on_triangle(point, p0, p1, p2);
+                let distance2 = closest.distance2(point);
+                if within_best(distance2) {
+                    best.set(Some((distance2, face_id, closest)));
+                }
+            },
+        );
+        best.get().map(|(_, face_id, closest)| (face_id, closest))
+    }
+
+    ///
+    /// Returns the faces which intersect the sphere with the given center and radius, ordered by id, see [Mesh::faces_within_sphere].
+    ///
+    pub fn faces_within_sphere(&self, center: Vec3, radius: f64) -> Vec<FaceID> {
+        let bounds = Bounds {
+            min: center - vec3(radius, radius, radius),
+            max: center + vec3(radius, radius, radius),
+        };
+        let mut faces = Vec::new();
+        self.bvh.for_each_overlapping(&bounds, 0.0, &mut |face_id| {
+            let (p0, p1, p2) = self.mesh.face_positions(face_id);
+            if closest_point_on_triangle(center, p0, p1, p2).distance2(center) <= radius * radius {
+                faces.push(face_id);
+            }
+        });
+        faces.sort();
+        faces
+    }
+}
+
+/// # Spatial queries
+///
+/// Methods for finding the primitives inside a region of space, for example for box selection.
+/// The faces are organised in a bounding volume hierarchy, so only the faces close to the region are tested.
+/// Each of the queries on the mesh builds the hierarchy before the query, so use [Mesh::spatial_index] to build it once when making several queries on the same mesh.
+///
+impl Mesh {
+    ///
+    /// Builds a [SpatialIndex] over the faces of the mesh, which can be reused for several spatial queries as long as the mesh is not changed.
+    /// The index is built using multiple threads if the `rayon` feature is enabled.
+    ///
+    pub fn spatial_index(&self) -> SpatialIndex<'_> {
+        SpatialIndex {
+            mesh: self,
+            bvh: Bvh::new(self),
+        }
+    }
+
+    ///
+    /// Returns the faces which intersect the axis aligned box given by the minimum and maximum corner, ordered by id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let faces = mesh.faces_in_aabb(vec3(0.5, -1.0, -1.0), vec3(2.0, 1.0, 1.0));
+    /// assert!(faces.len() > 0 && faces.len() < mesh.no_faces() / 2);
+    /// for face_id in faces {
+    ///     let (p0, p1, p2) = mesh.face_positions(face_id);
+    ///     assert!(p0.x > 0.0 || p1.x > 0.0 || p2.x > 0.0);
+    /// }
+    /// ```
+    ///
+    pub fn faces_in_aabb(&self, min: Vec3, max: Vec3) -> Vec<FaceID> {
+        self.spatial_index().faces_in_aabb(min, max)
+    }
+
+    ///
+    /// Returns the faces which are possibly inside the frustum given by six planes whose normals point towards the inside of the frustum, ordered by id.
+    /// A face is only discarded if all of its vertices are on the negative side of one of the planes,
+    /// so a few faces close to the corners of the frustum are returned even though they are outside the frustum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// // A box shaped frustum containing the positive x half of the mesh
+    /// let planes = [
+    ///     Plane::new(vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0)),
+    ///     Plane::new(vec3(2.0, 0.0, 0.0), vec3(-1.0, 0.0, 0.0)),
+    ///     Plane::new(vec3(0.0, -2.0, 0.0), vec3(0.0, 1.0, 0.0)),
+    ///     Plane::new(vec3(0.0, 2.0, 0.0), vec3(0.0, -1.0, 0.0)),
+    ///     Plane::new(vec3(0.0, 0.0, -2.0), vec3(0.0, 0.0, 1.0)),
+    ///     Plane::new(vec3(0.0, 0.0, 2.0), vec3(0.0, 0.0, -1.0)),
+    /// ];
+    /// let faces = mesh.faces_in_frustum(&planes);
+    /// assert_eq!(faces, mesh.faces_in_aabb(vec3(0.0, -2.0, -2.0), vec3(2.0, 2.0, 2.0)));
+    /// ```
+    ///
+    pub fn faces_in_frustum(&self, planes: &[Plane; 6]) -> Vec<FaceID> {
+        self.spatial_index().faces_in_frustum(planes)
+    }
+
+    ///
+    /// Returns the faces which intersect the sphere with the given center and radius, ordered by id.
+    /// This is for example useful for brush-style editing where the faces under the brush are modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let faces = mesh.faces_within_sphere(vec3(0.0, 1.0, 0.0), 0.5);
+    /// assert!(faces.len() > 0 && faces.len() < mesh.no_faces() / 2);
+    /// ```
+    ///
+    pub fn faces_within_sphere(&self, center: Vec3, radius: f64) -> Vec<FaceID> {
+        self.spatial_index().faces_within_sphere(center, radius)
+    }
+
+    ///
+    /// Returns the vertices inside the sphere with the given center and radius, ordered by id.
+    /// This is for example useful for brush-style editing where the vertices under the brush are moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+    /// let center = vec3(0.0, 1.0, 0.0);
+    /// for vertex_id in mesh.vertices_within_sphere(center, 0.5) {
+    ///     let p = mesh.vertex_position(vertex_id);
+    ///     mesh.move_vertex_to(vertex_id, p + 0.1 * (0.5 - p.distance(center)) * p);
+    /// }
+    /// ```
+    ///
+    pub fn vertices_within_sphere(&self, center: Vec3, radius: f64) -> Vec<VertexID> {
+        self.vertex_iter()
+            .filter(|vertex_id| {
+                self.vertex_position(*vertex_id).distance2(center) <= radius * radius
+            })
+            .collect()
+    }
+
+    /// Returns whether the face intersects the bounds using the separating axis theorem.
+    fn face_intersects_aabb(&self, face_id: FaceID, bounds: &Bounds) -> bool {
+        let center = bounds.center();
+        let half_size = 0.5 * (bounds.max - bounds.min);
+        let (p0, p1, p2) = self.face_positions(face_id);
+        let vertices = [p0 - center, p1 - center, p2 - center];
+        let edges = [
+            vertices[1] - vertices[0],
+            vertices[2] - vertices[1],
+            vertices[0] - vertices[2],
+        ];
+        let is_separating = |axis: Vec3| {
+            let projections = vertices.map(|v| v.dot(axis));
+            let min = projections[0].min(projections[1]).min(projections[2]);
+            let max = projections[0].max(projections[1]).max(projections[2]);
+            let radius = half_size.x * axis.x.abs()
+                + half_size.y * axis.y.abs()
+                + half_size.z * axis.z.abs();
+            min > radius || max < -radius
+        };
+        let box_axes = [Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()];
+        !(box_axes.into_iter().any(is_separating)
+            || is_separating(edges[0].cross(edges[1]))
+            || box_axes
+                .iter()
+                .any(|a| edges.iter().any(|e| is_separating(a.cross(*e)))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_faces_in_aabb() {
+        let mesh = crate::test_utility::square();
+
+        // Overlaps the bounds of both faces but only intersects one of them
+        let faces = mesh.faces_in_aabb(vec3(0.6, 0.6, -1.0), vec3(2.0, 2.0, 1.0));
+        assert_eq!(faces.len(), 1);
+        let (p0, p1, p2) = mesh.face_positions(faces[0]);
+        assert!([p0, p1, p2].contains(&vec3(1.0, 1.0, 0.0)));
+
+        assert_eq!(
+            mesh.faces_in_aabb(vec3(-0.1, -0.1, -0.1), vec3(0.1, 0.1, 0.1))
+                .len(),
+            2
+        );
+        assert!(mesh
+            .faces_in_aabb(vec3(-0.1, -0.1, 0.1), vec3(0.1, 0.1, 0.2))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_within_sphere() {
+        let mesh = crate::test_utility::square();
+
+        // Intersects one face but does not contain any vertices
+        let faces = mesh.faces_within_sphere(vec3(0.5, 0.5, 0.2), 0.3);
+        assert_eq!(faces.len(), 1);
+        let (p0, p1, p2) = mesh.face_positions(faces[0]);
+        assert!([p0, p1, p2].contains(&vec3(1.0, 1.0, 0.0)));
+        assert!(mesh
+            .vertices_within_sphere(vec3(0.5, 0.5, 0.2), 0.3)
+            .is_empty());
+
+        let vertices = mesh.vertices_within_sphere(vec3(1.0, 1.0, 0.1), 0.2);
+        assert_eq!(vertices.len(), 1);
+        assert_eq!(mesh.vertex_position(vertices[0]), vec3(1.0, 1.0, 0.0));
+        assert_eq!(mesh.faces_within_sphere(vec3(1.0, 1.0, 0.1), 0.2), faces);
+    }
+
+    #[test]
+    fn test_spatial_index() {
+        let mut mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+        let expected = mesh.faces_in_aabb(vec3(0.5, -1.0, -1.0), vec3(2.0, 1.0, 1.0));
+        {
+            let index = mesh.spatial_index();
+            assert_eq!(
+                index.faces_in_aabb(vec3(0.5, -1.0, -1.0), vec3(2.0, 1.0, 1.0)),
+                expected
+            );
+            assert_eq!(
+                index.faces_within_sphere(vec3(0.0, 1.0, 0.0), 0.5),
+                mesh.faces_within_sphere(vec3(0.0, 1.0, 0.0), 0.5)
+            );
+        }
+
+        // A new index is needed after changing the mesh
+        mesh.translate(vec3(-1.0, 0.0, 0.0));
+        let index = mesh.spatial_index();
+        assert!(index
+            .faces_in_aabb(vec3(0.5, -1.0, -1.0), vec3(2.0, 1.0, 1.0))
+            .is_empty());
+        assert_eq!(index.mesh().no_faces(), mesh.no_faces());
+    }
+
+    #[test]
+    fn test_faces_in_frustum() {
+        let mesh: Mesh = three_d_asset::TriMesh::sphere(8).into();
+        // A frustum looking down the negative z axis from (0, 0, 5) with a 10 degree opening angle
+        let (s, c) = (5.0f64).to_radians().sin_cos();
+        let eye = vec3(0.0, 0.0, 5.0);
+        let planes = [
+            Plane::new(eye, vec3(c, 0.0, -s)),
+            Plane::new(eye, vec3(-c, 0.0, -s)),
+            Plane::new(eye, vec3(0.0, c, -s)),
+            Plane::new(eye, vec3(0.0, -c, -s)),
+            Plane::new(vec3(0.0, 0.0, 4.0), vec3(0.0, 0.0, -1.0)),
+            Plane::new(vec3(0.0, 0.0, -10.0), vec3(0.0, 0.0, 1.0)),
+        ];
+        let faces = mesh.faces_in_frustum(&planes);
+        assert_eq!(mesh.spatial_index().faces_in_frustum(&planes), faces);
+        assert!(!faces.is_empty());
+        assert!(faces.len() < mesh.no_faces());
+        for face_id in mesh.face_iter() {
+            let center = mesh.face_center(face_id);
+            if planes.iter().all(|p| p.signed_distance(center) >= 0.0) {
+                assert!(faces.contains(&face_id));
+            }
+        }
+    }
+}
@@ -1,9 +1,38 @@
 //! See [Mesh](crate::mesh::Mesh).
 
 use crate::mesh::*;
+use crate::operations::bvh::*;
+use crate::operations::intersection::utility::{point_line_segment_distance, MARGIN};
 use crate::operations::*;
+use crate::Error;
 use std::collections::{HashMap, HashSet};
 
+///
+/// The mapping from the ids of a mesh created from a source mesh, for example using [Mesh::clone_subset_with_provenance], back to the ids of the source mesh.
+/// Can be used to transfer attributes and labels from the source mesh to the new mesh.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Provenance {
+    /// The id of the source vertex given by the id of the vertex in the new mesh.
+    /// Vertices which are not in the source mesh, for example vertices created at an intersection, are not included.
+    pub vertices: HashMap<VertexID, VertexID>,
+    /// The id of the source face given by the id of the face in the new mesh.
+    /// A face which is part of a source face that was split, is mapped to the source face.
+    pub faces: HashMap<FaceID, FaceID>,
+}
+
+/// The subsets of a mesh together with the mapping back to the ids of the mesh.
+type SubsetsWithProvenance = Vec<(Mesh, Provenance)>;
+
+/// The face before the split given by a face created by splitting the faces at an intersection.
+type Origins = HashMap<FaceID, FaceID>;
+
+/// The pairs of vertices at the intersection, where the first vertex is in the first mesh and the second vertex is in the second mesh.
+type Stitches = Vec<(VertexID, VertexID)>;
+
+/// The edges created in each of the two meshes when splitting at the intersection points.
+type NewEdges = (Vec<HalfEdgeID>, Vec<HalfEdgeID>);
+
 /// # Split
 impl Mesh {
     /// Clones a subset of this mesh defined by the is_included function.
@@ -17,6 +46,38 @@ impl Mesh {
         clone
     }
 
+    ///
+    /// Clones a subset of this mesh defined by the is_included function, like [Mesh::clone_subset],
+    /// but the ids of the returned mesh are compacted (see [Mesh::compact]) and the mapping back to the ids of this mesh is returned as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh = Mesh::icosphere(2);
+    /// let (subset, provenance) = mesh.clone_subset_with_provenance(&|mesh, face_id| {
+    ///     mesh.face_center(face_id).y > 0.0
+    /// });
+    ///
+    /// for vertex_id in subset.vertex_iter() {
+    ///     let source_vertex_id = provenance.vertices[&vertex_id];
+    ///     assert_eq!(subset.vertex_position(vertex_id), mesh.vertex_position(source_vertex_id));
+    /// }
+    /// ```
+    ///
+    pub fn clone_subset_with_provenance(
+        &self,
+        is_included: &dyn Fn(&Mesh, FaceID) -> bool,
+    ) -> (Mesh, Provenance) {
+        let mut subset = self.clone_subset(is_included);
+        let remap = subset.compact();
+        let provenance = Provenance {
+            vertices: remap.vertices.into_iter().map(|(s, n)| (n, s)).collect(),
+            faces: remap.faces.into_iter().map(|(s, n)| (n, s)).collect(),
+        };
+        (subset, provenance)
+    }
+
     ///
     /// Splits the mesh into subsets bounded by the edges where the is_at_split function returns true.
     ///
@@ -30,47 +91,384 @@ impl Mesh {
     }
 
     ///
-    /// Splits the two meshes into subsets bounded by the intersection between the two meshes.
+    /// Splits the mesh into subsets bounded by the edges where the is_at_split function returns true, like [Mesh::split],
+    /// but the ids of each subset are compacted and the mapping back to the ids of this mesh is returned as well, see [Mesh::clone_subset_with_provenance].
     ///
-    pub fn split_at_intersection(&mut self, other: &mut Mesh) -> (Vec<Mesh>, Vec<Mesh>) {
-        let stitches = self.split_primitives_at_intersection_internal(other);
-        let mut map1 = HashMap::new();
-        let mut map2 = HashMap::new();
-        stitches.iter().for_each(|(v0, v1)| {
-            map1.insert(*v0, *v1);
-            map2.insert(*v1, *v0);
+    pub fn split_with_provenance(
+        &self,
+        is_at_split: &dyn Fn(&Mesh, HalfEdgeID) -> bool,
+    ) -> Vec<(Mesh, Provenance)> {
+        let components =
+            self.connected_components_with_limit(&|halfedge_id| is_at_split(self, halfedge_id));
+        components
+            .iter()
+            .map(|cc| self.clone_subset_with_provenance(&|_, face_id| cc.contains(&face_id)))
+            .collect()
+    }
+
+    ///
+    /// Splits the mesh along the isoline where the scalar field, given as a value for each vertex, is equal to the given value.
+    /// First, a new vertex is inserted on each edge crossing the isoline using [Mesh::split_edge], which also splits the adjacent faces,
+    /// and then the mesh is split along the resulting edges using [Mesh::split].
+    /// Returns the subsets below the value and the subsets above or equal to the value.
+    ///
+    /// **Note:** The field must contain a value for each vertex and the inserted vertices are added to this mesh.
+    ///
+    pub fn split_at_isovalue(
+        &mut self,
+        field: &VertexProperty<f64>,
+        value: f64,
+    ) -> (Vec<Mesh>, Vec<Mesh>) {
+        let mut field = field.clone();
+        let crossings: Vec<_> = self
+            .edge_iter()
+            .filter_map(|edge_id| {
+                let halfedge_id = edge_id.into();
+                let (v0, v1) = self.edge_vertices(halfedge_id);
+                let (f0, f1) = (field[&v0], field[&v1]);
+                if (f0 - value) * (f1 - value) < 0.0 {
+                    let t = (value - f0) / (f1 - f0);
+                    let position = self.vertex_position(v0)
+                        + t * (self.vertex_position(v1) - self.vertex_position(v0));
+                    Some((halfedge_id, position))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        for (halfedge_id, position) in crossings {
+            let vertex_id = self.split_edge(halfedge_id, position);
+            field.insert(vertex_id, value);
+        }
+
+        let is_below = |mesh: &Mesh, face_id: FaceID| {
+            let (v0, v1, v2) = mesh.face_vertices(face_id);
+            field[&v0] + field[&v1] + field[&v2] < 3.0 * value
+        };
+        let meshes = self.split(&|mesh, halfedge_id| {
+            let mut walker = mesh.walker_from_halfedge(halfedge_id);
+            match (walker.face_id(), walker.as_twin().face_id()) {
+                (Some(f0), Some(f1)) => is_below(mesh, f0) != is_below(mesh, f1),
+                _ => false,
+            }
         });
+        meshes.into_iter().partition(|mesh| {
+            mesh.face_iter()
+                .next()
+                .map(|face_id| is_below(mesh, face_id))
+                .unwrap_or(false)
+        })
+    }
+
+    ///
+    /// Splits the two meshes into subsets bounded by the intersection between the two meshes.
+    /// Enable the `robust` feature to test whether the intersection points are inside the faces using exact predicates, which is more reliable for nearly degenerate input.
+    /// The `robust` feature also classifies the edges of each mesh against the planes of the faces of the other mesh using exact predicates, see [Mesh::face_line_piece_intersection].
+    /// Note that the intersection points are still snapped to nearby edges and vertices using a small margin, see [Mesh::face_point_intersection].
+    ///
+    /// # Error
+    ///
+    /// Returns an [Error::SplitFailed] error if an intersection point cannot be located on the faces and edges of one of the meshes due to numerical issues.
+    /// In that case, the two meshes are left split at the intersection points handled before the error, which are still valid meshes.
+    ///
+    /// **Note:** This is a breaking change, this method used to return the subsets directly and silently skip the intersection points it could not locate.
+    ///
+    pub fn split_at_intersection(
+        &mut self,
+        other: &mut Mesh,
+    ) -> Result<(Vec<Mesh>, Vec<Mesh>), Error> {
+        let (stitches, _, _) = self.split_primitives_at_intersection_internal(other)?;
+        let (map1, map2) = stitch_maps(&stitches);
 
         let meshes1 =
             self.split(&|_, halfedge_id| is_at_intersection(self, other, halfedge_id, &map1));
         let meshes2 =
             other.split(&|_, halfedge_id| is_at_intersection(other, self, halfedge_id, &map2));
-        (meshes1, meshes2)
+        Ok((meshes1, meshes2))
+    }
+
+    ///
+    /// Splits the two meshes into subsets bounded by the intersection between the two meshes, like [Mesh::split_at_intersection],
+    /// but the ids of each subset are compacted and the mapping back to the ids of the two meshes before the split is returned as well.
+    /// The faces of a subset which are part of a face that was split at the intersection, are mapped to that face
+    /// and the vertices created at the intersection are not mapped to any vertex.
+    ///
+    /// # Error
+    ///
+    /// Returns an error in the same cases as [Mesh::split_at_intersection].
+    ///
+    pub fn split_at_intersection_with_provenance(
+        &mut self,
+        other: &mut Mesh,
+    ) -> Result<(SubsetsWithProvenance, SubsetsWithProvenance), Error> {
+        let vertices1: HashSet<VertexID> = self.vertex_iter().collect();
+        let vertices2: HashSet<VertexID> = other.vertex_iter().collect();
+        let (stitches, origins1, origins2) =
+            self.split_primitives_at_intersection_internal(other)?;
+        let (map1, map2) = stitch_maps(&stitches);
+
+        let meshes1 = self
+            .split_with_provenance(&|_, halfedge_id| {
+                is_at_intersection(self, other, halfedge_id, &map1)
+            })
+            .into_iter()
+            .map(|(mesh, provenance)| (mesh, to_source(provenance, &vertices1, &origins1)))
+            .collect();
+        let meshes2 = other
+            .split_with_provenance(&|_, halfedge_id| {
+                is_at_intersection(other, self, halfedge_id, &map2)
+            })
+            .into_iter()
+            .map(|(mesh, provenance)| (mesh, to_source(provenance, &vertices2, &origins2)))
+            .collect();
+        Ok((meshes1, meshes2))
+    }
+
+    ///
+    /// Removes the self intersections of the mesh, both between different connected components and within a single connected component,
+    /// such that the result is the surface of the union of the solids enclosed by the mesh.
+    /// The faces are split at the intersections and then each face is kept if it separates the outside of all the solids from the inside of a solid,
+    /// which is determined from the winding number of the mesh just in front of and behind the face.
+    /// So the parts of the surface inside another part of the surface are removed, also when they do not intersect anything,
+    /// for example a component which is completely inside another component, while a cavity, ie. a component with inverted orientation inside another component, is kept.
+    ///
+    /// The mesh must be oriented such that the face normals point outwards, since the solids are defined by the orientation of the faces.
+    /// The vertex attributes are kept, but the face groups and creases are not.
+    ///
+    /// **Note:** Faces which overlap in a coplanar region, for example two components touching each other at a face,
+    /// are not supported and might result in an error or an invalid mesh.
+    /// The result might also contain very small faces where the edges of the split faces cross the intersection close to each other.
+    ///
+    /// # Error
+    ///
+    /// Returns an error and leaves the mesh unchanged if the mesh is not closed, since the inside is not defined,
+    /// or if the faces cannot be split at the intersections (see [Mesh::split_at_intersection]).
+    ///
+    pub fn remove_self_intersections(&mut self) -> Result<(), Error> {
+        if !self.is_closed() {
+            return Err(Error::MeshIsNotClosed(
+                "remove_self_intersections".to_string(),
+            ));
+        }
+
+        // Assign the faces to classes such that faces in the same class do not intersect each other
+        let mut conflicts: HashMap<FaceID, Vec<FaceID>> = HashMap::new();
+        for (face_id1, face_id2) in self.self_intersections() {
+            conflicts.entry(face_id1).or_default().push(face_id2);
+            conflicts.entry(face_id2).or_default().push(face_id1);
+        }
+        let mut classes: HashMap<FaceID, usize> = HashMap::new();
+        let mut conflicting_faces: Vec<FaceID> = conflicts.keys().copied().collect();
+        conflicting_faces.sort();
+        for face_id in conflicting_faces {
+            let used: HashSet<usize> = conflicts[&face_id]
+                .iter()
+                .filter_map(|f| classes.get(f).copied())
+                .collect();
+            let class = (0..).find(|class| !used.contains(class)).unwrap();
+            classes.insert(face_id, class);
+        }
+        let bvh = Bvh::new(self);
+        if classes.is_empty()
+            && self
+                .face_iter()
+                .all(|face_id| is_on_hull(self, &bvh, self, face_id))
+        {
+            return Ok(());
+        }
+
+        let no_classes = classes.values().max().map(|c| c + 1).unwrap_or(1);
+        let mut meshes: Vec<Mesh> = (0..no_classes)
+            .map(|class| {
+                manifold_subset(&[self], &|_, face_id| {
+                    classes.get(&face_id).copied().unwrap_or(0) == class
+                })
+            })
+            .collect();
+
+        // Split the faces of each class at the intersections with the faces of the other classes
+        for i in 0..meshes.len() {
+            let (first, rest) = meshes.split_at_mut(i + 1);
+            for other in rest {
+                first[i].split_primitives_at_intersection(other)?;
+            }
+        }
+
+        let mut result = manifold_subset(&meshes.iter().collect::<Vec<_>>(), &|mesh, face_id| {
+            is_on_hull(self, &bvh, mesh, face_id)
+        });
+        result.merge_overlapping_primitives();
+        *self = result;
+        Ok(())
     }
 
     ///
     /// Splits the primitives of the two meshes at the intersection between the two meshes.
     ///
-    pub fn split_primitives_at_intersection(&mut self, other: &mut Mesh) {
-        self.split_primitives_at_intersection_internal(other);
+    /// # Error
+    ///
+    /// Returns an error in the same cases as [Mesh::split_at_intersection].
+    ///
+    /// **Note:** This is a breaking change, this method used to return nothing and silently skip the intersection points it could not locate.
+    ///
+    pub fn split_primitives_at_intersection(&mut self, other: &mut Mesh) -> Result<(), Error> {
+        self.split_primitives_at_intersection_internal(other)?;
+        Ok(())
     }
 
+    /// Returns the pairs of vertices at the intersection and, for each of the two meshes, the face each new face is part of before the split.
     fn split_primitives_at_intersection_internal(
         &mut self,
         other: &mut Mesh,
-    ) -> Vec<(VertexID, VertexID)> {
-        let mut intersections = find_intersections(self, other);
+    ) -> Result<(Stitches, Origins, Origins), Error> {
+        let mut intersections = find_intersections(self, other)?;
         let mut stitches = Vec::new();
-        while let Some((ref new_edges1, ref new_edges2)) =
-            split_at_intersections(self, other, &intersections, &mut stitches)
-        {
+        let mut origins1 = HashMap::new();
+        let mut origins2 = HashMap::new();
+        while let Some((ref new_edges1, ref new_edges2)) = split_at_intersections(
+            self,
+            other,
+            &intersections,
+            &mut stitches,
+            &mut origins1,
+            &mut origins2,
+        )? {
             intersections =
-                find_intersections_between_edge_face(self, new_edges1, other, new_edges2);
+                find_intersections_between_edge_face(self, new_edges1, other, new_edges2)?;
         }
-        stitches
+        Ok((stitches, origins1, origins2))
     }
 }
 
+/// Returns the mapping from the vertices of the first mesh to the vertices of the second mesh at the intersection and the inverse mapping.
+fn stitch_maps(
+    stitches: &[(VertexID, VertexID)],
+) -> (HashMap<VertexID, VertexID>, HashMap<VertexID, VertexID>) {
+    let mut map1 = HashMap::new();
+    let mut map2 = HashMap::new();
+    stitches.iter().for_each(|(v0, v1)| {
+        map1.insert(*v0, *v1);
+        map2.insert(*v1, *v0);
+    });
+    (map1, map2)
+}
+
+/// Maps the provenance of a subset of a mesh which is split at an intersection back to the ids of the mesh before the split.
+fn to_source(
+    provenance: Provenance,
+    source_vertices: &HashSet<VertexID>,
+    origins: &Origins,
+) -> Provenance {
+    Provenance {
+        vertices: provenance
+            .vertices
+            .into_iter()
+            .filter(|(_, vertex_id)| source_vertices.contains(vertex_id))
+            .collect(),
+        faces: provenance
+            .faces
+            .into_iter()
+            .map(|(face_id, source_face_id)| {
+                (
+                    face_id,
+                    *origins.get(&source_face_id).unwrap_or(&source_face_id),
+                )
+            })
+            .collect(),
+    }
+}
+
+///
+/// Constructs a mesh from the faces of the given meshes where the given function returns true, including the vertex attributes.
+/// In contrast to [Mesh::clone_subset], the vertices are duplicated where needed to construct a manifold mesh,
+/// for example if two of the faces only share a vertex, and the faces of different meshes are not connected.
+///
+fn manifold_subset(meshes: &[&Mesh], is_included: &dyn Fn(&Mesh, FaceID) -> bool) -> Mesh {
+    fn concat<T: Copy>(values: Vec<&Option<Vec<T>>>) -> Option<Vec<T>> {
+        let mut result = Vec::new();
+        for values in values {
+            result.extend(values.as_ref()?);
+        }
+        Some(result)
+    }
+    let buffers: Vec<_> = meshes.iter().map(|mesh| mesh.export()).collect();
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+    for (mesh, buffer) in meshes.iter().zip(buffers.iter()) {
+        let offset = positions.len() as u32;
+        positions.extend(buffer.positions.to_f64());
+        for (face_id, triangle) in mesh
+            .face_iter()
+            .zip(buffer.indices.to_u32().unwrap().chunks(3))
+        {
+            if is_included(mesh, face_id) {
+                indices.extend(triangle.iter().map(|i| i + offset));
+            }
+        }
+    }
+    // The computed normals are exported for the vertices without a stored normal, but should not be stored in the new mesh
+    let has_stored_normals = meshes.iter().any(|mesh| {
+        mesh.vertex_iter()
+            .any(|vertex_id| mesh.stored_vertex_normal(vertex_id).is_some())
+    });
+    let (mut mesh, _) = Mesh::new_tolerant(&three_d_asset::TriMesh {
+        indices: three_d_asset::Indices::U32(indices),
+        positions: three_d_asset::Positions::F64(positions),
+        normals: if has_stored_normals {
+            concat(buffers.iter().map(|b| &b.normals).collect())
+        } else {
+            None
+        },
+        tangents: concat(buffers.iter().map(|b| &b.tangents).collect()),
+        uvs: concat(buffers.iter().map(|b| &b.uvs).collect()),
+        colors: concat(buffers.iter().map(|b| &b.colors).collect()),
+    });
+    mesh.remove_lonely_primitives();
+    mesh
+}
+
+///
+/// Returns whether the face, which is part of the surface of the closed mesh, separates the outside of all the solids enclosed by the closed mesh from the inside of a solid,
+/// ie. whether the winding number of the closed mesh is zero in front of the face and therefore one behind the face.
+///
+fn is_on_hull(closed_mesh: &Mesh, bvh: &Bvh, mesh: &Mesh, face_id: FaceID) -> bool {
+    let (p0, p1, p2) = mesh.face_positions(face_id);
+    let longest_edge = p0.distance(p1).max(p1.distance(p2)).max(p2.distance(p0));
+    // A fraction of the distance from the center to the closest edge, so that no other face is crossed
+    let offset = 0.001 * 2.0 * mesh.face_area(face_id) / (3.0 * longest_edge);
+    let point = mesh.face_center(face_id) + offset * mesh.face_normal(face_id);
+    winding_number(closed_mesh, bvh, &point) == 0
+}
+
+///
+/// Returns the winding number of the closed mesh at the point, ie. the number of times the surface winds around the point,
+/// which is computed by counting the number of times a ray starting at the point leaves the surface minus the number of times it enters the surface.
+///
+fn winding_number(mesh: &Mesh, bvh: &Bvh, point: &Vec3) -> i32 {
+    let direction = vec3(0.2672612419, 0.5345224838, 0.8017837257);
+    let mut hits = Vec::new();
+    bvh.for_each_on_ray(point, &direction, MARGIN, &mut |face_id| {
+        if let Some(hit) = mesh.face_ray_hit(face_id, point, &direction) {
+            hits.push(hit);
+        }
+    });
+    // A hit at an edge or a vertex is only counted once
+    let mut hit_primitives = HashSet::new();
+    hits.retain(|hit| match hit.primitive {
+        Primitive::Face(_) => true,
+        primitive => hit_primitives.insert(primitive),
+    });
+    hits.iter()
+        .map(|hit| {
+            if mesh.face_normal(hit.face_id).dot(direction) > 0.0 {
+                1
+            } else {
+                -1
+            }
+        })
+        .sum()
+}
+
 fn is_at_intersection(
     mesh1: &Mesh,
     mesh2: &Mesh,
@@ -121,7 +519,9 @@ fn split_at_intersections(
     mesh2: &mut Mesh,
     intersections: &HashMap<(Primitive, Primitive), Vec3>,
     stitches: &mut Vec<(VertexID, VertexID)>,
-) -> Option<(Vec<HalfEdgeID>, Vec<HalfEdgeID>)> {
+    origins1: &mut Origins,
+    origins2: &mut Origins,
+) -> Result<Option<NewEdges>, Error> {
     let mut new_edges1 = Vec::new();
     let mut new_edges2 = Vec::new();
 
@@ -131,39 +531,25 @@ fn split_at_intersections(
     let mut face_splits2 = HashMap::new();
     for ((id1, id2), point) in intersections.iter() {
         if let Primitive::Face(face_id) = id1 {
-            match find_face_primitive_to_split(&face_splits1, mesh1, *face_id, point) {
-                Primitive::Vertex(vertex_id) => {
-                    new_intersections.insert((Primitive::Vertex(vertex_id), *id2), *point);
-                }
-                Primitive::Edge(edge) => {
-                    new_intersections.insert((Primitive::Edge(edge), *id2), *point);
-                }
-                Primitive::Face(split_face_id) => {
-                    let vertex_id = mesh1.split_face(split_face_id, point.clone());
-                    insert_faces(&mut face_splits1, mesh1, *face_id, vertex_id);
-                    for halfedge_id in mesh1.vertex_halfedge_iter(vertex_id) {
-                        new_edges1.push(halfedge_id);
-                    }
-                    new_intersections.insert((Primitive::Vertex(vertex_id), *id2), *point);
-                }
-            }
+            let primitive = split_face_at_point(
+                &mut face_splits1,
+                &mut new_edges1,
+                origins1,
+                mesh1,
+                *face_id,
+                point,
+            )?;
+            new_intersections.insert((primitive, *id2), *point);
         } else if let Primitive::Face(face_id) = id2 {
-            match find_face_primitive_to_split(&face_splits2, mesh2, *face_id, point) {
-                Primitive::Vertex(vertex_id) => {
-                    new_intersections.insert((*id1, Primitive::Vertex(vertex_id)), *point);
-                }
-                Primitive::Edge(edge) => {
-                    new_intersections.insert((*id1, Primitive::Edge(edge)), *point);
-                }
-                Primitive::Face(split_face_id) => {
-                    let vertex_id = mesh2.split_face(split_face_id, point.clone());
-                    insert_faces(&mut face_splits2, mesh2, *face_id, vertex_id);
-                    for halfedge_id in mesh2.vertex_halfedge_iter(vertex_id) {
-                        new_edges2.push(halfedge_id);
-                    }
-                    new_intersections.insert((*id1, Primitive::Vertex(vertex_id)), *point);
-                }
-            }
+            let primitive = split_face_at_point(
+                &mut face_splits2,
+                &mut new_edges2,
+                origins2,
+                mesh2,
+                *face_id,
+                point,
+            )?;
+            new_intersections.insert((*id1, primitive), *point);
         } else {
             new_intersections.insert((*id1, *id2), *point);
         }
@@ -173,80 +559,149 @@ fn split_at_intersections(
     let mut edge_splits1 = HashMap::new();
     let mut edge_splits2 = HashMap::new();
     for ((id1, id2), point) in new_intersections.drain() {
-        let v0 = match id1 {
-            Primitive::Vertex(vertex_id) => vertex_id,
-            Primitive::Edge(edge) => {
-                match find_edge_primitive_to_split(&edge_splits1, mesh1, edge, &point) {
-                    Primitive::Vertex(vertex_id) => vertex_id,
-                    Primitive::Edge(split_edge) => {
-                        let (v0, v1) = mesh1.edge_vertices(split_edge);
-                        let vertex_id = mesh1.split_edge(split_edge, point);
-
-                        if !edge_splits1.contains_key(&edge) {
-                            edge_splits1.insert(edge, HashSet::new());
-                        }
-                        let list = edge_splits1.get_mut(&edge).unwrap();
-
-                        list.remove(&split_edge);
-                        for halfedge_id in mesh1.vertex_halfedge_iter(vertex_id) {
-                            let vid = mesh1.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
-                            if vid != v0 && vid != v1 {
-                                new_edges1.push(halfedge_id);
-                            } else {
-                                list.insert(halfedge_id);
-                            }
-                        }
-                        vertex_id
-                    }
-                    _ => {
-                        unreachable!()
-                    }
-                }
-            }
-            _ => {
-                unreachable!()
-            }
-        };
-        let v1 = match id2 {
-            Primitive::Vertex(vertex_id) => vertex_id,
-            Primitive::Edge(edge) => {
-                match find_edge_primitive_to_split(&edge_splits2, mesh2, edge, &point) {
-                    Primitive::Vertex(vertex_id) => vertex_id,
-                    Primitive::Edge(split_edge) => {
-                        let (v0, v1) = mesh2.edge_vertices(split_edge);
-                        let vertex_id = mesh2.split_edge(split_edge, point);
-
-                        if !edge_splits2.contains_key(&edge) {
-                            edge_splits2.insert(edge, HashSet::new());
-                        }
-                        let list = edge_splits2.get_mut(&edge).unwrap();
-
-                        list.remove(&split_edge);
-                        for halfedge_id in mesh2.vertex_halfedge_iter(vertex_id) {
-                            let vid = mesh2.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
-                            if vid != v0 && vid != v1 {
-                                new_edges2.push(halfedge_id);
-                            } else {
-                                list.insert(halfedge_id);
-                            }
-                        }
-                        vertex_id
-                    }
-                    _ => {
-                        unreachable!()
-                    }
-                }
-            }
-            _ => {
-                unreachable!()
-            }
-        };
+        let v0 = split_edge_at_point(
+            &mut edge_splits1,
+            &mut new_edges1,
+            origins1,
+            mesh1,
+            id1,
+            point,
+        )?;
+        let v1 = split_edge_at_point(
+            &mut edge_splits2,
+            &mut new_edges2,
+            origins2,
+            mesh2,
+            id2,
+            point,
+        )?;
         stitches.push((v0, v1));
     }
     if new_edges1.len() > 0 && new_edges2.len() > 0 {
-        Some((new_edges1, new_edges2))
+        Ok(Some((new_edges1, new_edges2)))
     } else {
-        None
+        Ok(None)
+    }
+}
+
+/// Splits the part of the given original face which contains the point, unless the point is at one of its vertices or edges.
+/// Returns the vertex or edge primitive at the point or an error if the point cannot be located due to numerical issues.
+fn split_face_at_point(
+    face_splits: &mut HashMap<FaceID, HashSet<FaceID>>,
+    new_edges: &mut Vec<HalfEdgeID>,
+    origins: &mut Origins,
+    mesh: &mut Mesh,
+    face_id: FaceID,
+    point: &Vec3,
+) -> Result<Primitive, Error> {
+    let primitive = find_face_primitive_to_split(face_splits, mesh, face_id, point)
+        .ok_or_else(|| not_located(point, "face", *face_id))?;
+    match primitive {
+        Primitive::Face(split_face_id) => {
+            let parents = faces_to_split(mesh, origins, &[split_face_id]);
+            let vertex_id = mesh.split_face(split_face_id, *point);
+            record_origins(mesh, origins, vertex_id, &parents);
+            insert_faces(face_splits, mesh, face_id, vertex_id);
+            for halfedge_id in mesh.vertex_halfedge_iter(vertex_id) {
+                new_edges.push(halfedge_id);
+            }
+            Ok(Primitive::Vertex(vertex_id))
+        }
+        primitive => Ok(primitive),
+    }
+}
+
+/// Splits the part of the given original edge which contains the point, unless the point is at one of its vertices.
+/// Returns the vertex at the point or an error if the point cannot be located due to numerical issues.
+fn split_edge_at_point(
+    edge_splits: &mut HashMap<HalfEdgeID, HashSet<HalfEdgeID>>,
+    new_edges: &mut Vec<HalfEdgeID>,
+    origins: &mut Origins,
+    mesh: &mut Mesh,
+    primitive: Primitive,
+    point: Vec3,
+) -> Result<VertexID, Error> {
+    let edge = match primitive {
+        Primitive::Vertex(vertex_id) => return Ok(vertex_id),
+        Primitive::Edge(edge) => edge,
+        Primitive::Face(face_id) => return Err(not_located(&point, "face", *face_id)),
+    };
+    let primitive = find_edge_primitive_to_split(edge_splits, mesh, edge, &point)
+        .ok_or_else(|| not_located(&point, "edge", *edge))?;
+    match primitive {
+        Primitive::Edge(split_edge) => {
+            let (v0, v1) = mesh.edge_vertices(split_edge);
+            let mut walker = mesh.walker_from_halfedge(split_edge);
+            let face_ids: Vec<FaceID> = [walker.face_id(), walker.as_twin().face_id()]
+                .into_iter()
+                .flatten()
+                .collect();
+            let parents = faces_to_split(mesh, origins, &face_ids);
+            let vertex_id = mesh.split_edge(split_edge, point);
+            record_origins(mesh, origins, vertex_id, &parents);
+
+            let list = edge_splits.entry(edge).or_default();
+            list.remove(&split_edge);
+            for halfedge_id in mesh.vertex_halfedge_iter(vertex_id) {
+                let vid = mesh.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                if vid != v0 && vid != v1 {
+                    new_edges.push(halfedge_id);
+                } else {
+                    list.insert(halfedge_id);
+                }
+            }
+            Ok(vertex_id)
+        }
+        Primitive::Vertex(vertex_id) => Ok(vertex_id),
+        Primitive::Face(face_id) => Err(not_located(&point, "face", *face_id)),
+    }
+}
+
+fn not_located(point: &Vec3, primitive: &str, index: u32) -> Error {
+    Error::SplitFailed(format!(
+        "the intersection point {:?} could not be located on the {} with index {}",
+        point, primitive, index
+    ))
+}
+
+/// Returns the face before the split and the vertices of each of the given faces which are about to be split.
+fn faces_to_split(
+    mesh: &Mesh,
+    origins: &Origins,
+    face_ids: &[FaceID],
+) -> Vec<(FaceID, (VertexID, VertexID, VertexID))> {
+    face_ids
+        .iter()
+        .map(|face_id| {
+            (
+                *origins.get(face_id).unwrap_or(face_id),
+                mesh.face_vertices(*face_id),
+            )
+        })
+        .collect()
+}
+
+/// Records the face before the split for each of the faces around the vertex inserted by splitting the given faces,
+/// where a face around the vertex is part of the split face which has two vertices in common with it.
+fn record_origins(
+    mesh: &Mesh,
+    origins: &mut Origins,
+    vertex_id: VertexID,
+    parents: &[(FaceID, (VertexID, VertexID, VertexID))],
+) {
+    for halfedge_id in mesh.vertex_halfedge_iter(vertex_id) {
+        if let Some(face_id) = mesh.walker_from_halfedge(halfedge_id).face_id() {
+            let (a, b, c) = mesh.face_vertices(face_id);
+            if let Some((origin, _)) = parents.iter().find(|(_, (p0, p1, p2))| {
+                [a, b, c]
+                    .iter()
+                    .filter(|v| *v == p0 || *v == p1 || *v == p2)
+                    .count()
+                    == 2
+            }) {
+                origins.insert(face_id, *origin);
+            }
+        }
     }
 }
 
@@ -255,18 +710,19 @@ fn find_face_primitive_to_split(
     mesh: &Mesh,
     face_id: FaceID,
     point: &Vec3,
-) -> Primitive {
+) -> Option<Primitive> {
     if let Some(new_faces) = face_splits.get(&face_id) {
-        for new_face_id in new_faces {
+        return new_faces.iter().find_map(|new_face_id| {
             if let Some(Intersection::Point { primitive, .. }) =
                 mesh.face_point_intersection(*new_face_id, point)
             {
-                return primitive;
+                Some(primitive)
+            } else {
+                None
             }
-        }
-        unreachable!()
+        });
     }
-    Primitive::Face(face_id)
+    Some(Primitive::Face(face_id))
 }
 
 fn find_edge_primitive_to_split(
@@ -274,18 +730,19 @@ fn find_edge_primitive_to_split(
     mesh: &Mesh,
     edge: HalfEdgeID,
     point: &Vec3,
-) -> Primitive {
+) -> Option<Primitive> {
     if let Some(new_edges) = edge_splits.get(&edge) {
-        for new_edge in new_edges {
+        return new_edges.iter().find_map(|new_edge| {
             if let Some(Intersection::Point { primitive, .. }) =
                 mesh.edge_point_intersection(*new_edge, point)
             {
-                return primitive;
+                Some(primitive)
+            } else {
+                None
             }
-        }
-        unreachable!()
+        });
     }
-    Primitive::Edge(edge)
+    Some(Primitive::Edge(edge))
 }
 
 fn insert_faces(
@@ -317,118 +774,162 @@ fn insert_faces(
     );
 }
 
-fn find_intersections(mesh1: &Mesh, mesh2: &Mesh) -> HashMap<(Primitive, Primitive), Vec3> {
-    let edges1: Vec<HalfEdgeID> = mesh1.edge_iter().collect();
-    let edges2: Vec<HalfEdgeID> = mesh2.edge_iter().collect();
+fn find_intersections(
+    mesh1: &Mesh,
+    mesh2: &Mesh,
+) -> Result<HashMap<(Primitive, Primitive), Vec3>, Error> {
+    let edges1: Vec<HalfEdgeID> = mesh1.edge_iter().map(HalfEdgeID::from).collect();
+    let edges2: Vec<HalfEdgeID> = mesh2.edge_iter().map(HalfEdgeID::from).collect();
     find_intersections_between_edge_face(mesh1, &edges1, mesh2, &edges2)
 }
 
+/// Finds the intersections between the given edges of one mesh and the faces of the other mesh and vice versa,
+/// where only the faces with bounds overlapping the bounds of an edge are tested.
 fn find_intersections_between_edge_face(
     mesh1: &Mesh,
     edges1: &Vec<HalfEdgeID>,
     mesh2: &Mesh,
     edges2: &Vec<HalfEdgeID>,
-) -> HashMap<(Primitive, Primitive), Vec3> {
+) -> Result<HashMap<(Primitive, Primitive), Vec3>, Error> {
     let mut intersections: HashMap<(Primitive, Primitive), Vec3> = HashMap::new();
-    for edge1 in edges1 {
-        for face_id2 in mesh2.face_iter() {
-            let (p0, p1) = mesh1.edge_positions(*edge1);
-            if let Some(intersection) = mesh2.face_line_piece_intersection(face_id2, &p0, &p1) {
-                match intersection {
-                    Intersection::Point {
-                        primitive: primitive2,
-                        point,
-                    } => {
-                        if let Some(Intersection::Point {
-                            primitive: primitive1,
-                            ..
-                        }) = mesh1.edge_point_intersection(*edge1, &point)
-                        {
+    let mut error = None;
+    if !edges1.is_empty() {
+        let bvh2 = Bvh::new(mesh2);
+        for edge1 in edges1 {
+            for_each_face_near_edge(&bvh2, mesh1, *edge1, &mut |face_id2| {
+                match edge_face_intersections(mesh1, *edge1, mesh2, face_id2) {
+                    Ok(points) => {
+                        for (primitive2, primitive1, point) in points {
                             intersections.insert((primitive1, primitive2), point);
-                        } else {
-                            unreachable!()
                         }
                     }
-                    Intersection::LinePiece {
-                        primitive0: primitive20,
-                        primitive1: primitive21,
-                        point0,
-                        point1,
-                    } => {
-                        if let Some(Intersection::Point {
-                            primitive: primitive1,
-                            ..
-                        }) = mesh1.edge_point_intersection(*edge1, &point0)
-                        {
-                            intersections.insert((primitive1, primitive20), point0);
-                        } else {
-                            unreachable!()
-                        }
-
-                        if let Some(Intersection::Point {
-                            primitive: primitive1,
-                            ..
-                        }) = mesh1.edge_point_intersection(*edge1, &point1)
-                        {
-                            intersections.insert((primitive1, primitive21), point1);
-                        } else {
-                            unreachable!()
-                        }
+                    Err(e) => {
+                        error.get_or_insert(e);
                     }
                 }
-            }
+            });
         }
     }
-    for edge2 in edges2 {
-        for face_id1 in mesh1.face_iter() {
-            let (p0, p1) = mesh2.edge_positions(*edge2);
-            if let Some(intersection) = mesh1.face_line_piece_intersection(face_id1, &p0, &p1) {
-                match intersection {
-                    Intersection::Point {
-                        primitive: primitive1,
-                        point,
-                    } => {
-                        if let Some(Intersection::Point {
-                            primitive: primitive2,
-                            ..
-                        }) = mesh2.edge_point_intersection(*edge2, &point)
-                        {
+    if !edges2.is_empty() {
+        let bvh1 = Bvh::new(mesh1);
+        for edge2 in edges2 {
+            for_each_face_near_edge(&bvh1, mesh2, *edge2, &mut |face_id1| {
+                match edge_face_intersections(mesh2, *edge2, mesh1, face_id1) {
+                    Ok(points) => {
+                        for (primitive1, primitive2, point) in points {
                             intersections.insert((primitive1, primitive2), point);
-                        } else {
-                            unreachable!()
                         }
                     }
-                    Intersection::LinePiece {
-                        primitive0: primitive10,
-                        primitive1: primitive11,
-                        point0,
-                        point1,
-                    } => {
-                        if let Some(Intersection::Point {
-                            primitive: primitive2,
-                            ..
-                        }) = mesh2.edge_point_intersection(*edge2, &point0)
-                        {
-                            intersections.insert((primitive10, primitive2), point0);
-                        } else {
-                            unreachable!()
-                        }
-
-                        if let Some(Intersection::Point {
-                            primitive: primitive2,
-                            ..
-                        }) = mesh2.edge_point_intersection(*edge2, &point1)
-                        {
-                            intersections.insert((primitive11, primitive2), point1);
-                        } else {
-                            unreachable!()
-                        }
+                    Err(e) => {
+                        error.get_or_insert(e);
                     }
                 }
+            });
+        }
+    }
+    match error {
+        Some(e) => Err(e),
+        None => Ok(intersections),
+    }
+}
+
+/// Calls the callback for each face in the hierarchy whose bounds overlaps the bounds of the edge in the edge mesh.
+fn for_each_face_near_edge(
+    bvh: &Bvh,
+    edge_mesh: &Mesh,
+    edge: HalfEdgeID,
+    callback: &mut dyn FnMut(FaceID),
+) {
+    let (p0, p1) = edge_mesh.edge_positions(edge);
+    bvh.for_each_overlapping(&Bounds::from_points(&[p0, p1]), MARGIN, callback);
+}
+
+/// Returns the intersection points between the edge in the edge mesh and the face in the face mesh,
+/// together with the primitive of the face mesh and the primitive of the edge mesh at each point.
+/// Returns an [Error::SplitFailed] error if one of the points cannot be located on the edge due to numerical issues.
+fn edge_face_intersections(
+    edge_mesh: &Mesh,
+    edge: HalfEdgeID,
+    face_mesh: &Mesh,
+    face_id: FaceID,
+) -> Result<Vec<(Primitive, Primitive, Vec3)>, Error> {
+    let (p0, p1) = edge_mesh.edge_positions(edge);
+    let mut points = match face_mesh.face_line_piece_intersection(face_id, &p0, &p1) {
+        Some(Intersection::Point { primitive, point }) => vec![(primitive, point)],
+        Some(Intersection::LinePiece {
+            primitive0,
+            primitive1,
+            point0,
+            point1,
+        }) => vec![(primitive0, point0), (primitive1, point1)],
+        None => Vec::new(),
+    };
+    points.extend(in_plane_crossings(face_mesh, face_id, &p0, &p1));
+    points
+        .into_iter()
+        .map(|(face_primitive, point)| {
+            if let Some(Intersection::Point {
+                primitive: edge_primitive,
+                ..
+            }) = edge_mesh.edge_point_intersection(edge, &point)
+            {
+                Ok((face_primitive, edge_primitive, point))
+            } else {
+                Err(not_located(&point, "edge", *edge))
+            }
+        })
+        .collect()
+}
+
+///
+/// Returns the points where the line piece crosses the edges and vertices of the face together with the primitive of the face at each point,
+/// if the line piece lies in the plane of the face.
+/// In that case, [Mesh::face_line_piece_intersection] only returns the end points of the line piece which are inside the face.
+///
+fn in_plane_crossings(
+    face_mesh: &Mesh,
+    face_id: FaceID,
+    p0: &Vec3,
+    p1: &Vec3,
+) -> Vec<(Primitive, Vec3)> {
+    let (a, b, c) = face_mesh.face_positions(face_id);
+    let n = face_mesh.face_normal(face_id);
+    if n.dot(p0 - a).abs() > MARGIN || n.dot(p1 - a).abs() > MARGIN {
+        return Vec::new();
+    }
+    let mut points = Vec::new();
+    for (q0, q1) in [(a, b), (b, c), (c, a)] {
+        if point_line_segment_distance(&q0, p0, p1) < MARGIN {
+            points.push(q0);
+        }
+        // The closest points between the line through the line piece and the line through the edge of the face
+        let d0 = p1 - p0;
+        let d1 = q1 - q0;
+        let r = p0 - q0;
+        let (e00, e01, e11) = (d0.dot(d0), d0.dot(d1), d1.dot(d1));
+        let denominator = e00 * e11 - e01 * e01;
+        if denominator <= f64::EPSILON * e00 * e11 {
+            // The line piece is parallel to the edge, so it can only cross the edge at the vertices
+            continue;
+        }
+        let s = (e01 * d1.dot(r) - e11 * d0.dot(r)) / denominator;
+        let t = (e00 * d1.dot(r) - e01 * d0.dot(r)) / denominator;
+        if (0.0..=1.0).contains(&s) && (0.0..=1.0).contains(&t) {
+            let point = p0 + s * d0;
+            if point.distance(q0 + t * d1) < MARGIN {
+                points.push(point);
             }
         }
     }
-    intersections
+    points
+        .into_iter()
+        .filter_map(
+            |point| match face_mesh.face_point_intersection(face_id, &point) {
+                Some(Intersection::Point { primitive, point }) => Some((primitive, point)),
+                _ => None,
+            },
+        )
+        .collect()
 }
 
 #[cfg(test)]
@@ -436,6 +937,22 @@ mod tests {
     use super::*;
     use three_d_asset::{Indices, Positions, TriMesh};
 
+    /// Returns whether the point is inside the closed mesh by counting the number of times a ray starting at the point crosses the surface.
+    fn is_inside(mesh: &Mesh, point: &Vec3) -> bool {
+        let direction = vec3(0.2672612419, 0.5345224838, 0.8017837257);
+        let mut hits: Vec<Vec3> = Vec::new();
+        for face_id in mesh.face_iter() {
+            if let Some(Intersection::Point { point: p, .. }) =
+                mesh.face_ray_intersection(face_id, point, &direction)
+            {
+                if !hits.iter().any(|hit| hit.distance2(p) < 0.0000001) {
+                    hits.push(p);
+                }
+            }
+        }
+        hits.len() % 2 == 1
+    }
+
     #[test]
     fn test_clone_subset() {
         let mesh = crate::test_utility::triangle_strip();
@@ -476,6 +993,69 @@ mod tests {
         assert_eq!(m2.no_faces(), 2);
     }
 
+    #[test]
+    fn test_split_with_provenance() {
+        let mut mesh = crate::test_utility::triangle_strip();
+        mesh.remove_face(mesh.face_iter().next().unwrap());
+
+        let meshes = mesh.split_with_provenance(&|mesh, he_id| {
+            let (p0, p1) = mesh.edge_positions(he_id);
+            p0.z > 0.75 && p0.z < 1.75 && p1.z > 0.75 && p1.z < 1.75
+        });
+
+        assert_eq!(meshes.len(), 2);
+        for (m, provenance) in meshes.iter() {
+            m.is_valid().unwrap();
+            assert_eq!(provenance.faces.len(), m.no_faces());
+            assert_eq!(provenance.vertices.len(), m.no_vertices());
+            for face_id in m.face_iter() {
+                assert_eq!(
+                    m.face_positions(face_id),
+                    mesh.face_positions(provenance.faces[&face_id])
+                );
+            }
+            for vertex_id in m.vertex_iter() {
+                assert_eq!(
+                    m.vertex_position(vertex_id),
+                    mesh.vertex_position(provenance.vertices[&vertex_id])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_at_isovalue() {
+        let mut mesh: Mesh = TriMesh::sphere(8).into();
+        let field: VertexProperty<f64> = mesh
+            .vertex_iter()
+            .map(|vertex_id| (vertex_id, mesh.vertex_position(vertex_id).y))
+            .collect();
+        let area = mesh.face_iter().map(|f| mesh.face_area(f)).sum::<f64>();
+
+        let (below, above) = mesh.split_at_isovalue(&field, 0.3);
+
+        mesh.is_valid().unwrap();
+        assert_eq!(below.len(), 1);
+        assert_eq!(above.len(), 1);
+        for face_id in below[0].face_iter() {
+            let (p0, p1, p2) = below[0].face_positions(face_id);
+            assert!(p0.y <= 0.3 + 0.00001 && p1.y <= 0.3 + 0.00001 && p2.y <= 0.3 + 0.00001);
+        }
+        for face_id in above[0].face_iter() {
+            let (p0, p1, p2) = above[0].face_positions(face_id);
+            assert!(p0.y >= 0.3 - 0.00001 && p1.y >= 0.3 - 0.00001 && p2.y >= 0.3 - 0.00001);
+        }
+        let split_area = below[0]
+            .face_iter()
+            .map(|f| below[0].face_area(f))
+            .sum::<f64>()
+            + above[0]
+                .face_iter()
+                .map(|f| above[0].face_area(f))
+                .sum::<f64>();
+        assert!((area - split_area).abs() < 0.00001);
+    }
+
     #[test]
     fn test_face_face_stitching_at_edge() {
         let mut mesh1: Mesh = TriMesh {
@@ -497,7 +1077,7 @@ mod tests {
         }
         .into();
 
-        let (meshes1, meshes2) = mesh1.split_at_intersection(&mut mesh2);
+        let (meshes1, meshes2) = mesh1.split_at_intersection(&mut mesh2).unwrap();
         assert_eq!(meshes1.len(), 1);
         assert_eq!(meshes2.len(), 1);
 
@@ -515,6 +1095,51 @@ mod tests {
         m2.is_valid().unwrap();
     }
 
+    #[test]
+    fn test_edge_face_intersections_when_edge_is_in_plane() {
+        let face_mesh: Mesh = TriMesh {
+            positions: Positions::F64(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.0, 0.0, 1.0),
+            ]),
+            ..Default::default()
+        }
+        .into();
+        let edge_mesh: Mesh = TriMesh {
+            positions: Positions::F64(vec![
+                vec3(0.2, 0.0, 0.2),
+                vec3(1.0, 0.0, 1.0),
+                vec3(0.2, 1.0, 0.2),
+            ]),
+            ..Default::default()
+        }
+        .into();
+        let face_id = face_mesh.face_iter().next().unwrap();
+        let edge = edge_mesh
+            .edge_iter()
+            .map(HalfEdgeID::from)
+            .find(|halfedge_id| {
+                let (p0, p1) = edge_mesh.edge_positions(*halfedge_id);
+                p0.y == 0.0 && p1.y == 0.0
+            })
+            .unwrap();
+
+        // Both the end point inside the face and the point where the edge leaves the face are found
+        let intersections = edge_face_intersections(&edge_mesh, edge, &face_mesh, face_id).unwrap();
+        assert_eq!(intersections.len(), 2);
+        assert!(intersections.iter().any(|(face_primitive, _, point)| {
+            matches!(face_primitive, Primitive::Face(_)) && *point == vec3(0.2, 0.0, 0.2)
+        }));
+        assert!(intersections
+            .iter()
+            .any(|(face_primitive, edge_primitive, point)| {
+                matches!(face_primitive, Primitive::Edge(_))
+                    && *edge_primitive == Primitive::Edge(edge)
+                    && point.distance(vec3(0.5, 0.0, 0.5)) < MARGIN
+            }));
+    }
+
     #[test]
     fn test_face_face_stitching_at_mid_edge() {
         let mut mesh1: Mesh = TriMesh {
@@ -536,7 +1161,7 @@ mod tests {
         }
         .into();
 
-        let (meshes1, meshes2) = mesh1.split_at_intersection(&mut mesh2);
+        let (meshes1, meshes2) = mesh1.split_at_intersection(&mut mesh2).unwrap();
         assert_eq!(meshes1.len(), 1);
         assert_eq!(meshes2.len(), 1);
 
@@ -560,7 +1185,7 @@ mod tests {
         let mut mesh2 = crate::test_utility::cube();
         mesh2.translate(vec3(0.5, 0.5, 0.5));
 
-        let (meshes1, meshes2) = mesh1.split_at_intersection(&mut mesh2);
+        let (meshes1, meshes2) = mesh1.split_at_intersection(&mut mesh2).unwrap();
         assert_eq!(meshes1.len(), 2);
         assert_eq!(meshes2.len(), 2);
 
@@ -606,7 +1231,7 @@ mod tests {
         let mut mesh2 = crate::test_utility::cube();
         mesh2.translate(vec3(0.5, 2.0, 0.5));
 
-        let (meshes1, meshes2) = mesh1.split_at_intersection(&mut mesh2);
+        let (meshes1, meshes2) = mesh1.split_at_intersection(&mut mesh2).unwrap();
         assert_eq!(meshes1.len(), 2);
         assert_eq!(meshes2.len(), 2);
 
@@ -633,6 +1258,165 @@ mod tests {
         m2.is_valid().unwrap();
     }
 
+    #[test]
+    fn test_split_at_intersection_far_from_origin() {
+        // The rounding errors of the coordinates are larger than the margin used to locate the intersection points
+        let mut mesh1 = Mesh::icosphere(2);
+        let mut mesh2 = Mesh::icosphere(2);
+        mesh2.translate(vec3(0.5, 0.3, 0.1));
+        mesh1.translate(vec3(1e10, 1e10, 1e10));
+        mesh2.translate(vec3(1e10, 1e10, 1e10));
+
+        // Some of the intersection points cannot be located, which is reported instead of skipping the points
+        let result = mesh1.split_at_intersection(&mut mesh2);
+        assert!(matches!(result, Err(Error::SplitFailed(_))));
+        // The meshes are partly split and the coordinates are too imprecise to avoid small faces, but the connectivity is valid
+        for mesh in [mesh1, mesh2] {
+            let report = mesh.validate();
+            assert!(report.issues.iter().all(|issue| issue.is_degenerate()));
+        }
+    }
+
+    #[test]
+    fn test_split_at_intersection_with_provenance() {
+        let mut mesh1 = crate::test_utility::cube();
+        let mut mesh2 = crate::test_utility::cube();
+        mesh2.translate(vec3(0.5, 0.5, 0.5));
+        let source1 = mesh1.clone();
+        let source2 = mesh2.clone();
+
+        let (meshes1, meshes2) = mesh1
+            .split_at_intersection_with_provenance(&mut mesh2)
+            .unwrap();
+        assert_eq!(meshes1.len(), 2);
+        assert_eq!(meshes2.len(), 2);
+
+        for (source, meshes) in [(&source1, meshes1), (&source2, meshes2)] {
+            for (m, provenance) in meshes.iter() {
+                m.is_valid().unwrap();
+                assert_eq!(provenance.faces.len(), m.no_faces());
+                for face_id in m.face_iter() {
+                    let source_face_id = provenance.faces[&face_id];
+                    assert!(source
+                        .face_point_intersection(source_face_id, &m.face_center(face_id))
+                        .is_some());
+                }
+                for vertex_id in m.vertex_iter() {
+                    match provenance.vertices.get(&vertex_id) {
+                        Some(source_vertex_id) => assert_eq!(
+                            m.vertex_position(vertex_id),
+                            source.vertex_position(*source_vertex_id)
+                        ),
+                        None => assert!(source
+                            .vertex_iter()
+                            .all(|v| source.vertex_position(v) != m.vertex_position(vertex_id))),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_remove_self_intersections() {
+        let mut mesh = crate::test_utility::cube();
+        let mut other = crate::test_utility::cube();
+        other.translate(vec3(0.5, 0.5, 0.5));
+        mesh.append(&other);
+        assert!(!mesh.self_intersections().is_empty());
+
+        mesh.remove_self_intersections().unwrap();
+
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        assert!(mesh.self_intersections().is_empty());
+        assert!(is_inside(&mesh, &vec3(1.4, 1.4, 1.4)));
+        assert!(is_inside(&mesh, &vec3(-0.9, -0.9, -0.9)));
+        assert!(!is_inside(&mesh, &vec3(1.2, -0.9, 1.2)));
+    }
+
+    #[test]
+    fn test_remove_self_intersections_within_component() {
+        // An ellipsoid bent along the curve (t^2, t^3 - t), so the two ends cross each other at a right angle at (1, 0, 0)
+        let mut mesh = Mesh::icosphere(3);
+        for vertex_id in mesh.vertex_iter() {
+            let p = mesh.vertex_position(vertex_id);
+            let t = 1.3 * p.x;
+            let tangent = vec2(2.0 * t, 3.0 * t * t - 1.0).normalize();
+            let normal = vec2(-tangent.y, tangent.x);
+            let center = vec2(t * t, t * t * t - t) + 0.2 * p.y * normal;
+            mesh.move_vertex_to(vertex_id, vec3(center.x, center.y, 0.2 * p.z));
+        }
+        assert!(!mesh.self_intersections().is_empty());
+        let volume = mesh.volume().unwrap();
+
+        mesh.remove_self_intersections().unwrap();
+
+        // The ends are joined, so the result is a torus
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.connected_components().len(), 1);
+        assert_eq!(mesh.euler_characteristic(), 0);
+        assert!(mesh.self_intersections().is_empty());
+        assert!(mesh.volume().unwrap() < volume);
+    }
+
+    #[test]
+    fn test_remove_self_intersections_between_three_components() {
+        let mut mesh = crate::test_utility::cube();
+        for translation in [vec3(0.5, 0.5, 0.5), vec3(-0.5, 0.7, 0.3)] {
+            let mut other = crate::test_utility::cube();
+            other.translate(translation);
+            mesh.append(&other);
+        }
+
+        mesh.remove_self_intersections().unwrap();
+
+        // Depending on the order the faces are split in, the edges of the split faces can cross the intersection
+        // close to each other, which results in small faces, but the connectivity is always valid
+        let report = mesh.validate();
+        assert!(report.issues.iter().all(|issue| issue.is_degenerate()));
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.connected_components().len(), 1);
+        assert!(mesh.self_intersections().is_empty());
+        assert!(is_inside(&mesh, &vec3(1.4, 1.4, 1.4)));
+        assert!(is_inside(&mesh, &vec3(-1.4, 1.6, 1.2)));
+        assert!(is_inside(&mesh, &vec3(-0.9, -0.9, -0.9)));
+        assert!(!is_inside(&mesh, &vec3(1.2, -0.9, 1.2)));
+    }
+
+    #[test]
+    fn test_remove_self_intersections_removes_inner_components() {
+        let mut mesh = Mesh::icosphere(2);
+        mesh.scale(2.0);
+        let mut inner = Mesh::icosphere(2);
+        mesh.append(&inner);
+        let no_faces = inner.no_faces();
+
+        // A component inside another component is removed
+        let mut solid = mesh.clone();
+        solid.remove_self_intersections().unwrap();
+        assert_eq!(solid.no_faces(), no_faces);
+        assert!(solid.volume().unwrap() > 30.0);
+
+        // but a cavity is kept
+        inner.flip_orientation();
+        let mut hollow = Mesh::icosphere(2);
+        hollow.scale(2.0);
+        hollow.append(&inner);
+        let volume = hollow.volume().unwrap();
+        hollow.remove_self_intersections().unwrap();
+        assert_eq!(hollow.no_faces(), 2 * no_faces);
+        assert!((hollow.volume().unwrap() - volume).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_remove_self_intersections_of_open_mesh() {
+        let mut mesh = crate::test_utility::square();
+        assert!(matches!(
+            mesh.remove_self_intersections(),
+            Err(Error::MeshIsNotClosed(_))
+        ));
+    }
+
     #[test]
     fn test_is_at_intersection_cube_cube() {
         let mesh1 = crate::test_utility::cube();
@@ -699,7 +1483,7 @@ mod tests {
         let mesh1 = create_simple_mesh_x_z();
         let mesh2 = create_simple_mesh_y_z();
 
-        let intersections = find_intersections(&mesh1, &mesh2);
+        let intersections = find_intersections(&mesh1, &mesh2).unwrap();
         assert_eq!(intersections.len(), 5);
 
         assert!(intersections
@@ -732,7 +1516,7 @@ mod tests {
         }
         .into();
 
-        let intersections = find_intersections(&mesh1, &mesh2);
+        let intersections = find_intersections(&mesh1, &mesh2).unwrap();
         assert_eq!(intersections.len(), 2);
     }
 
@@ -741,7 +1525,7 @@ mod tests {
         let mesh1 = create_simple_mesh_x_z();
         let mesh2 = create_single_triangle();
 
-        let intersections = find_intersections(&mesh1, &mesh2);
+        let intersections = find_intersections(&mesh1, &mesh2).unwrap();
         assert_eq!(intersections.len(), 1);
     }
 
@@ -749,7 +1533,7 @@ mod tests {
     fn test_finding_edge_vertex_intersections() {
         let mesh1 = create_simple_mesh_x_z();
         let mesh2 = create_single_triangle();
-        let intersections = find_intersections(&mesh1, &mesh2);
+        let intersections = find_intersections(&mesh1, &mesh2).unwrap();
         assert_eq!(intersections.len(), 1);
     }
 
@@ -758,19 +1542,58 @@ mod tests {
         let mesh1 = create_simple_mesh_x_z();
         let mesh2 = create_single_triangle();
 
-        let intersections = find_intersections(&mesh1, &mesh2);
+        let intersections = find_intersections(&mesh1, &mesh2).unwrap();
         assert_eq!(intersections.len(), 1);
     }
 
+    #[test]
+    fn test_finding_intersections_matches_testing_all_faces() {
+        let mesh1 = Mesh::icosphere(2);
+        let mut mesh2 = Mesh::icosphere(2);
+        mesh2.translate(vec3(0.5, 0.3, 0.1));
+
+        let mut expected = HashMap::new();
+        for edge1 in mesh1.edge_iter() {
+            for face_id2 in mesh2.face_iter() {
+                for (primitive2, primitive1, point) in
+                    edge_face_intersections(&mesh1, edge1.into(), &mesh2, face_id2).unwrap()
+                {
+                    expected.insert((primitive1, primitive2), point);
+                }
+            }
+        }
+        for edge2 in mesh2.edge_iter() {
+            for face_id1 in mesh1.face_iter() {
+                for (primitive1, primitive2, point) in
+                    edge_face_intersections(&mesh2, edge2.into(), &mesh1, face_id1).unwrap()
+                {
+                    expected.insert((primitive1, primitive2), point);
+                }
+            }
+        }
+
+        let intersections = find_intersections(&mesh1, &mesh2).unwrap();
+        assert!(!intersections.is_empty());
+        assert_eq!(intersections, expected);
+    }
+
     #[test]
     fn test_split_edges() {
         let mut mesh1 = create_simple_mesh_x_z();
         let mut mesh2 = create_simple_mesh_y_z();
 
-        let intersections = find_intersections(&mesh1, &mesh2);
+        let intersections = find_intersections(&mesh1, &mesh2).unwrap();
         let mut stitches = Vec::new();
-        let (new_edges1, new_edges2) =
-            split_at_intersections(&mut mesh1, &mut mesh2, &intersections, &mut stitches).unwrap();
+        let (new_edges1, new_edges2) = split_at_intersections(
+            &mut mesh1,
+            &mut mesh2,
+            &intersections,
+            &mut stitches,
+            &mut HashMap::new(),
+            &mut HashMap::new(),
+        )
+        .unwrap()
+        .unwrap();
 
         assert_eq!(mesh1.no_vertices(), 11);
         assert_eq!(mesh1.no_halfedges(), 12 * 3 + 8);
@@ -792,13 +1615,21 @@ mod tests {
         let mut mesh1 = create_simple_mesh_x_z();
         let mut mesh2 = create_shifted_simple_mesh_y_z();
 
-        let intersections = find_intersections(&mesh1, &mesh2);
+        let intersections = find_intersections(&mesh1, &mesh2).unwrap();
 
         assert_eq!(intersections.len(), 8);
 
         let mut stitches = Vec::new();
-        let (new_edges1, new_edges2) =
-            split_at_intersections(&mut mesh1, &mut mesh2, &intersections, &mut stitches).unwrap();
+        let (new_edges1, new_edges2) = split_at_intersections(
+            &mut mesh1,
+            &mut mesh2,
+            &intersections,
+            &mut stitches,
+            &mut HashMap::new(),
+            &mut HashMap::new(),
+        )
+        .unwrap()
+        .unwrap();
 
         assert_eq!(mesh1.no_vertices(), 14);
         assert_eq!(mesh1.no_faces(), 19);
@@ -838,13 +1669,21 @@ mod tests {
         }
         .into();
 
-        let intersections = find_intersections(&mesh1, &mesh2);
+        let intersections = find_intersections(&mesh1, &mesh2).unwrap();
 
         assert_eq!(intersections.len(), 2);
 
         let mut stitches = Vec::new();
-        let (new_edges1, new_edges2) =
-            split_at_intersections(&mut mesh1, &mut mesh2, &intersections, &mut stitches).unwrap();
+        let (new_edges1, new_edges2) = split_at_intersections(
+            &mut mesh1,
+            &mut mesh2,
+            &intersections,
+            &mut stitches,
+            &mut HashMap::new(),
+            &mut HashMap::new(),
+        )
+        .unwrap()
+        .unwrap();
 
         assert_eq!(mesh1.no_vertices(), 5);
         assert_eq!(mesh1.no_faces(), 5);
@@ -888,13 +1727,21 @@ mod tests {
         }
         .into();
 
-        let intersections = find_intersections(&mesh1, &mesh2);
+        let intersections = find_intersections(&mesh1, &mesh2).unwrap();
 
         assert_eq!(intersections.len(), 2);
 
         let mut stitches = Vec::new();
-        let (new_edges1, new_edges2) =
-            split_at_intersections(&mut mesh1, &mut mesh2, &intersections, &mut stitches).unwrap();
+        let (new_edges1, new_edges2) = split_at_intersections(
+            &mut mesh1,
+            &mut mesh2,
+            &intersections,
+            &mut stitches,
+            &mut HashMap::new(),
+            &mut HashMap::new(),
+        )
+        .unwrap()
+        .unwrap();
 
         assert_eq!(mesh1.no_vertices(), 5);
         assert_eq!(mesh1.no_faces(), 3);
@@ -933,7 +1780,7 @@ mod tests {
         }
         .into();
 
-        mesh1.split_primitives_at_intersection(&mut mesh2);
+        mesh1.split_primitives_at_intersection(&mut mesh2).unwrap();
 
         assert_eq!(mesh1.no_vertices(), 5);
         assert_eq!(mesh2.no_vertices(), 5);
@@ -947,7 +1794,7 @@ mod tests {
         let mut mesh1 = create_simple_mesh_x_z();
         let mut mesh2 = create_shifted_simple_mesh_y_z();
 
-        mesh1.split_primitives_at_intersection(&mut mesh2);
+        mesh1.split_primitives_at_intersection(&mut mesh2).unwrap();
 
         assert_eq!(mesh1.no_vertices(), 14);
         assert_eq!(mesh2.no_vertices(), 14);
@@ -962,7 +1809,7 @@ mod tests {
         let mut mesh2: Mesh = TriMesh::sphere(2).into();
         mesh2.translate(vec3(0.5, 0.5, 0.5));
 
-        mesh1.split_primitives_at_intersection(&mut mesh2);
+        mesh1.split_primitives_at_intersection(&mut mesh2).unwrap();
 
         mesh1.is_valid().unwrap();
         mesh2.is_valid().unwrap();
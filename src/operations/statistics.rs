@@ -0,0 +1,134 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::operations::MeasureSummary;
+use std::collections::BTreeMap;
+
+/// The number of bins in the histograms of the [Statistics].
+const NO_BINS: usize = 10;
+
+///
+/// A summary of the size, shape and connectivity of a mesh as returned by [Mesh::statistics].
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    /// The number of vertices
+    pub no_vertices: usize,
+    /// The number of edges
+    pub no_edges: usize,
+    /// The number of faces
+    pub no_faces: usize,
+    /// The length of the edges with a histogram from the shortest to the longest edge.
+    pub edge_length: MeasureSummary,
+    /// The area of the faces with a histogram from the smallest to the largest face.
+    pub face_area: MeasureSummary,
+    /// The minimum corner of the axis aligned bounding box.
+    pub bounding_box_min: Vec3,
+    /// The maximum corner of the axis aligned bounding box.
+    pub bounding_box_max: Vec3,
+    /// The number of vertices with a given valence, ie. number of connected edges.
+    pub valence_distribution: BTreeMap<usize, usize>,
+    /// The number of vertices on the boundary
+    pub no_boundary_vertices: usize,
+    /// The number of edges on the boundary
+    pub no_boundary_edges: usize,
+    /// The number of boundary loops, ie. holes
+    pub no_boundary_loops: usize,
+}
+
+/// # Statistics
+impl Mesh {
+    ///
+    /// Returns a summary of the mesh which is for example useful for logging the result of each step in a processing pipeline.
+    /// The bounding box is zero if the mesh has no vertices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// let statistics = mesh.statistics();
+    /// assert_eq!(statistics.no_faces, mesh.no_faces());
+    /// assert_eq!(statistics.no_boundary_loops, 0);
+    /// assert_eq!(statistics.valence_distribution.values().sum::<usize>(), mesh.no_vertices());
+    /// println!("{:#?}", statistics);
+    /// ```
+    ///
+    pub fn statistics(&self) -> Statistics {
+        let edge_lengths: Vec<f64> = self
+            .edge_iter()
+            .map(|edge_id| self.edge_length(edge_id.into()))
+            .collect();
+        let face_areas: Vec<f64> = self
+            .face_iter()
+            .map(|face_id| self.face_area(face_id))
+            .collect();
+
+        let mut bounding_box_min = vec3(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+        let mut bounding_box_max = vec3(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+        let mut valence_distribution = BTreeMap::new();
+        let mut no_boundary_vertices = 0;
+        for vertex_id in self.vertex_iter() {
+            let p = self.vertex_position(vertex_id);
+            for i in 0..3 {
+                bounding_box_min[i] = bounding_box_min[i].min(p[i]);
+                bounding_box_max[i] = bounding_box_max[i].max(p[i]);
+            }
+            *valence_distribution
+                .entry(self.vertex_valence(vertex_id))
+                .or_insert(0) += 1;
+            if self.is_vertex_on_boundary(vertex_id) {
+                no_boundary_vertices += 1;
+            }
+        }
+        if self.no_vertices() == 0 {
+            bounding_box_min = Vec3::zero();
+            bounding_box_max = Vec3::zero();
+        }
+
+        Statistics {
+            no_vertices: self.no_vertices(),
+            no_edges: self.no_edges(),
+            no_faces: self.no_faces(),
+            edge_length: MeasureSummary::new_with_value_range(&edge_lengths, NO_BINS),
+            face_area: MeasureSummary::new_with_value_range(&face_areas, NO_BINS),
+            bounding_box_min,
+            bounding_box_max,
+            valence_distribution,
+            no_boundary_vertices,
+            no_boundary_edges: self
+                .edge_iter()
+                .filter(|edge_id| self.is_edge_on_boundary((*edge_id).into()))
+                .count(),
+            no_boundary_loops: self.no_boundary_loops(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statistics() {
+        let mesh = crate::test_utility::square();
+        let statistics = mesh.statistics();
+        assert_eq!(statistics.no_vertices, 4);
+        assert_eq!(statistics.no_edges, 5);
+        assert_eq!(statistics.no_faces, 2);
+        assert_eq!(statistics.edge_length.min, 2.0);
+        assert_eq!(statistics.edge_length.max, 8.0f64.sqrt());
+        assert_eq!(statistics.edge_length.histogram.counts[0], 4);
+        assert_eq!(statistics.edge_length.histogram.counts[NO_BINS - 1], 1);
+        assert_eq!(statistics.face_area.mean, 2.0);
+        assert_eq!(statistics.bounding_box_min, vec3(-1.0, -1.0, 0.0));
+        assert_eq!(statistics.bounding_box_max, vec3(1.0, 1.0, 0.0));
+        assert_eq!(
+            statistics.valence_distribution,
+            BTreeMap::from([(2, 2), (3, 2)])
+        );
+        assert_eq!(statistics.no_boundary_vertices, 4);
+        assert_eq!(statistics.no_boundary_edges, 4);
+        assert_eq!(statistics.no_boundary_loops, 1);
+    }
+}
@@ -35,10 +35,9 @@ impl Mesh {
     /// ```
     ///
     pub fn scale(&mut self, scale: f64) {
-        for vertex_id in self.vertex_iter() {
-            let p = self.vertex_position(vertex_id);
-            self.move_vertex_to(vertex_id, p * scale);
-        }
+        self.transform_vertices(&Mat3::from_diagonal(vec3(scale, scale, scale)), |p| {
+            p * scale
+        });
     }
 
     /// Scales the entire mesh by multiplying `scale_x` to the x component of each vertex position, `scale_y` to the y component and `scale_z` to the z component.
@@ -70,10 +69,40 @@ impl Mesh {
     /// ```
     ///
     pub fn non_uniform_scale(&mut self, scale_x: f64, scale_y: f64, scale_z: f64) {
-        for vertex_id in self.vertex_iter() {
-            let p = self.vertex_position(vertex_id);
-            self.move_vertex_to(vertex_id, vec3(p.x * scale_x, p.y * scale_y, p.z * scale_z));
-        }
+        self.transform_vertices(&Mat3::from_diagonal(vec3(scale_x, scale_y, scale_z)), |p| {
+            vec3(p.x * scale_x, p.y * scale_y, p.z * scale_z)
+        });
+    }
+
+    ///
+    /// Scales the entire mesh by multiplying each component of `scale` to the corresponding component of each vertex position.
+    ///
+    /// Vertex and face normals are computed from the positions, so they are correct after the scaling,
+    /// ie. they behave as if transformed by the inverse transpose of the scaling and not by the scaling itself.
+    /// If an odd number of the components of `scale` are negative, the mesh is mirrored and the orientation of all faces is flipped
+    /// such that the normals keep pointing to the same side of the surface, see [Mesh::transform].
+    /// This is in contrast to [Mesh::non_uniform_scale] which never changes the orientation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// mesh.scale_non_uniform(vec3(2.0, 1.0, 0.5));
+    /// // The normals of the stretched sphere still point outwards
+    /// for vertex_id in mesh.vertex_iter() {
+    ///     assert!(mesh.vertex_normal(vertex_id).dot(mesh.vertex_position(vertex_id)) > 0.0);
+    /// }
+    ///
+    /// mesh.scale_non_uniform(vec3(-1.0, 1.0, 1.0));
+    /// for vertex_id in mesh.vertex_iter() {
+    ///     assert!(mesh.vertex_normal(vertex_id).dot(mesh.vertex_position(vertex_id)) > 0.0);
+    /// }
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn scale_non_uniform(&mut self, scale: Vec3) {
+        self.transform(&Mat4::from_nonuniform_scale(scale.x, scale.y, scale.z));
     }
 
     /// Translates the entire mesh by applying the `translation` to each vertex position.
@@ -95,13 +124,12 @@ impl Mesh {
     /// ```
     ///
     pub fn translate(&mut self, translation: Vec3) {
-        for vertex_id in self.vertex_iter() {
-            self.move_vertex_by(vertex_id, translation);
-        }
+        self.transform_vertices(&Mat3::identity(), |p| p + translation);
     }
 
     ///
-    /// Rotates the entire mesh by applying the given `rotation` to each vertex position.
+    /// Rotates the entire mesh around origo by applying the given `rotation` to each vertex position.
+    /// The rotation can be given as either a rotation matrix ([Mat3]) or a unit quaternion ([Quat]).
     ///
     /// # Examples
     ///
@@ -112,22 +140,43 @@ impl Mesh {
     ///     let mut mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
     /// #   let first_vertex_id = mesh.vertex_iter().next().unwrap();
     /// #   let vertex_position_before = mesh.vertex_position(first_vertex_id);
-    ///     mesh.apply_transformation(Mat4::from_angle_y(degrees(360.0)));
+    ///     mesh.rotate(Quat::from_angle_y(degrees(360.0)));
+    /// #   let vertex_position_after = mesh.vertex_position(first_vertex_id);
+    /// #   assert!((vertex_position_before - vertex_position_after).magnitude() < 0.000001);
+    ///     mesh.rotate(Mat3::from_angle_y(degrees(360.0)));
     /// #   let vertex_position_after = mesh.vertex_position(first_vertex_id);
     /// #   assert!((vertex_position_before - vertex_position_after).magnitude() < 0.000001);
     /// #   mesh.is_valid().unwrap();
     /// # }
     /// ```
     ///
-    pub fn rotate(&mut self, rotation: Mat3) {
-        for vertex_id in self.vertex_iter() {
-            let p = self.vertex_position(vertex_id);
-            self.move_vertex_to(vertex_id, rotation * p);
-        }
+    pub fn rotate(&mut self, rotation: impl Into<Mat3>) {
+        let rotation = rotation.into();
+        self.transform_vertices(&rotation, |p| rotation * p);
+    }
+
+    ///
+    /// Rotates the entire mesh the given `angle` around the axis through `pivot` with the direction `axis`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// mesh.move_vertex_to(vertex_id, vec3(2.0, 0.0, 0.0));
+    /// mesh.rotate_around(vec3(0.0, 0.0, 1.0), degrees(90.0), vec3(1.0, 0.0, 0.0));
+    /// assert!((mesh.vertex_position(vertex_id) - vec3(1.0, 1.0, 0.0)).magnitude() < 0.000001);
+    /// ```
+    ///
+    pub fn rotate_around(&mut self, axis: Vec3, angle: impl Into<Radians>, pivot: Vec3) {
+        let rotation = Mat3::from_axis_angle(axis.normalize(), angle);
+        self.transform_vertices(&rotation, |p| pivot + rotation * (p - pivot));
     }
 
     ///
     /// Transforms the entire mesh by applying the `transformation` to each vertex position.
+    /// The orientation of the faces is never changed, see [Mesh::transform] for a version that keeps the faces facing outwards when the transformation mirrors the mesh.
     ///
     /// # Examples
     ///
@@ -146,10 +195,76 @@ impl Mesh {
     /// ```
     ///
     pub fn apply_transformation(&mut self, transformation: Mat4) {
-        for vertex_id in self.vertex_iter() {
-            let p = self.vertex_position(vertex_id);
-            let p_new = (transformation * p.extend(1.0)).truncate();
-            self.move_vertex_to(vertex_id, p_new);
+        let linear = Mat3::from_cols(
+            transformation.x.truncate(),
+            transformation.y.truncate(),
+            transformation.z.truncate(),
+        );
+        self.transform_vertices(&linear, |p| (transformation * p.extend(1.0)).truncate());
+    }
+
+    ///
+    /// Transforms the entire mesh by applying the affine `transformation` to each vertex position.
+    /// If the transformation has a negative determinant, ie. if it mirrors the mesh, the orientation of all faces is flipped
+    /// such that the face normals keep pointing to the same side of the surface, for example outwards on a closed mesh.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let volume = mesh.volume().unwrap();
+    /// mesh.transform(&Mat4::from_nonuniform_scale(-1.0, 2.0, 1.0));
+    /// assert!((mesh.volume().unwrap() - 2.0 * volume).abs() < 0.000001);
+    /// # mesh.is_valid().unwrap();
+    /// ```
+    ///
+    pub fn transform(&mut self, transformation: &Mat4) {
+        self.apply_transformation(*transformation);
+        if transformation.determinant() < 0.0 {
+            self.flip_orientation();
         }
     }
+
+    ///
+    /// Returns a copy of the mesh transformed by the affine `transformation`, see [Mesh::transform].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let moved = mesh.transformed(&Mat4::from_translation(vec3(1.0, 0.0, 0.0)));
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// assert_eq!(mesh.vertex_position(vertex_id) + vec3(1.0, 0.0, 0.0), moved.vertex_position(vertex_id));
+    /// ```
+    ///
+    pub fn transformed(&self, transformation: &Mat4) -> Mesh {
+        let mut mesh = self.clone();
+        mesh.transform(transformation);
+        mesh
+    }
+
+    ///
+    /// Mirrors the entire mesh across the plane through `plane_point` with the normal `plane_normal`.
+    /// The orientation of all faces is flipped, such that the face normals keep pointing to the same side of the surface, for example outwards on a closed mesh.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh: Mesh = three_d_asset::TriMesh::sphere(4).into();
+    /// let vertex_id = mesh.vertex_iter().next().unwrap();
+    /// let p = mesh.vertex_position(vertex_id);
+    /// mesh.mirror(vec3(1.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0));
+    /// assert!((mesh.vertex_position(vertex_id) - vec3(2.0 - p.x, p.y, p.z)).magnitude() < 0.000001);
+    /// assert!(mesh.volume().unwrap() > 0.0);
+    /// ```
+    ///
+    pub fn mirror(&mut self, plane_point: Vec3, plane_normal: Vec3) {
+        let n = plane_normal.normalize();
+        let reflection = Mat3::identity() - 2.0 * Mat3::from_cols(n * n.x, n * n.y, n * n.z);
+        self.transform_vertices(&reflection, |p| p - 2.0 * (p - plane_point).dot(n) * n);
+        self.flip_orientation();
+    }
 }
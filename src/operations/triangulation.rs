@@ -0,0 +1,941 @@
+//! Triangulation of polygons in the plane.
+
+use crate::Error;
+use std::collections::{HashMap, HashSet};
+
+///
+/// Triangulates the polygon in the plane given by the `outline` and the `holes` using ear clipping.
+/// The outline and each of the holes are given as points in order and must be simple, ie. not self-intersecting, and the holes must be inside the outline and must not overlap each other.
+/// Each hole is connected to the outline by a bridge edge, which turns the polygon into a single (weakly) simple polygon that is then ear clipped.
+///
+/// Returns the triangles as indices into the points, where the points of the outline are numbered first followed by the points of each of the holes in order.
+/// The triangles have the same orientation as the outline, independent of the orientation of the holes.
+///
+/// # Error
+///
+/// Returns an error if the outline has less than three points or no area, for example because all of its points are on a line,
+/// if a hole is not inside the outline or if no ear can be found, which happens if the polygon is not simple.
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::*;
+/// let outline = [(0.0, 0.0), (3.0, 0.0), (3.0, 3.0), (0.0, 3.0)];
+/// let hole = vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)];
+/// let triangles = triangulate_polygon(&outline, &[hole]).unwrap();
+/// assert_eq!(triangles.len(), 8);
+/// ```
+///
+pub fn triangulate_polygon(
+    outline: &[(f64, f64)],
+    holes: &[Vec<(f64, f64)>],
+) -> Result<Vec<[usize; 3]>, Error> {
+    if outline.len() < 3 {
+        return Err(Error::TriangulationFailed(
+            "the outline has less than three points".to_string(),
+        ));
+    }
+    let scale = outline
+        .iter()
+        .map(|(x, y)| x.abs().max(y.abs()))
+        .fold(0.0, f64::max);
+    if signed_area(outline).abs() <= 1e-10 * scale * scale {
+        return Err(Error::TriangulationFailed(
+            "the outline has no area".to_string(),
+        ));
+    }
+    let mut points = outline.to_vec();
+    for hole in holes {
+        points.extend_from_slice(hole);
+    }
+    let orientation = signed_area(outline).signum();
+    let cross = |a: usize, b: usize, c: usize| {
+        let (ax, ay) = points[a];
+        let (bx, by) = points[b];
+        let (cx, cy) = points[c];
+        (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
+    };
+
+    // Make the outline counterclockwise and the holes clockwise, relative to the orientation of the outline
+    let mut polygon: Vec<usize> = (0..outline.len()).collect();
+    let mut hole_polygons = Vec::with_capacity(holes.len());
+    let mut start = outline.len();
+    for hole in holes {
+        let mut hole_polygon: Vec<usize> = (start..start + hole.len()).collect();
+        if signed_area(hole).signum() == orientation {
+            hole_polygon.reverse();
+        }
+        start += hole.len();
+        if hole_polygon.len() >= 3 {
+            hole_polygons.push(hole_polygon);
+        }
+    }
+
+    // Bridge the holes into the polygon, starting with the hole furthest along the x-axis
+    let max_x = |hole: &Vec<usize>| {
+        hole.iter()
+            .copied()
+            .max_by(|a, b| points[*a].0.total_cmp(&points[*b].0))
+            .unwrap()
+    };
+    hole_polygons.sort_by(|a, b| points[max_x(b)].0.total_cmp(&points[max_x(a)].0));
+    for hole in hole_polygons {
+        let m = max_x(&hole);
+        if !is_inside_polygon(outline, points[m]) {
+            return Err(Error::TriangulationFailed(
+                "a hole is not inside the outline".to_string(),
+            ));
+        }
+        let bridge = find_bridge(&points, &polygon, m, orientation, &cross).ok_or_else(|| {
+            Error::TriangulationFailed("a hole is not inside the outline".to_string())
+        })?;
+        let offset = hole.iter().position(|i| *i == m).unwrap();
+        let mut new_polygon = Vec::with_capacity(polygon.len() + hole.len() + 2);
+        new_polygon.extend_from_slice(&polygon[..=bridge]);
+        new_polygon.extend((0..=hole.len()).map(|i| hole[(offset + i) % hole.len()]));
+        new_polygon.extend_from_slice(&polygon[bridge..]);
+        polygon = new_polygon;
+    }
+
+    ear_clip(&points, &polygon, orientation, &cross)
+}
+
+/// A candidate ear in the queue of [ear_clip], where the version is used to skip the ears which have changed since they were queued.
+struct Ear {
+    quality: f64,
+    node: usize,
+    version: u32,
+}
+
+impl PartialEq for Ear {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Ear {}
+
+impl PartialOrd for Ear {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ear {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.quality
+            .total_cmp(&other.quality)
+            .then(self.node.cmp(&other.node))
+    }
+}
+
+///
+/// Triangulates the (weakly) simple polygon given as indices into the points by clipping the best shaped ear until only a triangle is left.
+/// The ears are kept in a priority queue and only the ears at the two neighbours of a clipped ear are updated,
+/// and only the reflex vertices are tested for being inside an ear, since a polygon vertex inside a triangle implies a reflex vertex inside it.
+///
+fn ear_clip(
+    points: &[(f64, f64)],
+    polygon: &[usize],
+    orientation: f64,
+    cross: &dyn Fn(usize, usize, usize) -> f64,
+) -> Result<Vec<[usize; 3]>, Error> {
+    let n = polygon.len();
+    let mut prev: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+    let mut is_alive = vec![true; n];
+    let mut versions = vec![0u32; n];
+    let mut reflex: HashSet<usize> = HashSet::new();
+    let mut queue = std::collections::BinaryHeap::new();
+
+    let corner = |prev: &[usize], next: &[usize], node: usize| {
+        (polygon[prev[node]], polygon[node], polygon[next[node]])
+    };
+    let is_reflex = |prev: &[usize], next: &[usize], node: usize| {
+        let (a, b, c) = corner(prev, next, node);
+        orientation * cross(a, b, c) <= 0.0
+    };
+    let is_ear = |prev: &[usize], next: &[usize], reflex: &HashSet<usize>, node: usize| {
+        let (a, b, c) = corner(prev, next, node);
+        orientation * cross(a, b, c) > 0.0
+            && reflex.iter().all(|&r| {
+                let p = polygon[r];
+                points[p] == points[a]
+                    || points[p] == points[b]
+                    || points[p] == points[c]
+                    || orientation * cross(a, b, p) < 0.0
+                    || orientation * cross(b, c, p) < 0.0
+                    || orientation * cross(c, a, p) < 0.0
+            })
+    };
+    // The ratio between the area and the squared edge lengths, which is small for sliver triangles
+    let quality = |prev: &[usize], next: &[usize], node: usize| {
+        let (a, b, c) = corner(prev, next, node);
+        let length2 = |p: usize, q: usize| {
+            (points[p].0 - points[q].0).powi(2) + (points[p].1 - points[q].1).powi(2)
+        };
+        orientation * cross(a, b, c) / (length2(a, b) + length2(b, c) + length2(c, a))
+    };
+
+    for node in 0..n {
+        if is_reflex(&prev, &next, node) {
+            reflex.insert(node);
+        }
+    }
+    for node in 0..n {
+        if is_ear(&prev, &next, &reflex, node) {
+            queue.push(Ear {
+                quality: quality(&prev, &next, node),
+                node,
+                version: 0,
+            });
+        }
+    }
+
+    let mut triangles = Vec::with_capacity(n.saturating_sub(2));
+    let mut no_alive = n;
+    let mut start = 0;
+    while no_alive > 3 {
+        let mut ear = None;
+        while let Some(candidate) = queue.pop() {
+            if is_alive[candidate.node] && versions[candidate.node] == candidate.version {
+                ear = Some(candidate.node);
+                break;
+            }
+        }
+        while !is_alive[start] {
+            start += 1;
+        }
+        let node = match ear {
+            Some(node) => node,
+            None => {
+                // The queue might miss an ear of a polygon which is only weakly simple, so all the remaining vertices are tested before giving up
+                let remaining: Vec<usize> =
+                    std::iter::successors(Some(start), |i| Some(next[*i]).filter(|i| *i != start))
+                        .collect();
+                if let Some(node) = remaining
+                    .iter()
+                    .copied()
+                    .filter(|node| is_ear(&prev, &next, &reflex, *node))
+                    .max_by(|a, b| quality(&prev, &next, *a).total_cmp(&quality(&prev, &next, *b)))
+                {
+                    node
+                } else {
+                    // If the remaining polygon has no area, for example because all the remaining points are on a line,
+                    // it is closed with degenerate triangles, otherwise the polygon is not simple
+                    let remaining: Vec<(f64, f64)> =
+                        remaining.iter().map(|i| points[polygon[*i]]).collect();
+                    let scale: f64 = remaining
+                        .iter()
+                        .map(|(x, y)| x.abs().max(y.abs()))
+                        .fold(0.0, f64::max);
+                    if signed_area(&remaining).abs() > 1e-10 * scale * scale {
+                        return Err(Error::TriangulationFailed(
+                            "no ear was found, the polygon is probably not simple".to_string(),
+                        ));
+                    }
+                    start
+                }
+            }
+        };
+
+        let (a, b, c) = corner(&prev, &next, node);
+        triangles.push([a, b, c]);
+        let (p, q) = (prev[node], next[node]);
+        next[p] = q;
+        prev[q] = p;
+        is_alive[node] = false;
+        reflex.remove(&node);
+        no_alive -= 1;
+        for neighbour in [p, q] {
+            versions[neighbour] += 1;
+            if is_reflex(&prev, &next, neighbour) {
+                reflex.insert(neighbour);
+            } else {
+                reflex.remove(&neighbour);
+            }
+        }
+        for neighbour in [p, q] {
+            if is_ear(&prev, &next, &reflex, neighbour) {
+                queue.push(Ear {
+                    quality: quality(&prev, &next, neighbour),
+                    node: neighbour,
+                    version: versions[neighbour],
+                });
+            }
+        }
+    }
+    if no_alive == 3 {
+        while !is_alive[start] {
+            start += 1;
+        }
+        triangles.push([polygon[prev[start]], polygon[start], polygon[next[start]]]);
+    }
+    Ok(triangles)
+}
+
+///
+/// Triangulates the polygon in the plane given by the `outline` and the `holes` like [triangulate_polygon], but such that the triangles are as well-shaped as possible,
+/// which is preferable to the fan-like output of ear clipping if the triangulated surface is subsequently deformed.
+/// The `interior_points` are inserted as vertices of the triangulation, which can be used to control the size of the triangles.
+///
+/// The result is the constrained Delaunay triangulation, ie. the edges of the outline and the holes are kept
+/// and otherwise no point is inside the circumcircle of a triangle which the point can see, which maximizes the minimum angle of the triangles.
+/// It is computed by inserting the interior points into the ear clipping triangulation and then flipping edges until the Delaunay condition is fulfilled.
+///
+/// Returns the triangles as indices into the points, where the points of the outline are numbered first followed by the points of each of the holes and finally the interior points.
+/// An interior point that coincides with another point is not used.
+/// The triangles have the same orientation as the outline.
+///
+/// # Error
+///
+/// Returns an error if the polygon cannot be triangulated (see [triangulate_polygon]) or if an interior point is not inside the polygon.
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::*;
+/// let outline = [(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)];
+/// let interior_points = [(1.0, 1.0)];
+/// let triangles = triangulate_polygon_delaunay(&outline, &[], &interior_points).unwrap();
+/// assert_eq!(triangles.len(), 4);
+/// ```
+///
+pub fn triangulate_polygon_delaunay(
+    outline: &[(f64, f64)],
+    holes: &[Vec<(f64, f64)>],
+    interior_points: &[(f64, f64)],
+) -> Result<Vec<[usize; 3]>, Error> {
+    let mut triangles = triangulate_polygon(outline, holes)?;
+    let is_clockwise = signed_area(outline) < 0.0;
+    if is_clockwise {
+        triangles.iter_mut().for_each(|t| t.swap(1, 2));
+    }
+
+    let mut points = Vec::new();
+    let mut constraints = HashSet::new();
+    for polygon in std::iter::once(outline).chain(holes.iter().map(|hole| hole.as_slice())) {
+        let start = points.len();
+        if polygon.len() >= 3 {
+            for i in 0..polygon.len() {
+                constraints.insert(edge_key(start + i, start + (i + 1) % polygon.len()));
+            }
+        }
+        points.extend_from_slice(polygon);
+    }
+    let mut triangulation = Triangulation::new(points, triangles, constraints);
+    for point in interior_points {
+        triangulation.insert_point(*point)?;
+    }
+    let mut triangles = triangulation.into_delaunay();
+    if is_clockwise {
+        triangles.iter_mut().for_each(|t| t.swap(1, 2));
+    }
+    Ok(triangles)
+}
+
+///
+/// Computes the Delaunay triangulation of the points in the plane, ie. a triangulation of the convex hull of the points where no point is inside the circumcircle of any triangle.
+/// This maximizes the minimum angle of the triangles and is for example useful for constructing a terrain from scattered height samples.
+///
+/// Returns the counterclockwise triangles as indices into the points.
+/// A point that coincides with another point is not used and if all of the points are on a line, no triangles are returned.
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::*;
+/// let points = [(0.0, 0.0), (1.0, 0.0), (0.5, 0.5), (1.0, 1.0), (0.0, 1.0)];
+/// let triangles = triangulate_points(&points);
+/// assert_eq!(triangles.len(), 4);
+/// ```
+///
+pub fn triangulate_points(points: &[(f64, f64)]) -> Vec<[usize; 3]> {
+    // Find the convex hull using the monotone chain algorithm
+    let mut sorted: Vec<usize> = (0..points.len()).collect();
+    sorted.sort_by(|a, b| {
+        points[*a]
+            .0
+            .total_cmp(&points[*b].0)
+            .then(points[*a].1.total_cmp(&points[*b].1))
+    });
+    let cross = |a: usize, b: usize, c: usize| {
+        let (ax, ay) = points[a];
+        let (bx, by) = points[b];
+        let (cx, cy) = points[c];
+        (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
+    };
+    let mut hull: Vec<usize> = Vec::new();
+    for pass in [sorted.clone(), sorted.into_iter().rev().collect()] {
+        let start = hull.len();
+        for i in pass {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], i) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(i);
+        }
+        hull.pop();
+    }
+    if hull.len() < 3 {
+        return Vec::new();
+    }
+
+    // Triangulate the convex hull as a fan and insert the remaining points
+    let is_on_hull: HashSet<usize> = hull.iter().copied().collect();
+    let interior: Vec<usize> = (0..points.len())
+        .filter(|i| !is_on_hull.contains(i))
+        .collect();
+    let triangles = (1..hull.len() - 1).map(|i| [0, i, i + 1]).collect();
+    let mut triangulation = Triangulation::new(
+        hull.iter().map(|i| points[*i]).collect(),
+        triangles,
+        HashSet::new(),
+    );
+    for i in interior.iter() {
+        // The points are inside the convex hull, so this can only fail due to rounding errors
+        triangulation.insert_point(points[*i]).ok();
+    }
+    triangulation
+        .into_delaunay()
+        .into_iter()
+        .map(|triangle| {
+            triangle.map(|i| {
+                if i < hull.len() {
+                    hull[i]
+                } else {
+                    interior[i - hull.len()]
+                }
+            })
+        })
+        .collect()
+}
+
+///
+/// A triangulation in the plane with counterclockwise triangles, used for computing the constrained Delaunay triangulation.
+///
+struct Triangulation {
+    points: Vec<(f64, f64)>,
+    triangles: Vec<[usize; 3]>,
+    /// The triangle on the left side of each directed edge.
+    halfedges: HashMap<(usize, usize), usize>,
+    /// The edges which must not be flipped, stored as the indices of the end points in increasing order.
+    constraints: HashSet<(usize, usize)>,
+    epsilon: f64,
+}
+
+impl Triangulation {
+    fn new(
+        points: Vec<(f64, f64)>,
+        triangles: Vec<[usize; 3]>,
+        constraints: HashSet<(usize, usize)>,
+    ) -> Self {
+        let scale = points
+            .iter()
+            .map(|(x, y)| x.abs().max(y.abs()))
+            .fold(0.0, f64::max);
+        let mut triangulation = Self {
+            points,
+            triangles: Vec::with_capacity(triangles.len()),
+            halfedges: HashMap::new(),
+            constraints,
+            epsilon: 1e-10 * scale * scale,
+        };
+        for triangle in triangles {
+            triangulation.set_triangle(triangulation.triangles.len(), triangle);
+        }
+        triangulation
+    }
+
+    ///
+    /// Flips all edges until the triangulation fulfills the Delaunay condition and returns the triangles.
+    ///
+    fn into_delaunay(mut self) -> Vec<[usize; 3]> {
+        let mut edges: Vec<(usize, usize)> = self.halfedges.keys().copied().collect();
+        self.flip_edges(&mut edges);
+        self.triangles
+    }
+
+    ///
+    /// Sets the triangle with the given index, or adds it if the index is equal to the number of triangles.
+    ///
+    fn set_triangle(&mut self, t: usize, triangle: [usize; 3]) {
+        if t < self.triangles.len() {
+            let old = self.triangles[t];
+            for i in 0..3 {
+                let edge = (old[i], old[(i + 1) % 3]);
+                if self.halfedges.get(&edge) == Some(&t) {
+                    self.halfedges.remove(&edge);
+                }
+            }
+            self.triangles[t] = triangle;
+        } else {
+            self.triangles.push(triangle);
+        }
+        for i in 0..3 {
+            self.halfedges
+                .insert((triangle[i], triangle[(i + 1) % 3]), t);
+        }
+    }
+
+    ///
+    /// Returns the vertex of the triangle on the left side of the directed edge which is not on the edge.
+    ///
+    fn opposite(&self, t: usize, (a, b): (usize, usize)) -> usize {
+        let triangle = self.triangles[t];
+        let i = (0..3)
+            .find(|i| triangle[*i] == a && triangle[(*i + 1) % 3] == b)
+            .unwrap();
+        triangle[(i + 2) % 3]
+    }
+
+    ///
+    /// Returns twice the signed area of the triangle, which is positive if the triangle is counterclockwise.
+    ///
+    fn cross(&self, a: usize, b: usize, p: (f64, f64)) -> f64 {
+        let (ax, ay) = self.points[a];
+        let (bx, by) = self.points[b];
+        (bx - ax) * (p.1 - ay) - (by - ay) * (p.0 - ax)
+    }
+
+    ///
+    /// Returns the triangle which contains the point, found by walking towards the point from the last added triangle.
+    /// If the walk is stopped by the boundary, for example because the polygon is not convex, all triangles are searched instead.
+    ///
+    fn locate(&self, point: (f64, f64)) -> Option<usize> {
+        let contains = |[a, b, c]: [usize; 3]| {
+            self.cross(a, b, point) >= -self.epsilon
+                && self.cross(b, c, point) >= -self.epsilon
+                && self.cross(c, a, point) >= -self.epsilon
+        };
+        let mut t = self.triangles.len().checked_sub(1)?;
+        for _ in 0..self.triangles.len() {
+            let triangle = self.triangles[t];
+            let next = (0..3)
+                .map(|i| (triangle[i], triangle[(i + 1) % 3]))
+                .find(|(a, b)| self.cross(*a, *b, point) < -self.epsilon)
+                .and_then(|(a, b)| self.halfedges.get(&(b, a)));
+            match next {
+                Some(next) => t = *next,
+                None if contains(triangle) => return Some(t),
+                None => break,
+            }
+        }
+        (0..self.triangles.len()).find(|t| contains(self.triangles[*t]))
+    }
+
+    ///
+    /// Inserts the point by splitting the triangle, or the two triangles adjacent to the edge, that contains the point.
+    ///
+    fn insert_point(&mut self, point: (f64, f64)) -> Result<(), Error> {
+        let k = self.points.len();
+        self.points.push(point);
+        let t = self.locate(point).ok_or_else(|| {
+            Error::TriangulationFailed("an interior point is not inside the polygon".to_string())
+        })?;
+        let [a, b, c] = self.triangles[t];
+        let is_coinciding = [a, b, c].iter().any(|v| {
+            let (x, y) = self.points[*v];
+            (x - point.0).powi(2) + (y - point.1).powi(2) <= self.epsilon
+        });
+        if is_coinciding {
+            return Ok(());
+        }
+
+        let mut edges = Vec::new();
+        let on_edge = [(a, b, c), (b, c, a), (c, a, b)]
+            .into_iter()
+            .find(|(a, b, _)| self.cross(*a, *b, point).abs() <= self.epsilon);
+        match on_edge {
+            Some((a, b, c)) => {
+                self.set_triangle(t, [a, k, c]);
+                self.set_triangle(self.triangles.len(), [k, b, c]);
+                edges.extend([(b, c), (c, a)]);
+                if let Some(t2) = self.halfedges.get(&(b, a)).copied() {
+                    let d = self.opposite(t2, (b, a));
+                    self.set_triangle(t2, [b, k, d]);
+                    self.set_triangle(self.triangles.len(), [k, a, d]);
+                    edges.extend([(a, d), (d, b)]);
+                }
+                if self.constraints.remove(&edge_key(a, b)) {
+                    self.constraints.insert(edge_key(a, k));
+                    self.constraints.insert(edge_key(k, b));
+                }
+            }
+            None => {
+                self.set_triangle(t, [a, b, k]);
+                self.set_triangle(self.triangles.len(), [b, c, k]);
+                self.set_triangle(self.triangles.len(), [c, a, k]);
+                edges.extend([(a, b), (b, c), (c, a)]);
+            }
+        }
+        self.flip_edges(&mut edges);
+        Ok(())
+    }
+
+    ///
+    /// Flips the given edges, and the edges affected by the flips, until they fulfill the Delaunay condition or are constrained.
+    ///
+    fn flip_edges(&mut self, edges: &mut Vec<(usize, usize)>) {
+        while let Some((a, b)) = edges.pop() {
+            if self.constraints.contains(&edge_key(a, b)) {
+                continue;
+            }
+            let (Some(t), Some(t2)) = (
+                self.halfedges.get(&(a, b)).copied(),
+                self.halfedges.get(&(b, a)).copied(),
+            ) else {
+                continue;
+            };
+            let c = self.opposite(t, (a, b));
+            let d = self.opposite(t2, (b, a));
+            let is_convex = self.cross(a, d, self.points[c]) > self.epsilon
+                && self.cross(d, b, self.points[c]) > self.epsilon;
+            if is_convex && self.in_circle(a, b, c, d) {
+                self.set_triangle(t, [a, d, c]);
+                self.set_triangle(t2, [d, b, c]);
+                edges.extend([(a, d), (d, b), (b, c), (c, a)]);
+            }
+        }
+    }
+
+    ///
+    /// Returns whether the point `d` is strictly inside the circumcircle of the counterclockwise triangle `a`, `b`, `c`.
+    ///
+    fn in_circle(&self, a: usize, b: usize, c: usize, d: usize) -> bool {
+        let (dx, dy) = self.points[d];
+        let [(ax, ay), (bx, by), (cx, cy)] =
+            [a, b, c].map(|v| (self.points[v].0 - dx, self.points[v].1 - dy));
+        let determinant = (ax * ax + ay * ay) * (bx * cy - cx * by)
+            + (bx * bx + by * by) * (cx * ay - ax * cy)
+            + (cx * cx + cy * cy) * (ax * by - bx * ay);
+        determinant > self.epsilon * self.epsilon
+    }
+}
+
+///
+/// Returns the undirected edge between the two points.
+///
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+///
+/// Returns twice the signed area of the polygon, which is positive if the polygon is counterclockwise.
+///
+pub(super) fn signed_area(points: &[(f64, f64)]) -> f64 {
+    (0..points.len())
+        .map(|i| {
+            let (x0, y0) = points[i];
+            let (x1, y1) = points[(i + 1) % points.len()];
+            x0 * y1 - x1 * y0
+        })
+        .sum()
+}
+
+///
+/// Returns whether the point is strictly inside the polygon using the even-odd rule.
+///
+pub(super) fn is_inside_polygon(polygon: &[(f64, f64)], (x, y): (f64, f64)) -> bool {
+    let mut is_inside = false;
+    for i in 0..polygon.len() {
+        let (x0, y0) = polygon[i];
+        let (x1, y1) = polygon[(i + 1) % polygon.len()];
+        if (y0 > y) != (y1 > y) && x < x0 + (y - y0) / (y1 - y0) * (x1 - x0) {
+            is_inside = !is_inside;
+        }
+    }
+    is_inside
+}
+
+///
+/// Finds a vertex of the polygon which can be connected to the hole vertex `m` without crossing any edges,
+/// by casting a ray from `m` along the x-axis (see 'Triangulation by Ear Clipping' by David Eberly).
+/// Returns the position of the vertex in the polygon.
+///
+fn find_bridge(
+    points: &[(f64, f64)],
+    polygon: &[usize],
+    m: usize,
+    orientation: f64,
+    cross: &dyn Fn(usize, usize, usize) -> f64,
+) -> Option<usize> {
+    let (mx, my) = points[m];
+    let n = polygon.len();
+
+    // Find the closest edge hit by the ray and the endpoint of that edge with the largest x-coordinate
+    let mut closest: Option<(f64, usize)> = None;
+    for i in 0..n {
+        let (p0, p1) = (points[polygon[i]], points[polygon[(i + 1) % n]]);
+        if (p0.1 > my) == (p1.1 > my) && p0.1 != my && p1.1 != my {
+            continue;
+        }
+        let x = if p0.1 == p1.1 {
+            p0.0.min(p1.0)
+        } else {
+            p0.0 + (my - p0.1) / (p1.1 - p0.1) * (p1.0 - p0.0)
+        };
+        let is_closer = match closest {
+            Some((closest_x, _)) => x < closest_x,
+            None => true,
+        };
+        if x >= mx && is_closer {
+            let candidate = if p0.0 > p1.0 { i } else { (i + 1) % n };
+            closest = Some((x, candidate));
+        }
+    }
+    let (ix, mut bridge) = closest?;
+    if points[polygon[bridge]] == (ix, my) {
+        return Some(choose_copy(points, polygon, bridge, m, orientation, cross));
+    }
+
+    // If a reflex vertex is inside the triangle spanned by m, the intersection and the candidate,
+    // choose the reflex vertex with the smallest angle to the ray instead
+    let (px, py) = points[polygon[bridge]];
+    let is_inside = |(x, y): (f64, f64)| {
+        let sign = |(ax, ay): (f64, f64), (bx, by): (f64, f64)| {
+            (bx - ax) * (y - ay) - (by - ay) * (x - ax)
+        };
+        let d0 = sign((mx, my), (ix, my));
+        let d1 = sign((ix, my), (px, py));
+        let d2 = sign((px, py), (mx, my));
+        !((d0 < 0.0 || d1 < 0.0 || d2 < 0.0) && (d0 > 0.0 || d1 > 0.0 || d2 > 0.0))
+    };
+    let mut best_angle = f64::INFINITY;
+    let mut best_distance = f64::INFINITY;
+    for i in 0..n {
+        let p = points[polygon[i]];
+        let is_reflex =
+            orientation * cross(polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]) <= 0.0;
+        if i == bridge || !is_reflex || p.0 < mx || !is_inside(p) {
+            continue;
+        }
+        let angle = (p.1 - my).abs().atan2(p.0 - mx);
+        let distance = (p.0 - mx).powi(2) + (p.1 - my).powi(2);
+        if angle < best_angle || (angle == best_angle && distance < best_distance) {
+            best_angle = angle;
+            best_distance = distance;
+            bridge = i;
+        }
+    }
+    Some(choose_copy(points, polygon, bridge, m, orientation, cross))
+}
+
+///
+/// Returns the position of the copy of the polygon vertex at the given position whose interior angle contains the direction towards the hole vertex `m`.
+/// A vertex appears more than once in the polygon if it is the end point of the bridge to an earlier hole,
+/// and connecting `m` to the wrong copy makes the polygon self-intersecting.
+///
+fn choose_copy(
+    points: &[(f64, f64)],
+    polygon: &[usize],
+    position: usize,
+    m: usize,
+    orientation: f64,
+    cross: &dyn Fn(usize, usize, usize) -> f64,
+) -> usize {
+    let n = polygon.len();
+    let v = polygon[position];
+    let is_in_wedge = |i: usize| {
+        let (a, b) = (polygon[(i + n - 1) % n], polygon[(i + 1) % n]);
+        let left_of_next = orientation * cross(v, b, m) > 0.0;
+        let left_of_prev = orientation * cross(a, v, m) > 0.0;
+        if orientation * cross(a, v, b) > 0.0 {
+            left_of_next && left_of_prev
+        } else {
+            left_of_next || left_of_prev
+        }
+    };
+    let mut copies = (0..n).filter(|i| points[polygon[*i]] == points[v]);
+    if copies.clone().nth(1).is_none() {
+        return position;
+    }
+    copies.find(|i| is_in_wedge(*i)).unwrap_or(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(points: &[(f64, f64)], triangles: &[[usize; 3]]) -> f64 {
+        let mut area = 0.0;
+        for [a, b, c] in triangles {
+            let ((ax, ay), (bx, by), (cx, cy)) = (points[*a], points[*b], points[*c]);
+            let triangle_area = 0.5 * ((bx - ax) * (cy - ay) - (by - ay) * (cx - ax));
+            assert!(triangle_area > 0.0);
+            area += triangle_area;
+        }
+        area
+    }
+
+    #[test]
+    fn test_triangulate_concave_polygon() {
+        // An L-shape
+        let points = [
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (1.0, 1.0),
+            (1.0, 2.0),
+            (0.0, 2.0),
+        ];
+        let triangles = triangulate_polygon(&points, &[]).unwrap();
+        assert_eq!(triangles.len(), 4);
+        assert!((area(&points, &triangles) - 3.0).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_triangulate_polygon_with_holes() {
+        let outline = vec![(0.0, 0.0), (6.0, 0.0), (6.0, 3.0), (0.0, 3.0)];
+        // The holes are given with different orientations
+        let hole0 = vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)];
+        let hole1 = vec![(4.0, 1.0), (3.0, 2.0), (5.0, 2.0)];
+        let triangles = triangulate_polygon(&outline, &[hole0.clone(), hole1.clone()]).unwrap();
+        assert_eq!(triangles.len(), 4 + 4 + 3 + 2 * 2 - 2);
+
+        let points: Vec<_> = [outline, hole0, hole1].concat();
+        assert!((area(&points, &triangles) - (18.0 - 1.0 - 1.0)).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_triangulate_polygon_with_holes_bridged_to_the_same_side() {
+        let outline = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+        let square = |x: f64, y: f64| vec![(x, y), (x + 1.0, y), (x + 1.0, y + 1.0), (x, y + 1.0)];
+        for i in 0..10 {
+            for j in 0..10 {
+                // The bridge of the second hole can hit the bridge of the first hole or one of its end points
+                let holes = [
+                    square(6.0, 0.5 + 0.85 * i as f64),
+                    square(2.0, 0.5 + 0.85 * j as f64),
+                    square(4.0, 0.5 + 0.85 * ((i + j) % 10) as f64),
+                ];
+                let mut points = outline.to_vec();
+                for hole in holes.iter() {
+                    points.extend_from_slice(hole);
+                }
+                let triangles = triangulate_polygon(&outline, &holes).unwrap();
+                assert_eq!(triangles.len(), points.len() + 2 * holes.len() - 2);
+                assert!((area(&points, &triangles) - 97.0).abs() < 0.000001);
+            }
+        }
+
+        let holes = [square(6.0, 1.0), square(2.0, 2.5)];
+        let triangles = triangulate_polygon_delaunay(&outline, &holes, &[]).unwrap();
+        assert_eq!(triangles.len(), 14);
+    }
+
+    #[test]
+    fn test_triangulate_polygon_without_area() {
+        assert!(matches!(
+            triangulate_polygon(&[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)], &[]),
+            Err(Error::TriangulationFailed(_))
+        ));
+        assert!(matches!(
+            triangulate_polygon_delaunay(
+                &[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (1.0, 1.0)],
+                &[],
+                &[]
+            ),
+            Err(Error::TriangulationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_triangulate_large_polygon() {
+        // A star shape with many reflex vertices
+        let n = 5000;
+        let points: Vec<(f64, f64)> = (0..n)
+            .map(|i| {
+                let angle = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+                let radius = if i % 2 == 0 { 1.0 } else { 0.9 };
+                (radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+        let triangles = triangulate_polygon(&points, &[]).unwrap();
+        assert_eq!(triangles.len(), n - 2);
+        assert!((area(&points, &triangles) - 0.5 * signed_area(&points)).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_triangulate_invalid_polygon() {
+        let outline = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)];
+        assert!(triangulate_polygon(&outline[..2], &[]).is_err());
+        let hole = vec![(2.0, 2.0), (3.0, 2.0), (3.0, 3.0)];
+        assert!(triangulate_polygon(&outline, &[hole]).is_err());
+        let hole = vec![(-2.0, 0.1), (-1.0, 0.1), (-1.0, 0.2)];
+        assert!(triangulate_polygon(&outline, &[hole]).is_err());
+    }
+
+    fn min_angle(points: &[(f64, f64)], triangles: &[[usize; 3]]) -> f64 {
+        let mut min_angle = f64::INFINITY;
+        for triangle in triangles {
+            for i in 0..3 {
+                let (ax, ay) = points[triangle[i]];
+                let (bx, by) = points[triangle[(i + 1) % 3]];
+                let (cx, cy) = points[triangle[(i + 2) % 3]];
+                let angle = ((bx - ax) * (cy - ay) - (by - ay) * (cx - ax))
+                    .atan2((bx - ax) * (cx - ax) + (by - ay) * (cy - ay));
+                min_angle = min_angle.min(angle.abs());
+            }
+        }
+        min_angle.to_degrees()
+    }
+
+    #[test]
+    fn test_triangulate_polygon_delaunay() {
+        // A long rectangle with points along the long sides
+        let mut outline: Vec<(f64, f64)> = (0..=10).map(|i| (i as f64, 0.0)).collect();
+        outline.extend((0..=10).rev().map(|i| (i as f64, 1.0)));
+        let triangles = triangulate_polygon_delaunay(&outline, &[], &[]).unwrap();
+        assert_eq!(triangles.len(), 20);
+        assert!((area(&outline, &triangles) - 10.0).abs() < 0.000001);
+        assert!(min_angle(&outline, &triangles) > 44.9);
+
+        // No point is strictly inside the circumcircle of any triangle
+        for [a, b, c] in triangles {
+            let ((ax, ay), (bx, by), (cx, cy)) = (outline[a], outline[b], outline[c]);
+            let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+            let ux = ((ax * ax + ay * ay) * (by - cy)
+                + (bx * bx + by * by) * (cy - ay)
+                + (cx * cx + cy * cy) * (ay - by))
+                / d;
+            let uy = ((ax * ax + ay * ay) * (cx - bx)
+                + (bx * bx + by * by) * (ax - cx)
+                + (cx * cx + cy * cy) * (bx - ax))
+                / d;
+            let radius2 = (ax - ux).powi(2) + (ay - uy).powi(2);
+            for (x, y) in outline.iter() {
+                assert!((x - ux).powi(2) + (y - uy).powi(2) > radius2 - 0.000001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_triangulate_polygon_delaunay_with_holes_and_interior_points() {
+        // A clockwise outline
+        let outline = vec![(0.0, 0.0), (0.0, 4.0), (4.0, 4.0), (4.0, 0.0)];
+        let hole = vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)];
+        let interior_points = [(3.0, 3.0), (3.0, 1.0), (1.0, 3.0), (2.0, 0.0), (3.0, 3.0)];
+        let triangles =
+            triangulate_polygon_delaunay(&outline, std::slice::from_ref(&hole), &interior_points)
+                .unwrap();
+
+        let points: Vec<_> = [outline, hole, interior_points.to_vec()].concat();
+        let flipped: Vec<_> = triangles.iter().map(|[a, b, c]| [*a, *c, *b]).collect();
+        assert!((area(&points, &flipped) - 15.0).abs() < 0.000001);
+        // The point on the outline splits the outline edge and the duplicated point is not used
+        for i in 0..points.len() - 1 {
+            assert!(triangles.iter().any(|t| t.contains(&i)));
+        }
+        assert!(!triangles.iter().any(|t| t.contains(&(points.len() - 1))));
+
+        assert!(triangulate_polygon_delaunay(&points[..4], &[], &[(5.0, 1.0)]).is_err());
+        assert!(triangulate_polygon_delaunay(
+            &points[..4],
+            &[points[4..8].to_vec()],
+            &[(1.5, 1.5)]
+        )
+        .is_err());
+    }
+}
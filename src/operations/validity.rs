@@ -1,7 +1,183 @@
 //! See [Mesh](crate::mesh::Mesh).
 
 use crate::mesh::*;
+use crate::operations::*;
 use crate::Error;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// The smallest length of an edge in a valid mesh.
+const MIN_EDGE_LENGTH: f64 = 0.00001;
+/// The smallest area of a face in a valid mesh.
+const MIN_FACE_AREA: f64 = 0.00001;
+
+///
+/// A problem with the connectivity or geometry of a mesh found by [Mesh::validate].
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    /// The vertex does not point to a half-edge.
+    VertexWithoutHalfEdge(VertexID),
+    /// The vertex points to a half-edge which is not in the mesh.
+    VertexWithInvalidHalfEdge(VertexID, HalfEdgeID),
+    /// The half-edge pointed to by the vertex starts in the other vertex.
+    VertexHalfEdgeStartsElsewhere(VertexID, HalfEdgeID, Option<VertexID>),
+    /// The half-edge does not point to a twin half-edge.
+    HalfEdgeWithoutTwin(HalfEdgeID),
+    /// The half-edge points to a twin half-edge which is not in the mesh.
+    HalfEdgeWithInvalidTwin(HalfEdgeID, HalfEdgeID),
+    /// The twin of the half-edge does not point back to the half-edge.
+    TwinMismatch(HalfEdgeID, HalfEdgeID),
+    /// The half-edge and its twin point to the same vertex.
+    InvalidOrientation(HalfEdgeID, HalfEdgeID),
+    /// The half-edge does not point to a vertex.
+    HalfEdgeWithoutVertex(HalfEdgeID),
+    /// The half-edge points to a vertex which is not in the mesh.
+    HalfEdgeWithInvalidVertex(HalfEdgeID, VertexID),
+    /// The half-edge points to a face which is not in the mesh.
+    HalfEdgeWithInvalidFace(HalfEdgeID, FaceID),
+    /// The half-edge points to a face but not to a next half-edge.
+    HalfEdgeWithoutNext(HalfEdgeID),
+    /// The half-edge points to a next half-edge which is not in the mesh.
+    HalfEdgeWithInvalidNext(HalfEdgeID, HalfEdgeID),
+    /// The half-edge points to a next half-edge but not to a face.
+    HalfEdgeWithoutFace(HalfEdgeID),
+    /// The next half-edge of the half-edge is not part of a loop of three half-edges back to the half-edge.
+    NextMismatch(HalfEdgeID, HalfEdgeID),
+    /// The edge is shorter than the smallest allowed length.
+    ShortEdge(HalfEdgeID, f64),
+    /// The face does not point to a half-edge.
+    FaceWithoutHalfEdge(FaceID),
+    /// The face points to a half-edge which is not in the mesh.
+    FaceWithInvalidHalfEdge(FaceID, HalfEdgeID),
+    /// The half-edge pointed to by the face does not point back to the face.
+    FaceHalfEdgeMismatch(FaceID, HalfEdgeID),
+    /// The face has a smaller area than the smallest allowed area.
+    SmallFace(FaceID, f64),
+    /// The first vertex is connected to the second vertex, but not the other way.
+    OneWayConnection(VertexID, VertexID),
+    /// The two vertices are connected by more than one edge.
+    MultipleEdges(VertexID, VertexID),
+}
+
+impl ValidationIssue {
+    /// Returns whether the issue is a degenerate edge or face, ie. a problem with the geometry rather than the connectivity.
+    pub fn is_degenerate(&self) -> bool {
+        matches!(self, Self::ShortEdge(_, _) | Self::SmallFace(_, _))
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::VertexWithoutHalfEdge(v) => write!(f, "Vertex {} does not point to a halfedge", v),
+            Self::VertexWithInvalidHalfEdge(v, h) => {
+                write!(f, "Vertex {} points to an invalid halfedge {}", v, h)
+            }
+            Self::VertexHalfEdgeStartsElsewhere(v, h, start) => write!(
+                f,
+                "Halfedge {} pointed to by vertex {} does not start in that vertex, but instead in {:?}",
+                h, v, start
+            ),
+            Self::HalfEdgeWithoutTwin(h) => {
+                write!(f, "Halfedge {} does not point to a twin halfedge", h)
+            }
+            Self::HalfEdgeWithInvalidTwin(h, t) => {
+                write!(f, "Halfedge {} points to an invalid twin halfedge {}", h, t)
+            }
+            Self::TwinMismatch(h, t) => write!(
+                f,
+                "Halfedge twin {} pointed to by halfedge {} does not point back to halfedge",
+                t, h
+            ),
+            Self::InvalidOrientation(h, t) => write!(
+                f,
+                "Invalid orientation: The halfedge {} and its twin halfedge {} points to the same vertex",
+                h, t
+            ),
+            Self::HalfEdgeWithoutVertex(h) => write!(f, "Halfedge {} does not point to a vertex", h),
+            Self::HalfEdgeWithInvalidVertex(h, v) => {
+                write!(f, "Halfedge {} points to an invalid vertex {}", h, v)
+            }
+            Self::HalfEdgeWithInvalidFace(h, face) => {
+                write!(f, "Halfedge {} points to an invalid face {}", h, face)
+            }
+            Self::HalfEdgeWithoutNext(h) => write!(
+                f,
+                "Halfedge {} points to a face but not a next halfedge",
+                h
+            ),
+            Self::HalfEdgeWithInvalidNext(h, n) => {
+                write!(f, "Halfedge {} points to an invalid next halfedge {}", h, n)
+            }
+            Self::HalfEdgeWithoutFace(h) => write!(
+                f,
+                "Halfedge {} points to a next halfedge but not a face",
+                h
+            ),
+            Self::NextMismatch(h, n) => write!(
+                f,
+                "Halfedge next {} pointed to by halfedge {} does not point back to halfedge",
+                n, h
+            ),
+            Self::ShortEdge(h, length) => {
+                write!(f, "Length of edge {} is too small ({})", h, length)
+            }
+            Self::FaceWithoutHalfEdge(face) => {
+                write!(f, "Face {} does not point to a halfedge", face)
+            }
+            Self::FaceWithInvalidHalfEdge(face, h) => {
+                write!(f, "Face {} points to an invalid halfedge {}", face, h)
+            }
+            Self::FaceHalfEdgeMismatch(face, h) => write!(
+                f,
+                "Halfedge {} pointed to by face {} does not point to back to face",
+                h, face
+            ),
+            Self::SmallFace(face, area) => {
+                write!(f, "Area of face {} is too small ({})", face, area)
+            }
+            Self::OneWayConnection(v0, v1) => write!(
+                f,
+                "Vertex {} and Vertex {} is connected one way, but not the other way",
+                v0, v1
+            ),
+            Self::MultipleEdges(v0, v1) => write!(
+                f,
+                "Vertex {} and Vertex {} is connected by multiple edges",
+                v0, v1
+            ),
+        }
+    }
+}
+
+///
+/// The result of validating a mesh using [Mesh::validate], ie. a list of the problems found.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    /// The problems found, in the order the vertices, half-edges and faces are visited.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Returns whether no problems were found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
 
 /// # Validity
 impl Mesh {
@@ -13,171 +189,295 @@ impl Mesh {
     ///
     /// # Errors
     ///
-    /// If the mesh is not valid, an [Error::MeshIsInvalid] error with a description of the problem is returned.
+    /// If the mesh is not valid, an [Error::MeshIsInvalid] error with the [ValidationReport] listing the problems is returned.
     ///
     pub fn is_valid(&self) -> Result<(), Error> {
+        let report = self.validate();
+        if report.is_valid() {
+            Ok(())
+        } else {
+            Err(Error::MeshIsInvalid(report))
+        }
+    }
+
+    ///
+    /// Tests if the mesh has correct connectivity and orientation and contains no degenerate triangles, like [Mesh::is_valid],
+    /// but returns a report listing all the problems found together with the ids of the primitives involved, for example to repair the mesh programmatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(1);
+    /// assert!(mesh.validate().is_valid());
+    ///
+    /// let vertex_id = mesh.add_vertex(vec3(0.0, 0.0, 0.0));
+    /// let report = mesh.validate();
+    /// assert_eq!(report.issues, vec![ValidationIssue::VertexWithoutHalfEdge(vertex_id)]);
+    /// ```
+    ///
+    pub fn validate(&self) -> ValidationReport {
+        let mut issues = Vec::new();
         for vertex_id in self.vertex_iter() {
-            if let Some(halfedge_id) = self.walker_from_vertex(vertex_id).halfedge_id() {
-                if !self.halfedge_iter().any(|he_id| he_id == halfedge_id) {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Vertex {} points to an invalid halfedge {}",
-                        vertex_id, halfedge_id
-                    )))?;
-                }
-                if self
-                    .walker_from_vertex(vertex_id)
-                    .as_twin()
-                    .vertex_id()
-                    .unwrap()
-                    != vertex_id
-                {
-                    Err(Error::MeshIsInvalid(format!("Halfedge {} pointed to by vertex {} does not start in that vertex, but instead in {}", self.walker_from_vertex(vertex_id).halfedge_id().unwrap(), vertex_id, self.walker_from_vertex(vertex_id).as_twin().vertex_id().unwrap())))?;
-                }
-            } else {
-                Err(Error::MeshIsInvalid(format!(
-                    "Vertex {} does not point to a halfedge",
-                    vertex_id
-                )))?;
-            }
+            self.validate_vertex(vertex_id, &mut issues);
         }
         for halfedge_id in self.halfedge_iter() {
-            let walker = self.walker_from_halfedge(halfedge_id);
-
-            if let Some(twin_id) = walker.twin_id() {
-                if !self.halfedge_iter().any(|he_id| he_id == twin_id) {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Halfedge {} points to an invalid twin halfedge {}",
-                        halfedge_id, twin_id
-                    )))?;
-                }
-                if self.walker_from_halfedge(twin_id).twin_id().unwrap() != halfedge_id {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Halfedge twin pointed to by halfedge {} does not point back to halfedge",
-                        halfedge_id
-                    )))?;
-                }
-                if self.walker_from_halfedge(twin_id).vertex_id() == walker.vertex_id() {
-                    Err(Error::MeshIsInvalid( format!("Invalid orientation: The halfedge {} and its twin halfedge {} points to the same vertex {}", halfedge_id, twin_id, walker.vertex_id().unwrap())))?;
-                }
-            } else {
-                Err(Error::MeshIsInvalid(format!(
-                    "Halfedge {} does not point to a twin halfedge",
-                    halfedge_id
-                )))?;
-            }
-
-            if let Some(vertex_id) = walker.vertex_id() {
-                if !self.vertex_iter().any(|vid| vid == vertex_id) {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Halfedge {} points to an invalid vertex {}",
-                        halfedge_id, vertex_id
-                    )))?;
-                }
-            } else {
-                Err(Error::MeshIsInvalid(format!(
-                    "Halfedge {} does not point to a vertex",
-                    halfedge_id
-                )))?;
-            }
-
-            if let Some(face_id) = walker.face_id() {
-                if !self.face_iter().any(|fid| fid == face_id) {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Halfedge {} points to an invalid face {}",
-                        halfedge_id, face_id
-                    )))?;
-                }
-                if walker.next_id().is_none() {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Halfedge {} points to a face but not a next halfedge",
-                        halfedge_id
-                    )))?;
+            self.validate_halfedge(halfedge_id, &mut issues);
+        }
+        for face_id in self.face_iter() {
+            self.validate_face(face_id, &mut issues);
+        }
+        ValidationReport { issues }
+    }
+
+    ///
+    /// Tests the given faces together with their vertices and edges in the same way as [Mesh::validate], which is much faster than validating the entire mesh after a local edit.
+    /// Faces which are not in the mesh are ignored.
+    ///
+    /// In debug builds, the connectivity around the vertices and faces changed by [Mesh::split_edge], [Mesh::split_face] and [Mesh::flip_edge]
+    /// is automatically validated in this way after the change, and a panic is raised if the connectivity is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mut mesh = Mesh::icosphere(3);
+    /// let face_id = mesh.face_iter().next().unwrap();
+    /// let vertex_id = mesh.split_face(face_id, mesh.face_center(face_id));
+    ///
+    /// let faces: FaceSet = mesh
+    ///     .vertex_halfedge_iter(vertex_id)
+    ///     .filter_map(|halfedge_id| mesh.walker_from_halfedge(halfedge_id).face_id())
+    ///     .collect();
+    /// assert!(mesh.validate_region(&faces).is_valid());
+    /// ```
+    ///
+    pub fn validate_region(&self, faces: &FaceSet) -> ValidationReport {
+        let mut vertices = BTreeSet::new();
+        let mut halfedges = BTreeSet::new();
+        let mut issues = Vec::new();
+        for face_id in faces.iter().filter(|face_id| self.contains_face(*face_id)) {
+            let mut walker = self.walker_from_face(face_id);
+            for _ in 0..3 {
+                if let Some(halfedge_id) = walker.halfedge_id() {
+                    halfedges.insert(halfedge_id);
+                    halfedges.extend(walker.twin_id());
+                    vertices.extend(walker.vertex_id());
                 }
+                walker.as_next();
             }
+        }
+        for vertex_id in vertices
+            .into_iter()
+            .filter(|vertex_id| self.contains_vertex(*vertex_id))
+        {
+            self.validate_vertex(vertex_id, &mut issues);
+        }
+        for halfedge_id in halfedges
+            .into_iter()
+            .filter(|halfedge_id| self.contains_halfedge(*halfedge_id))
+        {
+            self.validate_halfedge(halfedge_id, &mut issues);
+        }
+        for face_id in faces.iter().filter(|face_id| self.contains_face(*face_id)) {
+            self.validate_face(face_id, &mut issues);
+        }
+        ValidationReport { issues }
+    }
+
+    /// Panics in debug builds if the connectivity of the faces around the given vertices is invalid, see [Mesh::validate_region].
+    pub(crate) fn debug_validate_around(&self, vertices: &[VertexID]) {
+        if cfg!(debug_assertions) {
+            let faces: FaceSet = vertices
+                .iter()
+                .flat_map(|vertex_id| self.vertex_halfedge_iter(*vertex_id))
+                .filter_map(|halfedge_id| self.walker_from_halfedge(halfedge_id).face_id())
+                .collect();
+            let mut report = self.validate_region(&faces);
+            report.issues.retain(|issue| !issue.is_degenerate());
+            assert!(
+                report.is_valid(),
+                "the edit resulted in invalid connectivity:\n{}",
+                report
+            );
+        }
+    }
 
-            if let Some(next_id) = walker.next_id() {
-                if !self.halfedge_iter().any(|he_id| he_id == next_id) {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Halfedge {} points to an invalid next halfedge {}",
-                        halfedge_id, next_id
-                    )))?;
+    /// Adds the problems with the given vertex and its connections to the neighbouring vertices to the list of issues.
+    fn validate_vertex(&self, vertex_id: VertexID, issues: &mut Vec<ValidationIssue>) {
+        let Some(halfedge_id) = self.walker_from_vertex(vertex_id).halfedge_id() else {
+            issues.push(ValidationIssue::VertexWithoutHalfEdge(vertex_id));
+            return;
+        };
+        if !self.contains_halfedge(halfedge_id) {
+            issues.push(ValidationIssue::VertexWithInvalidHalfEdge(
+                vertex_id,
+                halfedge_id,
+            ));
+            return;
+        }
+        let start = self.walker_from_halfedge(halfedge_id).as_twin().vertex_id();
+        if start != Some(vertex_id) {
+            issues.push(ValidationIssue::VertexHalfEdgeStartsElsewhere(
+                vertex_id,
+                halfedge_id,
+                start,
+            ));
+            return;
+        }
+
+        let mut neighbours = Vec::new();
+        for halfedge_id in self.vertex_halfedge_iter(vertex_id) {
+            let Some(neighbour) = self.walker_from_halfedge(halfedge_id).vertex_id() else {
+                continue;
+            };
+            if neighbours.contains(&neighbour) {
+                if vertex_id < neighbour {
+                    issues.push(ValidationIssue::MultipleEdges(vertex_id, neighbour));
                 }
-                if walker.face_id().is_none() {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Halfedge {} points to a next halfedge but not a face",
-                        halfedge_id
-                    )))?;
+                continue;
+            }
+            neighbours.push(neighbour);
+            if self.contains_vertex(neighbour)
+                && self.connecting_edge(neighbour, vertex_id).is_none()
+            {
+                issues.push(ValidationIssue::OneWayConnection(vertex_id, neighbour));
+            }
+        }
+    }
+
+    /// Adds the problems with the given half-edge to the list of issues.
+    fn validate_halfedge(&self, halfedge_id: HalfEdgeID, issues: &mut Vec<ValidationIssue>) {
+        let no_issues = issues.len();
+        let walker = self.walker_from_halfedge(halfedge_id);
+
+        match walker.vertex_id() {
+            Some(vertex_id) if !self.contains_vertex(vertex_id) => issues.push(
+                ValidationIssue::HalfEdgeWithInvalidVertex(halfedge_id, vertex_id),
+            ),
+            None => issues.push(ValidationIssue::HalfEdgeWithoutVertex(halfedge_id)),
+            _ => {}
+        }
+
+        match walker.twin_id() {
+            Some(twin_id) if !self.contains_halfedge(twin_id) => issues.push(
+                ValidationIssue::HalfEdgeWithInvalidTwin(halfedge_id, twin_id),
+            ),
+            Some(twin_id) => {
+                let twin = self.walker_from_halfedge(twin_id);
+                if twin.twin_id() != Some(halfedge_id) {
+                    issues.push(ValidationIssue::TwinMismatch(halfedge_id, twin_id));
                 }
-                if self.walker_from_halfedge(next_id).previous_id().unwrap() != halfedge_id {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Halfedge next pointed to by halfedge {} does not point back to halfedge",
-                        halfedge_id
-                    )))?;
+                if twin.vertex_id() == walker.vertex_id() {
+                    issues.push(ValidationIssue::InvalidOrientation(halfedge_id, twin_id));
                 }
             }
+            None => issues.push(ValidationIssue::HalfEdgeWithoutTwin(halfedge_id)),
+        }
 
-            if self.edge_length(halfedge_id) < 0.00001 {
-                Err(Error::MeshIsInvalid(format!(
-                    "Length of edge {} is too small ({})",
+        if let Some(face_id) = walker.face_id() {
+            if !self.contains_face(face_id) {
+                issues.push(ValidationIssue::HalfEdgeWithInvalidFace(
                     halfedge_id,
-                    self.edge_length(halfedge_id)
-                )))?;
+                    face_id,
+                ));
+            }
+            if walker.next_id().is_none() {
+                issues.push(ValidationIssue::HalfEdgeWithoutNext(halfedge_id));
             }
         }
-        for face_id in self.face_iter() {
-            if let Some(halfedge_id) = self.walker_from_face(face_id).halfedge_id() {
-                if !self.halfedge_iter().any(|he_id| he_id == halfedge_id) {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Face {} points to an invalid halfedge {}",
-                        face_id, halfedge_id
-                    )))?;
-                }
-                if self.walker_from_face(face_id).face_id().unwrap() != face_id {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Halfedge pointed to by face {} does not point to back to face",
-                        face_id
-                    )))?;
-                }
-            } else {
-                Err(Error::MeshIsInvalid(format!(
-                    "Face {} does not point to a halfedge",
-                    face_id
-                )))?;
+
+        if let Some(next_id) = walker.next_id() {
+            if !self.contains_halfedge(next_id) {
+                issues.push(ValidationIssue::HalfEdgeWithInvalidNext(
+                    halfedge_id,
+                    next_id,
+                ));
+            } else if self.walker_from_halfedge(next_id).previous_id() != Some(halfedge_id) {
+                issues.push(ValidationIssue::NextMismatch(halfedge_id, next_id));
+            }
+            if walker.face_id().is_none() {
+                issues.push(ValidationIssue::HalfEdgeWithoutFace(halfedge_id));
             }
+        }
 
-            if self.face_area(face_id) < 0.00001 {
-                Err(Error::MeshIsInvalid(format!(
-                    "Area of face {} is too small ({})",
-                    face_id,
-                    self.face_area(face_id)
-                )))?;
+        // The length can only be computed if the connectivity of the half-edge is valid
+        if issues.len() == no_issues {
+            let length = self.edge_length(halfedge_id);
+            if length < MIN_EDGE_LENGTH {
+                issues.push(ValidationIssue::ShortEdge(halfedge_id, length));
             }
         }
+    }
 
-        for vertex_id1 in self.vertex_iter() {
-            for vertex_id2 in self.vertex_iter() {
-                if self.connecting_edge(vertex_id1, vertex_id2).is_some()
-                    != self.connecting_edge(vertex_id2, vertex_id1).is_some()
-                {
-                    Err(Error::MeshIsInvalid(format!(
-                        "Vertex {} and Vertex {} is connected one way, but not the other way",
-                        vertex_id1, vertex_id2
-                    )))?;
-                }
-                let mut found = false;
-                for halfedge_id in self.vertex_halfedge_iter(vertex_id1) {
-                    if self.walker_from_halfedge(halfedge_id).vertex_id().unwrap() == vertex_id2 {
-                        if found {
-                            Err(Error::MeshIsInvalid(format!(
-                                "Vertex {} and Vertex {} is connected by multiple edges",
-                                vertex_id1, vertex_id2
-                            )))?;
-                        }
-                        found = true;
-                    }
-                }
+    /// Adds the problems with the given face to the list of issues.
+    fn validate_face(&self, face_id: FaceID, issues: &mut Vec<ValidationIssue>) {
+        let Some(halfedge_id) = self.walker_from_face(face_id).halfedge_id() else {
+            issues.push(ValidationIssue::FaceWithoutHalfEdge(face_id));
+            return;
+        };
+        if !self.contains_halfedge(halfedge_id) {
+            issues.push(ValidationIssue::FaceWithInvalidHalfEdge(
+                face_id,
+                halfedge_id,
+            ));
+            return;
+        }
+        if self.walker_from_halfedge(halfedge_id).face_id() != Some(face_id) {
+            issues.push(ValidationIssue::FaceHalfEdgeMismatch(face_id, halfedge_id));
+            return;
+        }
+        let area = self.face_area(face_id);
+        if area < MIN_FACE_AREA {
+            issues.push(ValidationIssue::SmallFace(face_id, area));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_report() {
+        let mut mesh = crate::test_utility::cube();
+        assert!(mesh.validate().is_valid());
+        assert!(mesh.is_valid().is_ok());
+
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        let neighbour = mesh.walker_from_vertex(vertex_id).vertex_id().unwrap();
+        mesh.set_vertex_position(vertex_id, mesh.vertex_position(neighbour));
+        let report = mesh.validate();
+        assert!(report
+            .issues
+            .iter()
+            .any(|issue| matches!(issue, ValidationIssue::ShortEdge(_, _))));
+        assert!(report
+            .issues
+            .iter()
+            .any(|issue| matches!(issue, ValidationIssue::SmallFace(_, _))));
+
+        let faces: FaceSet = mesh.face_iter().take(3).collect();
+        assert!(!mesh.validate_region(&faces).is_valid());
+        let faces: FaceSet = mesh
+            .face_iter()
+            .filter(|face_id| mesh.face_area(*face_id) > 0.01)
+            .filter(|face_id| {
+                let (v0, v1, v2) = mesh.face_vertices(*face_id);
+                ![v0, v1, v2].contains(&vertex_id)
+            })
+            .collect();
+        assert!(mesh.validate_region(&faces).is_valid());
+
+        match mesh.is_valid() {
+            Err(Error::MeshIsInvalid(error_report)) => {
+                assert_eq!(error_report, report);
+                assert_eq!(
+                    error_report.to_string().lines().count(),
+                    report.issues.len()
+                );
             }
+            _ => panic!("the mesh should be invalid"),
         }
-        Ok(())
     }
 }
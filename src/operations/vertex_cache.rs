@@ -0,0 +1,202 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+/// The size of the simulated post-transform vertex cache.
+const CACHE_SIZE: usize = 32;
+const CACHE_DECAY_POWER: f64 = 1.5;
+const LAST_TRIANGLE_SCORE: f64 = 0.75;
+const VALENCE_BOOST_SCALE: f64 = 2.0;
+const VALENCE_BOOST_POWER: f64 = 0.5;
+
+/// # Vertex cache optimization
+impl Mesh {
+    ///
+    /// Returns the triangle indices like [Mesh::export], but with the triangles reordered such that vertices are reused while they are still in the post-transform vertex cache of the GPU,
+    /// which reduces the number of times each vertex is processed when rendering the mesh.
+    /// The indices refer to the vertices in the same order as [Mesh::export] (see also [Mesh::vertex_index_map]) and the orientation of each triangle is preserved.
+    ///
+    /// The triangles are reordered using the linear-speed vertex cache optimization algorithm by Tom Forsyth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::*;
+    /// let mesh: Mesh = three_d_asset::TriMesh::sphere(16).into();
+    /// let mut tri_mesh = mesh.export();
+    /// tri_mesh.indices = three_d_asset::Indices::U32(mesh.indices_buffer_optimized());
+    /// assert_eq!(tri_mesh.triangle_count(), mesh.no_faces());
+    /// ```
+    ///
+    pub fn indices_buffer_optimized(&self) -> Vec<u32> {
+        let vertex_indices = self.vertex_index_map();
+        let mut triangles = Vec::with_capacity(self.no_faces());
+        for face_id in self.face_iter() {
+            let mut triangle = [0; 3];
+            for (i, halfedge_id) in self.face_halfedge_iter(face_id).enumerate() {
+                let vertex_id = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                triangle[i] = vertex_indices[&vertex_id];
+            }
+            triangles.push(triangle);
+        }
+        optimize_vertex_cache(&triangles, self.no_vertices())
+            .into_iter()
+            .flat_map(|t| triangles[t])
+            .map(|i| i as u32)
+            .collect()
+    }
+}
+
+///
+/// Returns the order in which to render the triangles to minimize the number of vertex cache misses.
+///
+fn optimize_vertex_cache(triangles: &[[usize; 3]], no_vertices: usize) -> Vec<usize> {
+    let mut vertex_triangles = vec![Vec::new(); no_vertices];
+    for (t, triangle) in triangles.iter().enumerate() {
+        for v in triangle {
+            vertex_triangles[*v].push(t);
+        }
+    }
+    let mut cache_positions: Vec<Option<usize>> = vec![None; no_vertices];
+    let mut vertex_scores: Vec<f64> = vertex_triangles
+        .iter()
+        .map(|t| vertex_score(None, t.len()))
+        .collect();
+    let mut triangle_scores: Vec<f64> = triangles
+        .iter()
+        .map(|t| t.iter().map(|v| vertex_scores[*v]).sum())
+        .collect();
+    let mut is_emitted = vec![false; triangles.len()];
+
+    let mut order = Vec::with_capacity(triangles.len());
+    let mut cache: Vec<usize> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut next_unemitted = 0;
+    let mut best_triangle = (0..triangles.len()).max_by(|a, b| {
+        triangle_scores[*a]
+            .partial_cmp(&triangle_scores[*b])
+            .unwrap()
+    });
+    while let Some(t) = best_triangle {
+        order.push(t);
+        is_emitted[t] = true;
+        for v in triangles[t] {
+            vertex_triangles[v].retain(|other| *other != t);
+        }
+
+        // Move the vertices of the triangle to the front of the cache and push the rest back
+        let mut new_cache = triangles[t].to_vec();
+        new_cache.extend(cache.iter().filter(|v| !triangles[t].contains(v)));
+        for v in new_cache.iter().skip(CACHE_SIZE) {
+            cache_positions[*v] = None;
+        }
+        for (i, v) in new_cache.iter().enumerate().take(CACHE_SIZE) {
+            cache_positions[*v] = Some(i);
+        }
+
+        // Update the scores of the affected vertices and triangles and find the best triangle among them
+        for v in new_cache.iter() {
+            vertex_scores[*v] = vertex_score(cache_positions[*v], vertex_triangles[*v].len());
+        }
+        best_triangle = None;
+        let mut best_score = f64::NEG_INFINITY;
+        for v in new_cache.iter() {
+            for other in vertex_triangles[*v].iter() {
+                let score = triangles[*other].iter().map(|v| vertex_scores[*v]).sum();
+                triangle_scores[*other] = score;
+                if score > best_score {
+                    best_score = score;
+                    best_triangle = Some(*other);
+                }
+            }
+        }
+        new_cache.truncate(CACHE_SIZE);
+        cache = new_cache;
+
+        // If none of the cached vertices have any triangles left, continue with the next unemitted triangle
+        if best_triangle.is_none() {
+            while next_unemitted < triangles.len() && is_emitted[next_unemitted] {
+                next_unemitted += 1;
+            }
+            if next_unemitted < triangles.len() {
+                best_triangle = Some(next_unemitted);
+            }
+        }
+    }
+    order
+}
+
+///
+/// The score of a vertex given its position in the cache and the number of triangles which are not yet emitted that uses the vertex.
+///
+fn vertex_score(cache_position: Option<usize>, no_remaining_triangles: usize) -> f64 {
+    if no_remaining_triangles == 0 {
+        return -1.0;
+    }
+    let cache_score = match cache_position {
+        Some(position) if position < 3 => LAST_TRIANGLE_SCORE,
+        Some(position) => {
+            (1.0 - (position - 3) as f64 / (CACHE_SIZE - 3) as f64).powf(CACHE_DECAY_POWER)
+        }
+        None => 0.0,
+    };
+    cache_score + VALENCE_BOOST_SCALE * (no_remaining_triangles as f64).powf(-VALENCE_BOOST_POWER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use three_d_asset::TriMesh;
+
+    /// Returns the average number of cache misses per triangle when simulating a FIFO cache of the given size.
+    fn average_cache_miss_ratio(indices: &[u32], cache_size: usize) -> f64 {
+        let mut cache = std::collections::VecDeque::new();
+        let mut misses = 0;
+        for i in indices {
+            if !cache.contains(i) {
+                misses += 1;
+                cache.push_back(*i);
+                if cache.len() > cache_size {
+                    cache.pop_front();
+                }
+            }
+        }
+        3.0 * misses as f64 / indices.len() as f64
+    }
+
+    #[test]
+    fn test_indices_buffer_optimized() {
+        // Shuffle the triangles to make the original order cache unfriendly
+        let mut tri_mesh = TriMesh::sphere(32);
+        let indices = tri_mesh.indices.to_u32().unwrap();
+        let no_triangles = indices.len() / 3;
+        let mut shuffled = Vec::with_capacity(indices.len());
+        for i in 0..no_triangles {
+            let t = (i * 7919) % no_triangles;
+            shuffled.extend_from_slice(&indices[3 * t..3 * t + 3]);
+        }
+        tri_mesh.indices = three_d_asset::Indices::U32(shuffled);
+        let mesh: Mesh = tri_mesh.into();
+        let original = match mesh.export().indices {
+            three_d_asset::Indices::U32(indices) => indices,
+            _ => unreachable!(),
+        };
+        let optimized = mesh.indices_buffer_optimized();
+
+        // The same triangles with the same orientation
+        let canonical = |indices: &[u32]| {
+            let mut triangles: Vec<[u32; 3]> = indices
+                .chunks(3)
+                .map(|t| {
+                    let i = (0..3).min_by_key(|i| t[*i]).unwrap();
+                    [t[i], t[(i + 1) % 3], t[(i + 2) % 3]]
+                })
+                .collect();
+            triangles.sort();
+            triangles
+        };
+        assert_eq!(canonical(&original), canonical(&optimized));
+
+        assert!(average_cache_miss_ratio(&optimized, 16) < average_cache_miss_ratio(&original, 16));
+        assert!(average_cache_miss_ratio(&optimized, 16) < 0.8);
+    }
+}